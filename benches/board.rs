@@ -0,0 +1,87 @@
+//! Search, sort and load over synthetic boards.
+//!
+//! Run with `cargo bench --features testing`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use hlavi_core::{
+    domain::{parse_query, SearchConstraints},
+    sort_tasks,
+    storage::{file_storage::FileStorage, Storage},
+    testing::generate_board,
+    BoardService, SortField, SortOrder,
+};
+use std::hint::black_box;
+
+const SIZES: &[usize] = &[100, 1_000, 5_000];
+const SEED: u64 = 42;
+
+fn search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("search");
+    let filter = parse_query("priority >= high and tag = backend and status != closed").unwrap();
+    let constraints = SearchConstraints::default();
+    for &size in SIZES {
+        let tasks = generate_board(size, SEED).tasks;
+        group.bench_with_input(BenchmarkId::new("query", size), &tasks, |b, tasks| {
+            b.iter(|| tasks.iter().filter(|t| filter.matches(t)).count())
+        });
+        group.bench_with_input(BenchmarkId::new("text", size), &tasks, |b, tasks| {
+            b.iter(|| {
+                tasks
+                    .iter()
+                    .filter(|t| constraints.matches(t) && t.matches_query(black_box("webhook")))
+                    .count()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn sort(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sort");
+    for &size in SIZES {
+        let tasks = generate_board(size, SEED).tasks;
+        for field in [SortField::Rank, SortField::Title, SortField::Updated] {
+            group.bench_with_input(
+                BenchmarkId::new(format!("{:?}", field), size),
+                &tasks,
+                |b, tasks| {
+                    b.iter_batched(
+                        || tasks.clone(),
+                        |mut tasks| sort_tasks(&mut tasks, field, SortOrder::Descending),
+                        criterion::BatchSize::LargeInput,
+                    )
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+fn load(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut group = c.benchmark_group("load");
+    group.sample_size(20);
+    for &size in &SIZES[..2] {
+        let dir = tempfile::TempDir::new().unwrap();
+        let service = runtime.block_on(async {
+            let storage = FileStorage::new(dir.path());
+            storage.initialize().await.unwrap();
+            let generated = generate_board(size, SEED);
+            for task in &generated.tasks {
+                storage.save_task(task).await.unwrap();
+            }
+            storage.save_board(&generated.board).await.unwrap();
+            BoardService::new(storage)
+        });
+        group.bench_function(BenchmarkId::new("list_tasks", size), |b| {
+            b.iter(|| runtime.block_on(service.list_tasks()).unwrap().len())
+        });
+        group.bench_function(BenchmarkId::new("list_task_headers", size), |b| {
+            b.iter(|| runtime.block_on(service.list_task_headers()).unwrap().len())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, search, sort, load);
+criterion_main!(benches);
@@ -0,0 +1,143 @@
+use crate::{domain::task::Task, error::HlaviError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A record of something an agent decided while working a task, kept apart
+/// from anything people write so reviewers can see why the agent did what it
+/// did
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentNote {
+    /// Agent session or name that made the decision
+    pub agent: String,
+    pub at: DateTime<Utc>,
+    /// What the agent decided or did, in one line
+    pub decision: String,
+    /// Why, in the agent's words
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+    /// Model that produced the decision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Reference to the prompt or instructions used, such as a file path or
+    /// template name, rather than the prompt itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_ref: Option<String>,
+    /// How sure the agent was, from 0 to 100
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<u8>,
+    /// Files, commits, URLs or other outputs the decision produced
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<String>,
+}
+
+impl AgentNote {
+    pub fn new(agent: &str, decision: &str) -> Self {
+        Self {
+            agent: agent.to_string(),
+            at: Utc::now(),
+            decision: decision.to_string(),
+            rationale: None,
+            model: None,
+            prompt_ref: None,
+            confidence: None,
+            artifacts: Vec::new(),
+        }
+    }
+
+    pub fn with_rationale(mut self, rationale: &str) -> Self {
+        self.rationale = Some(rationale.to_string());
+        self
+    }
+
+    pub fn with_model(mut self, model: &str) -> Self {
+        self.model = Some(model.to_string());
+        self
+    }
+
+    pub fn with_prompt_ref(mut self, prompt_ref: &str) -> Self {
+        self.prompt_ref = Some(prompt_ref.to_string());
+        self
+    }
+
+    pub fn with_confidence(mut self, confidence: u8) -> Self {
+        self.confidence = Some(confidence);
+        self
+    }
+
+    pub fn with_artifact(mut self, artifact: &str) -> Self {
+        self.artifacts.push(artifact.to_string());
+        self
+    }
+
+    fn validate(&self) -> Result<(), HlaviError> {
+        if self.agent.trim().is_empty() {
+            return Err(HlaviError::Validation(
+                "An agent note needs an agent".to_string(),
+            ));
+        }
+        if self.decision.trim().is_empty() {
+            return Err(HlaviError::Validation(
+                "An agent note needs a decision".to_string(),
+            ));
+        }
+        if let Some(confidence) = self.confidence.filter(|c| *c > 100) {
+            return Err(HlaviError::Validation(format!(
+                "Confidence must be between 0 and 100, not {}",
+                confidence
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Task {
+    /// Appends an agent's decision to the task's agent log
+    ///
+    /// Notes are only ever appended, never edited, so the log stays a
+    /// faithful record.
+    pub fn add_agent_note(&mut self, note: AgentNote) -> Result<(), HlaviError> {
+        note.validate()?;
+        self.agent_notes.push(note);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Notes left by `agent`, oldest first
+    pub fn agent_notes_by<'a>(&'a self, agent: &'a str) -> impl Iterator<Item = &'a AgentNote> {
+        self.agent_notes
+            .iter()
+            .filter(move |note| note.agent.eq_ignore_ascii_case(agent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::task::TaskId;
+
+    #[test]
+    fn test_add_agent_note() {
+        let mut task = Task::new(TaskId::new(1), "Fix flaky test".to_string());
+        let note = AgentNote::new("fixer-bot", "Retried the network call")
+            .with_rationale("The failure only happens on timeouts")
+            .with_model("local-7b")
+            .with_prompt_ref("prompts/fix-test.md")
+            .with_confidence(80)
+            .with_artifact("commit:abc123");
+        task.add_agent_note(note.clone()).unwrap();
+        assert_eq!(task.agent_notes, vec![note]);
+        assert_eq!(task.agent_notes_by("Fixer-Bot").count(), 1);
+
+        assert!(task
+            .add_agent_note(AgentNote::new("fixer-bot", " "))
+            .is_err());
+        assert!(task
+            .add_agent_note(AgentNote::new("fixer-bot", "Guessed").with_confidence(101))
+            .is_err());
+        assert_eq!(task.agent_notes.len(), 1);
+
+        // Optional fields are left out of stored tasks
+        let json = serde_json::to_value(AgentNote::new("bot", "Skipped")).unwrap();
+        assert_eq!(json.as_object().unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,535 @@
+use crate::domain::task::{Priority, Severity, Task, TaskStatus};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A persisted automation rule: when every condition holds, apply the actions
+///
+/// Rules are level-triggered — they are evaluated against the task state after
+/// each mutation, and actions that would not change anything are skipped, so a
+/// rule never fires twice for the same state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutomationRule {
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub conditions: Vec<RuleCondition>,
+    pub actions: Vec<RuleAction>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Condition that must hold for a rule to fire
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleCondition {
+    /// Task carries the given tag
+    HasTag { tag: String },
+    /// Task is in the given status
+    StatusIs { status: TaskStatus },
+    /// Task has the given priority
+    PriorityIs { priority: Priority },
+    /// Task is of the given type
+    TypeIs { task_type: String },
+    /// Task has a severity of at least the given level
+    SeverityAtLeast { severity: Severity },
+    /// Task touches the given component
+    HasComponent { component: String },
+    /// Task was reported in the given environment
+    EnvironmentIs { environment: String },
+    /// Task has at least one acceptance criterion and all are completed
+    AllAcceptanceCriteriaCompleted,
+    /// Task is flagged as blocked
+    IsBlocked,
+    /// Task was updated less than `seconds` ago
+    UpdatedWithin { seconds: i64 },
+    /// Task was last updated after `at`
+    UpdatedAfter { at: DateTime<Utc> },
+    /// Task was created less than `seconds` ago
+    CreatedWithin { seconds: i64 },
+    /// Task was created after `at`
+    CreatedAfter { at: DateTime<Utc> },
+    /// Task text contains the query, like `Task::matches_query`
+    TextMatches { query: String },
+    /// Every inner condition holds
+    All { conditions: Vec<RuleCondition> },
+    /// At least one inner condition holds
+    Any { conditions: Vec<RuleCondition> },
+    /// The inner condition does not hold
+    Not { condition: Box<RuleCondition> },
+}
+
+/// Action applied when a rule fires
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleAction {
+    SetPriority {
+        priority: Priority,
+    },
+    AddTag {
+        tag: String,
+    },
+    RemoveTag {
+        tag: String,
+    },
+    /// Moves the task to the given status if the workflow allows it
+    TransitionTo {
+        status: TaskStatus,
+    },
+    AssignAgent,
+}
+
+/// What happened (or would happen) to a single action during evaluation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionOutcome {
+    Applied,
+    /// The task already satisfies the action
+    NoChange,
+    /// The action is not allowed from the current state
+    Rejected(String),
+}
+
+/// Per-rule explanation produced by evaluation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleExplanation {
+    pub rule: String,
+    pub matched: bool,
+    pub conditions: Vec<(RuleCondition, bool)>,
+    pub actions: Vec<(RuleAction, ActionOutcome)>,
+}
+
+impl AutomationRule {
+    pub fn new(name: String, conditions: Vec<RuleCondition>, actions: Vec<RuleAction>) -> Self {
+        Self {
+            name,
+            enabled: true,
+            conditions,
+            actions,
+        }
+    }
+
+    /// Checks whether every condition holds for the task
+    pub fn matches(&self, task: &Task) -> bool {
+        self.enabled && self.conditions.iter().all(|c| c.holds(task))
+    }
+}
+
+impl RuleCondition {
+    /// Checks whether the condition holds for the task
+    pub fn holds(&self, task: &Task) -> bool {
+        match self {
+            Self::HasTag { tag } => task.has_tag(tag),
+            Self::StatusIs { status } => &task.status == status,
+            Self::PriorityIs { priority } => task.priority.as_ref() == Some(priority),
+            Self::TypeIs { task_type } => task.is_type(task_type),
+            Self::SeverityAtLeast { severity } => task.severity >= Some(*severity),
+            Self::HasComponent { component } => task.has_component(component),
+            Self::EnvironmentIs { environment } => task
+                .environment
+                .as_deref()
+                .is_some_and(|e| e.eq_ignore_ascii_case(environment)),
+            Self::AllAcceptanceCriteriaCompleted => task.all_acceptance_criteria_completed(),
+            Self::IsBlocked => task.is_blocked(),
+            Self::UpdatedWithin { seconds } => {
+                Utc::now() - task.updated_at < Duration::seconds(*seconds)
+            }
+            Self::UpdatedAfter { at } => task.updated_at > *at,
+            Self::CreatedWithin { seconds } => {
+                Utc::now() - task.created_at < Duration::seconds(*seconds)
+            }
+            Self::CreatedAfter { at } => task.created_at > *at,
+            Self::TextMatches { query } => task.matches_query(query),
+            Self::All { conditions } => conditions.iter().all(|c| c.holds(task)),
+            Self::Any { conditions } => conditions.iter().any(|c| c.holds(task)),
+            Self::Not { condition } => !condition.holds(task),
+        }
+    }
+}
+
+impl RuleAction {
+    /// Applies the action to the task, reporting whether it changed anything
+    pub fn apply(&self, task: &mut Task) -> ActionOutcome {
+        let outcome = self.preview(task);
+        if outcome != ActionOutcome::Applied {
+            return outcome;
+        }
+
+        match self {
+            Self::SetPriority { priority } => task.set_priority(*priority),
+            Self::AddTag { tag } => {
+                task.add_tag(tag.clone());
+            }
+            Self::RemoveTag { tag } => {
+                task.remove_tag(tag);
+            }
+            Self::TransitionTo { status } => {
+                // preview() already checked the transition is legal
+                let _ = task.transition_to(status.clone(), None);
+            }
+            Self::AssignAgent => {
+                task.agent_assigned = true;
+                task.updated_at = chrono::Utc::now();
+            }
+        }
+        ActionOutcome::Applied
+    }
+
+    /// Reports what `apply` would do without mutating the task
+    pub fn preview(&self, task: &Task) -> ActionOutcome {
+        let changes = match self {
+            Self::SetPriority { priority } => task.priority != Some(*priority),
+            Self::AddTag { tag } => !task.has_tag(tag),
+            Self::RemoveTag { tag } => task.has_tag(tag),
+            Self::TransitionTo { status } => {
+                if &task.status == status {
+                    false
+                } else if task.status.can_transition_to(status) {
+                    true
+                } else {
+                    return ActionOutcome::Rejected(format!(
+                        "Invalid task status transition from {} to {}",
+                        task.status, status
+                    ));
+                }
+            }
+            Self::AssignAgent => !task.agent_assigned,
+        };
+
+        if changes {
+            ActionOutcome::Applied
+        } else {
+            ActionOutcome::NoChange
+        }
+    }
+}
+
+impl fmt::Display for RuleCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HasTag { tag } => write!(f, "has tag '{}'", tag),
+            Self::StatusIs { status } => write!(f, "status is {}", status),
+            Self::PriorityIs { priority } => write!(f, "priority is {}", priority),
+            Self::TypeIs { task_type } => write!(f, "type is {}", task_type),
+            Self::SeverityAtLeast { severity } => write!(f, "severity is at least {}", severity),
+            Self::HasComponent { component } => write!(f, "has component '{}'", component),
+            Self::EnvironmentIs { environment } => write!(f, "environment is {}", environment),
+            Self::AllAcceptanceCriteriaCompleted => {
+                write!(f, "all acceptance criteria completed")
+            }
+            Self::IsBlocked => write!(f, "is blocked"),
+            Self::UpdatedWithin { seconds } => {
+                write!(f, "updated in the last {}", describe_seconds(*seconds))
+            }
+            Self::UpdatedAfter { at } => write!(f, "updated after {}", at.format("%Y-%m-%d")),
+            Self::CreatedWithin { seconds } => {
+                write!(f, "created in the last {}", describe_seconds(*seconds))
+            }
+            Self::CreatedAfter { at } => write!(f, "created after {}", at.format("%Y-%m-%d")),
+            Self::TextMatches { query } => write!(f, "mentions '{}'", query),
+            Self::All { conditions } => write_joined(f, conditions, " and "),
+            Self::Any { conditions } => write_joined(f, conditions, " or "),
+            Self::Not { condition } => write!(f, "not ({})", condition),
+        }
+    }
+}
+
+fn write_joined(
+    f: &mut fmt::Formatter<'_>,
+    conditions: &[RuleCondition],
+    sep: &str,
+) -> fmt::Result {
+    write!(f, "(")?;
+    for (i, condition) in conditions.iter().enumerate() {
+        if i > 0 {
+            write!(f, "{}", sep)?;
+        }
+        write!(f, "{}", condition)?;
+    }
+    write!(f, ")")
+}
+
+/// `seconds` in the largest whole unit, e.g. `7 days`
+fn describe_seconds(seconds: i64) -> String {
+    let (count, unit) = [
+        (604_800, "week"),
+        (86_400, "day"),
+        (3_600, "hour"),
+        (60, "minute"),
+    ]
+    .into_iter()
+    .find(|(size, _)| seconds >= *size && seconds % size == 0)
+    .map_or((seconds, "second"), |(size, unit)| (seconds / size, unit));
+    if count == 1 {
+        format!("{} {}", count, unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+impl fmt::Display for RuleAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SetPriority { priority } => write!(f, "set priority {}", priority),
+            Self::AddTag { tag } => write!(f, "add tag '{}'", tag),
+            Self::RemoveTag { tag } => write!(f, "remove tag '{}'", tag),
+            Self::TransitionTo { status } => write!(f, "move to {}", status),
+            Self::AssignAgent => write!(f, "assign agent"),
+        }
+    }
+}
+
+/// Applies all matching rules to the task in order, in a single pass
+///
+/// Later rules see the changes made by earlier ones. Returns an explanation
+/// for every rule, including those that did not match.
+pub fn apply_rules(rules: &[AutomationRule], task: &mut Task) -> Vec<RuleExplanation> {
+    rules
+        .iter()
+        .map(|rule| {
+            let conditions = explain_conditions(rule, task);
+            let matched = rule.enabled && conditions.iter().all(|(_, held)| *held);
+            let actions = if matched {
+                rule.actions
+                    .iter()
+                    .map(|action| (action.clone(), action.apply(task)))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            RuleExplanation {
+                rule: rule.name.clone(),
+                matched,
+                conditions,
+                actions,
+            }
+        })
+        .collect()
+}
+
+/// Dry-run: explains what `apply_rules` would do without mutating the task
+pub fn explain_rules(rules: &[AutomationRule], task: &Task) -> Vec<RuleExplanation> {
+    let mut scratch = task.clone();
+    apply_rules(rules, &mut scratch)
+}
+
+fn explain_conditions(rule: &AutomationRule, task: &Task) -> Vec<(RuleCondition, bool)> {
+    rule.conditions
+        .iter()
+        .map(|c| (c.clone(), c.holds(task)))
+        .collect()
+}
+
+/// Escalates tasks that sit untouched for too long
+///
+/// Unlike automation rules, aging rules depend on the clock and are only
+/// evaluated when the host application runs them on a schedule (see
+/// `BoardService::apply_aging`). Escalating a task updates it, so the same
+/// rule fires again only after another full `after_days` without activity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgingRule {
+    pub name: String,
+    /// Statuses the rule watches; empty means every open status
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub statuses: Vec<TaskStatus>,
+    /// Days without an update before the rule fires
+    pub after_days: u32,
+    /// Raise the priority one level (unset becomes Low)
+    #[serde(default)]
+    pub escalate: bool,
+    /// Tag to add, such as "aging"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+impl AgingRule {
+    pub fn new(name: String, after_days: u32) -> Self {
+        Self {
+            name,
+            statuses: Vec::new(),
+            after_days,
+            escalate: false,
+            tag: None,
+        }
+    }
+
+    /// Checks whether the task has gone untouched long enough to age
+    pub fn is_due(&self, task: &Task, now: DateTime<Utc>) -> bool {
+        let watched = if self.statuses.is_empty() {
+            !matches!(task.status, TaskStatus::Done | TaskStatus::Closed)
+        } else {
+            self.statuses.contains(&task.status)
+        };
+        task.is_on_board()
+            && watched
+            && now - task.updated_at >= Duration::days(self.after_days.into())
+    }
+
+    /// The actions this rule applies to a due task
+    pub fn actions(&self, task: &Task) -> Vec<RuleAction> {
+        let mut actions = Vec::new();
+        if self.escalate {
+            let next = match task.priority {
+                Some(priority) => priority.escalated(),
+                None => Some(Priority::Low),
+            };
+            if let Some(priority) = next {
+                actions.push(RuleAction::SetPriority { priority });
+            }
+        }
+        if let Some(tag) = &self.tag {
+            actions.push(RuleAction::AddTag { tag: tag.clone() });
+        }
+        actions
+    }
+
+    /// Ages the task if due, returning whether anything changed
+    pub fn apply(&self, task: &mut Task, now: DateTime<Utc>) -> bool {
+        if !self.is_due(task, now) {
+            return false;
+        }
+        let mut changed = false;
+        for action in self.actions(task) {
+            changed |= action.apply(task) == ActionOutcome::Applied;
+        }
+        if changed {
+            task.updated_at = now;
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::task::TaskId;
+
+    fn bug_priority_rule() -> AutomationRule {
+        AutomationRule::new(
+            "Bugs are high priority".to_string(),
+            vec![RuleCondition::HasTag {
+                tag: "bug".to_string(),
+            }],
+            vec![RuleAction::SetPriority {
+                priority: Priority::High,
+            }],
+        )
+    }
+
+    #[test]
+    fn test_rule_applies_when_conditions_hold() {
+        let mut task = Task::new(TaskId::new(1), "Crash".to_string());
+        task.add_tag("bug".to_string());
+
+        let results = apply_rules(&[bug_priority_rule()], &mut task);
+
+        assert!(results[0].matched);
+        assert_eq!(results[0].actions[0].1, ActionOutcome::Applied);
+        assert_eq!(task.priority, Some(Priority::High));
+
+        // Second evaluation is a no-op
+        let results = apply_rules(&[bug_priority_rule()], &mut task);
+        assert_eq!(results[0].actions[0].1, ActionOutcome::NoChange);
+    }
+
+    #[test]
+    fn test_rule_does_not_fire_without_match() {
+        let mut task = Task::new(TaskId::new(1), "Feature".to_string());
+        let results = apply_rules(&[bug_priority_rule()], &mut task);
+
+        assert!(!results[0].matched);
+        assert!(results[0].actions.is_empty());
+        assert!(task.priority.is_none());
+    }
+
+    #[test]
+    fn test_disabled_rule_never_matches() {
+        let mut rule = bug_priority_rule();
+        rule.enabled = false;
+        let mut task = Task::new(TaskId::new(1), "Crash".to_string());
+        task.add_tag("bug".to_string());
+
+        assert!(!rule.matches(&task));
+        apply_rules(&[rule], &mut task);
+        assert!(task.priority.is_none());
+    }
+
+    #[test]
+    fn test_explain_does_not_mutate() {
+        let rule = AutomationRule::new(
+            "Complete ACs go to review".to_string(),
+            vec![
+                RuleCondition::StatusIs {
+                    status: TaskStatus::InProgress,
+                },
+                RuleCondition::AllAcceptanceCriteriaCompleted,
+            ],
+            vec![RuleAction::TransitionTo {
+                status: TaskStatus::Review,
+            }],
+        );
+        let mut task = Task::new(TaskId::new(1), "Work".to_string());
+        task.status = TaskStatus::InProgress;
+        task.add_acceptance_criterion("Done".to_string());
+        task.acceptance_criteria[0].mark_completed();
+
+        let explanation = explain_rules(&[rule], &task);
+
+        assert!(explanation[0].matched);
+        assert_eq!(explanation[0].actions[0].1, ActionOutcome::Applied);
+        assert_eq!(task.status, TaskStatus::InProgress);
+    }
+
+    #[test]
+    fn test_invalid_transition_is_rejected() {
+        let action = RuleAction::TransitionTo {
+            status: TaskStatus::Done,
+        };
+        let mut task = Task::new(TaskId::new(1), "New task".to_string());
+
+        assert!(matches!(
+            action.apply(&mut task),
+            ActionOutcome::Rejected(_)
+        ));
+        assert_eq!(task.status, TaskStatus::New);
+    }
+
+    #[test]
+    fn test_rule_serialization() {
+        let json = serde_json::to_string(&bug_priority_rule()).unwrap();
+        assert!(json.contains(r#""type":"has_tag""#));
+        assert!(json.contains(r#""type":"set_priority""#));
+
+        let rule: AutomationRule = serde_json::from_str(
+            r#"{"name":"r","conditions":[{"type":"all_acceptance_criteria_completed"}],"actions":[{"type":"assign_agent"}]}"#,
+        )
+        .unwrap();
+        assert!(rule.enabled);
+    }
+
+    #[test]
+    fn test_aging_rule() {
+        let mut rule = AgingRule::new("Stale work".to_string(), 7);
+        rule.escalate = true;
+        rule.tag = Some("aging".to_string());
+
+        let mut task = Task::new(TaskId::new(1), "Forgotten".to_string());
+        task.status = TaskStatus::InProgress;
+        task.set_priority(Priority::High);
+        let now = task.updated_at + Duration::days(8);
+
+        assert!(!rule.is_due(&task, task.updated_at + Duration::days(6)));
+        assert!(rule.apply(&mut task, now));
+        assert_eq!(task.priority, Some(Priority::Critical));
+        assert!(task.has_tag("aging"));
+
+        // Touched by the escalation, so not due again straight away
+        assert!(!rule.apply(&mut task, now));
+
+        let mut done = Task::new(TaskId::new(2), "Finished".to_string());
+        done.status = TaskStatus::Done;
+        assert!(!rule.is_due(&done, done.updated_at + Duration::days(30)));
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{domain::task::Task, error::HlaviError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Why a task cannot move forward
+///
+/// Being blocked is independent of status: an in-progress task waiting on a
+/// vendor and a task in review waiting on a decision are both blocked, and
+/// stay in their columns until they are unblocked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockedInfo {
+    pub reason: String,
+    pub since: DateTime<Utc>,
+    /// Who flagged the task as blocked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by: Option<String>,
+}
+
+impl fmt::Display for BlockedInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.reason)?;
+        if let Some(by) = &self.by {
+            write!(f, " (by {})", by)?;
+        }
+        Ok(())
+    }
+}
+
+impl Task {
+    pub fn is_blocked(&self) -> bool {
+        self.blocked.is_some()
+    }
+
+    /// Flags the task as blocked, replacing any earlier reason
+    ///
+    /// The reason is required; a blank one fails with
+    /// `HlaviError::Validation`. Re-blocking keeps the original `since`.
+    pub fn block(&mut self, reason: &str, by: Option<&str>) -> Result<(), HlaviError> {
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(HlaviError::Validation(
+                "A blocked task needs a reason".to_string(),
+            ));
+        }
+        let now = Utc::now();
+        let since = self.blocked.as_ref().map_or(now, |info| info.since);
+        self.blocked = Some(BlockedInfo {
+            reason: reason.to_string(),
+            since,
+            by: by.map(str::to_string),
+        });
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Clears the blocked flag, returning what it was
+    pub fn unblock(&mut self) -> Option<BlockedInfo> {
+        let info = self.blocked.take();
+        if info.is_some() {
+            self.updated_at = Utc::now();
+        }
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::task::TaskId;
+
+    #[test]
+    fn test_block_and_unblock() {
+        let mut task = Task::new(TaskId::new(1), "Integrate".to_string());
+        assert!(task.block("  ", None).is_err());
+
+        task.block("Waiting on vendor API keys", Some("ana"))
+            .unwrap();
+        let since = task.blocked.as_ref().unwrap().since;
+        task.block("Waiting on legal", None).unwrap();
+        let info = task.blocked.as_ref().unwrap();
+        assert_eq!(info.since, since);
+        assert_eq!(info.to_string(), "Waiting on legal");
+
+        assert!(task.unblock().is_some());
+        assert!(!task.is_blocked());
+        assert!(task.unblock().is_none());
+    }
+}
@@ -1,6 +1,24 @@
-use crate::domain::task::{TaskId, TaskStatus};
+use crate::{
+    domain::{
+        automation::{AgingRule, AutomationRule},
+        checklist::Checklist,
+        connector::ConnectorConfig,
+        notification::NotificationSettings,
+        quota::Quota,
+        secrets::SecretPolicy,
+        task::{IdFormat, Priority, Task, TaskId, TaskStatus},
+        task_type::TaskType,
+        view::SavedView,
+    },
+    error::{HlaviError, Result},
+};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    str::FromStr,
+};
 
 /// Configuration for a kanban board column
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +27,183 @@ pub struct Column {
     pub status: TaskStatus,
     pub agent_enabled: bool,
     pub agent_mode: Option<AgentMode>,
+    /// Maximum number of tasks allowed in the column
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wip_limit: Option<u32>,
+    /// Further statuses shown in this column, for boards that collapse the workflow
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_statuses: Vec<TaskStatus>,
+    /// Hex color such as `#1f6feb`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Icon name or emoji, interpreted by the frontend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Fields a task must have set to move into this column
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_fields: Vec<RequiredField>,
+    /// Items added to every task that moves into this column
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition_of_done: Option<DefinitionOfDone>,
+    /// Left off the board by frontends unless a person chooses to show it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+    /// Shown as a collapsed strip until a person expands it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub collapsed_by_default: bool,
+}
+
+/// A column's definition of done: steps every task entering the column must
+/// complete
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DefinitionOfDone {
+    pub items: Vec<String>,
+    /// Adds the items to a checklist of this name instead of as acceptance
+    /// criteria
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checklist: Option<String>,
+}
+
+impl DefinitionOfDone {
+    /// Adds the items the task does not have yet, returning how many were
+    /// added
+    ///
+    /// Items are matched case-insensitively, so a task that re-enters the
+    /// column does not get duplicates.
+    pub fn apply(&self, task: &mut Task) -> usize {
+        let mut added = 0;
+        match &self.checklist {
+            Some(name) => {
+                if task.checklist(name).is_none() {
+                    task.checklists.push(Checklist::new(name.clone()));
+                }
+                let checklist = task
+                    .checklists
+                    .iter_mut()
+                    .find(|c| c.name.eq_ignore_ascii_case(name))
+                    .expect("checklist was just ensured");
+                for item in &self.items {
+                    if !checklist
+                        .items
+                        .iter()
+                        .any(|i| i.text.eq_ignore_ascii_case(item))
+                    {
+                        checklist.add_item(item.clone());
+                        added += 1;
+                    }
+                }
+            }
+            None => {
+                for item in &self.items {
+                    if !task
+                        .acceptance_criteria
+                        .iter()
+                        .any(|ac| ac.description.eq_ignore_ascii_case(item))
+                    {
+                        task.add_acceptance_criterion(item.clone());
+                        added += 1;
+                    }
+                }
+            }
+        }
+        added
+    }
+}
+
+/// A task field a column can require
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequiredField {
+    Description,
+    Priority,
+    Assignee,
+    Estimate,
+    StartDate,
+    EndDate,
+    Tags,
+    AcceptanceCriteria,
+    TaskType,
+    Components,
+    Environment,
+}
+
+impl RequiredField {
+    /// Checks whether the field is set on the task
+    pub fn is_set(&self, task: &Task) -> bool {
+        match self {
+            Self::Description => task
+                .description
+                .as_deref()
+                .is_some_and(|d| !d.trim().is_empty()),
+            Self::Priority => task.priority.is_some(),
+            Self::Assignee => task.assignee.is_some(),
+            Self::Estimate => task.estimate.is_some(),
+            Self::StartDate => task.start_date.is_some(),
+            Self::EndDate => task.end_date.is_some(),
+            Self::Tags => !task.tags.is_empty(),
+            Self::AcceptanceCriteria => !task.acceptance_criteria.is_empty(),
+            Self::TaskType => task.task_type.is_some(),
+            Self::Components => !task.components.is_empty(),
+            Self::Environment => task.environment.is_some(),
+        }
+    }
+}
+
+impl fmt::Display for RequiredField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Description => "description",
+            Self::Priority => "priority",
+            Self::Assignee => "assignee",
+            Self::Estimate => "estimate",
+            Self::StartDate => "start date",
+            Self::EndDate => "end date",
+            Self::Tags => "tags",
+            Self::AcceptanceCriteria => "acceptance criteria",
+            Self::TaskType => "type",
+            Self::Components => "components",
+            Self::Environment => "environment",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Colors for tags and priorities shared by every frontend
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Theme {
+    /// Tag (lowercase) to hex color
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tag_colors: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub priority_colors: BTreeMap<Priority, String>,
+}
+
+impl Theme {
+    pub fn is_empty(&self) -> bool {
+        self.tag_colors.is_empty() && self.priority_colors.is_empty()
+    }
+
+    /// Sets the color for a tag, matched case-insensitively
+    pub fn set_tag_color(&mut self, tag: &str, color: String) {
+        self.tag_colors.insert(tag.to_lowercase(), color);
+    }
+
+    pub fn tag_color(&self, tag: &str) -> Option<&str> {
+        self.tag_colors.get(&tag.to_lowercase()).map(String::as_str)
+    }
+
+    pub fn priority_color(&self, priority: Priority) -> Option<&str> {
+        self.priority_colors.get(&priority).map(String::as_str)
+    }
+}
+
+/// Checks for `#rgb` or `#rrggbb`
+fn is_hex_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
 }
 
 /// Agent execution mode
@@ -26,14 +221,96 @@ impl Column {
             status,
             agent_enabled: false,
             agent_mode: None,
+            wip_limit: None,
+            extra_statuses: Vec::new(),
+            color: None,
+            icon: None,
+            description: None,
+            required_fields: Vec::new(),
+            definition_of_done: None,
+            hidden: false,
+            collapsed_by_default: false,
         }
     }
 
+    /// Requires `field` to be set on tasks moving into the column
+    pub fn requiring(mut self, field: RequiredField) -> Self {
+        self.required_fields.push(field);
+        self
+    }
+
+    /// Required fields the task has not set
+    pub fn missing_fields(&self, task: &Task) -> Vec<RequiredField> {
+        self.required_fields
+            .iter()
+            .filter(|field| !field.is_set(task))
+            .copied()
+            .collect()
+    }
+
     pub fn with_agent(mut self, mode: AgentMode) -> Self {
         self.agent_enabled = true;
         self.agent_mode = Some(mode);
         self
     }
+
+    pub fn with_wip_limit(mut self, limit: u32) -> Self {
+        self.wip_limit = Some(limit);
+        self
+    }
+
+    pub fn with_color(mut self, color: &str) -> Self {
+        self.color = Some(color.to_string());
+        self
+    }
+
+    pub fn with_icon(mut self, icon: &str) -> Self {
+        self.icon = Some(icon.to_string());
+        self
+    }
+
+    pub fn hidden(mut self) -> Self {
+        self.hidden = true;
+        self
+    }
+
+    pub fn collapsed(mut self) -> Self {
+        self.collapsed_by_default = true;
+        self
+    }
+
+    /// Also shows tasks in `status` in this column
+    pub fn with_status(mut self, status: TaskStatus) -> Self {
+        self.extra_statuses.push(status);
+        self
+    }
+
+    /// All statuses shown in the column, primary status first
+    pub fn statuses(&self) -> impl Iterator<Item = &TaskStatus> {
+        std::iter::once(&self.status).chain(&self.extra_statuses)
+    }
+
+    /// Checks whether tasks in `status` appear in this column
+    pub fn holds(&self, status: &TaskStatus) -> bool {
+        self.statuses().any(|s| s == status)
+    }
+}
+
+/// Starting board layouts for common workflows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Preset {
+    /// One column per status, with an agent on In Progress
+    #[default]
+    Standard,
+    /// To Do / Doing / Done
+    Simple,
+    /// Backlog, sprint-ready, and WIP-limited in-progress and review columns
+    Scrum,
+    /// Triage queue with a waiting-on-customer column
+    SupportQueue,
+    /// Agents implement unattended and reviews are attended
+    AgentPipeline,
 }
 
 /// Board configuration
@@ -41,58 +318,783 @@ impl Column {
 pub struct BoardConfig {
     pub name: String,
     pub columns: Vec<Column>,
+    /// Automation rules evaluated after each task mutation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<AutomationRule>,
+    /// Escalation rules for tasks left untouched, run on a schedule
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aging: Vec<AgingRule>,
+    #[serde(default, skip_serializing_if = "Theme::is_empty")]
+    pub theme: Theme,
+    /// Saved views shared by everyone using the board
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub views: Vec<SavedView>,
+    /// Allowed task types; when empty, tasks are untyped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub task_types: Vec<TaskType>,
+    /// Allowed components; when empty, any component is accepted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<String>,
+    /// Allowed environments; when empty, any environment is accepted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub environments: Vec<String>,
+    /// Limits on how often actors may create, update or delete tasks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quotas: Vec<Quota>,
+    /// Moves an in-progress task to Review once its last acceptance criterion
+    /// is completed
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_review: bool,
+    /// How task IDs are displayed, such as `HLA-0042`
+    #[serde(default, skip_serializing_if = "IdFormat::is_default")]
+    pub id_format: IdFormat,
+    /// Connectors forwarding events to, and taking payloads from, other
+    /// tools
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub connectors: Vec<ConnectorConfig>,
+    /// What to do when a task about to be saved contains a credential
+    #[serde(default, skip_serializing_if = "SecretPolicy::is_default")]
+    pub secret_policy: SecretPolicy,
+    /// How often each person receives digests of task events
+    #[serde(default, skip_serializing_if = "NotificationSettings::is_empty")]
+    pub notifications: NotificationSettings,
 }
 
 impl Default for BoardConfig {
     fn default() -> Self {
+        Self::preset(Preset::Standard)
+    }
+}
+
+impl BoardConfig {
+    /// Builds the board layout for a preset
+    pub fn preset(preset: Preset) -> Self {
+        let col = |name: &str, status| Column::new(name.to_string(), status);
+        let (name, columns) = match preset {
+            Preset::Standard => (
+                "Default Board",
+                vec![
+                    col("New", TaskStatus::New),
+                    col("Open", TaskStatus::Open),
+                    col("In Progress", TaskStatus::InProgress).with_agent(AgentMode::Unattended),
+                    col("Pending", TaskStatus::Pending),
+                    col("Review", TaskStatus::Review),
+                    col("Done", TaskStatus::Done),
+                    col("Closed", TaskStatus::Closed),
+                ],
+            ),
+            Preset::Simple => (
+                "Simple Board",
+                vec![
+                    col("To Do", TaskStatus::New).with_status(TaskStatus::Open),
+                    col("Doing", TaskStatus::InProgress)
+                        .with_status(TaskStatus::Pending)
+                        .with_status(TaskStatus::Review),
+                    col("Done", TaskStatus::Done).with_status(TaskStatus::Closed),
+                ],
+            ),
+            Preset::Scrum => (
+                "Scrum Board",
+                vec![
+                    col("Backlog", TaskStatus::New),
+                    col("Sprint Ready", TaskStatus::Open),
+                    col("In Progress", TaskStatus::InProgress).with_wip_limit(5),
+                    col("Blocked", TaskStatus::Pending),
+                    col("Review", TaskStatus::Review).with_wip_limit(3),
+                    col("Done", TaskStatus::Done),
+                    col("Closed", TaskStatus::Closed),
+                ],
+            ),
+            Preset::SupportQueue => (
+                "Support Queue",
+                vec![
+                    col("Triage", TaskStatus::New),
+                    col("Queued", TaskStatus::Open),
+                    col("Working", TaskStatus::InProgress).with_wip_limit(10),
+                    col("Waiting on Customer", TaskStatus::Pending),
+                    col("Verification", TaskStatus::Review),
+                    col("Resolved", TaskStatus::Done).with_status(TaskStatus::Closed),
+                ],
+            ),
+            Preset::AgentPipeline => (
+                "Agent Pipeline",
+                vec![
+                    col("Inbox", TaskStatus::New),
+                    col("Ready for Agent", TaskStatus::Open),
+                    col("Agent Working", TaskStatus::InProgress)
+                        .with_agent(AgentMode::Unattended)
+                        .with_wip_limit(3),
+                    col("Needs Input", TaskStatus::Pending),
+                    col("Human Review", TaskStatus::Review).with_agent(AgentMode::Attended),
+                    col("Done", TaskStatus::Done),
+                    col("Closed", TaskStatus::Closed),
+                ],
+            ),
+        };
         Self {
-            name: "Default Board".to_string(),
-            columns: vec![
-                Column::new("New".to_string(), TaskStatus::New),
-                Column::new("Open".to_string(), TaskStatus::Open),
-                Column::new("In Progress".to_string(), TaskStatus::InProgress)
-                    .with_agent(AgentMode::Unattended),
-                Column::new("Pending".to_string(), TaskStatus::Pending),
-                Column::new("Review".to_string(), TaskStatus::Review),
-                Column::new("Done".to_string(), TaskStatus::Done),
-                Column::new("Closed".to_string(), TaskStatus::Closed),
-            ],
+            name: name.to_string(),
+            columns,
+            rules: Vec::new(),
+            aging: Vec::new(),
+            theme: Theme::default(),
+            views: Vec::new(),
+            task_types: Vec::new(),
+            components: Vec::new(),
+            environments: Vec::new(),
+            quotas: Vec::new(),
+            auto_review: false,
+            id_format: IdFormat::default(),
+            connectors: Vec::new(),
+            secret_policy: SecretPolicy::default(),
+            notifications: NotificationSettings::default(),
         }
     }
+
+    /// Checks that the task has every field its column requires
+    ///
+    /// Only moves into a different column are checked, so a task can still
+    /// be edited where it is; pass `None` as `previous` to check regardless.
+    /// Fails with `HlaviError::MissingRequiredFields`.
+    pub fn check_required_fields(&self, task: &Task, previous: Option<&TaskStatus>) -> Result<()> {
+        let Some(column) = self.entered_column(task, previous) else {
+            return Ok(());
+        };
+        let missing = column.missing_fields(task);
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(HlaviError::MissingRequiredFields {
+            task_id: task.id.to_string(),
+            column: column.name.clone(),
+            fields: missing,
+        })
+    }
+
+    /// Adds the definition of done of the column the task is moving into,
+    /// returning how many items were added
+    ///
+    /// As with `check_required_fields`, `None` as `previous` applies it
+    /// regardless of where the task was.
+    pub fn apply_definition_of_done(
+        &self,
+        task: &mut Task,
+        previous: Option<&TaskStatus>,
+    ) -> usize {
+        let definition = self
+            .entered_column(task, previous)
+            .and_then(|column| column.definition_of_done.clone());
+        definition.map_or(0, |definition| definition.apply(task))
+    }
+
+    /// Sends the task to Review if `auto_review` is on and the edit from
+    /// `before` completed its last acceptance criterion, returning whether it
+    /// moved
+    ///
+    /// The move follows the task's type and the Review column's required
+    /// fields; when either forbids it the task stays in progress.
+    pub fn apply_auto_review(&self, before: &Task, task: &mut Task) -> bool {
+        if !self.auto_review
+            || task.status != TaskStatus::InProgress
+            || before.all_acceptance_criteria_completed()
+            || !task.all_acceptance_criteria_completed()
+        {
+            return false;
+        }
+        let task_type = task.task_type.as_deref().and_then(|t| self.task_type(t));
+        let mut moved = task.clone();
+        if moved
+            .transition_as(task_type, TaskStatus::Review, None)
+            .is_err()
+            || self
+                .check_required_fields(&moved, Some(&task.status))
+                .is_err()
+        {
+            return false;
+        }
+        *task = moved;
+        true
+    }
+
+    /// The task's column, unless `previous` was already in it
+    fn entered_column(&self, task: &Task, previous: Option<&TaskStatus>) -> Option<&Column> {
+        self.columns
+            .iter()
+            .find(|col| col.holds(&task.status))
+            .filter(|col| !previous.is_some_and(|previous| col.holds(previous)))
+    }
+
+    /// Looks up a task type by name (case-insensitive)
+    pub fn task_type(&self, name: &str) -> Option<&TaskType> {
+        self.task_types
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Looks up a saved view by name
+    pub fn view(&self, name: &str) -> Option<&SavedView> {
+        self.views.iter().find(|v| v.name == name)
+    }
+
+    /// Adds a view, rejecting duplicate names
+    pub fn add_view(&mut self, view: SavedView) -> Result<()> {
+        if self.view(&view.name).is_some() {
+            return Err(HlaviError::ConfigError(format!(
+                "View '{}' already exists",
+                view.name
+            )));
+        }
+        self.views.push(view);
+        Ok(())
+    }
+
+    /// Replaces the view called `name`, which may also rename it
+    pub fn update_view(&mut self, name: &str, view: SavedView) -> Result<()> {
+        if view.name != name && self.view(&view.name).is_some() {
+            return Err(HlaviError::ConfigError(format!(
+                "View '{}' already exists",
+                view.name
+            )));
+        }
+        let slot = self
+            .views
+            .iter_mut()
+            .find(|v| v.name == name)
+            .ok_or_else(|| HlaviError::ViewNotFound(name.to_string()))?;
+        *slot = view;
+        Ok(())
+    }
+
+    pub fn remove_view(&mut self, name: &str) -> Result<SavedView> {
+        let index = self
+            .views
+            .iter()
+            .position(|v| v.name == name)
+            .ok_or_else(|| HlaviError::ViewNotFound(name.to_string()))?;
+        Ok(self.views.remove(index))
+    }
+
+    /// Checks that the columns describe a usable board
+    ///
+    /// Every status needs exactly one column (as its primary or an extra
+    /// status), columns need non-empty names, and WIP limits must be greater
+    /// than zero.
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(HlaviError::ConfigError(
+                "Board name must not be empty".to_string(),
+            ));
+        }
+        if !["", "-", "_"].contains(&self.id_format.separator.as_str()) {
+            return Err(HlaviError::ConfigError(format!(
+                "ID separator must be empty, '-' or '_', got '{}'",
+                self.id_format.separator
+            )));
+        }
+        if self.id_format.padding > IdFormat::MAX_PADDING {
+            return Err(HlaviError::ConfigError(format!(
+                "ID padding must be at most {}",
+                IdFormat::MAX_PADDING
+            )));
+        }
+
+        for (index, column) in self.columns.iter().enumerate() {
+            if column.name.trim().is_empty() {
+                return Err(HlaviError::ConfigError(format!(
+                    "Column {} ({}) has an empty name",
+                    index + 1,
+                    column.status
+                )));
+            }
+            if let Some(color) = column.color.as_deref().filter(|c| !is_hex_color(c)) {
+                return Err(HlaviError::ConfigError(format!(
+                    "Column '{}' has invalid color '{}'; use #rgb or #rrggbb",
+                    column.name, color
+                )));
+            }
+            if column.wip_limit == Some(0) {
+                return Err(HlaviError::ConfigError(format!(
+                    "Column '{}' has a WIP limit of 0; omit the limit to disable it",
+                    column.name
+                )));
+            }
+            for (offset, status) in column.statuses().enumerate() {
+                let earlier = self.columns[..index]
+                    .iter()
+                    .find(|other| other.holds(status))
+                    .or_else(|| {
+                        column
+                            .statuses()
+                            .take(offset)
+                            .any(|s| s == status)
+                            .then_some(column)
+                    });
+                if let Some(other) = earlier {
+                    return Err(HlaviError::ConfigError(format!(
+                        "Columns '{}' and '{}' both map to status {}",
+                        other.name, column.name, status
+                    )));
+                }
+            }
+        }
+
+        for (index, view) in self.views.iter().enumerate() {
+            if view.name.trim().is_empty() {
+                return Err(HlaviError::ConfigError(format!(
+                    "View {} has an empty name",
+                    index + 1
+                )));
+            }
+            if self.views[..index].iter().any(|v| v.name == view.name) {
+                return Err(HlaviError::ConfigError(format!(
+                    "View '{}' is defined more than once",
+                    view.name
+                )));
+            }
+        }
+
+        for (index, task_type) in self.task_types.iter().enumerate() {
+            if task_type.name.trim().is_empty() {
+                return Err(HlaviError::ConfigError(format!(
+                    "Task type {} has an empty name",
+                    index + 1
+                )));
+            }
+            if self.task_types[..index]
+                .iter()
+                .any(|t| t.name.eq_ignore_ascii_case(&task_type.name))
+            {
+                return Err(HlaviError::ConfigError(format!(
+                    "Task type '{}' is defined more than once",
+                    task_type.name
+                )));
+            }
+            if task_type.skip_statuses.contains(&TaskStatus::New) {
+                return Err(HlaviError::ConfigError(format!(
+                    "Task type '{}' cannot skip New, where every task starts",
+                    task_type.name
+                )));
+            }
+        }
+
+        for rule in &self.aging {
+            if rule.after_days == 0 {
+                return Err(HlaviError::ConfigError(format!(
+                    "Aging rule '{}' must wait at least one day",
+                    rule.name
+                )));
+            }
+            if !rule.escalate && rule.tag.is_none() {
+                return Err(HlaviError::ConfigError(format!(
+                    "Aging rule '{}' neither escalates nor tags",
+                    rule.name
+                )));
+            }
+        }
+
+        for quota in &self.quotas {
+            if quota.actor.trim().is_empty() {
+                return Err(HlaviError::ConfigError(
+                    "Quota actor must not be empty; use '*' for everyone".to_string(),
+                ));
+            }
+            if quota.window_minutes == 0 {
+                return Err(HlaviError::ConfigError(format!(
+                    "Quota for '{}' must have a window of at least one minute",
+                    quota.actor
+                )));
+            }
+        }
+
+        for (index, connector) in self.connectors.iter().enumerate() {
+            connector.validate()?;
+            if self.connectors[..index]
+                .iter()
+                .any(|c| c.name == connector.name)
+            {
+                return Err(HlaviError::ConfigError(format!(
+                    "Connector '{}' is defined more than once",
+                    connector.name
+                )));
+            }
+        }
+
+        let mut theme_colors = self
+            .theme
+            .tag_colors
+            .values()
+            .chain(self.theme.priority_colors.values());
+        if let Some(color) = theme_colors.find(|c| !is_hex_color(c)) {
+            return Err(HlaviError::ConfigError(format!(
+                "Invalid theme color '{}'; use #rgb or #rrggbb",
+                color
+            )));
+        }
+
+        let missing: Vec<String> = TaskStatus::ALL
+            .iter()
+            .filter(|status| !self.columns.iter().any(|col| col.holds(status)))
+            .map(|status| status.to_string())
+            .collect();
+        if !missing.is_empty() {
+            return Err(HlaviError::ConfigError(format!(
+                "No column for status: {}",
+                missing.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Denormalized task metadata kept on the board
+///
+/// Lets column counts and ordering be computed without loading task files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "BoardEntryRepr")]
+pub struct BoardEntry {
+    pub id: TaskId,
+    pub status: TaskStatus,
+    pub rank: i64,
+}
+
+/// Older boards stored only the task ID; those entries load as `New` until
+/// the task is next saved
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BoardEntryRepr {
+    Full {
+        id: TaskId,
+        status: TaskStatus,
+        #[serde(default)]
+        rank: i64,
+    },
+    Legacy(TaskId),
+}
+
+impl From<BoardEntryRepr> for BoardEntry {
+    fn from(repr: BoardEntryRepr) -> Self {
+        match repr {
+            BoardEntryRepr::Full { id, status, rank } => Self { id, status, rank },
+            BoardEntryRepr::Legacy(id) => Self {
+                id,
+                status: TaskStatus::New,
+                rank: 0,
+            },
+        }
+    }
+}
+
+impl BoardEntry {
+    pub fn from_task(task: &Task) -> Self {
+        Self {
+            id: task.id.clone(),
+            status: task.status.clone(),
+            rank: task.rank,
+        }
+    }
+}
+
+/// A task created with an idempotency key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdempotencyRecord {
+    pub task_id: TaskId,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A block of task numbers kept free for an import, such as 1-500 for tasks
+/// coming from Jira
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReservedRange {
+    /// First reserved number
+    pub start: u32,
+    /// Last reserved number, inclusive
+    pub end: u32,
+    pub label: String,
+}
+
+impl ReservedRange {
+    pub fn contains(&self, number: u32) -> bool {
+        (self.start..=self.end).contains(&number)
+    }
 }
 
 /// Kanban board state
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Board {
     pub config: BoardConfig,
-    pub tasks: HashMap<String, TaskId>,
+    pub tasks: HashMap<String, BoardEntry>,
     pub next_task_number: u32,
+    /// Recent creations by idempotency key, so a retried request returns the
+    /// task it already created
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub idempotency_keys: BTreeMap<String, IdempotencyRecord>,
+    /// Numbers that native creation skips, left for imports
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reserved_ranges: Vec<ReservedRange>,
+    /// Flow counters by column name, kept up to date as tasks move
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_counters: BTreeMap<String, ColumnCounter>,
+}
+
+/// Running totals of the tasks moving through a column
+///
+/// Counters start when a column first sees a change; a board saved before
+/// counters existed starts each column at its current count.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnCounter {
+    /// Tasks that came into the column, including new ones
+    pub entries: u64,
+    /// Tasks that left the column, including deleted ones
+    pub exits: u64,
+    /// Tasks in the column now
+    pub current: usize,
+    /// Most tasks the column has held at once
+    pub max: usize,
+}
+
+impl ColumnCounter {
+    fn enter(&mut self) {
+        self.entries += 1;
+        self.current += 1;
+        self.max = self.max.max(self.current);
+    }
+
+    fn exit(&mut self) {
+        self.exits += 1;
+        self.current = self.current.saturating_sub(1);
+    }
 }
 
 impl Board {
+    /// How long an idempotency key is remembered
+    pub const IDEMPOTENCY_WINDOW_HOURS: i64 = 24;
+
     pub fn new(config: BoardConfig) -> Self {
         Self {
             config,
             tasks: HashMap::new(),
             next_task_number: 1,
+            idempotency_keys: BTreeMap::new(),
+            reserved_ranges: Vec::new(),
+            column_counters: BTreeMap::new(),
         }
     }
 
+    /// The task created with `key`, if the key was used within the window
+    pub fn idempotent_task(&self, key: &str, now: DateTime<Utc>) -> Option<&TaskId> {
+        self.idempotency_keys
+            .get(key)
+            .filter(|record| now - record.created_at < Self::idempotency_window())
+            .map(|record| &record.task_id)
+    }
+
+    /// Remembers that `key` created `task_id`, forgetting expired keys
+    pub fn record_idempotency_key(&mut self, key: &str, task_id: TaskId, now: DateTime<Utc>) {
+        self.idempotency_keys
+            .retain(|_, record| now - record.created_at < Self::idempotency_window());
+        self.idempotency_keys.insert(
+            key.to_string(),
+            IdempotencyRecord {
+                task_id,
+                created_at: now,
+            },
+        );
+    }
+
+    fn idempotency_window() -> Duration {
+        Duration::hours(Self::IDEMPOTENCY_WINDOW_HOURS)
+    }
+
     /// Generates the next task ID
     pub fn next_task_id(&mut self) -> TaskId {
-        let id = TaskId::new(self.next_task_number);
+        self.next_task_id_with_prefix(TaskId::DEFAULT_PREFIX)
+    }
+
+    /// Generates the next task ID using a project-specific prefix, skipping
+    /// reserved numbers
+    pub fn next_task_id_with_prefix(&mut self, prefix: &str) -> TaskId {
+        while let Some(range) = self.reserved_range(self.next_task_number) {
+            self.next_task_number = range.end.saturating_add(1);
+        }
+        let id = TaskId::with_prefix(prefix, self.next_task_number);
         self.next_task_number += 1;
         id
     }
 
-    /// Adds a task to the board tracking
-    pub fn add_task(&mut self, task_id: TaskId) {
-        self.tasks.insert(task_id.as_str().to_string(), task_id);
+    /// Keeps `start..=end` free for an import under `label`
+    ///
+    /// Fails with `HlaviError::Validation` if the range is empty, starts at
+    /// zero, overlaps another reservation or covers a task on the board.
+    pub fn reserve_ids(&mut self, start: u32, end: u32, label: &str) -> Result<()> {
+        let invalid = |reason: String| Err(HlaviError::Validation(reason));
+        if start == 0 || start > end {
+            return invalid(format!("Invalid ID range {}-{}", start, end));
+        }
+        if label.trim().is_empty() {
+            return invalid("A reserved ID range needs a label".to_string());
+        }
+        let range = ReservedRange {
+            start,
+            end,
+            label: label.trim().to_string(),
+        };
+        if let Some(other) = self
+            .reserved_ranges
+            .iter()
+            .find(|other| other.start <= end && start <= other.end)
+        {
+            return invalid(format!(
+                "ID range {}-{} overlaps '{}' ({}-{})",
+                start, end, other.label, other.start, other.end
+            ));
+        }
+        if let Some(taken) = self
+            .tasks
+            .keys()
+            .filter_map(|id| TaskId::from_str(id).ok())
+            .find(|id| id.number().is_some_and(|n| range.contains(n)))
+        {
+            return invalid(format!(
+                "ID range {}-{} includes existing task {}",
+                start, end, taken
+            ));
+        }
+        self.reserved_ranges.push(range);
+        Ok(())
+    }
+
+    /// Drops the reservation named `label`, returning it
+    pub fn release_ids(&mut self, label: &str) -> Option<ReservedRange> {
+        let index = self
+            .reserved_ranges
+            .iter()
+            .position(|range| range.label.eq_ignore_ascii_case(label))?;
+        Some(self.reserved_ranges.remove(index))
+    }
+
+    /// The reservation covering `number`, if any
+    pub fn reserved_range(&self, number: u32) -> Option<&ReservedRange> {
+        self.reserved_ranges
+            .iter()
+            .find(|range| range.contains(number))
+    }
+
+    /// Adds a task to the board tracking, or refreshes its metadata
+    ///
+    /// Returns true if the board changed.
+    pub fn add_task(&mut self, task: &Task) -> bool {
+        let entry = BoardEntry::from_task(task);
+        let previous = self.tasks.get(task.id.as_str());
+        if previous == Some(&entry) {
+            return false;
+        }
+        let from = previous.and_then(|p| self.column_name(&p.status));
+        let to = self.column_name(&entry.status);
+        if from != to {
+            self.move_counters(from.as_deref(), to.as_deref());
+        }
+        self.tasks.insert(task.id.as_str().to_string(), entry);
+        true
+    }
+
+    /// Stops tracking a task, returning its entry if it was tracked
+    pub fn remove_task(&mut self, task_id: &TaskId) -> Option<BoardEntry> {
+        let status = self.tasks.get(task_id.as_str())?.status.clone();
+        let from = self.column_name(&status);
+        self.move_counters(from.as_deref(), None);
+        self.tasks.remove(task_id.as_str())
+    }
+
+    /// The flow counter of each column, in column order
+    pub fn flow_counters(&self) -> Vec<(&Column, ColumnCounter)> {
+        self.column_counts()
+            .into_iter()
+            .map(|(column, count)| {
+                let counter =
+                    self.column_counters
+                        .get(&column.name)
+                        .copied()
+                        .unwrap_or(ColumnCounter {
+                            current: count,
+                            max: count,
+                            ..ColumnCounter::default()
+                        });
+                (column, counter)
+            })
+            .collect()
+    }
+
+    fn column_name(&self, status: &TaskStatus) -> Option<String> {
+        self.get_column_for_status(status).map(|c| c.name.clone())
+    }
+
+    /// Counts a task leaving `from` and entering `to`, before the board's
+    /// entries change
+    fn move_counters(&mut self, from: Option<&str>, to: Option<&str>) {
+        for name in [from, to].into_iter().flatten() {
+            if !self.column_counters.contains_key(name) {
+                let current = self
+                    .config
+                    .columns
+                    .iter()
+                    .find(|c| c.name == name)
+                    .map_or(0, |c| c.statuses().map(|s| self.count_in_status(s)).sum());
+                self.column_counters.insert(
+                    name.to_string(),
+                    ColumnCounter {
+                        current,
+                        max: current,
+                        ..ColumnCounter::default()
+                    },
+                );
+            }
+        }
+        if let Some(counter) = from.and_then(|name| self.column_counters.get_mut(name)) {
+            counter.exit();
+        }
+        if let Some(counter) = to.and_then(|name| self.column_counters.get_mut(name)) {
+            counter.enter();
+        }
+    }
+
+    /// Number of tracked tasks in the given status
+    pub fn count_in_status(&self, status: &TaskStatus) -> usize {
+        self.tasks
+            .values()
+            .filter(|entry| &entry.status == status)
+            .count()
+    }
+
+    /// Task counts per column, in column order
+    pub fn column_counts(&self) -> Vec<(&Column, usize)> {
+        self.config
+            .columns
+            .iter()
+            .map(|col| {
+                let count = col.statuses().map(|s| self.count_in_status(s)).sum();
+                (col, count)
+            })
+            .collect()
+    }
+
+    /// IDs of the tasks in a status, highest rank first
+    pub fn task_ids_in_status(&self, status: &TaskStatus) -> Vec<TaskId> {
+        let mut entries: Vec<&BoardEntry> = self
+            .tasks
+            .values()
+            .filter(|entry| &entry.status == status)
+            .collect();
+        entries.sort_by(|a, b| {
+            b.rank
+                .cmp(&a.rank)
+                .then_with(|| a.id.as_str().cmp(b.id.as_str()))
+        });
+        entries.into_iter().map(|entry| entry.id.clone()).collect()
     }
 
     /// Gets the column configuration for a status
     pub fn get_column_for_status(&self, status: &TaskStatus) -> Option<&Column> {
-        self.config.columns.iter().find(|col| &col.status == status)
+        self.config.columns.iter().find(|col| col.holds(status))
     }
 
     /// Checks if agent mode is enabled for a status
@@ -119,6 +1121,138 @@ impl Default for Board {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_default_config_is_valid() {
+        assert!(BoardConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_config_validation_errors() {
+        let expect_error = |config: BoardConfig, needle: &str| match config.validate() {
+            Err(HlaviError::ConfigError(msg)) => assert!(msg.contains(needle), "{msg}"),
+            other => panic!("expected config error, got {other:?}"),
+        };
+
+        let mut config = BoardConfig::default();
+        config.columns[1].status = TaskStatus::New;
+        expect_error(config, "both map to status New");
+
+        let mut config = BoardConfig::default();
+        config.columns[2].name = "  ".to_string();
+        expect_error(config, "empty name");
+
+        let mut config = BoardConfig::default();
+        config
+            .columns
+            .retain(|col| col.status != TaskStatus::Review);
+        expect_error(config, "No column for status: Review");
+
+        let mut config = BoardConfig::default();
+        config.columns[2] = config.columns[2].clone().with_wip_limit(0);
+        expect_error(config, "WIP limit");
+
+        let mut config = BoardConfig::default();
+        config.id_format.separator = "/".to_string();
+        expect_error(config, "ID separator");
+
+        let mut config = BoardConfig::default();
+        config.aging.push(AgingRule::new("Stale".to_string(), 3));
+        expect_error(config, "neither escalates nor tags");
+
+        let mut config = BoardConfig {
+            task_types: TaskType::defaults(),
+            ..BoardConfig::default()
+        };
+        config.task_types.push(TaskType::new("Bug"));
+        expect_error(config, "defined more than once");
+    }
+
+    #[test]
+    fn test_presets_are_valid() {
+        for preset in [
+            Preset::Standard,
+            Preset::Simple,
+            Preset::Scrum,
+            Preset::SupportQueue,
+            Preset::AgentPipeline,
+        ] {
+            let config = BoardConfig::preset(preset);
+            assert!(config.validate().is_ok(), "{preset:?} is invalid");
+        }
+    }
+
+    #[test]
+    fn test_theming_metadata() {
+        let mut config = BoardConfig::default();
+        config.columns[0] = config.columns[0]
+            .clone()
+            .with_color("#1f6feb")
+            .with_icon("inbox");
+        config.theme.set_tag_color("Bug", "#d73a49".to_string());
+        config
+            .theme
+            .priority_colors
+            .insert(Priority::Critical, "#f00".to_string());
+        assert!(config.validate().is_ok());
+        assert_eq!(config.theme.tag_color("bug"), Some("#d73a49"));
+
+        let json = serde_json::to_string(&config).unwrap();
+        let loaded: BoardConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.columns[0].icon.as_deref(), Some("inbox"));
+        assert_eq!(
+            loaded.theme.priority_color(Priority::Critical),
+            Some("#f00")
+        );
+
+        config.columns[1].color = Some("blue".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_view_crud() {
+        use crate::domain::view::ViewFilter;
+
+        let mut config = BoardConfig::default();
+        config
+            .add_view(SavedView::new("Mine", ViewFilter::default()))
+            .unwrap();
+        assert!(config
+            .add_view(SavedView::new("Mine", ViewFilter::default()))
+            .is_err());
+
+        config
+            .update_view(
+                "Mine",
+                SavedView::new("Review queue", ViewFilter::default()),
+            )
+            .unwrap();
+        assert!(config.view("Mine").is_none());
+        assert!(config.view("Review queue").is_some());
+        assert!(config.validate().is_ok());
+
+        config.remove_view("Review queue").unwrap();
+        assert!(matches!(
+            config.remove_view("Review queue"),
+            Err(HlaviError::ViewNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_collapsed_columns() {
+        let mut board = Board::new(BoardConfig::preset(Preset::Simple));
+        let mut task = Task::new(board.next_task_id(), "Review me".to_string());
+        task.status = TaskStatus::Review;
+        board.add_task(&task);
+
+        let doing = board.get_column_for_status(&TaskStatus::Review).unwrap();
+        assert_eq!(doing.name, "Doing");
+        assert_eq!(board.column_counts()[1].1, 1);
+
+        let mut config = BoardConfig::preset(Preset::Simple);
+        config.columns[0].extra_statuses.push(TaskStatus::Review);
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_board_creation() {
         let board = Board::default();
@@ -137,6 +1271,183 @@ mod tests {
         assert_eq!(id2.as_str(), "HLA2");
     }
 
+    #[test]
+    fn test_board_tracks_status_and_rank() {
+        let mut board = Board::default();
+        let mut first = Task::new(board.next_task_id(), "First".to_string());
+        let mut second = Task::new(board.next_task_id(), "Second".to_string());
+        second.rank = 5;
+
+        assert!(board.add_task(&first));
+        assert!(board.add_task(&second));
+        assert!(!board.add_task(&first));
+        assert_eq!(board.count_in_status(&TaskStatus::New), 2);
+        assert_eq!(
+            board.task_ids_in_status(&TaskStatus::New),
+            vec![second.id.clone(), first.id.clone()]
+        );
+
+        first.transition_to(TaskStatus::Open, None).unwrap();
+        assert!(board.add_task(&first));
+        let counts = board.column_counts();
+        assert_eq!(counts[0].1, 1);
+        assert_eq!(counts[1].1, 1);
+    }
+
+    #[test]
+    fn test_remove_task() {
+        let mut board = Board::default();
+        let task = Task::new(board.next_task_id(), "Gone".to_string());
+        board.add_task(&task);
+
+        assert!(board.remove_task(&task.id).is_some());
+        assert!(board.remove_task(&task.id).is_none());
+        assert_eq!(board.count_in_status(&TaskStatus::New), 0);
+    }
+
+    #[test]
+    fn test_column_flow_counters() {
+        let mut board = Board::default();
+        let mut legacy = Task::new(board.next_task_id(), "Before counters".to_string());
+        board.add_task(&legacy);
+        // As if loaded from a board saved before counters existed
+        board.column_counters.clear();
+
+        let mut task = Task::new(board.next_task_id(), "Flow".to_string());
+        board.add_task(&task);
+        let new = board.column_counters["New"];
+        assert_eq!((new.entries, new.current, new.max), (1, 2, 2));
+
+        task.transition_to(TaskStatus::Open, None).unwrap();
+        board.add_task(&task);
+        legacy.transition_to(TaskStatus::Open, None).unwrap();
+        board.add_task(&legacy);
+        task.rank = 3;
+        board.add_task(&task);
+        board.remove_task(&task.id);
+
+        let new = board.column_counters["New"];
+        assert_eq!((new.entries, new.exits, new.current, new.max), (1, 2, 0, 2));
+        let open = board.column_counters["Open"];
+        assert_eq!(
+            (open.entries, open.exits, open.current, open.max),
+            (2, 1, 1, 2)
+        );
+        let flow = board.flow_counters();
+        assert_eq!(flow[1].1, open);
+        assert_eq!(flow[2].1, ColumnCounter::default());
+
+        let json = serde_json::to_string(&board).unwrap();
+        let loaded: Board = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.column_counters, board.column_counters);
+    }
+
+    #[test]
+    fn test_definition_of_done() {
+        let mut config = BoardConfig::default();
+        config.columns[4].definition_of_done = Some(DefinitionOfDone {
+            items: vec!["Docs updated".to_string(), "Tests pass".to_string()],
+            checklist: None,
+        });
+        config.columns[2].definition_of_done = Some(DefinitionOfDone {
+            items: vec!["Branch created".to_string()],
+            checklist: Some("Setup".to_string()),
+        });
+
+        let mut task = Task::new(TaskId::new(1), "Ship it".to_string());
+        task.add_acceptance_criterion("tests pass".to_string());
+        task.status = TaskStatus::Review;
+        assert_eq!(
+            config.apply_definition_of_done(&mut task, Some(&TaskStatus::InProgress)),
+            1
+        );
+        assert_eq!(task.acceptance_criteria.len(), 2);
+        // Moving within the column or re-entering adds nothing new
+        assert_eq!(
+            config.apply_definition_of_done(&mut task, Some(&TaskStatus::Review)),
+            0
+        );
+        assert_eq!(config.apply_definition_of_done(&mut task, None), 0);
+
+        task.status = TaskStatus::InProgress;
+        config.apply_definition_of_done(&mut task, Some(&TaskStatus::Review));
+        assert_eq!(task.checklist("setup").unwrap().items.len(), 1);
+    }
+
+    #[test]
+    fn test_reserved_ids_are_skipped() {
+        let mut board = Board::new(BoardConfig::default());
+        let first = Task::new(board.next_task_id(), "Native".to_string());
+        board.add_task(&first);
+
+        assert!(board.reserve_ids(1, 3, "jira").is_err());
+        assert!(board.reserve_ids(5, 4, "jira").is_err());
+        board.reserve_ids(2, 4, "jira").unwrap();
+        board.reserve_ids(6, 6, "asana").unwrap();
+        assert!(board.reserve_ids(4, 5, "linear").is_err());
+
+        let ids: Vec<u32> = (0..3)
+            .map(|_| board.next_task_id().number().unwrap())
+            .collect();
+        assert_eq!(ids, vec![5, 7, 8]);
+        assert_eq!(board.reserved_range(3).unwrap().label, "jira");
+
+        assert!(board.release_ids("JIRA").is_some());
+        assert!(board.reserved_range(3).is_none());
+    }
+
+    #[test]
+    fn test_auto_review() {
+        let mut config = BoardConfig::default();
+        let mut task = Task::new(TaskId::new(1), "Finish".to_string());
+        task.status = TaskStatus::InProgress;
+        let first = task.add_acceptance_criterion("a".to_string());
+        let last = task.add_acceptance_criterion("b".to_string());
+        task.toggle_criterion(first).unwrap();
+        let before = task.clone();
+        task.toggle_criterion(last).unwrap();
+
+        assert!(!config.apply_auto_review(&before, &mut task.clone()));
+        config.auto_review = true;
+        config.task_types = TaskType::defaults();
+        let mut chore = task.clone();
+        chore.task_type = Some("chore".to_string());
+        assert!(!config.apply_auto_review(&before, &mut chore));
+        // Tasks that were already complete are left where they are
+        assert!(!config.apply_auto_review(&task.clone(), &mut task.clone()));
+
+        assert!(config.apply_auto_review(&before, &mut task));
+        assert_eq!(task.status, TaskStatus::Review);
+    }
+
+    #[test]
+    fn test_idempotency_keys_expire() {
+        let mut board = Board::default();
+        let now = Utc::now();
+        board.record_idempotency_key("req-1", TaskId::new(1), now);
+        assert_eq!(board.idempotent_task("req-1", now), Some(&TaskId::new(1)));
+        assert_eq!(board.idempotent_task("req-2", now), None);
+
+        let later = now + Duration::hours(Board::IDEMPOTENCY_WINDOW_HOURS);
+        assert_eq!(board.idempotent_task("req-1", later), None);
+        board.record_idempotency_key("req-2", TaskId::new(2), later);
+        assert_eq!(board.idempotency_keys.len(), 1);
+    }
+
+    #[test]
+    fn test_legacy_board_entries_load() {
+        let json = r#"{
+            "config": {"name": "Old", "columns": []},
+            "tasks": {"HLA1": "HLA1", "HLA2": {"id": "HLA2", "status": "done", "rank": 3}},
+            "next_task_number": 3
+        }"#;
+        let board: Board = serde_json::from_str(json).unwrap();
+
+        assert_eq!(board.tasks["HLA1"].status, TaskStatus::New);
+        assert_eq!(board.tasks["HLA2"].status, TaskStatus::Done);
+        assert_eq!(board.tasks["HLA2"].rank, 3);
+    }
+
     #[test]
     fn test_agent_configuration() {
         let board = Board::default();
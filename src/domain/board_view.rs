@@ -0,0 +1,216 @@
+//! Paging through long columns.
+//!
+//! A [`BoardView`] windows each column of a [`Board`] using only the
+//! board's denormalized entries, so a TUI can show a page of a column with
+//! hundreds of tasks and load just the tasks on screen.
+
+use crate::domain::{
+    board::{Board, BoardEntry},
+    task::TaskId,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Tasks shown per column when no viewport is given
+pub const DEFAULT_PAGE_SIZE: usize = 25;
+
+/// The visible slice of a column: `page_size` tasks starting at `offset`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Viewport {
+    pub offset: usize,
+    pub page_size: usize,
+}
+
+impl Viewport {
+    /// The first page
+    pub fn first(page_size: usize) -> Self {
+        Self {
+            offset: 0,
+            page_size: page_size.max(1),
+        }
+    }
+
+    /// The page after this one, if there are tasks past it
+    pub fn next(self, total: usize) -> Option<Self> {
+        let offset = self.offset + self.page_size;
+        (offset < total).then_some(Self { offset, ..self })
+    }
+
+    /// The page before this one, unless this is the first
+    pub fn prev(self) -> Option<Self> {
+        (self.offset > 0).then(|| Self {
+            offset: self.offset.saturating_sub(self.page_size),
+            ..self
+        })
+    }
+
+    /// The viewport moved back onto the last page if it is past the end,
+    /// as happens when tasks leave a column
+    pub fn clamp(self, total: usize) -> Self {
+        let page_size = self.page_size.max(1);
+        let last = total.saturating_sub(1) / page_size * page_size;
+        Self {
+            offset: self.offset.min(last),
+            page_size,
+        }
+    }
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self::first(DEFAULT_PAGE_SIZE)
+    }
+}
+
+/// One page of a column
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnWindow {
+    /// Column name
+    pub column: String,
+    /// The viewport actually shown, after clamping
+    pub viewport: Viewport,
+    /// Tasks on the page, highest rank first
+    pub task_ids: Vec<TaskId>,
+    /// Tasks in the whole column
+    pub total: usize,
+}
+
+impl ColumnWindow {
+    /// Viewport for the next page, if any
+    pub fn next_cursor(&self) -> Option<Viewport> {
+        self.viewport.next(self.total)
+    }
+
+    /// Viewport for the previous page, if any
+    pub fn prev_cursor(&self) -> Option<Viewport> {
+        self.viewport.prev()
+    }
+
+    /// Zero-based index of the page shown
+    pub fn page(&self) -> usize {
+        self.viewport.offset / self.viewport.page_size
+    }
+
+    /// Number of pages, at least one even for an empty column
+    pub fn page_count(&self) -> usize {
+        self.total.div_ceil(self.viewport.page_size).max(1)
+    }
+}
+
+/// A page of every column on the board, in column order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoardView {
+    pub columns: Vec<ColumnWindow>,
+}
+
+impl BoardView {
+    /// Windows every column of `board`
+    ///
+    /// `viewports` holds the viewport per column name; columns without one
+    /// show their first page of `default_page_size` tasks.
+    pub fn new(
+        board: &Board,
+        viewports: &BTreeMap<String, Viewport>,
+        default_page_size: usize,
+    ) -> Self {
+        let columns = board
+            .config
+            .columns
+            .iter()
+            .map(|column| {
+                let mut entries: Vec<&BoardEntry> = board
+                    .tasks
+                    .values()
+                    .filter(|entry| column.holds(&entry.status))
+                    .collect();
+                let total = entries.len();
+                let viewport = viewports
+                    .get(&column.name)
+                    .copied()
+                    .unwrap_or_else(|| Viewport::first(default_page_size))
+                    .clamp(total);
+                entries.sort_by(|a, b| {
+                    b.rank
+                        .cmp(&a.rank)
+                        .then_with(|| a.id.as_str().cmp(b.id.as_str()))
+                });
+                ColumnWindow {
+                    column: column.name.clone(),
+                    viewport,
+                    task_ids: entries
+                        .into_iter()
+                        .skip(viewport.offset)
+                        .take(viewport.page_size)
+                        .map(|entry| entry.id.clone())
+                        .collect(),
+                    total,
+                }
+            })
+            .collect();
+        Self { columns }
+    }
+
+    pub fn column(&self, name: &str) -> Option<&ColumnWindow> {
+        self.columns.iter().find(|window| window.column == name)
+    }
+
+    /// IDs of every task on screen, column by column
+    pub fn visible_ids(&self) -> Vec<TaskId> {
+        self.columns
+            .iter()
+            .flat_map(|window| window.task_ids.iter().cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{BoardConfig, Task, TaskStatus};
+
+    #[test]
+    fn test_windows_and_cursors() {
+        let mut board = Board::new(BoardConfig::default());
+        for number in 1..=7 {
+            let mut task = Task::new(TaskId::new(number), format!("Task {}", number));
+            task.rank = number as i64;
+            board.add_task(&task);
+        }
+        let mut done = Task::new(TaskId::new(8), "Shipped".to_string());
+        done.status = TaskStatus::Done;
+        board.add_task(&done);
+
+        let view = BoardView::new(&board, &BTreeMap::new(), 3);
+        let new = view.column("New").unwrap();
+        assert_eq!(new.total, 7);
+        assert_eq!(new.page_count(), 3);
+        assert_eq!(
+            new.task_ids,
+            vec![TaskId::new(7), TaskId::new(6), TaskId::new(5)]
+        );
+        assert_eq!(new.prev_cursor(), None);
+        let next = new.next_cursor().unwrap();
+        assert_eq!(next.offset, 3);
+
+        let last = Viewport {
+            offset: 6,
+            page_size: 3,
+        };
+        let viewports = BTreeMap::from([("New".to_string(), last)]);
+        let view = BoardView::new(&board, &viewports, 3);
+        let new = view.column("New").unwrap();
+        assert_eq!(new.page(), 2);
+        assert_eq!(new.task_ids, vec![TaskId::new(1)]);
+        assert_eq!(new.next_cursor(), None);
+        assert_eq!(new.prev_cursor(), Some(next));
+        assert_eq!(view.column("Done").unwrap().total, 1);
+
+        // A viewport past the end falls back to the last page
+        let stale = Viewport {
+            offset: 40,
+            page_size: 3,
+        };
+        assert_eq!(stale.clamp(7), last);
+        assert_eq!(Viewport::first(3).clamp(0).offset, 0);
+    }
+}
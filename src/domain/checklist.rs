@@ -0,0 +1,154 @@
+use crate::{
+    domain::task::{parse_checklist_line, render_checklist_line},
+    error::HlaviError,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A single checklist entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    pub id: usize,
+    pub text: String,
+    pub completed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl ChecklistItem {
+    pub fn new(id: usize, text: String) -> Self {
+        Self {
+            id,
+            text,
+            completed: false,
+            completed_at: None,
+        }
+    }
+
+    pub fn mark_completed(&mut self) {
+        self.completed = true;
+        self.completed_at = Some(Utc::now());
+    }
+
+    pub fn mark_incomplete(&mut self) {
+        self.completed = false;
+        self.completed_at = None;
+    }
+
+    pub fn toggle(&mut self) {
+        if self.completed {
+            self.mark_incomplete();
+        } else {
+            self.mark_completed();
+        }
+    }
+}
+
+/// A named checklist on a task (e.g., "Deployment steps"), separate from acceptance criteria
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checklist {
+    pub name: String,
+    pub items: Vec<ChecklistItem>,
+}
+
+impl Checklist {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            items: Vec::new(),
+        }
+    }
+
+    /// Adds an item, returning its ID (unique within the checklist)
+    pub fn add_item(&mut self, text: String) -> usize {
+        let id = self.items.iter().map(|i| i.id).max().unwrap_or(0) + 1;
+        self.items.push(ChecklistItem::new(id, text));
+        id
+    }
+
+    /// Removes an item by ID
+    pub fn remove_item(&mut self, id: usize) -> Result<ChecklistItem, HlaviError> {
+        let pos = self
+            .items
+            .iter()
+            .position(|i| i.id == id)
+            .ok_or(HlaviError::ChecklistItemNotFound(id))?;
+        Ok(self.items.remove(pos))
+    }
+
+    /// Toggles an item, returning its new completion state
+    pub fn toggle_item(&mut self, id: usize) -> Result<bool, HlaviError> {
+        let item = self
+            .items
+            .iter_mut()
+            .find(|i| i.id == id)
+            .ok_or(HlaviError::ChecklistItemNotFound(id))?;
+        item.toggle();
+        Ok(item.completed)
+    }
+
+    /// Returns `(completed, total)` item counts
+    pub fn progress(&self) -> (usize, usize) {
+        let completed = self.items.iter().filter(|i| i.completed).count();
+        (completed, self.items.len())
+    }
+
+    /// Checks if the checklist has items and all are completed
+    pub fn is_complete(&self) -> bool {
+        !self.items.is_empty() && self.items.iter().all(|i| i.completed)
+    }
+
+    /// Builds a checklist from Markdown task-list lines, ignoring other lines
+    pub fn from_markdown(name: String, markdown: &str) -> Self {
+        let mut checklist = Self::new(name);
+        for (completed, text) in markdown.lines().filter_map(parse_checklist_line) {
+            let id = checklist.add_item(text.to_string());
+            if completed {
+                checklist.toggle_item(id).expect("item was just added");
+            }
+        }
+        checklist
+    }
+
+    /// Renders the items as a Markdown checklist
+    pub fn to_markdown(&self) -> String {
+        self.items
+            .iter()
+            .map(|i| render_checklist_line(i.completed, &i.text))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checklist_progress() {
+        let mut checklist = Checklist::new("Deployment steps".to_string());
+        assert!(!checklist.is_complete());
+
+        let build = checklist.add_item("Build image".to_string());
+        let deploy = checklist.add_item("Deploy".to_string());
+        assert_eq!(checklist.progress(), (0, 2));
+
+        assert!(checklist.toggle_item(build).unwrap());
+        assert_eq!(checklist.progress(), (1, 2));
+
+        checklist.toggle_item(deploy).unwrap();
+        assert!(checklist.is_complete());
+
+        checklist.remove_item(build).unwrap();
+        assert_eq!(checklist.progress(), (1, 1));
+        assert!(checklist.toggle_item(build).is_err());
+    }
+
+    #[test]
+    fn test_checklist_markdown_round_trip() {
+        let checklist =
+            Checklist::from_markdown("Release".to_string(), "- [x] Tag\n- [ ] Publish\nnotes");
+        assert_eq!(checklist.progress(), (1, 2));
+        assert_eq!(checklist.to_markdown(), "- [x] Tag\n- [ ] Publish");
+    }
+}
@@ -0,0 +1,326 @@
+use crate::{
+    domain::{
+        board::{AgentMode, Preset},
+        identity::IdentityMap,
+        sorting::{SortField, SortOrder},
+        task::{Task, TaskId, TaskStatus},
+    },
+    error::{HlaviError, Result},
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Project-level settings read from `.hlavi/config.toml`
+///
+/// Every key is optional; missing keys fall back to the defaults below.
+///
+/// ```toml
+/// id_prefix = "HLA"
+/// preset = "scrum"
+/// default_sort = "rank"
+/// default_sort_order = "desc"
+///
+/// [archive]
+/// after_days = 30
+/// statuses = ["done", "closed"]
+///
+/// [trash]
+/// purge_after_days = 30
+///
+/// [agent]
+/// mode = "attended"
+/// assign_new_tasks = false
+///
+/// [[identities]]
+/// name = "Ana Lee"
+/// emails = ["ana@example.com"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectConfig {
+    /// Prefix for newly minted task IDs (e.g. `HLA` gives `HLA1`)
+    pub id_prefix: String,
+    /// Earlier prefixes still accepted when parsing IDs, e.g. after a rename
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub legacy_prefixes: Vec<String>,
+    /// Board layout created by `initialize()`
+    pub preset: Preset,
+    /// Sort field applied when listing tasks (see `SortField`)
+    pub default_sort: String,
+    /// `asc` or `desc`
+    pub default_sort_order: String,
+    pub archive: ArchivePolicy,
+    pub trash: TrashPolicy,
+    pub agent: AgentDefaults,
+    /// People known under several names; see [`IdentityMap`]
+    #[serde(skip_serializing_if = "IdentityMap::is_empty")]
+    pub identities: IdentityMap,
+}
+
+/// When finished tasks become eligible for archiving
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArchivePolicy {
+    /// Days since the last update before a task is archived; `None` disables archiving
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_days: Option<u32>,
+    pub statuses: Vec<TaskStatus>,
+}
+
+/// When trashed tasks are deleted for good
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrashPolicy {
+    /// Days in the trash before a task is purged; `None` keeps trashed tasks forever
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purge_after_days: Option<u32>,
+}
+
+/// Defaults for agent-enabled columns and new tasks
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentDefaults {
+    /// Mode for agent-enabled columns of a freshly initialized board,
+    /// overriding the preset's choice
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<AgentMode>,
+    /// Whether new tasks start with an agent assigned
+    pub assign_new_tasks: bool,
+}
+
+impl Default for ProjectConfig {
+    fn default() -> Self {
+        Self {
+            id_prefix: "HLA".to_string(),
+            legacy_prefixes: Vec::new(),
+            preset: Preset::default(),
+            default_sort: "id".to_string(),
+            default_sort_order: "asc".to_string(),
+            archive: ArchivePolicy::default(),
+            trash: TrashPolicy::default(),
+            agent: AgentDefaults::default(),
+            identities: IdentityMap::default(),
+        }
+    }
+}
+
+impl Default for ArchivePolicy {
+    fn default() -> Self {
+        Self {
+            after_days: None,
+            statuses: vec![TaskStatus::Done, TaskStatus::Closed],
+        }
+    }
+}
+
+impl ArchivePolicy {
+    /// Checks whether an active task has been finished long enough to archive
+    pub fn is_due(&self, task: &Task, now: DateTime<Utc>) -> bool {
+        self.after_days.is_some_and(|days| {
+            task.is_active()
+                && self.statuses.contains(&task.status)
+                && now - task.updated_at >= Duration::days(days.into())
+        })
+    }
+}
+
+impl TrashPolicy {
+    /// Checks whether a trashed task has been in the trash long enough to purge
+    pub fn is_due(&self, task: &Task, now: DateTime<Utc>) -> bool {
+        match (self.purge_after_days, task.trashed_at) {
+            (Some(days), Some(trashed_at)) => now - trashed_at >= Duration::days(days.into()),
+            _ => false,
+        }
+    }
+}
+
+impl ProjectConfig {
+    /// Parses and validates a config.toml document
+    pub fn from_toml(contents: &str) -> Result<Self> {
+        let config: Self = toml::from_str(contents)
+            .map_err(|e| HlaviError::ConfigError(format!("Invalid config.toml: {}", e)))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Renders the config as a config.toml document
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self)
+            .map_err(|e| HlaviError::ConfigError(format!("Cannot write config.toml: {}", e)))
+    }
+
+    /// Checks that every setting is usable
+    pub fn validate(&self) -> Result<()> {
+        for prefix in std::iter::once(&self.id_prefix).chain(&self.legacy_prefixes) {
+            if prefix.is_empty()
+                || prefix.len() > 10
+                || !prefix.chars().all(|c| c.is_ascii_alphabetic())
+            {
+                return Err(HlaviError::ConfigError(format!(
+                    "ID prefixes must be 1-10 ASCII letters, got '{}'",
+                    prefix
+                )));
+            }
+        }
+        self.sort()?;
+        if self.archive.after_days == Some(0) {
+            return Err(HlaviError::ConfigError(
+                "archive.after_days must be greater than zero".to_string(),
+            ));
+        }
+        if self.trash.purge_after_days == Some(0) {
+            return Err(HlaviError::ConfigError(
+                "trash.purge_after_days must be greater than zero".to_string(),
+            ));
+        }
+        if self.archive.after_days.is_some() && self.archive.statuses.is_empty() {
+            return Err(HlaviError::ConfigError(
+                "archive.statuses must not be empty when archiving is enabled".to_string(),
+            ));
+        }
+        self.identities.validate()
+    }
+
+    /// Every prefix accepted for this project, current prefix first
+    pub fn id_prefixes(&self) -> Vec<&str> {
+        std::iter::once(self.id_prefix.as_str())
+            .chain(self.legacy_prefixes.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// Parses a task ID as typed by a user (see `TaskId::parse_with_prefixes`)
+    pub fn parse_task_id(&self, input: &str) -> Result<TaskId> {
+        TaskId::parse_with_prefixes(input, &self.id_prefixes())
+    }
+
+    /// The configured default sort as typed values
+    pub fn sort(&self) -> Result<(SortField, SortOrder)> {
+        let field = SortField::from_str(&self.default_sort).map_err(HlaviError::ConfigError)?;
+        let order =
+            SortOrder::from_str(&self.default_sort_order).map_err(HlaviError::ConfigError)?;
+        Ok((field, order))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::identity::Identity;
+
+    #[test]
+    fn test_empty_config_uses_defaults() {
+        let config = ProjectConfig::from_toml("").unwrap();
+        assert_eq!(config, ProjectConfig::default());
+        assert_eq!(
+            config.sort().unwrap(),
+            (SortField::Id, SortOrder::Ascending)
+        );
+    }
+
+    #[test]
+    fn test_partial_config() {
+        let config = ProjectConfig::from_toml(
+            r#"
+            id_prefix = "WEB"
+            preset = "support_queue"
+            default_sort = "rank"
+
+            [archive]
+            after_days = 14
+
+            [agent]
+            mode = "unattended"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.id_prefix, "WEB");
+        assert_eq!(config.archive.after_days, Some(14));
+        assert_eq!(config.archive.statuses.len(), 2);
+        assert_eq!(config.preset, Preset::SupportQueue);
+        assert_eq!(config.agent.mode, Some(AgentMode::Unattended));
+        assert!(!config.agent.assign_new_tasks);
+    }
+
+    #[test]
+    fn test_retention_policies() {
+        let config = ProjectConfig::from_toml(
+            r#"
+            [archive]
+            after_days = 60
+            statuses = ["closed"]
+
+            [trash]
+            purge_after_days = 30
+            "#,
+        )
+        .unwrap();
+        let now = Utc::now();
+
+        let mut task = Task::new(TaskId::new(1), "Old".to_string());
+        task.status = TaskStatus::Closed;
+        task.updated_at = now - Duration::days(61);
+        assert!(config.archive.is_due(&task, now));
+        task.status = TaskStatus::Done;
+        assert!(!config.archive.is_due(&task, now));
+
+        task.trash(now - Duration::days(29));
+        assert!(!config.trash.is_due(&task, now));
+        assert!(config.trash.is_due(&task, now + Duration::days(1)));
+        assert!(!ProjectConfig::default().trash.is_due(&task, now));
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        for toml in [
+            r#"id_prefix = "H-1""#,
+            r#"id_prefix = """#,
+            r#"default_sort = "colour""#,
+            r#"default_sort_order = "up""#,
+            "[archive]\nafter_days = 0",
+            "[archive]\nafter_days = 3\nstatuses = []",
+            "id_prefix = [",
+            r#"preset = "kanban""#,
+            "[[identities]]\nname = \"Ana\"\n[[identities]]\nname = \"Ben\"\naliases = [\"ana\"]",
+        ] {
+            assert!(
+                matches!(
+                    ProjectConfig::from_toml(toml),
+                    Err(HlaviError::ConfigError(_))
+                ),
+                "accepted {toml:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_task_id() {
+        let config = ProjectConfig::from_toml(
+            r#"
+            id_prefix = "WEB"
+            legacy_prefixes = ["HLA"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.parse_task_id("7").unwrap().as_str(), "WEB7");
+        assert_eq!(config.parse_task_id("hla2").unwrap().as_str(), "HLA2");
+        assert!(config.parse_task_id("API2").is_err());
+        assert!(ProjectConfig::from_toml("legacy_prefixes = [\"H1\"]").is_err());
+    }
+
+    #[test]
+    fn test_toml_roundtrip() {
+        let mut config = ProjectConfig::default();
+        config.archive.after_days = Some(30);
+        config.identities.add(
+            Identity::new("Ana Lee")
+                .with_email("ana@example.com")
+                .with_account("gitlab", "analee"),
+        );
+        let toml = config.to_toml().unwrap();
+        assert!(toml.contains("[[identities]]"));
+        assert_eq!(ProjectConfig::from_toml(&toml).unwrap(), config);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{
+    domain::event::TaskEventKind,
+    error::{HlaviError, Result},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Event names a connector may subscribe to
+pub const EVENT_NAMES: &[&str] = &[
+    "created",
+    "updated",
+    "status_changed",
+    "deleted",
+    "archived",
+    "trashed",
+    "restored",
+    "purged",
+    "aged",
+    "published",
+    "secret_detected",
+];
+
+/// A connector enabled on the board, such as a chat notifier or a tracker
+/// sync
+///
+/// `kind` names the connector implementation that handles it, which the
+/// application registers with the service layer; the board only stores which
+/// connectors are enabled and their settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectorConfig {
+    /// Unique name, used to route inbound payloads
+    pub name: String,
+    pub kind: String,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    /// Event names to forward; when empty, every event is forwarded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+    /// Free-form settings read by the connector, such as a target URL
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub settings: BTreeMap<String, String>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+impl ConnectorConfig {
+    pub fn new(name: &str, kind: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            enabled: true,
+            events: Vec::new(),
+            settings: BTreeMap::new(),
+        }
+    }
+
+    /// Whether events of `kind` should be forwarded
+    pub fn wants(&self, kind: &TaskEventKind) -> bool {
+        self.enabled && (self.events.is_empty() || self.events.iter().any(|e| e == kind.name()))
+    }
+
+    pub fn setting(&self, key: &str) -> Option<&str> {
+        self.settings.get(key).map(String::as_str)
+    }
+
+    /// Checks the name, kind and event names
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() || self.kind.trim().is_empty() {
+            return Err(HlaviError::ConfigError(
+                "Connectors need a name and a kind".to_string(),
+            ));
+        }
+        if let Some(event) = self
+            .events
+            .iter()
+            .find(|e| !EVENT_NAMES.contains(&e.as_str()))
+        {
+            return Err(HlaviError::ConfigError(format!(
+                "Connector '{}' subscribes to unknown event '{}'; valid events: {}",
+                self.name,
+                event,
+                EVENT_NAMES.join(", ")
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TaskStatus;
+
+    #[test]
+    fn test_wants_subscribed_events() {
+        let mut config = ConnectorConfig::new("chat", "webhook");
+        assert!(config.wants(&TaskEventKind::Created));
+
+        config.events = vec!["status_changed".to_string()];
+        assert!(!config.wants(&TaskEventKind::Created));
+        assert!(config.wants(&TaskEventKind::StatusChanged {
+            from: TaskStatus::New,
+            to: TaskStatus::Open,
+        }));
+
+        config.enabled = false;
+        assert!(!config.wants(&TaskEventKind::Updated));
+
+        config.events.push("moved".to_string());
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,441 @@
+//! Conflict-free replicated representations of task collections.
+//!
+//! Tasks can opt in with [`Task::enable_crdt`]. The regular `tags` and
+//! `acceptance_criteria` fields remain the readable source for every consumer;
+//! the CRDT state rides along in an optional `crdt` field (ignored by older
+//! readers) and lets two offline copies merge without conflicts:
+//!
+//! * tags are an observed-remove set (concurrent add and remove: add wins),
+//! * criteria are an add-wins map of last-writer-wins registers for the text
+//!   and completion state, ordered by creation.
+
+use crate::domain::task::{AcceptanceCriteria, Task};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Unique operation identifier: replica name plus that replica's counter
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Dot {
+    pub replica: String,
+    pub counter: u64,
+}
+
+impl Dot {
+    fn rename(&mut self, from: &str, to: &str) {
+        if self.replica == from {
+            self.replica = to.to_string();
+        }
+    }
+}
+
+fn rename_dots(dots: &mut BTreeSet<Dot>, from: &str, to: &str) {
+    *dots = std::mem::take(dots)
+        .into_iter()
+        .map(|mut dot| {
+            dot.rename(from, to);
+            dot
+        })
+        .collect();
+}
+
+/// Observed-remove set: removing only cancels the adds the remover had seen
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrSet {
+    entries: BTreeMap<String, BTreeSet<Dot>>,
+    tombstones: BTreeSet<Dot>,
+}
+
+impl OrSet {
+    pub fn add(&mut self, value: String, dot: Dot) {
+        self.entries.entry(value).or_default().insert(dot);
+    }
+
+    pub fn remove(&mut self, value: &str) {
+        if let Some(dots) = self.entries.remove(value) {
+            self.tombstones.extend(dots);
+        }
+    }
+
+    pub fn contains(&self, value: &str) -> bool {
+        self.entries.get(value).is_some_and(|dots| !dots.is_empty())
+    }
+
+    /// Live values in sorted order
+    pub fn values(&self) -> impl Iterator<Item = &String> {
+        self.entries
+            .iter()
+            .filter(|(_, dots)| !dots.is_empty())
+            .map(|(value, _)| value)
+    }
+
+    /// Reattributes every operation by `from` to `to`
+    pub fn rename_replica(&mut self, from: &str, to: &str) {
+        for dots in self.entries.values_mut() {
+            rename_dots(dots, from, to);
+        }
+        rename_dots(&mut self.tombstones, from, to);
+    }
+
+    pub fn merge(&mut self, other: &OrSet) {
+        self.tombstones.extend(other.tombstones.iter().cloned());
+        for (value, dots) in &other.entries {
+            self.entries
+                .entry(value.clone())
+                .or_default()
+                .extend(dots.iter().cloned());
+        }
+        let tombstones = &self.tombstones;
+        for dots in self.entries.values_mut() {
+            dots.retain(|dot| !tombstones.contains(dot));
+        }
+        self.entries.retain(|_, dots| !dots.is_empty());
+    }
+}
+
+/// Last-writer-wins register ordered by the writing operation's dot counter
+/// (ties broken by replica name)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LwwRegister<T> {
+    pub value: T,
+    pub stamp: Dot,
+}
+
+impl<T: Clone> LwwRegister<T> {
+    fn set(&mut self, value: T, stamp: Dot) {
+        if (stamp.counter, &stamp.replica) > (self.stamp.counter, &self.stamp.replica) {
+            self.value = value;
+            self.stamp = stamp;
+        }
+    }
+
+    fn merge(&mut self, other: &LwwRegister<T>) {
+        self.set(other.value.clone(), other.stamp.clone());
+    }
+}
+
+/// Replicated acceptance criterion
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrdtCriterion {
+    /// Dot of the operation that created the criterion
+    pub dot: Dot,
+    /// ID assigned by the creating replica (collisions are resolved on projection)
+    pub id: usize,
+    pub description: LwwRegister<String>,
+    pub completed: LwwRegister<bool>,
+}
+
+/// Replicated state of a task's tags and acceptance criteria
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrdtFields {
+    /// Name of the local replica (e.g., a machine or user name)
+    pub replica: String,
+    /// Highest counter seen from any replica
+    pub counter: u64,
+    pub tags: OrSet,
+    /// Criteria ordered by creation (counter, then replica)
+    pub criteria: Vec<CrdtCriterion>,
+    pub removed_criteria: BTreeSet<Dot>,
+}
+
+impl CrdtFields {
+    pub fn new(replica: String) -> Self {
+        Self {
+            replica,
+            counter: 0,
+            tags: OrSet::default(),
+            criteria: Vec::new(),
+            removed_criteria: BTreeSet::new(),
+        }
+    }
+
+    fn next_dot(&mut self) -> Dot {
+        self.counter += 1;
+        Dot {
+            replica: self.replica.clone(),
+            counter: self.counter,
+        }
+    }
+
+    /// Records local edits: brings the replicated state in line with the
+    /// task's plain `tags` and `acceptance_criteria`
+    pub fn observe(&mut self, task: &Task) {
+        let live: Vec<String> = self.tags.values().cloned().collect();
+        for tag in live.iter().filter(|t| !task.has_tag(t)) {
+            self.tags.remove(tag);
+        }
+        for tag in &task.tags {
+            if !live.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                let dot = self.next_dot();
+                self.tags.add(tag.clone(), dot);
+            }
+        }
+
+        let (removed, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.criteria)
+            .into_iter()
+            .partition(|c| task.find_criterion(c.id).is_none());
+        self.criteria = kept;
+        self.removed_criteria
+            .extend(removed.into_iter().map(|c| c.dot));
+
+        for ac in &task.acceptance_criteria {
+            match self.criteria.iter().position(|c| c.id == ac.id) {
+                Some(pos) => {
+                    let stamp = self.next_dot();
+                    let entry = &mut self.criteria[pos];
+                    if entry.description.value != ac.description {
+                        entry.description.set(ac.description.clone(), stamp.clone());
+                    }
+                    if entry.completed.value != ac.completed {
+                        entry.completed.set(ac.completed, stamp);
+                    }
+                }
+                None => {
+                    let dot = self.next_dot();
+                    self.criteria.push(CrdtCriterion {
+                        dot: dot.clone(),
+                        id: ac.id,
+                        description: LwwRegister {
+                            value: ac.description.clone(),
+                            stamp: dot.clone(),
+                        },
+                        completed: LwwRegister {
+                            value: ac.completed,
+                            stamp: dot,
+                        },
+                    });
+                }
+            }
+        }
+    }
+
+    /// Every replica name the state refers to, including the local one
+    pub fn replicas(&self) -> BTreeSet<&str> {
+        let criteria = self
+            .criteria
+            .iter()
+            .flat_map(|c| [&c.dot, &c.description.stamp, &c.completed.stamp]);
+        self.tags
+            .entries
+            .values()
+            .flatten()
+            .chain(&self.tags.tombstones)
+            .chain(&self.removed_criteria)
+            .chain(criteria)
+            .map(|dot| dot.replica.as_str())
+            .chain(std::iter::once(self.replica.as_str()))
+            .collect()
+    }
+
+    /// Reattributes the local replica and every operation recorded under
+    /// `from` to `to`
+    ///
+    /// Every copy of the task must be renamed the same way, or merges will
+    /// treat the renamed operations as new ones.
+    pub fn rename_replica(&mut self, from: &str, to: &str) {
+        if self.replica == from {
+            self.replica = to.to_string();
+        }
+        self.tags.rename_replica(from, to);
+        rename_dots(&mut self.removed_criteria, from, to);
+        for criterion in &mut self.criteria {
+            criterion.dot.rename(from, to);
+            criterion.description.stamp.rename(from, to);
+            criterion.completed.stamp.rename(from, to);
+        }
+    }
+
+    /// Merges another replica's state into this one
+    pub fn merge(&mut self, other: &CrdtFields) {
+        self.counter = self.counter.max(other.counter);
+        self.tags.merge(&other.tags);
+        self.removed_criteria
+            .extend(other.removed_criteria.iter().cloned());
+        for theirs in &other.criteria {
+            match self.criteria.iter_mut().find(|c| c.dot == theirs.dot) {
+                Some(ours) => {
+                    ours.description.merge(&theirs.description);
+                    ours.completed.merge(&theirs.completed);
+                }
+                None => self.criteria.push(theirs.clone()),
+            }
+        }
+        let removed = &self.removed_criteria;
+        self.criteria.retain(|c| !removed.contains(&c.dot));
+        self.criteria
+            .sort_by(|a, b| (a.dot.counter, &a.dot.replica).cmp(&(b.dot.counter, &b.dot.replica)));
+    }
+
+    /// Writes the replicated state into the task's plain fields
+    ///
+    /// Criteria created concurrently with the same ID are given fresh IDs
+    /// deterministically, so every replica projects the same result.
+    pub fn project(&mut self, task: &mut Task) {
+        task.tags = self.tags.values().cloned().collect();
+
+        let previous = std::mem::take(&mut task.acceptance_criteria);
+        let mut used = HashSet::new();
+        let mut next_id = self.criteria.iter().map(|c| c.id).max().unwrap_or(0) + 1;
+        for entry in self.criteria.iter_mut() {
+            if !used.insert(entry.id) {
+                entry.id = next_id;
+                used.insert(next_id);
+                next_id += 1;
+            }
+
+            let mut ac = previous
+                .iter()
+                .find(|ac| ac.id == entry.id)
+                .cloned()
+                .unwrap_or_else(|| AcceptanceCriteria::new(entry.id, String::new()));
+            ac.description = entry.description.value.clone();
+            if entry.completed.value && !ac.completed {
+                ac.mark_completed();
+            } else if !entry.completed.value && ac.completed {
+                ac.mark_incomplete();
+            }
+            task.acceptance_criteria.push(ac);
+        }
+        task.next_criterion_id = task.next_criterion_id.max(next_id);
+    }
+}
+
+impl Task {
+    /// Opts the task into CRDT tracking of tags and criteria under the given replica name
+    pub fn enable_crdt(&mut self, replica: String) {
+        let mut fields = CrdtFields::new(replica);
+        fields.observe(self);
+        self.crdt = Some(fields);
+    }
+
+    /// Records local edits into the CRDT state, if enabled
+    pub fn refresh_crdt(&mut self) {
+        if let Some(mut fields) = self.crdt.take() {
+            fields.observe(self);
+            self.crdt = Some(fields);
+        }
+    }
+
+    /// Merges another copy's replicated tags and criteria into this task
+    ///
+    /// Both copies should have CRDT tracking enabled; otherwise this is a no-op
+    /// and returns false.
+    pub fn merge_crdt(&mut self, other: &Task) -> bool {
+        self.refresh_crdt();
+        let (Some(mut fields), Some(theirs)) = (self.crdt.take(), other.crdt.as_ref()) else {
+            return false;
+        };
+        let mut theirs = theirs.clone();
+        theirs.observe(other);
+
+        fields.merge(&theirs);
+        fields.project(self);
+        self.crdt = Some(fields);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::task::TaskId;
+
+    fn replicas() -> (Task, Task) {
+        let mut task = Task::new(TaskId::new(1), "Shared".to_string());
+        task.add_tag("base".to_string());
+        task.add_acceptance_criterion("a".to_string());
+        task.enable_crdt("laptop".to_string());
+
+        let mut other = task.clone();
+        other.crdt.as_mut().unwrap().replica = "desktop".to_string();
+        (task, other)
+    }
+
+    #[test]
+    fn test_concurrent_tag_edits_merge() {
+        let (mut laptop, mut desktop) = replicas();
+        laptop.add_tag("frontend".to_string());
+        desktop.remove_tag("base");
+        desktop.add_tag("backend".to_string());
+
+        laptop.merge_crdt(&desktop);
+        desktop.merge_crdt(&laptop);
+
+        assert_eq!(laptop.tags, vec!["backend", "frontend"]);
+        assert_eq!(laptop.tags, desktop.tags);
+    }
+
+    #[test]
+    fn test_concurrent_add_wins_over_remove() {
+        let (mut laptop, mut desktop) = replicas();
+        laptop.remove_tag("base");
+        desktop.remove_tag("base");
+        desktop.refresh_crdt();
+        desktop.add_tag("base".to_string());
+
+        laptop.merge_crdt(&desktop);
+        assert!(laptop.has_tag("base"));
+    }
+
+    #[test]
+    fn test_concurrent_criteria_edits_merge() {
+        let (mut laptop, mut desktop) = replicas();
+        laptop.add_acceptance_criterion("from laptop".to_string());
+        laptop.toggle_criterion(1).unwrap();
+        desktop.add_acceptance_criterion("from desktop".to_string());
+        desktop
+            .update_criterion(1, "a (edited)".to_string())
+            .unwrap();
+
+        laptop.merge_crdt(&desktop);
+        desktop.merge_crdt(&laptop);
+
+        let describe = |t: &Task| {
+            t.acceptance_criteria
+                .iter()
+                .map(|ac| (ac.id, ac.description.clone(), ac.completed))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(describe(&laptop), describe(&desktop));
+        assert_eq!(laptop.acceptance_criteria.len(), 3);
+        assert_eq!(laptop.find_criterion(1).unwrap().description, "a (edited)");
+        assert!(laptop.find_criterion(1).unwrap().completed);
+
+        let mut ids: Vec<usize> = laptop.acceptance_criteria.iter().map(|ac| ac.id).collect();
+        ids.dedup();
+        assert_eq!(ids.len(), 3);
+    }
+
+    #[test]
+    fn test_removed_criterion_stays_removed() {
+        let (mut laptop, desktop) = replicas();
+        laptop.remove_acceptance_criterion("1").unwrap();
+
+        laptop.merge_crdt(&desktop);
+        assert!(laptop.acceptance_criteria.is_empty());
+    }
+
+    #[test]
+    fn test_rename_replica() {
+        let (mut laptop, mut desktop) = replicas();
+        desktop.add_tag("remote".to_string());
+        laptop.merge_crdt(&desktop);
+        let fields = laptop.crdt.as_mut().unwrap();
+        assert!(fields.replicas().contains("desktop"));
+
+        fields.rename_replica("desktop", "anonymous");
+        let replicas = fields.replicas();
+        assert!(!replicas.contains("desktop"));
+        assert!(replicas.contains("anonymous"));
+    }
+
+    #[test]
+    fn test_serialization_is_backwards_compatible() {
+        let (laptop, _) = replicas();
+        let json = serde_json::to_string(&laptop).unwrap();
+        let loaded: Task = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.crdt, laptop.crdt);
+
+        let plain = Task::new(TaskId::new(2), "Plain".to_string());
+        assert!(!serde_json::to_string(&plain).unwrap().contains("crdt"));
+    }
+}
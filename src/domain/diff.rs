@@ -0,0 +1,978 @@
+use crate::domain::{
+    agent_note::AgentNote,
+    blocked::BlockedInfo,
+    checklist::Checklist,
+    history::StatusTransition,
+    markdown::DescriptionFormat,
+    task::{
+        AcceptanceCriteria, ExternalRef, Priority, Severity, Task, TaskId, TaskLink, TaskStatus,
+    },
+    worklog::WorkLogEntry,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A single field-level change between two versions of a task
+///
+/// Bookkeeping timestamps (`created_at`, `updated_at`) are not diffed.
+/// Acceptance criteria are matched by their stable ID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "field", rename_all = "snake_case")]
+pub enum FieldChange {
+    Title {
+        from: String,
+        to: String,
+    },
+    Description {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    DescriptionFormat {
+        from: DescriptionFormat,
+        to: DescriptionFormat,
+    },
+    Status {
+        from: TaskStatus,
+        to: TaskStatus,
+    },
+    CompletedAt {
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    },
+    ArchivedAt {
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    },
+    TrashedAt {
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    },
+    Priority {
+        from: Option<Priority>,
+        to: Option<Priority>,
+    },
+    StartDate {
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    },
+    EndDate {
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    },
+    Parent {
+        from: Option<TaskId>,
+        to: Option<TaskId>,
+    },
+    Rank {
+        from: i64,
+        to: i64,
+    },
+    AgentAssigned {
+        from: bool,
+        to: bool,
+    },
+    Assignee {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    Estimate {
+        from: Option<u32>,
+        to: Option<u32>,
+    },
+    TaskType {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    Severity {
+        from: Option<Severity>,
+        to: Option<Severity>,
+    },
+    RejectionReason {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    /// Applied as the difference between the counts, so reports counted on
+    /// different copies add up
+    Occurrences {
+        from: u32,
+        to: u32,
+    },
+    TagAdded {
+        tag: String,
+    },
+    TagRemoved {
+        tag: String,
+    },
+    ComponentAdded {
+        component: String,
+    },
+    ComponentRemoved {
+        component: String,
+    },
+    Environment {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    Draft {
+        from: bool,
+        to: bool,
+    },
+    Blocked {
+        from: Option<BlockedInfo>,
+        to: Option<BlockedInfo>,
+    },
+    BlockAdded {
+        task_id: TaskId,
+    },
+    BlockRemoved {
+        task_id: TaskId,
+    },
+    LinkAdded {
+        link: TaskLink,
+    },
+    LinkRemoved {
+        link: TaskLink,
+    },
+    ExternalRefAdded {
+        external: ExternalRef,
+    },
+    ExternalRefRemoved {
+        external: ExternalRef,
+    },
+    CriterionAdded {
+        criterion: AcceptanceCriteria,
+    },
+    CriterionRemoved {
+        criterion: AcceptanceCriteria,
+    },
+    CriterionEdited {
+        id: usize,
+        from: String,
+        to: String,
+    },
+    CriterionToggled {
+        id: usize,
+        completed: bool,
+    },
+    CriterionAssignee {
+        id: usize,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    CriterionNotes {
+        id: usize,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    CriterionEvidence {
+        id: usize,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    ChecklistAdded {
+        checklist: Checklist,
+    },
+    ChecklistRemoved {
+        name: String,
+    },
+    /// A checklist's items changed; carries the new version
+    ChecklistChanged {
+        checklist: Checklist,
+    },
+    /// An agent note was appended; notes are never edited or removed
+    AgentNoteAdded {
+        note: AgentNote,
+    },
+    /// A status change was recorded in the task's history; always precedes
+    /// the `Status` change it led to
+    StatusRecorded {
+        transition: StatusTransition,
+    },
+    /// Time was logged; entries are never edited or removed
+    WorkLogged {
+        entry: WorkLogEntry,
+    },
+}
+
+/// Per-field differences between two versions of a task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskDiff {
+    pub task_id: TaskId,
+    pub changes: Vec<FieldChange>,
+}
+
+impl TaskDiff {
+    /// Returns true if the two versions are equivalent
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Applies every change to the task
+    pub fn apply_to(&self, task: &mut Task) {
+        for change in &self.changes {
+            change.apply(task);
+        }
+    }
+}
+
+impl FieldChange {
+    /// Applies the change to a task, setting the new value directly
+    ///
+    /// Status changes bypass workflow validation: applying a diff reproduces a
+    /// state another copy of the task already reached, though the move is
+    /// still recorded in the status history. `updated_at` is left to
+    /// the caller. Added criteria whose ID is taken get a fresh ID.
+    pub fn apply(&self, task: &mut Task) {
+        match self.clone() {
+            Self::Title { to, .. } => task.title = to,
+            Self::Description { to, .. } => task.description = to,
+            Self::DescriptionFormat { to, .. } => task.description_format = to,
+            // A transition already recorded by `StatusRecorded` is reused, so
+            // history gains exactly one entry either way
+            Self::Status { to, .. } => {
+                let recorded = task
+                    .status_history
+                    .last()
+                    .filter(|last| last.to == to)
+                    .map(|last| last.at);
+                let at = recorded.unwrap_or_else(Utc::now);
+                task.stamp_completion(&to, at);
+                match recorded {
+                    Some(at) => {
+                        task.status_changed_at = Some(at);
+                        task.status = to;
+                    }
+                    None => task.record_status(to, at),
+                }
+            }
+            Self::CompletedAt { to, .. } => task.completed_at = to,
+            Self::ArchivedAt { to, .. } => task.archived_at = to,
+            Self::TrashedAt { to, .. } => task.trashed_at = to,
+            Self::Priority { to, .. } => task.priority = to,
+            Self::StartDate { to, .. } => task.start_date = to,
+            Self::EndDate { to, .. } => task.end_date = to,
+            Self::Parent { to, .. } => task.parent = to,
+            Self::Rank { to, .. } => task.rank = to,
+            Self::AgentAssigned { to, .. } => task.agent_assigned = to,
+            Self::Assignee { to, .. } => task.assignee = to,
+            Self::Estimate { to, .. } => task.estimate = to,
+            Self::TaskType { to, .. } => task.task_type = to,
+            Self::Severity { to, .. } => task.severity = to,
+            Self::RejectionReason { to, .. } => task.rejection_reason = to,
+            Self::Occurrences { from, to } => {
+                let count = i64::from(task.occurrences) + i64::from(to) - i64::from(from);
+                task.occurrences = u32::try_from(count.max(0)).unwrap_or(u32::MAX);
+            }
+            Self::TagAdded { tag } => {
+                if !task.has_tag(&tag) {
+                    task.tags.push(tag);
+                }
+            }
+            Self::TagRemoved { tag } => task.tags.retain(|t| !t.eq_ignore_ascii_case(&tag)),
+            Self::ComponentAdded { component } => {
+                if !task.has_component(&component) {
+                    task.components.push(component);
+                }
+            }
+            Self::ComponentRemoved { component } => task
+                .components
+                .retain(|c| !c.eq_ignore_ascii_case(&component)),
+            Self::Environment { to, .. } => task.environment = to,
+            Self::Draft { to, .. } => task.draft = to,
+            Self::Blocked { to, .. } => task.blocked = to,
+            Self::BlockAdded { task_id } => {
+                if !task.blocks.contains(&task_id) {
+                    task.blocks.push(task_id);
+                }
+            }
+            Self::BlockRemoved { task_id } => task.blocks.retain(|b| b != &task_id),
+            Self::LinkAdded { link } => {
+                if !task.links.contains(&link) {
+                    task.links.push(link);
+                }
+            }
+            Self::LinkRemoved { link } => task.links.retain(|l| l != &link),
+            Self::ExternalRefAdded { external } => {
+                task.add_external_ref(external);
+            }
+            Self::ExternalRefRemoved { external } => task.external_refs.retain(|r| r != &external),
+            Self::CriterionAdded { mut criterion } => {
+                if task.find_criterion(criterion.id).is_some() {
+                    criterion.id = task.allocate_criterion_id();
+                } else {
+                    task.next_criterion_id = task.next_criterion_id.max(criterion.id + 1);
+                }
+                task.acceptance_criteria.push(criterion);
+            }
+            Self::CriterionRemoved { criterion } => {
+                task.acceptance_criteria.retain(|ac| ac.id != criterion.id)
+            }
+            Self::CriterionEdited { id, to, .. } => {
+                if let Some(ac) = task.acceptance_criteria.iter_mut().find(|ac| ac.id == id) {
+                    ac.description = to;
+                }
+            }
+            Self::CriterionToggled { id, completed } => {
+                if let Some(ac) = task.acceptance_criteria.iter_mut().find(|ac| ac.id == id) {
+                    if completed && !ac.completed {
+                        ac.mark_completed();
+                    } else if !completed && ac.completed {
+                        ac.mark_incomplete();
+                    }
+                }
+            }
+            Self::CriterionAssignee { id, to, .. } => {
+                if let Some(ac) = task.acceptance_criteria.iter_mut().find(|ac| ac.id == id) {
+                    ac.assignee = to;
+                }
+            }
+            Self::CriterionNotes { id, to, .. } => {
+                if let Some(ac) = task.acceptance_criteria.iter_mut().find(|ac| ac.id == id) {
+                    ac.notes = to;
+                }
+            }
+            Self::CriterionEvidence { id, to, .. } => {
+                if let Some(ac) = task.acceptance_criteria.iter_mut().find(|ac| ac.id == id) {
+                    ac.evidence_url = to;
+                }
+            }
+            Self::ChecklistAdded { checklist } | Self::ChecklistChanged { checklist } => match task
+                .checklists
+                .iter_mut()
+                .find(|c| c.name == checklist.name)
+            {
+                Some(existing) => *existing = checklist,
+                None => task.checklists.push(checklist),
+            },
+            Self::ChecklistRemoved { name } => task.checklists.retain(|c| c.name != name),
+            Self::AgentNoteAdded { note } => {
+                if !task.agent_notes.contains(&note) {
+                    task.agent_notes.push(note);
+                }
+            }
+            Self::StatusRecorded { transition } => {
+                if !task.status_history.contains(&transition) {
+                    task.status_history.push(transition);
+                }
+            }
+            // The same time can be logged twice, so entries are never deduplicated
+            Self::WorkLogged { entry } => task.work_log.push(entry),
+        }
+    }
+}
+
+fn scalar<T: PartialEq + Clone>(
+    changes: &mut Vec<FieldChange>,
+    from: &T,
+    to: &T,
+    make: impl FnOnce(T, T) -> FieldChange,
+) {
+    if from != to {
+        changes.push(make(from.clone(), to.clone()));
+    }
+}
+
+fn set_changes<T: PartialEq + Clone>(
+    changes: &mut Vec<FieldChange>,
+    from: &[T],
+    to: &[T],
+    added: impl Fn(T) -> FieldChange,
+    removed: impl Fn(T) -> FieldChange,
+) {
+    for item in from.iter().filter(|i| !to.contains(i)) {
+        changes.push(removed(item.clone()));
+    }
+    for item in to.iter().filter(|i| !from.contains(i)) {
+        changes.push(added(item.clone()));
+    }
+}
+
+/// Entries of an append-only log found in `to` but not in `from`
+///
+/// Each entry in `from` accounts for one equal entry in `to`, so repeated
+/// entries are counted rather than collapsed.
+fn appended<T: PartialEq + Clone>(
+    changes: &mut Vec<FieldChange>,
+    from: &[T],
+    to: &[T],
+    added: impl Fn(T) -> FieldChange,
+) {
+    let mut unmatched: Vec<&T> = from.iter().collect();
+    for item in to {
+        match unmatched.iter().position(|i| *i == item) {
+            Some(index) => {
+                unmatched.swap_remove(index);
+            }
+            None => changes.push(added(item.clone())),
+        }
+    }
+}
+
+impl Task {
+    /// Computes the changes that turn `self` into `other`
+    pub fn diff(&self, other: &Task) -> TaskDiff {
+        let mut changes = Vec::new();
+
+        scalar(&mut changes, &self.title, &other.title, |from, to| {
+            FieldChange::Title { from, to }
+        });
+        scalar(
+            &mut changes,
+            &self.description,
+            &other.description,
+            |from, to| FieldChange::Description { from, to },
+        );
+        scalar(
+            &mut changes,
+            &self.description_format,
+            &other.description_format,
+            |from, to| FieldChange::DescriptionFormat { from, to },
+        );
+        appended(
+            &mut changes,
+            &self.status_history,
+            &other.status_history,
+            |transition| FieldChange::StatusRecorded { transition },
+        );
+        scalar(&mut changes, &self.status, &other.status, |from, to| {
+            FieldChange::Status { from, to }
+        });
+        scalar(
+            &mut changes,
+            &self.completed_at,
+            &other.completed_at,
+            |from, to| FieldChange::CompletedAt { from, to },
+        );
+        scalar(
+            &mut changes,
+            &self.archived_at,
+            &other.archived_at,
+            |from, to| FieldChange::ArchivedAt { from, to },
+        );
+        scalar(
+            &mut changes,
+            &self.trashed_at,
+            &other.trashed_at,
+            |from, to| FieldChange::TrashedAt { from, to },
+        );
+        scalar(&mut changes, &self.priority, &other.priority, |from, to| {
+            FieldChange::Priority { from, to }
+        });
+        scalar(
+            &mut changes,
+            &self.start_date,
+            &other.start_date,
+            |from, to| FieldChange::StartDate { from, to },
+        );
+        scalar(&mut changes, &self.end_date, &other.end_date, |from, to| {
+            FieldChange::EndDate { from, to }
+        });
+        scalar(&mut changes, &self.parent, &other.parent, |from, to| {
+            FieldChange::Parent { from, to }
+        });
+        scalar(&mut changes, &self.rank, &other.rank, |from, to| {
+            FieldChange::Rank { from, to }
+        });
+        scalar(
+            &mut changes,
+            &self.agent_assigned,
+            &other.agent_assigned,
+            |from, to| FieldChange::AgentAssigned { from, to },
+        );
+        scalar(&mut changes, &self.assignee, &other.assignee, |from, to| {
+            FieldChange::Assignee { from, to }
+        });
+        scalar(&mut changes, &self.estimate, &other.estimate, |from, to| {
+            FieldChange::Estimate { from, to }
+        });
+        scalar(
+            &mut changes,
+            &self.task_type,
+            &other.task_type,
+            |from, to| FieldChange::TaskType { from, to },
+        );
+        scalar(&mut changes, &self.severity, &other.severity, |from, to| {
+            FieldChange::Severity { from, to }
+        });
+        scalar(
+            &mut changes,
+            &self.rejection_reason,
+            &other.rejection_reason,
+            |from, to| FieldChange::RejectionReason { from, to },
+        );
+        scalar(
+            &mut changes,
+            &self.occurrences,
+            &other.occurrences,
+            |from, to| FieldChange::Occurrences { from, to },
+        );
+
+        set_changes(
+            &mut changes,
+            &self.tags,
+            &other.tags,
+            |tag| FieldChange::TagAdded { tag },
+            |tag| FieldChange::TagRemoved { tag },
+        );
+        set_changes(
+            &mut changes,
+            &self.components,
+            &other.components,
+            |component| FieldChange::ComponentAdded { component },
+            |component| FieldChange::ComponentRemoved { component },
+        );
+        scalar(
+            &mut changes,
+            &self.environment,
+            &other.environment,
+            |from, to| FieldChange::Environment { from, to },
+        );
+        scalar(&mut changes, &self.draft, &other.draft, |from, to| {
+            FieldChange::Draft { from, to }
+        });
+        scalar(&mut changes, &self.blocked, &other.blocked, |from, to| {
+            FieldChange::Blocked { from, to }
+        });
+        set_changes(
+            &mut changes,
+            &self.blocks,
+            &other.blocks,
+            |task_id| FieldChange::BlockAdded { task_id },
+            |task_id| FieldChange::BlockRemoved { task_id },
+        );
+        set_changes(
+            &mut changes,
+            &self.links,
+            &other.links,
+            |link| FieldChange::LinkAdded { link },
+            |link| FieldChange::LinkRemoved { link },
+        );
+        set_changes(
+            &mut changes,
+            &self.external_refs,
+            &other.external_refs,
+            |external| FieldChange::ExternalRefAdded { external },
+            |external| FieldChange::ExternalRefRemoved { external },
+        );
+
+        for ac in &self.acceptance_criteria {
+            match other.find_criterion(ac.id) {
+                None => changes.push(FieldChange::CriterionRemoved {
+                    criterion: ac.clone(),
+                }),
+                Some(new) => {
+                    if ac.description != new.description {
+                        changes.push(FieldChange::CriterionEdited {
+                            id: ac.id,
+                            from: ac.description.clone(),
+                            to: new.description.clone(),
+                        });
+                    }
+                    if ac.completed != new.completed {
+                        changes.push(FieldChange::CriterionToggled {
+                            id: ac.id,
+                            completed: new.completed,
+                        });
+                    }
+                    scalar(&mut changes, &ac.assignee, &new.assignee, |from, to| {
+                        FieldChange::CriterionAssignee {
+                            id: ac.id,
+                            from,
+                            to,
+                        }
+                    });
+                    scalar(&mut changes, &ac.notes, &new.notes, |from, to| {
+                        FieldChange::CriterionNotes {
+                            id: ac.id,
+                            from,
+                            to,
+                        }
+                    });
+                    scalar(
+                        &mut changes,
+                        &ac.evidence_url,
+                        &new.evidence_url,
+                        |from, to| FieldChange::CriterionEvidence {
+                            id: ac.id,
+                            from,
+                            to,
+                        },
+                    );
+                }
+            }
+        }
+        for ac in &other.acceptance_criteria {
+            if self.find_criterion(ac.id).is_none() {
+                changes.push(FieldChange::CriterionAdded {
+                    criterion: ac.clone(),
+                });
+            }
+        }
+
+        for checklist in &self.checklists {
+            match other.checklists.iter().find(|c| c.name == checklist.name) {
+                None => changes.push(FieldChange::ChecklistRemoved {
+                    name: checklist.name.clone(),
+                }),
+                Some(new) if new != checklist => changes.push(FieldChange::ChecklistChanged {
+                    checklist: new.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        for checklist in &other.checklists {
+            if !self.checklists.iter().any(|c| c.name == checklist.name) {
+                changes.push(FieldChange::ChecklistAdded {
+                    checklist: checklist.clone(),
+                });
+            }
+        }
+        appended(
+            &mut changes,
+            &self.agent_notes,
+            &other.agent_notes,
+            |note| FieldChange::AgentNoteAdded { note },
+        );
+        appended(&mut changes, &self.work_log, &other.work_log, |entry| {
+            FieldChange::WorkLogged { entry }
+        });
+
+        TaskDiff {
+            task_id: other.id.clone(),
+            changes,
+        }
+    }
+}
+
+fn show<T: fmt::Display>(value: &Option<T>) -> String {
+    match value {
+        Some(v) => format!("'{}'", v),
+        None => "(none)".to_string(),
+    }
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Title { from, to } => write!(f, "title: '{}' → '{}'", from, to),
+            Self::Description { from, to } => match (from, to) {
+                (None, Some(_)) => write!(f, "description added"),
+                (Some(_), None) => write!(f, "description removed"),
+                _ => write!(f, "description edited"),
+            },
+            Self::DescriptionFormat { from, to } => {
+                write!(f, "description format: {} → {}", from, to)
+            }
+            Self::Status { from, to } => write!(f, "status: {} → {}", from, to),
+            Self::CompletedAt { from, to } => {
+                write!(f, "completed at: {} → {}", show(from), show(to))
+            }
+            Self::ArchivedAt { to, .. } => {
+                write!(
+                    f,
+                    "{}",
+                    if to.is_some() {
+                        "archived"
+                    } else {
+                        "unarchived"
+                    }
+                )
+            }
+            Self::TrashedAt { to, .. } => write!(
+                f,
+                "{}",
+                if to.is_some() {
+                    "moved to trash"
+                } else {
+                    "restored from trash"
+                }
+            ),
+            Self::Priority { from, to } => write!(f, "priority: {} → {}", show(from), show(to)),
+            Self::StartDate { from, to } => {
+                write!(f, "start date: {} → {}", show(from), show(to))
+            }
+            Self::EndDate { from, to } => write!(f, "end date: {} → {}", show(from), show(to)),
+            Self::Parent { from, to } => write!(f, "parent: {} → {}", show(from), show(to)),
+            Self::Rank { from, to } => write!(f, "rank: {} → {}", from, to),
+            Self::AgentAssigned { to, .. } => {
+                write!(f, "agent {}", if *to { "assigned" } else { "unassigned" })
+            }
+            Self::Assignee { from, to } => {
+                write!(f, "assignee: {} → {}", show(from), show(to))
+            }
+            Self::Estimate { from, to } => write!(f, "estimate: {} → {}", show(from), show(to)),
+            Self::TaskType { from, to } => write!(f, "type: {} → {}", show(from), show(to)),
+            Self::Severity { from, to } => write!(f, "severity: {} → {}", show(from), show(to)),
+            Self::RejectionReason { from, to } => {
+                write!(f, "rejection reason: {} → {}", show(from), show(to))
+            }
+            Self::Occurrences { from, to } => write!(f, "occurrences: {} → {}", from, to),
+            Self::TagAdded { tag } => write!(f, "+ tag '{}'", tag),
+            Self::TagRemoved { tag } => write!(f, "- tag '{}'", tag),
+            Self::ComponentAdded { component } => write!(f, "+ component '{}'", component),
+            Self::ComponentRemoved { component } => write!(f, "- component '{}'", component),
+            Self::Environment { from, to } => {
+                write!(f, "environment: {} → {}", show(from), show(to))
+            }
+            Self::Draft { to, .. } => {
+                write!(f, "{}", if *to { "made a draft" } else { "published" })
+            }
+            Self::Blocked { to: Some(info), .. } => write!(f, "blocked: {}", info),
+            Self::Blocked { to: None, .. } => write!(f, "unblocked"),
+            Self::BlockAdded { task_id } => write!(f, "+ blocks {}", task_id),
+            Self::BlockRemoved { task_id } => write!(f, "- blocks {}", task_id),
+            Self::LinkAdded { link } => write!(f, "+ {}", link),
+            Self::LinkRemoved { link } => write!(f, "- {}", link),
+            Self::ExternalRefAdded { external } => write!(f, "+ ref {}", external),
+            Self::ExternalRefRemoved { external } => write!(f, "- ref {}", external),
+            Self::CriterionAdded { criterion } => {
+                write!(f, "+ AC #{}: {}", criterion.id, criterion.description)
+            }
+            Self::CriterionRemoved { criterion } => {
+                write!(f, "- AC #{}: {}", criterion.id, criterion.description)
+            }
+            Self::CriterionEdited { id, from, to } => {
+                write!(f, "~ AC #{}: '{}' → '{}'", id, from, to)
+            }
+            Self::CriterionToggled { id, completed } => write!(
+                f,
+                "AC #{} marked {}",
+                id,
+                if *completed { "complete" } else { "incomplete" }
+            ),
+            Self::CriterionAssignee { id, from, to } => {
+                write!(f, "AC #{} assignee: {} → {}", id, show(from), show(to))
+            }
+            Self::CriterionNotes { id, from, to } => match (from, to) {
+                (None, Some(_)) => write!(f, "AC #{} notes added", id),
+                (Some(_), None) => write!(f, "AC #{} notes removed", id),
+                _ => write!(f, "AC #{} notes edited", id),
+            },
+            Self::CriterionEvidence { id, from, to } => {
+                write!(f, "AC #{} evidence: {} → {}", id, show(from), show(to))
+            }
+            Self::ChecklistAdded { checklist } => write!(f, "+ checklist '{}'", checklist.name),
+            Self::ChecklistRemoved { name } => write!(f, "- checklist '{}'", name),
+            Self::ChecklistChanged { checklist } => {
+                write!(f, "~ checklist '{}'", checklist.name)
+            }
+            Self::AgentNoteAdded { note } => {
+                write!(f, "+ agent note by {}: {}", note.agent, note.decision)
+            }
+            Self::StatusRecorded { transition } => write!(
+                f,
+                "+ moved {} → {} at {}",
+                transition.from,
+                transition.to,
+                transition.at.to_rfc3339()
+            ),
+            Self::WorkLogged { entry } => write!(
+                f,
+                "+ {} minutes logged by {} on {}",
+                entry.minutes, entry.actor, entry.date
+            ),
+        }
+    }
+}
+
+impl fmt::Display for TaskDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return write!(f, "{}: no changes", self.task_id);
+        }
+        write!(f, "{}:", self.task_id)?;
+        for change in &self.changes {
+            write!(f, "\n  {}", change)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_tasks_have_no_diff() {
+        let task = Task::new(TaskId::new(1), "Same".to_string());
+        let mut touched = task.clone();
+        touched.updated_at = Utc::now() + chrono::Duration::seconds(5);
+
+        assert!(task.diff(&touched).is_empty());
+        assert_eq!(task.diff(&touched).to_string(), "HLA1: no changes");
+    }
+
+    #[test]
+    fn test_scalar_and_tag_changes() {
+        let before = Task::new(TaskId::new(1), "Old".to_string());
+        let mut after = before.clone();
+        after.set_title("New".to_string()).unwrap();
+        after.transition_to(TaskStatus::Open, None).unwrap();
+        after.set_priority(Priority::High);
+        after.add_tag("bug".to_string());
+
+        let diff = before.diff(&after);
+        let transition = after.status_history[0].clone();
+        let moved_at = transition.at.to_rfc3339();
+
+        assert_eq!(
+            diff.changes,
+            vec![
+                FieldChange::Title {
+                    from: "Old".to_string(),
+                    to: "New".to_string()
+                },
+                FieldChange::StatusRecorded { transition },
+                FieldChange::Status {
+                    from: TaskStatus::New,
+                    to: TaskStatus::Open
+                },
+                FieldChange::Priority {
+                    from: None,
+                    to: Some(Priority::High)
+                },
+                FieldChange::TagAdded {
+                    tag: "bug".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            diff.to_string(),
+            format!(
+                "HLA1:\n  title: 'Old' → 'New'\n  + moved New → Open at {}\n  status: New → Open\n  priority: (none) → 'High'\n  + tag 'bug'",
+                moved_at
+            )
+        );
+    }
+
+    #[test]
+    fn test_criterion_changes() {
+        let mut before = Task::new(TaskId::new(1), "Work".to_string());
+        let keep = before.add_acceptance_criterion("keep".to_string());
+        let edit = before.add_acceptance_criterion("edit me".to_string());
+        let remove = before.add_acceptance_criterion("remove".to_string());
+
+        let mut after = before.clone();
+        after.toggle_criterion(keep).unwrap();
+        after.update_criterion(edit, "edited".to_string()).unwrap();
+        after
+            .remove_acceptance_criterion(&remove.to_string())
+            .unwrap();
+        let added = after.add_acceptance_criterion("added".to_string());
+
+        let diff = before.diff(&after);
+        let rendered: Vec<String> = diff.changes.iter().map(|c| c.to_string()).collect();
+
+        assert_eq!(
+            rendered,
+            vec![
+                format!("AC #{} marked complete", keep),
+                format!("~ AC #{}: 'edit me' → 'edited'", edit),
+                format!("- AC #{}: remove", remove),
+                format!("+ AC #{}: added", added),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_criterion_detail_changes() {
+        let mut before = Task::new(TaskId::new(1), "Work".to_string());
+        let id = before.add_acceptance_criterion("verified".to_string());
+        let mut after = before.clone();
+        after.assign_criterion(id, Some("ana".to_string())).unwrap();
+        let ac = after
+            .acceptance_criteria
+            .iter_mut()
+            .find(|ac| ac.id == id)
+            .unwrap();
+        ac.notes = Some("Checked on staging".to_string());
+        ac.evidence_url = Some("https://ci.example/1".to_string());
+
+        let diff = before.diff(&after);
+        let rendered: Vec<String> = diff.changes.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                format!("AC #{} assignee: (none) → 'ana'", id),
+                format!("AC #{} notes added", id),
+                format!("AC #{} evidence: (none) → 'https://ci.example/1'", id),
+            ]
+        );
+
+        let mut replayed = before.clone();
+        diff.apply_to(&mut replayed);
+        assert_eq!(replayed.acceptance_criteria, after.acceptance_criteria);
+    }
+
+    #[test]
+    fn test_apply_diff_reproduces_target() {
+        let mut before = Task::new(TaskId::new(1), "Old".to_string());
+        before.add_acceptance_criterion("a".to_string());
+        let mut after = before.clone();
+        after.set_title("New".to_string()).unwrap();
+        after.add_tag("x".to_string());
+        after.toggle_criterion(1).unwrap();
+        after.add_acceptance_criterion("b".to_string());
+        after.add_checklist("Steps".to_string()).unwrap();
+
+        let mut replayed = before.clone();
+        before.diff(&after).apply_to(&mut replayed);
+
+        assert!(replayed.diff(&after).is_empty());
+    }
+
+    #[test]
+    fn test_diff_serialization() {
+        let before = Task::new(TaskId::new(1), "Old".to_string());
+        let mut after = before.clone();
+        after.set_title("New".to_string()).unwrap();
+
+        let json = serde_json::to_string(&before.diff(&after)).unwrap();
+        assert!(json.contains(r#""field":"title""#));
+    }
+
+    #[test]
+    fn test_lifecycle_timestamps_are_diffed() {
+        let before = Task::new(TaskId::new(1), "Old".to_string());
+        let at = Utc::now();
+        let mut after = before.clone();
+        after.archive(at);
+
+        let diff = before.diff(&after);
+        assert_eq!(
+            diff.changes,
+            vec![FieldChange::ArchivedAt {
+                from: None,
+                to: Some(at)
+            }]
+        );
+        assert_eq!(diff.changes[0].to_string(), "archived");
+
+        let mut restored = after.clone();
+        restored.restore();
+        restored.trash(at);
+        let mut copy = after.clone();
+        after.diff(&restored).apply_to(&mut copy);
+        assert_eq!(copy.archived_at, None);
+        assert_eq!(copy.trashed_at, Some(at));
+    }
+
+    #[test]
+    fn test_applied_status_change_sets_completion() {
+        let mut before = Task::new(TaskId::new(1), "Ship".to_string());
+        before.status = TaskStatus::Review;
+        let patch = TaskDiff {
+            task_id: before.id.clone(),
+            changes: vec![FieldChange::Status {
+                from: TaskStatus::Review,
+                to: TaskStatus::Done,
+            }],
+        };
+
+        let mut done = before.clone();
+        patch.apply_to(&mut done);
+        assert!(done.completed_at.is_some());
+        assert_eq!(done.completion_time(), done.completed_at);
+    }
+}
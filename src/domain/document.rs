@@ -0,0 +1,296 @@
+//! A single task as a standalone Markdown document, for attaching to pull
+//! requests or reviewing offline.
+//!
+//! ```markdown
+//! # HLA12: Fix login crash
+//!
+//! | Field | Value |
+//! | --- | --- |
+//! | Status | In Progress |
+//! | Priority | High |
+//!
+//! ## Description
+//!
+//! Crashes when the password is empty.
+//!
+//! ## Acceptance criteria
+//!
+//! - [x] Empty passwords are rejected
+//! - [ ] Regression test added
+//! ```
+//!
+//! The work log and history sections are written for readers only;
+//! [`Task::from_markdown`] reads the title, metadata table, description and
+//! acceptance criteria back.
+
+use crate::{
+    domain::task::{Priority, Task, TaskId, TaskStatus},
+    error::{HlaviError, Result},
+};
+use chrono::{DateTime, Utc};
+use std::{fmt::Write, str::FromStr};
+
+const DESCRIPTION: &str = "## Description";
+const CRITERIA: &str = "## Acceptance criteria";
+const WORK_LOG: &str = "## Work log";
+const AGENT_NOTES: &str = "## Agent notes";
+const HISTORY: &str = "## History";
+
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+impl Task {
+    /// Renders the task as a Markdown document
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# {}: {}\n\n| Field | Value |\n| --- | --- |\n",
+            self.id, self.title
+        );
+        let mut row = |field: &str, value: &str| {
+            let _ = writeln!(out, "| {} | {} |", field, escape_cell(value));
+        };
+        row("Status", &self.status.to_string());
+        if let Some(priority) = self.priority {
+            row("Priority", &priority.to_string());
+        }
+        if let Some(task_type) = &self.task_type {
+            row("Type", task_type);
+        }
+        if let Some(assignee) = &self.assignee {
+            row("Assignee", assignee);
+        }
+        if !self.tags.is_empty() {
+            row("Tags", &self.tags.join(", "));
+        }
+        if let Some(parent) = &self.parent {
+            row("Parent", parent.as_str());
+        }
+        if let Some(estimate) = self.estimate {
+            row("Estimate", &estimate.to_string());
+        }
+        if let Some(blocked) = &self.blocked {
+            row("Blocked", &blocked.to_string());
+        }
+        row("Created", &self.created_at.to_rfc3339());
+        row("Updated", &self.updated_at.to_rfc3339());
+
+        if let Some(description) = self.description.as_deref().filter(|d| !d.trim().is_empty()) {
+            let _ = write!(out, "\n{}\n\n{}\n", DESCRIPTION, description.trim_end());
+        }
+        if !self.acceptance_criteria.is_empty() {
+            let _ = writeln!(out, "\n{}\n", CRITERIA);
+            for ac in &self.acceptance_criteria {
+                let mark = if ac.completed { 'x' } else { ' ' };
+                let _ = writeln!(out, "- [{}] {}", mark, ac.description);
+            }
+        }
+        if !self.work_log.is_empty() {
+            let _ = writeln!(out, "\n{}\n", WORK_LOG);
+            for entry in &self.work_log {
+                let _ = write!(out, "- {} {} {}m", entry.date, entry.actor, entry.minutes);
+                if let Some(note) = &entry.note {
+                    let _ = write!(out, ": {}", note);
+                }
+                out.push('\n');
+            }
+        }
+        if !self.agent_notes.is_empty() {
+            let _ = writeln!(out, "\n{}\n", AGENT_NOTES);
+            for note in &self.agent_notes {
+                let _ = write!(
+                    out,
+                    "- {} {}: {}",
+                    note.at.to_rfc3339(),
+                    note.agent,
+                    note.decision
+                );
+                if let Some(confidence) = note.confidence {
+                    let _ = write!(out, " ({}% confident)", confidence);
+                }
+                out.push('\n');
+                if let Some(rationale) = &note.rationale {
+                    let _ = writeln!(out, "  - Why: {}", rationale);
+                }
+                if let Some(model) = &note.model {
+                    let _ = writeln!(out, "  - Model: {}", model);
+                }
+                if let Some(prompt_ref) = &note.prompt_ref {
+                    let _ = writeln!(out, "  - Prompt: {}", prompt_ref);
+                }
+                for artifact in &note.artifacts {
+                    let _ = writeln!(out, "  - Artifact: {}", artifact);
+                }
+            }
+        }
+        if !self.status_history.is_empty() {
+            let _ = writeln!(out, "\n{}\n", HISTORY);
+            for transition in &self.status_history {
+                let _ = writeln!(
+                    out,
+                    "- {} {} → {}",
+                    transition.at.to_rfc3339(),
+                    transition.from,
+                    transition.to
+                );
+            }
+        }
+        out
+    }
+
+    /// Reads a task back from a document written by `to_markdown`
+    ///
+    /// Fields missing from the table keep their defaults. A malformed heading,
+    /// ID or field value is an error.
+    pub fn from_markdown(text: &str) -> Result<Task> {
+        let mut lines = text.lines().skip_while(|line| line.trim().is_empty());
+        let heading = lines
+            .next()
+            .and_then(|line| line.strip_prefix("# "))
+            .ok_or_else(|| invalid("the document must start with '# ID: title'"))?;
+        let (id, title) = heading
+            .split_once(':')
+            .ok_or_else(|| invalid("the heading must read '# ID: title'"))?;
+        let mut task = Task::try_new(TaskId::from_str(id.trim())?, title.trim())?;
+
+        let mut section = "";
+        let mut description: Vec<&str> = Vec::new();
+        let mut updated = None;
+        for line in lines {
+            if [DESCRIPTION, CRITERIA, WORK_LOG, AGENT_NOTES, HISTORY].contains(&line.trim_end()) {
+                section = line.trim_end();
+                continue;
+            }
+            match section {
+                "" => {
+                    if let Some((field, value)) = table_row(line) {
+                        set_field(&mut task, &field, &value)?;
+                        if field == "Updated" {
+                            updated = Some(task.updated_at);
+                        }
+                    }
+                }
+                DESCRIPTION => description.push(line),
+                CRITERIA => {
+                    let item = line.trim_start().strip_prefix("- [");
+                    let Some((mark, text)) = item.and_then(|rest| rest.split_once("] ")) else {
+                        continue;
+                    };
+                    let id = task.add_acceptance_criterion(text.trim().to_string());
+                    if mark.eq_ignore_ascii_case("x") {
+                        task.toggle_criterion(id)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let description = description.join("\n");
+        if !description.trim().is_empty() {
+            task.set_description(description.trim().to_string())?;
+        }
+        // Rebuilding criteria and description touched the task; keep the
+        // documented time
+        task.updated_at = updated.unwrap_or(task.updated_at);
+        Ok(task)
+    }
+}
+
+fn invalid(reason: &str) -> HlaviError {
+    HlaviError::Validation(format!("Invalid task document: {}", reason))
+}
+
+/// Field and value of a metadata table row, skipping the header and rule
+fn table_row(line: &str) -> Option<(String, String)> {
+    let inner = line.trim().strip_prefix('|')?.strip_suffix('|')?;
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => cell.push(chars.next()?),
+            '|' => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell);
+    let [field, value] = <[String; 2]>::try_from(cells).ok()?;
+    let (field, value) = (field.trim().to_string(), value.trim().to_string());
+    (field != "Field" && !field.starts_with("---")).then_some((field, value))
+}
+
+fn set_field(task: &mut Task, field: &str, value: &str) -> Result<()> {
+    let timestamp = |value: &str| {
+        DateTime::parse_from_rfc3339(value)
+            .map(|at| at.with_timezone(&Utc))
+            .map_err(|_| invalid(&format!("'{}' is not an RFC 3339 timestamp", value)))
+    };
+    match field {
+        "Status" => task.status = TaskStatus::from_str(value)?,
+        "Priority" => task.priority = Some(Priority::from_str(value)?),
+        "Type" => task.task_type = Some(value.to_string()),
+        "Assignee" => task.assignee = Some(value.to_string()),
+        "Tags" => {
+            for tag in value.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+                task.add_tag(tag.to_string());
+            }
+        }
+        "Parent" => task.parent = Some(TaskId::from_str(value)?),
+        "Estimate" => {
+            task.estimate = Some(
+                value
+                    .parse()
+                    .map_err(|_| invalid(&format!("estimate '{}' is not a number", value)))?,
+            )
+        }
+        "Created" => task.created_at = timestamp(value)?,
+        "Updated" => task.updated_at = timestamp(value)?,
+        // Blocked and unknown rows are informational
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_round_trip() {
+        let mut task = Task::new(TaskId::new(12), "Fix login | crash".to_string());
+        task.enter_status(TaskStatus::Open, None);
+        task.priority = Some(Priority::High);
+        task.add_tag("auth".to_string());
+        task.add_tag("backend".to_string());
+        task.parent = Some(TaskId::new(3));
+        task.estimate = Some(5);
+        task.set_description("Crashes on empty password.\n\n## Steps\n\n1. Submit".to_string())
+            .unwrap();
+        let done = task.add_acceptance_criterion("Empty passwords are rejected".to_string());
+        task.toggle_criterion(done).unwrap();
+        task.add_acceptance_criterion("Regression test added".to_string());
+
+        let text = task.to_markdown();
+        assert!(text.starts_with("# HLA12: Fix login | crash\n"));
+        assert!(text.contains("| Status | Open |"));
+        assert!(text.contains("- [x] Empty passwords are rejected"));
+        assert!(text.contains("## History\n\n- "));
+
+        let read = Task::from_markdown(&text).unwrap();
+        assert_eq!(read.id, task.id);
+        assert_eq!(read.title, task.title);
+        assert_eq!(read.status, TaskStatus::Open);
+        assert_eq!(read.priority, task.priority);
+        assert_eq!(read.tags, task.tags);
+        assert_eq!(read.parent, task.parent);
+        assert_eq!(read.estimate, Some(5));
+        assert_eq!(read.description, task.description);
+        assert_eq!(read.created_at, task.created_at);
+        assert_eq!(read.updated_at, task.updated_at);
+        assert_eq!(read.acceptance_criteria.len(), 2);
+        assert!(read.acceptance_criteria[0].completed);
+        assert!(!read.acceptance_criteria[1].completed);
+
+        assert!(Task::from_markdown("no heading").is_err());
+        assert!(Task::from_markdown("# HLA1: Ok\n\n| Status | Sideways |\n").is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use crate::domain::task::{TaskId, TaskStatus};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Something that happened to a task, published by the service layer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskEvent {
+    pub task_id: TaskId,
+    pub kind: TaskEventKind,
+    pub at: DateTime<Utc>,
+}
+
+/// Kind of task event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TaskEventKind {
+    Created,
+    Updated,
+    StatusChanged {
+        from: TaskStatus,
+        to: TaskStatus,
+    },
+    Deleted,
+    Archived,
+    Trashed,
+    Restored,
+    /// Deleted for good after sitting in the trash
+    Purged,
+    /// Escalated by an aging rule after sitting untouched
+    Aged {
+        rule: String,
+    },
+    /// A draft became a regular task
+    Published,
+    /// Secrets were found in the task as it was saved, and redacted or
+    /// kept according to the board's secret policy
+    SecretDetected {
+        fields: Vec<String>,
+        redacted: bool,
+    },
+    /// The task started or stopped matching a saved view someone is
+    /// subscribed to. Raised for notifications, not on the event bus.
+    ViewMatch {
+        view: String,
+        matching: bool,
+    },
+}
+
+impl TaskEvent {
+    pub fn new(task_id: TaskId, kind: TaskEventKind) -> Self {
+        Self {
+            task_id,
+            kind,
+            at: Utc::now(),
+        }
+    }
+}
+
+impl TaskEventKind {
+    /// Returns the snake_case name used in serialized form
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::StatusChanged { .. } => "status_changed",
+            Self::Deleted => "deleted",
+            Self::Archived => "archived",
+            Self::Trashed => "trashed",
+            Self::Restored => "restored",
+            Self::Purged => "purged",
+            Self::Aged { .. } => "aged",
+            Self::Published => "published",
+            Self::SecretDetected { .. } => "secret_detected",
+            Self::ViewMatch { .. } => "view_match",
+        }
+    }
+}
+
+impl fmt::Display for TaskEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TaskEventKind::Created => write!(f, "{} created", self.task_id),
+            TaskEventKind::Updated => write!(f, "{} updated", self.task_id),
+            TaskEventKind::StatusChanged { from, to } => {
+                write!(f, "{} moved from {} to {}", self.task_id, from, to)
+            }
+            TaskEventKind::Deleted => write!(f, "{} deleted", self.task_id),
+            TaskEventKind::Archived => write!(f, "{} archived", self.task_id),
+            TaskEventKind::Trashed => write!(f, "{} moved to trash", self.task_id),
+            TaskEventKind::Restored => write!(f, "{} restored", self.task_id),
+            TaskEventKind::Purged => write!(f, "{} purged", self.task_id),
+            TaskEventKind::Aged { rule } => {
+                write!(f, "{} escalated by aging rule '{}'", self.task_id, rule)
+            }
+            TaskEventKind::Published => write!(f, "{} published", self.task_id),
+            TaskEventKind::SecretDetected { fields, redacted } => write!(
+                f,
+                "{} had secrets {} in {}",
+                self.task_id,
+                if *redacted { "redacted" } else { "left" },
+                fields.join(", ")
+            ),
+            TaskEventKind::ViewMatch { view, matching } => write!(
+                f,
+                "{} {} view '{}'",
+                self.task_id,
+                if *matching {
+                    "now matches"
+                } else {
+                    "no longer matches"
+                },
+                view
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_display() {
+        let event = TaskEvent::new(
+            TaskId::new(3),
+            TaskEventKind::StatusChanged {
+                from: TaskStatus::Open,
+                to: TaskStatus::InProgress,
+            },
+        );
+        assert_eq!(event.to_string(), "HLA3 moved from Open to In Progress");
+        assert_eq!(event.kind.name(), "status_changed");
+    }
+
+    #[test]
+    fn test_event_serialization() {
+        let event = TaskEvent::new(TaskId::new(1), TaskEventKind::Created);
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""type":"created""#));
+
+        let deserialized: TaskEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, event);
+    }
+}
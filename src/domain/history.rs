@@ -0,0 +1,135 @@
+use crate::domain::task::{Task, TaskId, TaskStatus};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// One recorded status change of a task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusTransition {
+    pub from: TaskStatus,
+    pub to: TaskStatus,
+    pub at: DateTime<Utc>,
+}
+
+/// Every task's status at a past moment, replayed from status history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoardState {
+    pub at: DateTime<Utc>,
+    /// Tasks that existed at `at`, ordered by ID number
+    pub statuses: Vec<(TaskId, TaskStatus)>,
+}
+
+impl BoardState {
+    /// Replays `tasks` back to `at`, leaving out drafts, tasks created after
+    /// it and tasks already archived or trashed by then
+    pub fn replay<'a>(tasks: impl IntoIterator<Item = &'a Task>, at: DateTime<Utc>) -> Self {
+        let set_aside = |when: Option<DateTime<Utc>>| when.is_some_and(|when| when <= at);
+        let mut statuses: Vec<(TaskId, TaskStatus)> = tasks
+            .into_iter()
+            .filter(|task| {
+                !task.draft && !set_aside(task.archived_at) && !set_aside(task.trashed_at)
+            })
+            .filter_map(|task| task.status_at(at).map(|status| (task.id.clone(), status)))
+            .collect();
+        statuses.sort_by_key(|(id, _)| id.number());
+        Self { at, statuses }
+    }
+
+    pub fn status_of(&self, id: &TaskId) -> Option<&TaskStatus> {
+        self.statuses
+            .iter()
+            .find(|(task_id, _)| task_id == id)
+            .map(|(_, status)| status)
+    }
+
+    /// Number of tasks in `status`
+    pub fn count(&self, status: &TaskStatus) -> usize {
+        self.statuses.iter().filter(|(_, s)| s == status).count()
+    }
+
+    /// Tasks whose status differs from `earlier`, with the earlier status
+    /// (`None` for tasks created since) and the status here
+    pub fn moved_since(
+        &self,
+        earlier: &BoardState,
+    ) -> Vec<(TaskId, Option<TaskStatus>, TaskStatus)> {
+        self.statuses
+            .iter()
+            .filter_map(|(id, status)| {
+                let before = earlier.status_of(id);
+                (before != Some(status)).then(|| (id.clone(), before.cloned(), status.clone()))
+            })
+            .collect()
+    }
+}
+
+impl Task {
+    /// The task's status at `at`, or `None` if it did not exist yet
+    ///
+    /// Moves made before history was recorded are not known, so such tasks
+    /// report the earliest status their history shows.
+    pub fn status_at(&self, at: DateTime<Utc>) -> Option<TaskStatus> {
+        if self.created_at > at {
+            return None;
+        }
+        let last = self
+            .status_history
+            .iter()
+            .take_while(|transition| transition.at <= at)
+            .last();
+        Some(match (last, self.status_history.first()) {
+            (Some(transition), _) => transition.to.clone(),
+            (None, Some(first)) => first.from.clone(),
+            (None, None) => self.status.clone(),
+        })
+    }
+
+    /// Moves the task to `status` at `at`, recording the change
+    pub(crate) fn record_status(&mut self, status: TaskStatus, at: DateTime<Utc>) {
+        if status == self.status {
+            return;
+        }
+        self.status_history.push(StatusTransition {
+            from: self.status.clone(),
+            to: status.clone(),
+            at,
+        });
+        self.status_changed_at = Some(at);
+        self.status = status;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_replay() {
+        let start = Utc::now();
+        let mut moved = Task::new(TaskId::new(1), "Moved".to_string());
+        moved.created_at = start;
+        moved.record_status(TaskStatus::Open, start + Duration::days(1));
+        moved.record_status(TaskStatus::InProgress, start + Duration::days(3));
+        let mut late = Task::new(TaskId::new(2), "Late".to_string());
+        late.created_at = start + Duration::days(2);
+
+        let monday = BoardState::replay([&moved, &late], start + Duration::days(1));
+        assert_eq!(monday.statuses, vec![(TaskId::new(1), TaskStatus::Open)]);
+        assert_eq!(moved.status_at(start), Some(TaskStatus::New));
+        assert_eq!(late.status_at(start), None);
+
+        let now = BoardState::replay([&moved, &late], start + Duration::days(4));
+        assert_eq!(now.count(&TaskStatus::InProgress), 1);
+        assert_eq!(
+            now.moved_since(&monday),
+            vec![
+                (
+                    TaskId::new(1),
+                    Some(TaskStatus::Open),
+                    TaskStatus::InProgress
+                ),
+                (TaskId::new(2), None, TaskStatus::New),
+            ]
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! One person, many names: git emails, display names and usernames in other
+//! systems mapped to a single actor.
+//!
+//! ```toml
+//! [[identities]]
+//! name = "Ana Lee"
+//! emails = ["ana@example.com", "ana.lee@users.noreply.github.com"]
+//! aliases = ["ana", "alee"]
+//!
+//! [identities.accounts]
+//! gitlab = "analee"
+//! linear = "ana.lee"
+//! ```
+
+use crate::{
+    domain::privacy::same_actor,
+    error::{HlaviError, Result},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Everything one person is known as
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Identity {
+    /// Display name shown for the person everywhere
+    pub name: String,
+    /// Commit author emails
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub emails: Vec<String>,
+    /// Other names the person has acted under, such as a short handle
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Username per external system, keyed like `ExternalRef::system`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub accounts: BTreeMap<String, String>,
+}
+
+impl Identity {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_email(mut self, email: &str) -> Self {
+        self.emails.push(email.to_string());
+        self
+    }
+
+    pub fn with_alias(mut self, alias: &str) -> Self {
+        self.aliases.push(alias.to_string());
+        self
+    }
+
+    pub fn with_account(mut self, system: &str, username: &str) -> Self {
+        self.accounts
+            .insert(system.to_string(), username.to_string());
+        self
+    }
+
+    /// Checks whether `actor` is this person's name, an alias or an email
+    pub fn is(&self, actor: &str) -> bool {
+        std::iter::once(&self.name)
+            .chain(&self.aliases)
+            .chain(&self.emails)
+            .any(|known| same_actor(known, actor))
+    }
+
+    /// Checks whether `username` is this person's account in `system`
+    pub fn has_account(&self, system: &str, username: &str) -> bool {
+        self.accounts
+            .iter()
+            .any(|(s, u)| s.eq_ignore_ascii_case(system) && same_actor(u, username))
+    }
+}
+
+/// The project's known people
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct IdentityMap(pub Vec<Identity>);
+
+impl IdentityMap {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn add(&mut self, identity: Identity) {
+        self.0.push(identity);
+    }
+
+    /// The person known by `actor`, a name, alias or email
+    pub fn find(&self, actor: &str) -> Option<&Identity> {
+        self.0.iter().find(|identity| identity.is(actor))
+    }
+
+    /// The person whose `system` account is `username`
+    pub fn find_account(&self, system: &str, username: &str) -> Option<&Identity> {
+        self.0
+            .iter()
+            .find(|identity| identity.has_account(system, username))
+    }
+
+    /// The display name for `actor`, or `actor` itself when unknown
+    pub fn canonical(&self, actor: &str) -> String {
+        self.find(actor)
+            .map_or_else(|| actor.trim().to_string(), |i| i.name.clone())
+    }
+
+    /// The display name for a `system` username, which may also be written
+    /// as a known name or email; unknown usernames are kept as they are
+    pub fn canonical_account(&self, system: &str, username: &str) -> String {
+        self.find_account(system, username)
+            .or_else(|| self.find(username))
+            .map_or_else(|| username.trim().to_string(), |i| i.name.clone())
+    }
+
+    /// The `system` username of the person known by `actor`
+    pub fn account(&self, actor: &str, system: &str) -> Option<&str> {
+        let identity = self.find(actor)?;
+        identity
+            .accounts
+            .iter()
+            .find(|(s, _)| s.eq_ignore_ascii_case(system))
+            .map(|(_, username)| username.as_str())
+    }
+
+    /// Checks that every person has a name and that no name, alias, email
+    /// or account belongs to two people
+    pub fn validate(&self) -> Result<()> {
+        let mut seen: BTreeMap<String, &str> = BTreeMap::new();
+        for identity in &self.0 {
+            if identity.name.trim().is_empty() {
+                return Err(HlaviError::ConfigError(
+                    "Every identity needs a name".to_string(),
+                ));
+            }
+            let keys = std::iter::once(&identity.name)
+                .chain(&identity.aliases)
+                .chain(&identity.emails)
+                .map(|known| known.trim().to_lowercase())
+                .chain(identity.accounts.iter().map(|(system, username)| {
+                    format!(
+                        "{}:{}",
+                        system.to_lowercase(),
+                        username.trim().to_lowercase()
+                    )
+                }));
+            let mut own = Vec::new();
+            for key in keys {
+                match seen.get(&key) {
+                    Some(other) if !same_actor(other, &identity.name) => {
+                        return Err(HlaviError::ConfigError(format!(
+                            "'{}' is claimed by both {} and {}",
+                            key, other, identity.name
+                        )));
+                    }
+                    _ => own.push(key),
+                }
+            }
+            if seen.contains_key(&identity.name.trim().to_lowercase()) {
+                return Err(HlaviError::ConfigError(format!(
+                    "Identity '{}' is listed twice",
+                    identity.name
+                )));
+            }
+            for key in own {
+                seen.insert(key, &identity.name);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn people() -> IdentityMap {
+        IdentityMap(vec![
+            Identity::new("Ana Lee")
+                .with_email("ana@example.com")
+                .with_alias("ana")
+                .with_account("gitlab", "analee"),
+            Identity::new("Ben Okafor").with_account("linear", "ben.o"),
+        ])
+    }
+
+    #[test]
+    fn test_resolution() {
+        let map = people();
+        assert_eq!(map.canonical("ANA@example.com"), "Ana Lee");
+        assert_eq!(map.canonical(" ana "), "Ana Lee");
+        assert_eq!(map.canonical("carol"), "carol");
+        assert_eq!(map.canonical_account("gitlab", "AnaLee"), "Ana Lee");
+        assert_eq!(map.canonical_account("linear", "ben.o"), "Ben Okafor");
+        // An account in another system is not a match
+        assert_eq!(map.canonical_account("linear", "analee"), "analee");
+        assert_eq!(map.account("ana@example.com", "GitLab"), Some("analee"));
+        assert_eq!(map.account("Ben Okafor", "gitlab"), None);
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut map = people();
+        assert!(map.validate().is_ok());
+        map.add(Identity::new("Anna").with_alias("ana"));
+        assert!(map.validate().is_err());
+
+        let mut map = people();
+        map.add(Identity::new("ana lee"));
+        assert!(map.validate().is_err());
+        assert!(IdentityMap(vec![Identity::new(" ")]).validate().is_err());
+    }
+}
@@ -0,0 +1,276 @@
+use crate::{
+    domain::task::{Priority, TaskStatus},
+    error::{HlaviError, Result},
+};
+use std::collections::HashMap;
+
+/// Locale code of the built-in strings
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// A translation table mapping message keys such as `status.in_progress`
+/// to display strings
+///
+/// Messages may contain positional placeholders (`{0}`, `{1}`, ...) that are
+/// filled from the arguments of the value being displayed. Keys missing from
+/// the table fall back to the English `Display` output, so partial tables
+/// are fine.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Locale {
+    code: String,
+    messages: HashMap<String, String>,
+}
+
+impl Locale {
+    /// Creates an empty table for `code`; everything renders in English
+    pub fn new(code: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            messages: HashMap::new(),
+        }
+    }
+
+    /// The built-in English locale
+    pub fn english() -> Self {
+        Self::new(DEFAULT_LOCALE)
+    }
+
+    /// Loads a table from flat TOML, e.g. `"status.done" = "Erledigt"`
+    pub fn from_toml(code: &str, input: &str) -> Result<Self> {
+        let messages: HashMap<String, String> = toml::from_str(input).map_err(|e| {
+            HlaviError::ConfigError(format!("Invalid translation table '{}': {}", code, e))
+        })?;
+        Ok(Self {
+            code: code.to_string(),
+            messages,
+        })
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Adds or replaces a message
+    pub fn insert(&mut self, key: &str, message: &str) {
+        self.messages.insert(key.to_string(), message.to_string());
+    }
+
+    pub fn with(mut self, key: &str, message: &str) -> Self {
+        self.insert(key, message);
+        self
+    }
+
+    pub fn message(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+
+    /// Renders `value` in this locale
+    pub fn format<T: Localize + ?Sized>(&self, value: &T) -> String {
+        value.localized(self)
+    }
+}
+
+/// Values with a user-facing string that can be translated
+pub trait Localize {
+    /// Key looked up in the translation table
+    fn message_key(&self) -> &'static str;
+
+    /// Values substituted for `{0}`, `{1}`, ... in the translated message
+    fn message_args(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// English text used when the locale has no entry for the key
+    fn fallback(&self) -> String;
+
+    fn localized(&self, locale: &Locale) -> String {
+        match locale.message(self.message_key()) {
+            Some(template) => fill_placeholders(template, &self.message_args()),
+            None => self.fallback(),
+        }
+    }
+}
+
+fn fill_placeholders(template: &str, args: &[String]) -> String {
+    args.iter()
+        .enumerate()
+        .fold(template.to_string(), |message, (i, arg)| {
+            message.replace(&format!("{{{}}}", i), arg)
+        })
+}
+
+impl Localize for TaskStatus {
+    fn message_key(&self) -> &'static str {
+        match self {
+            Self::New => "status.new",
+            Self::Open => "status.open",
+            Self::InProgress => "status.in_progress",
+            Self::Pending => "status.pending",
+            Self::Review => "status.review",
+            Self::Done => "status.done",
+            Self::Closed => "status.closed",
+        }
+    }
+
+    fn fallback(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Localize for Priority {
+    fn message_key(&self) -> &'static str {
+        match self {
+            Self::Low => "priority.low",
+            Self::Medium => "priority.medium",
+            Self::High => "priority.high",
+            Self::Critical => "priority.critical",
+        }
+    }
+
+    fn fallback(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Localize for HlaviError {
+    fn message_key(&self) -> &'static str {
+        match self {
+            Self::TaskNotFound(_) => "error.task_not_found",
+            Self::BoardNotInitialized => "error.board_not_initialized",
+            Self::InvalidStatusTransition { .. } => "error.invalid_status_transition",
+            Self::InvalidTaskId(_) => "error.invalid_task_id",
+            Self::StorageError(_) => "error.storage",
+            Self::IoError(_) => "error.io",
+            #[cfg(feature = "storage")]
+            Self::SerializationError(_) => "error.serialization",
+            Self::ConfigError(_) => "error.config",
+            Self::AcceptanceCriteriaNotFound => "error.acceptance_criteria_not_found",
+            Self::ChecklistNotFound(_) => "error.checklist_not_found",
+            Self::ChecklistItemNotFound(_) => "error.checklist_item_not_found",
+            Self::ViewNotFound(_) => "error.view_not_found",
+            Self::InvalidDateRange { .. } => "error.invalid_date_range",
+            Self::ProjectNotInitialized => "error.project_not_initialized",
+            Self::MergeConflict { .. } => "error.merge_conflict",
+            Self::ScriptError(_) => "error.script",
+            Self::Validation(_) => "error.validation",
+            Self::QuotaExceeded { .. } => "error.quota_exceeded",
+            Self::TaskLocked { .. } => "error.task_locked",
+            Self::MissingRequiredFields { .. } => "error.missing_required_fields",
+            Self::UnsupportedFormatVersion { .. } => "error.unsupported_format_version",
+            Self::SecretDetected { .. } => "error.secret_detected",
+            Self::InvalidQuery { .. } => "error.invalid_query",
+            Self::Other(_) => "error.other",
+        }
+    }
+
+    fn message_args(&self) -> Vec<String> {
+        match self {
+            Self::TaskNotFound(s)
+            | Self::InvalidTaskId(s)
+            | Self::StorageError(s)
+            | Self::ConfigError(s)
+            | Self::ChecklistNotFound(s)
+            | Self::ViewNotFound(s)
+            | Self::ScriptError(s)
+            | Self::Validation(s)
+            | Self::Other(s) => vec![s.clone()],
+            Self::InvalidStatusTransition { from, to } => vec![from.clone(), to.clone()],
+            Self::TaskLocked {
+                task_id,
+                actor,
+                until,
+            } => vec![task_id.clone(), actor.clone(), until.clone()],
+            Self::MissingRequiredFields {
+                task_id,
+                column,
+                fields,
+            } => {
+                let mut args = vec![task_id.clone(), column.clone()];
+                args.extend(fields.iter().map(ToString::to_string));
+                args
+            }
+            Self::QuotaExceeded {
+                actor,
+                operation,
+                limit,
+                window_minutes,
+            } => vec![
+                actor.clone(),
+                operation.clone(),
+                limit.to_string(),
+                window_minutes.to_string(),
+            ],
+            Self::UnsupportedFormatVersion {
+                found,
+                oldest,
+                newest,
+            } => vec![found.to_string(), oldest.to_string(), newest.to_string()],
+            Self::SecretDetected {
+                task_id,
+                field,
+                kind,
+            } => vec![task_id.clone(), kind.clone(), field.clone()],
+            Self::InvalidQuery { position, message } => {
+                vec![position.to_string(), message.clone()]
+            }
+            Self::IoError(e) => vec![e.to_string()],
+            #[cfg(feature = "storage")]
+            Self::SerializationError(e) => vec![e.to_string()],
+            Self::ChecklistItemNotFound(index) => vec![index.to_string()],
+            Self::InvalidDateRange { start, end } => vec![start.clone(), end.clone()],
+            Self::MergeConflict { task_id, conflicts } => {
+                vec![task_id.clone(), conflicts.len().to_string()]
+            }
+            Self::BoardNotInitialized
+            | Self::AcceptanceCriteriaNotFound
+            | Self::ProjectNotInitialized => Vec::new(),
+        }
+    }
+
+    fn fallback(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_falls_back_to_display() {
+        let en = Locale::english();
+        assert_eq!(en.format(&TaskStatus::InProgress), "In Progress");
+        assert_eq!(en.format(&Priority::High), "High");
+        let err = HlaviError::TaskNotFound("HLA1".to_string());
+        assert_eq!(en.format(&err), err.to_string());
+    }
+
+    #[test]
+    fn test_translation_table() {
+        let de = Locale::from_toml(
+            "de",
+            r#"
+"status.in_progress" = "In Bearbeitung"
+"priority.high" = "Hoch"
+"error.invalid_status_transition" = "Übergang von {0} nach {1} nicht erlaubt"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(de.code(), "de");
+        assert_eq!(de.format(&TaskStatus::InProgress), "In Bearbeitung");
+        assert_eq!(de.format(&Priority::High), "Hoch");
+        // Missing keys use the English text
+        assert_eq!(de.format(&TaskStatus::Done), "Done");
+
+        let err = HlaviError::InvalidStatusTransition {
+            from: "New".to_string(),
+            to: "Done".to_string(),
+        };
+        assert_eq!(de.format(&err), "Übergang von New nach Done nicht erlaubt");
+    }
+
+    #[test]
+    fn test_invalid_table() {
+        assert!(Locale::from_toml("fr", "not = [valid").is_err());
+    }
+}
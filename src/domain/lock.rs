@@ -0,0 +1,70 @@
+use crate::domain::{privacy::same_actor, task::Task};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// An editing lock on a task
+///
+/// Locks are advisory claims that stop two people, or a person and an
+/// attended agent session, editing the same task at once. They expire on
+/// their own, so a crashed session cannot hold a task forever.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskLock {
+    pub actor: String,
+    pub acquired_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl TaskLock {
+    pub fn new(actor: &str, now: DateTime<Utc>, ttl: Duration) -> Self {
+        Self {
+            actor: actor.to_string(),
+            acquired_at: now,
+            expires_at: now + ttl,
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Checks whether `actor` holds the lock (case-insensitive)
+    pub fn is_held_by(&self, actor: &str) -> bool {
+        same_actor(&self.actor, actor)
+    }
+}
+
+impl Task {
+    /// The task's lock, unless it has expired
+    pub fn active_lock(&self, now: DateTime<Utc>) -> Option<&TaskLock> {
+        self.lock.as_ref().filter(|lock| !lock.is_expired(now))
+    }
+
+    /// Checks whether someone other than `actor` holds an unexpired lock;
+    /// with no actor, any unexpired lock counts
+    pub fn is_locked_against(&self, actor: Option<&str>, now: DateTime<Utc>) -> bool {
+        self.active_lock(now)
+            .is_some_and(|lock| !actor.is_some_and(|actor| lock.is_held_by(actor)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::task::TaskId;
+
+    #[test]
+    fn test_lock_expiry_and_holder() {
+        let now = Utc::now();
+        let mut task = Task::new(TaskId::new(1), "Shared".to_string());
+        assert!(!task.is_locked_against(None, now));
+
+        task.lock = Some(TaskLock::new("Agent", now, Duration::minutes(15)));
+        assert!(!task.is_locked_against(Some("agent"), now));
+        assert!(task.is_locked_against(Some("ana"), now));
+        assert!(task.is_locked_against(None, now));
+
+        let later = now + Duration::minutes(15);
+        assert!(task.active_lock(later).is_none());
+        assert!(!task.is_locked_against(Some("ana"), later));
+    }
+}
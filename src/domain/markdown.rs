@@ -0,0 +1,198 @@
+//! Helpers for rendering task bodies consistently across frontends.
+//!
+//! These are deliberately lightweight: they understand the Markdown that
+//! task descriptions commonly use (headings, lists, emphasis, code, links)
+//! rather than the full CommonMark grammar.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How a task description should be interpreted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DescriptionFormat {
+    #[default]
+    Plain,
+    Markdown,
+}
+
+impl DescriptionFormat {
+    pub fn is_plain(&self) -> bool {
+        *self == Self::Plain
+    }
+}
+
+impl fmt::Display for DescriptionFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Plain => write!(f, "plain"),
+            Self::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+/// The first paragraph of the text, with formatting removed and lines joined
+pub fn summary(text: &str, format: DescriptionFormat) -> String {
+    let plain = strip_formatting(text, format);
+    plain
+        .split("\n\n")
+        .map(|p| p.split_whitespace().collect::<Vec<_>>().join(" "))
+        .find(|p| !p.is_empty())
+        .unwrap_or_default()
+}
+
+/// Removes Markdown syntax, keeping the readable text; plain text is returned as-is
+pub fn strip_formatting(text: &str, format: DescriptionFormat) -> String {
+    if format.is_plain() {
+        return text.to_string();
+    }
+
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            lines.push(line.to_string());
+            continue;
+        }
+        if is_rule(trimmed) {
+            lines.push(String::new());
+            continue;
+        }
+        lines.push(strip_inline(strip_block_marker(trimmed)));
+    }
+    lines.join("\n")
+}
+
+/// Number of words in the readable text
+pub fn word_count(text: &str, format: DescriptionFormat) -> usize {
+    strip_formatting(text, format)
+        .split_whitespace()
+        .filter(|w| w.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+fn is_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|m| compact.chars().all(|c| c == *m))
+}
+
+/// Strips heading, quote and list markers from the start of a line
+fn strip_block_marker(line: &str) -> &str {
+    let mut line = line;
+    loop {
+        let before = line;
+        line = line.trim_start_matches('>').trim_start();
+        if let Some(rest) = line.strip_prefix('#') {
+            line = rest.trim_start_matches('#').trim_start();
+        }
+        for marker in ["- ", "* ", "+ "] {
+            if let Some(rest) = line.strip_prefix(marker) {
+                line = rest;
+            }
+        }
+        let digits = line.chars().take_while(char::is_ascii_digit).count();
+        if digits > 0 {
+            if let Some(rest) = line[digits..]
+                .strip_prefix(". ")
+                .or_else(|| line[digits..].strip_prefix(") "))
+            {
+                line = rest;
+            }
+        }
+        for checkbox in ["[ ] ", "[x] ", "[X] "] {
+            if let Some(rest) = line.strip_prefix(checkbox) {
+                line = rest;
+            }
+        }
+        if line == before {
+            return line;
+        }
+    }
+}
+
+/// Removes emphasis, inline code, strikethrough, and link/image syntax
+fn strip_inline(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        // [text](url) and ![alt](url) keep only the text
+        let link_start = rest.strip_prefix("![").or_else(|| rest.strip_prefix('['));
+        if let Some(after) = link_start {
+            if let Some(close) = after.find("](") {
+                if let Some(end) = after[close + 2..].find(')') {
+                    out.push_str(&strip_inline(&after[..close]));
+                    rest = &after[close + 2 + end + 1..];
+                    continue;
+                }
+            }
+        }
+        if rest.starts_with("**") || rest.starts_with("__") || rest.starts_with("~~") {
+            rest = &rest[2..];
+            continue;
+        }
+        if c == '*' || c == '`' {
+            rest = &rest[1..];
+            continue;
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "# Login fails\n\nUsers on **iOS** can't log in after the\n`2.3` update. See [the report](https://example.com/r/1).\n\n## Steps\n\n1. Open the app\n- [ ] Tap *Sign in*\n\n```\nlet x = **y**;\n```\n";
+
+    #[test]
+    fn test_strip_formatting() {
+        let plain = strip_formatting(DOC, DescriptionFormat::Markdown);
+        assert!(plain.contains("Users on iOS can't log in after the"));
+        assert!(plain.contains("2.3 update. See the report."));
+        assert!(plain.contains("\nOpen the app\nTap Sign in\n"));
+        // Code blocks are kept verbatim
+        assert!(plain.contains("let x = **y**;"));
+        assert!(!plain.contains('#'));
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(summary(DOC, DescriptionFormat::Markdown), "Login fails");
+        let body = "\n\nFirst line\ncontinues here.\n\nSecond paragraph.";
+        assert_eq!(
+            summary(body, DescriptionFormat::Plain),
+            "First line continues here."
+        );
+        assert_eq!(summary("", DescriptionFormat::Markdown), "");
+    }
+
+    #[test]
+    fn test_word_count() {
+        assert_eq!(
+            word_count("**Fix** the `login` - now", DescriptionFormat::Markdown),
+            4
+        );
+        assert_eq!(word_count("**Fix** it", DescriptionFormat::Plain), 2);
+    }
+
+    #[test]
+    fn test_snake_case_survives() {
+        assert_eq!(
+            strip_formatting(
+                "rename user_id to *account_id*",
+                DescriptionFormat::Markdown
+            ),
+            "rename user_id to account_id"
+        );
+    }
+}
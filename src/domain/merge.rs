@@ -0,0 +1,442 @@
+use crate::domain::{diff::FieldChange, task::Task};
+use serde::{Deserialize, Serialize};
+
+/// Both sides changed the same field in different ways
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeConflict {
+    /// Name of the conflicting field (e.g., "title", "ac:3", "checklist:Steps")
+    pub field: String,
+    pub ours: FieldChange,
+    pub theirs: FieldChange,
+}
+
+/// Outcome of a three-way merge
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeOutcome {
+    /// Merged task; conflicting fields hold our version
+    pub merged: Task,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MergeOutcome {
+    /// Returns true if every change merged without conflict
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Identifies the field a change touches, for conflict detection
+///
+/// Criterion changes are keyed as `ac:<id>` with an optional aspect, so that
+/// removing a criterion conflicts with editing or toggling it.
+fn change_key(change: &FieldChange) -> (String, Option<&'static str>) {
+    let key = |s: &str| (s.to_string(), None);
+    match change {
+        FieldChange::Title { .. } => key("title"),
+        FieldChange::Description { .. } => key("description"),
+        FieldChange::DescriptionFormat { .. } => key("description_format"),
+        FieldChange::Status { .. } => key("status"),
+        FieldChange::CompletedAt { .. } => key("completed_at"),
+        FieldChange::ArchivedAt { .. } => key("archived_at"),
+        FieldChange::TrashedAt { .. } => key("trashed_at"),
+        FieldChange::Priority { .. } => key("priority"),
+        FieldChange::StartDate { .. } => key("start_date"),
+        FieldChange::EndDate { .. } => key("end_date"),
+        FieldChange::Parent { .. } => key("parent"),
+        FieldChange::Rank { .. } => key("rank"),
+        FieldChange::AgentAssigned { .. } => key("agent_assigned"),
+        FieldChange::Assignee { .. } => key("assignee"),
+        FieldChange::Estimate { .. } => key("estimate"),
+        FieldChange::TaskType { .. } => key("task_type"),
+        FieldChange::Severity { .. } => key("severity"),
+        FieldChange::RejectionReason { .. } => key("rejection_reason"),
+        FieldChange::Occurrences { .. } => key("occurrences"),
+        FieldChange::TagAdded { tag } | FieldChange::TagRemoved { tag } => {
+            (format!("tag:{}", tag.to_lowercase()), None)
+        }
+        FieldChange::ComponentAdded { component } | FieldChange::ComponentRemoved { component } => {
+            (format!("component:{}", component.to_lowercase()), None)
+        }
+        FieldChange::Environment { .. } => key("environment"),
+        FieldChange::Draft { .. } => key("draft"),
+        FieldChange::Blocked { .. } => key("blocked"),
+        FieldChange::BlockAdded { task_id } | FieldChange::BlockRemoved { task_id } => {
+            (format!("blocks:{}", task_id), None)
+        }
+        FieldChange::LinkAdded { link } | FieldChange::LinkRemoved { link } => {
+            (format!("link:{}", link), None)
+        }
+        FieldChange::ExternalRefAdded { external }
+        | FieldChange::ExternalRefRemoved { external } => (format!("ref:{}", external), None),
+        // New criteria never conflict; colliding IDs are reassigned on apply
+        FieldChange::CriterionAdded { criterion } => {
+            (format!("ac-new:{}", criterion.description), None)
+        }
+        FieldChange::CriterionRemoved { criterion } => (format!("ac:{}", criterion.id), None),
+        FieldChange::CriterionEdited { id, .. } => (format!("ac:{}", id), Some("text")),
+        FieldChange::CriterionToggled { id, .. } => (format!("ac:{}", id), Some("done")),
+        FieldChange::CriterionAssignee { id, .. } => (format!("ac:{}", id), Some("assignee")),
+        FieldChange::CriterionNotes { id, .. } => (format!("ac:{}", id), Some("notes")),
+        FieldChange::CriterionEvidence { id, .. } => (format!("ac:{}", id), Some("evidence")),
+        FieldChange::ChecklistAdded { checklist } | FieldChange::ChecklistChanged { checklist } => {
+            (format!("checklist:{}", checklist.name), None)
+        }
+        FieldChange::ChecklistRemoved { name } => (format!("checklist:{}", name), None),
+        FieldChange::AgentNoteAdded { .. } => key("agent_notes"),
+        FieldChange::StatusRecorded { .. } => key("status_history"),
+        FieldChange::WorkLogged { .. } => key("work_log"),
+    }
+}
+
+/// Returns true for entries appended to a log and for counts, which never
+/// conflict
+fn is_appended(change: &FieldChange) -> bool {
+    matches!(
+        change,
+        FieldChange::AgentNoteAdded { .. }
+            | FieldChange::StatusRecorded { .. }
+            | FieldChange::WorkLogged { .. }
+            | FieldChange::Occurrences { .. }
+    )
+}
+
+/// Returns true if both sides completed, archived or trashed the task,
+/// which agree even though they happened at different moments
+fn both_stamped(a: &FieldChange, b: &FieldChange) -> bool {
+    matches!(
+        (a, b),
+        (
+            FieldChange::CompletedAt { to: Some(_), .. },
+            FieldChange::CompletedAt { to: Some(_), .. }
+        ) | (
+            FieldChange::ArchivedAt { to: Some(_), .. },
+            FieldChange::ArchivedAt { to: Some(_), .. }
+        ) | (
+            FieldChange::TrashedAt { to: Some(_), .. },
+            FieldChange::TrashedAt { to: Some(_), .. }
+        )
+    )
+}
+
+fn overlaps(a: &FieldChange, b: &FieldChange) -> bool {
+    if is_appended(a) || is_appended(b) {
+        return false;
+    }
+    let (a_field, a_aspect) = change_key(a);
+    let (b_field, b_aspect) = change_key(b);
+    a_field == b_field && (a_aspect.is_none() || b_aspect.is_none() || a_aspect == b_aspect)
+}
+
+/// Three-way merges two edited versions of a task against their common base
+///
+/// All of our changes are applied to the base, followed by every change of
+/// theirs that doesn't touch a field we also changed differently. Identical
+/// changes on both sides are applied once. Overlapping, divergent changes
+/// are reported as conflicts, keeping our value in `merged`. Append-only
+/// logs such as agent notes, status history and the work log are unioned,
+/// and occurrences counted on each side are added together. If both sides
+/// completed, archived or trashed the task, our timestamp is kept.
+pub fn merge(base: &Task, ours: &Task, theirs: &Task) -> MergeOutcome {
+    let our_changes = base.diff(ours).changes;
+    let their_changes = base.diff(theirs).changes;
+
+    let mut merged = base.clone();
+    for change in &our_changes {
+        change.apply(&mut merged);
+    }
+
+    let mut conflicts = Vec::new();
+    for change in their_changes {
+        // Equal counts on both sides are separate reports, so both add up
+        let counted = matches!(change, FieldChange::Occurrences { .. });
+        if our_changes.contains(&change) && !counted {
+            continue;
+        }
+        // Both sides archived, trashed or completed it; our time is kept
+        if our_changes.iter().any(|ours| both_stamped(ours, &change)) {
+            continue;
+        }
+        match our_changes.iter().find(|ours| overlaps(ours, &change)) {
+            Some(ours) => conflicts.push(MergeConflict {
+                field: change_key(&change).0,
+                ours: ours.clone(),
+                theirs: change,
+            }),
+            None => change.apply(&mut merged),
+        }
+    }
+
+    merged.agent_notes.sort_by_key(|note| note.at);
+    merged.work_log.sort_by_key(|entry| entry.date);
+    merged
+        .status_history
+        .sort_by_key(|transition| transition.at);
+    merged.updated_at = ours.updated_at.max(theirs.updated_at);
+    MergeOutcome { merged, conflicts }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        agent_note::AgentNote,
+        task::{Priority, TaskId, TaskStatus},
+        worklog::WorkLogEntry,
+    };
+
+    fn base() -> Task {
+        let mut task = Task::new(TaskId::new(1), "Base".to_string());
+        task.add_acceptance_criterion("a".to_string());
+        task.add_acceptance_criterion("b".to_string());
+        task
+    }
+
+    #[test]
+    fn test_non_conflicting_changes_merge() {
+        let base = base();
+        let mut ours = base.clone();
+        ours.set_title("Ours".to_string()).unwrap();
+        ours.add_tag("backend".to_string());
+        let mut theirs = base.clone();
+        theirs.set_priority(Priority::High);
+        theirs.toggle_criterion(2).unwrap();
+
+        let outcome = merge(&base, &ours, &theirs);
+
+        assert!(outcome.is_clean());
+        assert_eq!(outcome.merged.title, "Ours");
+        assert!(outcome.merged.has_tag("backend"));
+        assert_eq!(outcome.merged.priority, Some(Priority::High));
+        assert!(outcome.merged.find_criterion(2).unwrap().completed);
+    }
+
+    #[test]
+    fn test_identical_changes_do_not_conflict() {
+        let base = base();
+        let mut ours = base.clone();
+        ours.transition_to(TaskStatus::Open, None).unwrap();
+        let theirs = ours.clone();
+
+        let outcome = merge(&base, &ours, &theirs);
+        assert!(outcome.is_clean());
+        assert_eq!(outcome.merged.status, TaskStatus::Open);
+    }
+
+    #[test]
+    fn test_divergent_field_conflicts() {
+        let base = base();
+        let mut ours = base.clone();
+        ours.set_title("Ours".to_string()).unwrap();
+        let mut theirs = base.clone();
+        theirs.set_title("Theirs".to_string()).unwrap();
+
+        let outcome = merge(&base, &ours, &theirs);
+
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].field, "title");
+        assert_eq!(outcome.merged.title, "Ours");
+    }
+
+    #[test]
+    fn test_remove_vs_edit_criterion_conflicts() {
+        let base = base();
+        let mut ours = base.clone();
+        ours.remove_acceptance_criterion("1").unwrap();
+        let mut theirs = base.clone();
+        theirs
+            .update_criterion(1, "a (edited)".to_string())
+            .unwrap();
+
+        let outcome = merge(&base, &ours, &theirs);
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].field, "ac:1");
+    }
+
+    #[test]
+    fn test_edit_and_toggle_same_criterion_merge() {
+        let base = base();
+        let mut ours = base.clone();
+        ours.update_criterion(1, "a (edited)".to_string()).unwrap();
+        let mut theirs = base.clone();
+        theirs.toggle_criterion(1).unwrap();
+
+        let outcome = merge(&base, &ours, &theirs);
+        assert!(outcome.is_clean());
+        let ac = outcome.merged.find_criterion(1).unwrap();
+        assert_eq!(ac.description, "a (edited)");
+        assert!(ac.completed);
+    }
+
+    #[test]
+    fn test_concurrent_criterion_additions_get_distinct_ids() {
+        let base = base();
+        let mut ours = base.clone();
+        ours.add_acceptance_criterion("ours".to_string());
+        let mut theirs = base.clone();
+        theirs.add_acceptance_criterion("theirs".to_string());
+
+        let outcome = merge(&base, &ours, &theirs);
+
+        assert!(outcome.is_clean());
+        let ids: Vec<usize> = outcome
+            .merged
+            .acceptance_criteria
+            .iter()
+            .map(|ac| ac.id)
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+        assert_eq!(outcome.merged.acceptance_criteria[3].description, "theirs");
+    }
+
+    #[test]
+    fn test_agent_notes_from_both_sides_are_kept() {
+        let base = base();
+        let mut ours = base.clone();
+        ours.add_agent_note(AgentNote::new("bot-a", "Opened it"))
+            .unwrap();
+        ours.transition_to(TaskStatus::Open, None).unwrap();
+        let mut theirs = base.clone();
+        theirs
+            .add_agent_note(AgentNote::new("bot-b", "Tagged it"))
+            .unwrap();
+
+        let outcome = merge(&base, &ours, &theirs);
+
+        assert!(outcome.is_clean());
+        let agents: Vec<&str> = outcome
+            .merged
+            .agent_notes
+            .iter()
+            .map(|note| note.agent.as_str())
+            .collect();
+        assert_eq!(agents, vec!["bot-a", "bot-b"]);
+
+        // The same note on both sides is kept once
+        let outcome = merge(&base, &ours, &ours);
+        assert_eq!(outcome.merged.agent_notes.len(), 1);
+    }
+
+    #[test]
+    fn test_work_logged_on_both_sides_is_kept() {
+        let day = chrono::NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let mut base = base();
+        base.log_work(WorkLogEntry::new("dana", day, 30)).unwrap();
+        let mut ours = base.clone();
+        ours.log_work(WorkLogEntry::new("dana", day, 30)).unwrap();
+        let mut theirs = base.clone();
+        theirs
+            .log_work(WorkLogEntry::new("eli", day, 45).with_note("review"))
+            .unwrap();
+
+        let outcome = merge(&base, &ours, &theirs);
+
+        assert!(outcome.is_clean());
+        // Our repeat of the base entry is its own half hour, not a duplicate
+        assert_eq!(outcome.merged.logged_minutes(), 105);
+        assert_eq!(outcome.merged.work_log.len(), 3);
+    }
+
+    #[test]
+    fn test_status_history_is_unioned() {
+        let base = base();
+        let mut ours = base.clone();
+        ours.transition_to(TaskStatus::Open, None).unwrap();
+        let mut theirs = base.clone();
+        theirs.transition_to(TaskStatus::Open, None).unwrap();
+        theirs.transition_to(TaskStatus::InProgress, None).unwrap();
+
+        let outcome = merge(&base, &ours, &theirs);
+
+        // Both sides moved the task, so the status conflicts but every move
+        // stays in the history
+        assert_eq!(outcome.conflicts.len(), 1);
+        let merged = outcome.merged;
+        assert_eq!(merged.status, TaskStatus::Open);
+        assert_eq!(merged.status_history.len(), 3);
+        assert!(merged
+            .status_history
+            .windows(2)
+            .all(|pair| pair[0].at <= pair[1].at));
+        assert_eq!(merged.status_changed_at, Some(ours.status_history[0].at));
+    }
+
+    #[test]
+    fn test_status_change_without_history_is_recorded() {
+        let base = base();
+        let mut ours = base.clone();
+        ours.status = TaskStatus::Open;
+
+        let merged = merge(&base, &ours, &base).merged;
+
+        assert_eq!(merged.status, TaskStatus::Open);
+        assert_eq!(merged.status_history.len(), 1);
+        assert_eq!(merged.status_history[0].from, TaskStatus::New);
+        assert!(merged.status_changed_at.is_some());
+    }
+
+    #[test]
+    fn test_occurrences_from_both_sides_add_up() {
+        let mut base = base();
+        base.occurrences = 2;
+        let mut ours = base.clone();
+        ours.occurrences = 3;
+        let mut theirs = base.clone();
+        theirs.occurrences = 3;
+
+        let outcome = merge(&base, &ours, &theirs);
+
+        assert!(outcome.is_clean());
+        assert_eq!(outcome.merged.occurrences, 4);
+        assert_eq!(merge(&base, &ours, &base).merged.occurrences, 3);
+    }
+
+    #[test]
+    fn test_criterion_details_merge_by_aspect() {
+        let base = base();
+        let mut ours = base.clone();
+        ours.acceptance_criteria[0].notes = Some("Checked".to_string());
+        let mut theirs = base.clone();
+        theirs.acceptance_criteria[0].assignee = Some("ana".to_string());
+
+        let outcome = merge(&base, &ours, &theirs);
+        assert!(outcome.is_clean());
+        let ac = &outcome.merged.acceptance_criteria[0];
+        assert_eq!(ac.notes.as_deref(), Some("Checked"));
+        assert_eq!(ac.assignee.as_deref(), Some("ana"));
+
+        theirs.acceptance_criteria[0].notes = Some("Failed".to_string());
+        let outcome = merge(&base, &ours, &theirs);
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].field, "ac:1");
+    }
+
+    #[test]
+    fn test_archive_survives_concurrent_edit() {
+        let base = base();
+        let mut ours = base.clone();
+        ours.archive(chrono::Utc::now());
+        let mut theirs = base.clone();
+        theirs.add_tag("later".to_string());
+
+        let outcome = merge(&base, &ours, &theirs);
+        assert!(outcome.is_clean());
+        assert_eq!(outcome.merged.archived_at, ours.archived_at);
+        assert!(outcome.merged.has_tag("later"));
+
+        let outcome = merge(&base, &theirs, &ours);
+        assert_eq!(outcome.merged.archived_at, ours.archived_at);
+    }
+
+    #[test]
+    fn test_archiving_on_both_sides_keeps_our_time() {
+        let base = base();
+        let mut ours = base.clone();
+        ours.archive(chrono::Utc::now());
+        let mut theirs = base.clone();
+        theirs.archive(chrono::Utc::now() + chrono::Duration::seconds(5));
+
+        let outcome = merge(&base, &ours, &theirs);
+        assert!(outcome.is_clean());
+        assert_eq!(outcome.merged.archived_at, ours.archived_at);
+    }
+}
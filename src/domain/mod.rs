@@ -1,7 +1,80 @@
+pub mod agent_note;
+pub mod automation;
+pub mod blocked;
 pub mod board;
+pub mod board_view;
+pub mod checklist;
+pub mod config;
+pub mod connector;
+pub mod crdt;
+pub mod diff;
+pub mod document;
+pub mod event;
+pub mod history;
+pub mod identity;
+pub mod locale;
+pub mod lock;
+pub mod markdown;
+pub mod merge;
+pub mod notification;
+pub mod patch;
+pub mod privacy;
+pub mod query;
+pub mod quota;
+pub mod read_state;
+pub mod refs;
+pub mod search;
+pub mod secrets;
+pub mod similarity;
+pub mod snapshot;
 pub mod sorting;
 pub mod task;
+pub mod task_type;
+pub mod template;
+pub mod text;
+pub mod view;
+pub mod view_preferences;
+pub mod worklog;
 
-pub use board::{Board, BoardConfig, Column};
+pub use agent_note::AgentNote;
+pub use automation::{ActionOutcome, AgingRule, AutomationRule, RuleAction, RuleCondition};
+pub use blocked::BlockedInfo;
+pub use board::{
+    Board, BoardConfig, BoardEntry, Column, ColumnCounter, DefinitionOfDone, IdempotencyRecord,
+    Preset, RequiredField, ReservedRange, Theme,
+};
+pub use board_view::{BoardView, ColumnWindow, Viewport};
+pub use checklist::{Checklist, ChecklistItem};
+pub use config::ProjectConfig;
+pub use connector::ConnectorConfig;
+pub use diff::{FieldChange, TaskDiff};
+pub use event::{TaskEvent, TaskEventKind};
+pub use history::{BoardState, StatusTransition};
+pub use identity::{Identity, IdentityMap};
+pub use locale::{Locale, Localize};
+pub use lock::TaskLock;
+pub use markdown::DescriptionFormat;
+pub use merge::{merge, MergeConflict, MergeOutcome};
+pub use notification::{
+    Digest, DigestFrequency, Notification, NotificationQueue, NotificationSettings,
+};
+pub use patch::TaskPatch;
+pub use privacy::{ActorRedaction, PurgedField};
+pub use query::{parse_query, show_position};
+pub use quota::{Quota, QuotaOperation, QuotaTracker};
+pub use read_state::ReadState;
+pub use refs::{extract_task_refs, find_refs, RefMatch, TaskRef};
+pub use search::{SearchConstraints, TaskSummary};
+pub use secrets::{SecretFinding, SecretKind, SecretPolicy};
+pub use snapshot::{BoardSnapshot, SnapshotDelta};
 pub use sorting::{sort_tasks, SortField, SortOrder};
-pub use task::{AcceptanceCriteria, Task, TaskId, TaskStatus};
+pub use task::{
+    AcceptanceCriteria, CloneOptions, ExternalRef, IdFormat, LinkKind, Priority, Severity, Task,
+    TaskId, TaskLink, TaskLocation, TaskStatus,
+};
+pub use task_type::TaskType;
+pub use template::{ProjectTemplate, TemplateTask};
+pub use text::{Description, Title};
+pub use view::{GroupBy, SavedView, ViewFilter, ViewSort};
+pub use view_preferences::{ColumnPreference, ViewPreferences};
+pub use worklog::WorkLogEntry;
@@ -0,0 +1,199 @@
+//! Batching task events into per-person digests.
+//!
+//! Instead of one message per event, events are queued for the people they
+//! concern and released as a [`Digest`] according to each person's
+//! [`DigestFrequency`]. The queue is plain data so the service layer can
+//! persist it between runs.
+
+use crate::domain::{event::TaskEvent, privacy::same_actor};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How often a person receives their notifications
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestFrequency {
+    /// With the next delivery run
+    #[default]
+    Immediate,
+    Hourly,
+    Daily,
+}
+
+impl DigestFrequency {
+    /// How long events wait before their digest is due
+    pub fn period(&self) -> Duration {
+        match self {
+            Self::Immediate => Duration::zero(),
+            Self::Hourly => Duration::hours(1),
+            Self::Daily => Duration::days(1),
+        }
+    }
+}
+
+/// Board-wide notification preferences
+///
+/// ```toml
+/// [notifications]
+/// default = "hourly"
+///
+/// [notifications.actors]
+/// "Ana Lee" = "daily"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    /// Frequency for anyone without their own preference
+    #[serde(default)]
+    pub default: DigestFrequency,
+    /// Preferences by actor name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub actors: BTreeMap<String, DigestFrequency>,
+}
+
+impl NotificationSettings {
+    pub fn is_empty(&self) -> bool {
+        self.default == DigestFrequency::default() && self.actors.is_empty()
+    }
+
+    /// The frequency `actor` has chosen, or the default
+    pub fn frequency(&self, actor: &str) -> DigestFrequency {
+        self.actors
+            .iter()
+            .find(|(name, _)| same_actor(name, actor))
+            .map_or(self.default, |(_, frequency)| *frequency)
+    }
+}
+
+/// An event waiting to be delivered to one person
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Notification {
+    pub actor: String,
+    pub event: TaskEvent,
+}
+
+/// The events released to one person in one delivery
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Digest {
+    pub actor: String,
+    pub frequency: DigestFrequency,
+    /// Oldest first
+    pub events: Vec<TaskEvent>,
+}
+
+/// Notifications not yet delivered, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationQueue {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending: Vec<Notification>,
+}
+
+impl NotificationQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Queues `event` for `actor`
+    pub fn push(&mut self, actor: &str, event: TaskEvent) {
+        self.pending.push(Notification {
+            actor: actor.trim().to_string(),
+            event,
+        });
+    }
+
+    /// Removes and returns the digests due at `now`
+    ///
+    /// A person's digest is due once their oldest waiting event is at least
+    /// one period old, so an hourly digest gathers everything in the hour
+    /// after its first event. Digests come out ordered by actor.
+    pub fn take_due(&mut self, settings: &NotificationSettings, now: DateTime<Utc>) -> Vec<Digest> {
+        let mut by_actor: BTreeMap<String, Vec<&Notification>> = BTreeMap::new();
+        for notification in &self.pending {
+            by_actor
+                .entry(notification.actor.to_lowercase())
+                .or_default()
+                .push(notification);
+        }
+
+        let mut due = Vec::new();
+        for notifications in by_actor.values() {
+            let actor = &notifications[0].actor;
+            let frequency = settings.frequency(actor);
+            let oldest = notifications
+                .iter()
+                .map(|n| n.event.at)
+                .min()
+                .unwrap_or(now);
+            if now - oldest >= frequency.period() {
+                due.push(Digest {
+                    actor: actor.clone(),
+                    frequency,
+                    events: notifications.iter().map(|n| n.event.clone()).collect(),
+                });
+            }
+        }
+
+        self.pending
+            .retain(|n| !due.iter().any(|digest| same_actor(&digest.actor, &n.actor)));
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{event::TaskEventKind, TaskId};
+
+    fn event_at(number: u32, at: DateTime<Utc>) -> TaskEvent {
+        TaskEvent {
+            at,
+            ..TaskEvent::new(TaskId::new(number), TaskEventKind::Updated)
+        }
+    }
+
+    #[test]
+    fn test_digests_follow_frequency() {
+        let settings = NotificationSettings {
+            default: DigestFrequency::Hourly,
+            actors: BTreeMap::from([
+                ("Ana".to_string(), DigestFrequency::Immediate),
+                ("Ben".to_string(), DigestFrequency::Daily),
+            ]),
+        };
+        assert_eq!(settings.frequency("ana"), DigestFrequency::Immediate);
+        assert_eq!(settings.frequency("carol"), DigestFrequency::Hourly);
+
+        let start = Utc::now();
+        let mut queue = NotificationQueue::new();
+        queue.push("Ana", event_at(1, start));
+        queue.push("ben", event_at(1, start));
+        queue.push("Carol", event_at(2, start));
+        queue.push("Carol", event_at(3, start + Duration::minutes(30)));
+
+        let due = queue.take_due(&settings, start);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].actor, "Ana");
+        assert_eq!(queue.len(), 3);
+
+        let due = queue.take_due(&settings, start + Duration::hours(1));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].actor, "Carol");
+        assert_eq!(due[0].frequency, DigestFrequency::Hourly);
+        assert_eq!(due[0].events.len(), 2);
+
+        assert!(queue
+            .take_due(&settings, start + Duration::hours(2))
+            .is_empty());
+        let due = queue.take_due(&settings, start + Duration::days(1));
+        assert_eq!(due[0].actor, "ben");
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,241 @@
+use crate::{
+    domain::{
+        diff::FieldChange,
+        task::{Priority, Severity, Task, TaskStatus},
+        text::{Description, Title},
+    },
+    error::{HlaviError, Result},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// A partial update to a task, such as the body of an RPC update call
+///
+/// Absent fields are left alone. Fields that can be cleared are doubly
+/// optional: `Some(None)`, or `null` in JSON, clears the field.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskPatch {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<Option<String>>,
+    /// Moved to following the workflow's transition rules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<TaskStatus>,
+    /// Recorded with `status` when sending a task back
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejection_reason: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub priority: Option<Option<Priority>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub assignee: Option<Option<String>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub estimate: Option<Option<u32>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub start_date: Option<Option<DateTime<Utc>>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub end_date: Option<Option<DateTime<Utc>>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub task_type: Option<Option<String>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub severity: Option<Option<Severity>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub environment: Option<Option<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_assigned: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_components: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_components: Vec<String>,
+}
+
+/// Reads a present key, including `null`, as `Some`
+fn nullable<'de, D, T>(deserializer: D) -> std::result::Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+impl TaskPatch {
+    /// Returns true if the patch would not touch any field
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+impl Task {
+    /// Applies every field of `patch`, returning the fields that changed
+    ///
+    /// The patch is applied all or nothing: the title and description are
+    /// validated, the status must be reachable by a valid transition and the
+    /// dates must form a valid range, and on any error the task is left
+    /// untouched. `updated_at` only moves if something changed.
+    pub fn apply_patch(&mut self, patch: &TaskPatch) -> Result<Vec<FieldChange>> {
+        let mut next = self.clone();
+
+        if let Some(title) = &patch.title {
+            next.title = Title::new(title)?.into_string();
+        }
+        if let Some(description) = &patch.description {
+            next.description = description
+                .as_deref()
+                .map(Description::new)
+                .transpose()?
+                .map(Description::into_string);
+            next.body_omitted = false;
+        }
+        if let Some(status) = &patch.status {
+            next.transition_to(status.clone(), patch.rejection_reason.clone())?;
+        } else if patch.rejection_reason.is_some() {
+            return Err(HlaviError::Validation(
+                "A rejection reason can only be given with a status".to_string(),
+            ));
+        }
+        if let Some(priority) = patch.priority {
+            next.priority = priority;
+        }
+        if let Some(assignee) = &patch.assignee {
+            next.assignee = assignee.clone();
+        }
+        if let Some(estimate) = patch.estimate {
+            next.estimate = estimate;
+        }
+        if let Some(start_date) = patch.start_date {
+            next.start_date = start_date;
+        }
+        if let Some(end_date) = patch.end_date {
+            next.end_date = end_date;
+        }
+        if let (Some(start), Some(end)) = (next.start_date, next.end_date) {
+            if start > end {
+                return Err(HlaviError::InvalidDateRange {
+                    start: start.to_rfc3339(),
+                    end: end.to_rfc3339(),
+                });
+            }
+        }
+        if let Some(task_type) = &patch.task_type {
+            next.task_type = task_type.clone();
+        }
+        if let Some(severity) = patch.severity {
+            next.severity = severity;
+        }
+        if let Some(environment) = &patch.environment {
+            next.environment = environment.clone();
+        }
+        if let Some(agent_assigned) = patch.agent_assigned {
+            next.agent_assigned = agent_assigned;
+        }
+        for tag in &patch.remove_tags {
+            next.remove_tag(tag);
+        }
+        for tag in &patch.add_tags {
+            next.add_tag(tag.clone());
+        }
+        for component in &patch.remove_components {
+            next.remove_component(component);
+        }
+        for component in &patch.add_components {
+            next.add_component(component.clone());
+        }
+
+        let changes = self.diff(&next).changes;
+        if !changes.is_empty() {
+            next.updated_at = Utc::now();
+            *self = next;
+        }
+        Ok(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::task::TaskId;
+
+    #[test]
+    fn test_apply_patch() {
+        let mut task = Task::new(TaskId::new(1), "Draft".to_string());
+        task.set_priority(Priority::Low);
+        let patch: TaskPatch = serde_json::from_str(
+            r#"{"title": " Final ", "priority": null, "status": "open", "add_tags": ["api"]}"#,
+        )
+        .unwrap();
+        assert_eq!(patch.priority, Some(None));
+        assert_eq!(patch.assignee, None);
+
+        let changes = task.apply_patch(&patch).unwrap();
+        assert_eq!(task.title, "Final");
+        assert_eq!(task.priority, None);
+        assert_eq!(task.status, TaskStatus::Open);
+        assert!(task.has_tag("api"));
+        // The move to open is also recorded in the status history
+        assert_eq!(changes.len(), 5);
+        assert!(changes.contains(&FieldChange::TagAdded {
+            tag: "api".to_string()
+        }));
+
+        assert!(task.apply_patch(&patch).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_patch_leaves_task_untouched() {
+        let mut task = Task::new(TaskId::new(1), "Draft".to_string());
+        let before = task.clone();
+        let patch = TaskPatch {
+            title: Some("Renamed".to_string()),
+            status: Some(TaskStatus::Done),
+            ..TaskPatch::default()
+        };
+        assert!(matches!(
+            task.apply_patch(&patch),
+            Err(HlaviError::InvalidStatusTransition { .. })
+        ));
+        assert_eq!(task, before);
+
+        assert!(serde_json::from_str::<TaskPatch>(r#"{"titel": "x"}"#).is_err());
+    }
+}
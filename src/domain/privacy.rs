@@ -0,0 +1,222 @@
+//! Removing people's names from tasks.
+
+use crate::domain::task::{Task, TaskId};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// How to treat a purged actor's name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActorRedaction {
+    /// Clear the name wherever the field is optional
+    ///
+    /// Replicated (CRDT) state needs an identity for every operation, so it is
+    /// reattributed to `alias` instead.
+    Remove { alias: String },
+    /// Substitute another name everywhere, e.g. "former-employee"
+    Replace(String),
+}
+
+/// A field that named the purged actor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PurgedField {
+    pub task_id: TaskId,
+    /// Field path such as `acceptance_criteria[2].assignee`
+    pub field: String,
+    /// Value written in place of the name; `None` if it was cleared
+    pub replacement: Option<String>,
+}
+
+impl ActorRedaction {
+    /// Removal with a fresh random alias for replicated state
+    pub fn remove() -> Self {
+        let id = Uuid::new_v4().simple().to_string();
+        Self::Remove {
+            alias: format!("anonymous-{}", &id[..8]),
+        }
+    }
+
+    fn alias(&self) -> &str {
+        match self {
+            Self::Remove { alias } | Self::Replace(alias) => alias,
+        }
+    }
+
+    fn replacement(&self) -> Option<String> {
+        match self {
+            Self::Remove { .. } => None,
+            Self::Replace(name) => Some(name.clone()),
+        }
+    }
+}
+
+/// Compares actor names ignoring case and surrounding whitespace
+pub fn same_actor(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+impl Task {
+    /// Removes or replaces `actor` in every field that records a person,
+    /// leaving the task's content alone
+    ///
+    /// Locks the actor holds are released rather than renamed. Returns the
+    /// fields that were changed.
+    pub fn purge_actor(&mut self, actor: &str, redaction: &ActorRedaction) -> Vec<PurgedField> {
+        let mut purged = Vec::new();
+
+        if self
+            .assignee
+            .as_deref()
+            .is_some_and(|a| same_actor(a, actor))
+        {
+            self.assignee = redaction.replacement();
+            purged.push(PurgedField {
+                task_id: self.id.clone(),
+                field: "assignee".to_string(),
+                replacement: redaction.replacement(),
+            });
+        }
+
+        for ac in &mut self.acceptance_criteria {
+            if ac.assignee.as_deref().is_some_and(|a| same_actor(a, actor)) {
+                ac.assignee = redaction.replacement();
+                purged.push(PurgedField {
+                    task_id: self.id.clone(),
+                    field: format!("acceptance_criteria[{}].assignee", ac.id),
+                    replacement: redaction.replacement(),
+                });
+            }
+        }
+
+        if let Some(info) = self.blocked.as_mut() {
+            if info.by.as_deref().is_some_and(|by| same_actor(by, actor)) {
+                info.by = redaction.replacement();
+                purged.push(PurgedField {
+                    task_id: self.id.clone(),
+                    field: "blocked.by".to_string(),
+                    replacement: redaction.replacement(),
+                });
+            }
+        }
+
+        if self
+            .lock
+            .as_ref()
+            .is_some_and(|lock| lock.is_held_by(actor))
+        {
+            self.lock = None;
+            purged.push(PurgedField {
+                task_id: self.id.clone(),
+                field: "lock".to_string(),
+                replacement: None,
+            });
+        }
+
+        // Logged time must stay attributable for invoicing, so entries are
+        // reattributed to the alias rather than cleared
+        for (index, entry) in self.work_log.iter_mut().enumerate() {
+            if same_actor(&entry.actor, actor) {
+                entry.actor = redaction.alias().to_string();
+                purged.push(PurgedField {
+                    task_id: self.id.clone(),
+                    field: format!("work_log[{}].actor", index),
+                    replacement: Some(redaction.alias().to_string()),
+                });
+            }
+        }
+
+        if let Some(crdt) = self.crdt.as_mut() {
+            let replicas: Vec<String> = crdt
+                .replicas()
+                .into_iter()
+                .filter(|r| same_actor(r, actor))
+                .map(str::to_string)
+                .collect();
+            for replica in &replicas {
+                crdt.rename_replica(replica, redaction.alias());
+            }
+            if !replicas.is_empty() {
+                purged.push(PurgedField {
+                    task_id: self.id.clone(),
+                    field: "crdt".to_string(),
+                    replacement: Some(redaction.alias().to_string()),
+                });
+            }
+        }
+
+        if !purged.is_empty() {
+            self.updated_at = chrono::Utc::now();
+        }
+        purged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::lock::TaskLock;
+
+    fn task() -> Task {
+        let mut task = Task::new(TaskId::new(1), "Ship it".to_string());
+        let id = task.add_acceptance_criterion("Tests pass".to_string());
+        task.assign_criterion(id, Some("Alice".to_string()))
+            .unwrap();
+        task.add_acceptance_criterion("Docs".to_string());
+        task
+    }
+
+    #[test]
+    fn test_remove_actor() {
+        let mut task = task();
+        let purged = task.purge_actor("alice", &ActorRedaction::remove());
+        assert_eq!(purged.len(), 1);
+        assert_eq!(purged[0].field, "acceptance_criteria[1].assignee");
+        assert!(task.acceptance_criteria[0].assignee.is_none());
+        assert_eq!(task.acceptance_criteria[0].description, "Tests pass");
+
+        assert!(task
+            .purge_actor("alice", &ActorRedaction::remove())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_purge_work_log() {
+        use crate::domain::WorkLogEntry;
+
+        let mut task = task();
+        let day = chrono::NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        task.log_work(WorkLogEntry::new("alice", day, 30)).unwrap();
+        let redaction = ActorRedaction::remove();
+        let purged = task.purge_actor("alice", &redaction);
+        assert_eq!(purged.len(), 2);
+        assert_eq!(task.work_log[0].actor, redaction.alias());
+        assert_eq!(task.logged_minutes(), 30);
+    }
+
+    #[test]
+    fn test_purge_releases_locks() {
+        let mut task = task();
+        let now = chrono::Utc::now();
+        task.lock = Some(TaskLock::new("Alice", now, chrono::Duration::hours(1)));
+
+        let purged = task.purge_actor("alice", &ActorRedaction::Replace("bob".to_string()));
+        assert!(purged.iter().any(|field| field.field == "lock"));
+        assert!(task.lock.is_none());
+        assert!(!task.is_locked_against(None, now));
+    }
+
+    #[test]
+    fn test_replace_actor_in_crdt_state() {
+        let mut task = task();
+        task.enable_crdt("alice".to_string());
+        let redaction = ActorRedaction::Replace("former-staff".to_string());
+
+        let purged = task.purge_actor("Alice", &redaction);
+        assert_eq!(purged.len(), 2);
+        assert_eq!(
+            task.acceptance_criteria[0].assignee.as_deref(),
+            Some("former-staff")
+        );
+        let json = serde_json::to_string(&task).unwrap();
+        assert!(!json.to_lowercase().contains("alice"));
+    }
+}
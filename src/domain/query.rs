@@ -0,0 +1,607 @@
+//! A small query language for filtering tasks.
+//!
+//! ```text
+//! status in (open, "in progress") and priority >= high and updated > -7d
+//! ```
+//!
+//! A clause compares a field with a value; clauses combine with `and`, `or`,
+//! `not` and parentheses, and `and` binds tighter than `or`. Clauses side by
+//! side are joined with `and`, and `field:value` is short for
+//! `field = value`, so `priority:high tag:backend` also works. A bare word or
+//! quoted string searches the task's text, and `blocked` matches blocked
+//! tasks.
+//!
+//! | Field | Operators | Values |
+//! |-------|-----------|--------|
+//! | `status` | `=` `!=` `in` | any status, e.g. `"in progress"` |
+//! | `priority`, `severity` | `=` `!=` `<` `<=` `>` `>=` `in` | `high`, `blocker`, ... |
+//! | `tag`, `type`, `component`, `environment` | `=` `!=` `in` | any text |
+//! | `updated`, `created` | `<` `<=` `>` `>=` | `-7d`, `-12h`, `-2w`, `-30m` or `2024-05-01` |
+//! | `text` | `=` `!=` | any text |
+//!
+//! Queries compile to a [`ViewFilter`], so the same text filters on the
+//! command line, over RPC and in saved views. Errors carry the character
+//! position of the problem; [`show_position`] points at it.
+
+use crate::{
+    domain::{
+        automation::RuleCondition,
+        task::{Priority, Severity, TaskStatus},
+        view::ViewFilter,
+    },
+    error::{HlaviError, Result},
+};
+use chrono::{NaiveDate, TimeZone, Utc};
+use std::str::FromStr;
+
+/// Fields a clause may name
+pub const QUERY_FIELDS: &[&str] = &[
+    "status",
+    "priority",
+    "severity",
+    "tag",
+    "type",
+    "component",
+    "environment",
+    "updated",
+    "created",
+    "text",
+];
+
+/// Compiles `input` to a filter; an empty query matches every task
+pub fn parse_query(input: &str) -> Result<ViewFilter> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Ok(ViewFilter::default());
+    }
+    let mut parser = Parser {
+        tokens,
+        next: 0,
+        end: input.chars().count(),
+    };
+    let condition = parser.or_expr()?;
+    if let Some(token) = parser.peek() {
+        return Err(error(token.position, "expected 'and', 'or' or the end"));
+    }
+    let conditions = match condition {
+        RuleCondition::All { conditions } => conditions,
+        condition => vec![condition],
+    };
+    Ok(ViewFilter {
+        conditions,
+        ..ViewFilter::default()
+    })
+}
+
+/// `input` with a caret under the character at `position`, for showing
+/// where a query went wrong
+pub fn show_position(input: &str, position: usize) -> String {
+    format!("{}\n{}^", input, " ".repeat(position))
+}
+
+impl FromStr for ViewFilter {
+    type Err = HlaviError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse_query(s)
+    }
+}
+
+fn error(position: usize, message: impl Into<String>) -> HlaviError {
+    HlaviError::InvalidQuery {
+        position,
+        message: message.into(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    Word(String),
+    Quoted(String),
+    Op(Op),
+    Open,
+    Close,
+    Comma,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: Kind,
+    position: usize,
+}
+
+impl Token {
+    /// Whether the token is the keyword `keyword`, in any case
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(&self.kind, Kind::Word(word) if word.eq_ignore_ascii_case(keyword))
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | '@' | '+' | '#')
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let kind = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => Kind::Open,
+            ')' => Kind::Close,
+            ',' => Kind::Comma,
+            '=' | ':' => Kind::Op(Op::Eq),
+            '!' if chars.get(i + 1) == Some(&'=') => {
+                i += 1;
+                Kind::Op(Op::Ne)
+            }
+            '<' | '>' => {
+                let inclusive = chars.get(i + 1) == Some(&'=');
+                if inclusive {
+                    i += 1;
+                }
+                Kind::Op(match (c, inclusive) {
+                    ('<', false) => Op::Lt,
+                    ('<', true) => Op::Le,
+                    ('>', false) => Op::Gt,
+                    _ => Op::Ge,
+                })
+            }
+            '"' => {
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(error(start, "unterminated string")),
+                        Some('"') => break,
+                        Some('\\') if matches!(chars.get(i + 1), Some('"' | '\\')) => {
+                            text.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        Some(&c) => {
+                            text.push(c);
+                            i += 1;
+                        }
+                    }
+                }
+                Kind::Quoted(text)
+            }
+            c if is_word_char(c) => {
+                while i + 1 < chars.len() && is_word_char(chars[i + 1]) {
+                    i += 1;
+                }
+                Kind::Word(chars[start..=i].iter().collect())
+            }
+            c => return Err(error(start, format!("unexpected character '{}'", c))),
+        };
+        tokens.push(Token {
+            kind,
+            position: start,
+        });
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    next: usize,
+    /// Position reported for errors at the end of the input
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.next).cloned();
+        self.next += 1;
+        token
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek().is_some_and(|t| t.is_keyword(keyword)) {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or_expr(&mut self) -> Result<RuleCondition> {
+        let mut any = vec![self.and_expr()?];
+        while self.eat_keyword("or") {
+            any.push(self.and_expr()?);
+        }
+        Ok(if any.len() == 1 {
+            any.remove(0)
+        } else {
+            RuleCondition::Any { conditions: any }
+        })
+    }
+
+    fn and_expr(&mut self) -> Result<RuleCondition> {
+        let mut all = vec![self.unary()?];
+        loop {
+            if self.eat_keyword("and") {
+                all.push(self.unary()?);
+                continue;
+            }
+            // Clauses side by side are joined with `and`
+            match self.peek() {
+                Some(token)
+                    if matches!(token.kind, Kind::Word(_) | Kind::Quoted(_) | Kind::Open)
+                        && !token.is_keyword("or") =>
+                {
+                    all.push(self.unary()?)
+                }
+                _ => break,
+            }
+        }
+        Ok(if all.len() == 1 {
+            all.remove(0)
+        } else {
+            RuleCondition::All { conditions: all }
+        })
+    }
+
+    fn unary(&mut self) -> Result<RuleCondition> {
+        if self.eat_keyword("not") {
+            return Ok(not(self.unary()?));
+        }
+        let Some(token) = self.advance() else {
+            return Err(error(self.end, "expected a condition"));
+        };
+        match token.kind {
+            Kind::Open => {
+                let inner = self.or_expr()?;
+                match self.advance() {
+                    Some(Token {
+                        kind: Kind::Close, ..
+                    }) => Ok(inner),
+                    Some(other) => Err(error(other.position, "expected ')'")),
+                    None => Err(error(self.end, "missing ')'")),
+                }
+            }
+            Kind::Quoted(text) => Ok(RuleCondition::TextMatches { query: text }),
+            Kind::Word(word) => self.clause(word, token.position),
+            _ => Err(error(token.position, "expected a condition")),
+        }
+    }
+
+    fn clause(&mut self, word: String, position: usize) -> Result<RuleCondition> {
+        let field = word.to_lowercase();
+        let op = match self.peek().map(|t| &t.kind) {
+            Some(Kind::Op(op)) => Some(*op),
+            _ => None,
+        };
+        let is_in = self.peek().is_some_and(|t| t.is_keyword("in"));
+        if op.is_none() && !is_in {
+            if ["and", "or", "in"].contains(&field.as_str()) {
+                return Err(error(
+                    position,
+                    format!("expected a condition before '{}'", word),
+                ));
+            }
+            if field == "blocked" {
+                return Ok(RuleCondition::IsBlocked);
+            }
+            if QUERY_FIELDS.contains(&field.as_str()) {
+                return Err(error(
+                    self.peek().map_or(self.end, |t| t.position),
+                    format!("expected an operator after '{}'", word),
+                ));
+            }
+            return Ok(RuleCondition::TextMatches { query: word });
+        }
+        if !QUERY_FIELDS.contains(&field.as_str()) {
+            return Err(error(
+                position,
+                format!(
+                    "unknown field '{}'; fields: {}",
+                    word,
+                    QUERY_FIELDS.join(", ")
+                ),
+            ));
+        }
+        let op_position = self.advance().map_or(self.end, |t| t.position);
+
+        if is_in {
+            if matches!(field.as_str(), "updated" | "created" | "text") {
+                return Err(error(
+                    op_position,
+                    format!("'in' does not apply to '{}'", field),
+                ));
+            }
+            let values = self.value_list()?;
+            let conditions = values
+                .into_iter()
+                .map(|(value, at)| compile(&field, Op::Eq, &value, at, op_position))
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(RuleCondition::Any { conditions });
+        }
+        let (value, at) = self.value()?;
+        compile(&field, op.unwrap_or(Op::Eq), &value, at, op_position)
+    }
+
+    fn value(&mut self) -> Result<(String, usize)> {
+        match self.advance() {
+            Some(Token {
+                kind: Kind::Word(value) | Kind::Quoted(value),
+                position,
+            }) => Ok((value, position)),
+            Some(token) => Err(error(token.position, "expected a value")),
+            None => Err(error(self.end, "expected a value")),
+        }
+    }
+
+    fn value_list(&mut self) -> Result<Vec<(String, usize)>> {
+        match self.advance() {
+            Some(Token {
+                kind: Kind::Open, ..
+            }) => {}
+            Some(token) => return Err(error(token.position, "expected '(' after 'in'")),
+            None => return Err(error(self.end, "expected '(' after 'in'")),
+        }
+        let mut values = vec![self.value()?];
+        loop {
+            match self.advance() {
+                Some(Token {
+                    kind: Kind::Comma, ..
+                }) => values.push(self.value()?),
+                Some(Token {
+                    kind: Kind::Close, ..
+                }) => return Ok(values),
+                Some(token) => return Err(error(token.position, "expected ',' or ')'")),
+                None => return Err(error(self.end, "missing ')'")),
+            }
+        }
+    }
+}
+
+fn not(condition: RuleCondition) -> RuleCondition {
+    match condition {
+        RuleCondition::Not { condition } => *condition,
+        condition => RuleCondition::Not {
+            condition: Box::new(condition),
+        },
+    }
+}
+
+/// Compiles one comparison; `at` is the value's position and `op_at` the
+/// operator's, for errors
+fn compile(field: &str, op: Op, value: &str, at: usize, op_at: usize) -> Result<RuleCondition> {
+    let equality = |is: RuleCondition| match op {
+        Op::Eq => Ok(is),
+        Op::Ne => Ok(not(is)),
+        _ => Err(error(
+            op_at,
+            format!("'{}' can only be compared with '=' or '!='", field),
+        )),
+    };
+    let text = value.to_string();
+
+    match field {
+        "status" => {
+            let status = TaskStatus::from_str(value).map_err(|_| {
+                error(
+                    at,
+                    format!("unknown status '{}'; use one of new, open, in-progress, pending, review, done, closed", value),
+                )
+            })?;
+            equality(RuleCondition::StatusIs { status })
+        }
+        "priority" => {
+            let priority = Priority::from_str(value).map_err(|_| {
+                error(
+                    at,
+                    format!(
+                        "unknown priority '{}'; use low, medium, high or critical",
+                        value
+                    ),
+                )
+            })?;
+            Ok(match op {
+                Op::Eq => RuleCondition::PriorityIs { priority },
+                Op::Ne => not(RuleCondition::PriorityIs { priority }),
+                _ => RuleCondition::Any {
+                    conditions: Priority::ALL
+                        .into_iter()
+                        .filter(|p| compare(op, p, &priority))
+                        .map(|priority| RuleCondition::PriorityIs { priority })
+                        .collect(),
+                },
+            })
+        }
+        "severity" => {
+            let severity = Severity::from_str(value).map_err(|_| {
+                error(
+                    at,
+                    format!(
+                        "unknown severity '{}'; use trivial, minor, major, critical or blocker",
+                        value
+                    ),
+                )
+            })?;
+            Ok(severity_condition(op, severity))
+        }
+        "tag" => equality(RuleCondition::HasTag { tag: text }),
+        "type" => equality(RuleCondition::TypeIs { task_type: text }),
+        "component" => equality(RuleCondition::HasComponent { component: text }),
+        "environment" => equality(RuleCondition::EnvironmentIs { environment: text }),
+        "text" => equality(RuleCondition::TextMatches { query: text }),
+        _ => {
+            let updated = field == "updated";
+            let since = if let Some(seconds) = parse_age(value) {
+                if updated {
+                    RuleCondition::UpdatedWithin { seconds }
+                } else {
+                    RuleCondition::CreatedWithin { seconds }
+                }
+            } else if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+                let at = Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default());
+                if updated {
+                    RuleCondition::UpdatedAfter { at }
+                } else {
+                    RuleCondition::CreatedAfter { at }
+                }
+            } else {
+                return Err(error(
+                    at,
+                    format!(
+                        "expected an age such as -7d or a date such as 2024-05-01, got '{}'",
+                        value
+                    ),
+                ));
+            };
+            match op {
+                Op::Gt | Op::Ge => Ok(since),
+                Op::Lt | Op::Le => Ok(not(since)),
+                Op::Eq | Op::Ne => Err(error(
+                    op_at,
+                    format!("'{}' can only be compared with <, <=, > or >=", field),
+                )),
+            }
+        }
+    }
+}
+
+fn compare<T: Ord>(op: Op, left: &T, right: &T) -> bool {
+    match op {
+        Op::Eq => left == right,
+        Op::Ne => left != right,
+        Op::Lt => left < right,
+        Op::Le => left <= right,
+        Op::Gt => left > right,
+        Op::Ge => left >= right,
+    }
+}
+
+/// Severity comparisons in terms of `SeverityAtLeast`
+fn severity_condition(op: Op, severity: Severity) -> RuleCondition {
+    let at_least = |severity| RuleCondition::SeverityAtLeast { severity };
+    let next = Severity::ALL.into_iter().find(|s| *s > severity);
+    let exactly = match next {
+        Some(next) => RuleCondition::All {
+            conditions: vec![at_least(severity), not(at_least(next))],
+        },
+        None => at_least(severity),
+    };
+    match op {
+        Op::Eq => exactly,
+        Op::Ne => not(exactly),
+        Op::Ge => at_least(severity),
+        Op::Lt => not(at_least(severity)),
+        // Nothing is above the highest severity, and everything is at or
+        // below it
+        Op::Gt => next.map_or(RuleCondition::Any { conditions: vec![] }, at_least),
+        Op::Le => next.map_or(RuleCondition::All { conditions: vec![] }, |next| {
+            not(at_least(next))
+        }),
+    }
+}
+
+/// Seconds in an age such as `-7d` or `12h`; the sign is optional
+fn parse_age(value: &str) -> Option<i64> {
+    let value = value.strip_prefix('-').unwrap_or(value);
+    let unit = value.chars().last()?;
+    let count: i64 = value[..value.len() - unit.len_utf8()].parse().ok()?;
+    let size = match unit {
+        'm' => 60,
+        'h' => 3_600,
+        'd' => 86_400,
+        'w' => 604_800,
+        _ => return None,
+    };
+    count.checked_mul(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Task, TaskId};
+    use chrono::Duration;
+
+    fn position(query: &str) -> usize {
+        match parse_query(query) {
+            Err(HlaviError::InvalidQuery { position, .. }) => position,
+            other => panic!("expected a query error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_compiles_example_query() {
+        let filter = parse_query(
+            r#"status in (open, "in progress") and priority >= high and updated > -7d"#,
+        )
+        .unwrap();
+        assert_eq!(filter.conditions.len(), 3);
+        assert_eq!(
+            filter.conditions[2],
+            RuleCondition::UpdatedWithin {
+                seconds: 7 * 86_400
+            }
+        );
+
+        let mut task = Task::new(TaskId::new(1), "Login fails".to_string());
+        task.status = TaskStatus::InProgress;
+        task.priority = Some(Priority::Critical);
+        assert!(filter.matches(&task));
+        task.priority = Some(Priority::Medium);
+        assert!(!filter.matches(&task));
+        task.priority = Some(Priority::High);
+        task.updated_at = Utc::now() - Duration::days(8);
+        assert!(!filter.matches(&task));
+    }
+
+    #[test]
+    fn test_operators_and_shorthand() {
+        let mut task = Task::new(TaskId::new(1), "Flaky deploy".to_string());
+        task.priority = Some(Priority::High);
+        task.add_tag("backend".to_string());
+        task.severity = Some(Severity::Major);
+        let matches = |query: &str| parse_query(query).unwrap().matches(&task);
+
+        assert!(matches("priority:high tag:backend"));
+        assert!(matches("tag = frontend or deploy"));
+        assert!(matches("not (tag = frontend or priority < medium)"));
+        assert!(matches(
+            "severity = major and severity >= minor and severity < critical"
+        ));
+        assert!(!matches("severity > major or severity <= minor"));
+        assert!(matches(r#"text != "rollback" and created > 2000-01-01"#));
+        assert!(!matches("blocked"));
+        assert!(matches(""));
+    }
+
+    #[test]
+    fn test_error_positions() {
+        assert_eq!(position("priority >= urgent"), 12);
+        assert_eq!(position("owner = ana"), 0);
+        assert_eq!(position("status in (open"), 15);
+        assert_eq!(position("tag > backend"), 4);
+        assert_eq!(position("updated > yesterday"), 10);
+        assert_eq!(position("title = \"open"), 8);
+        assert_eq!(position("status"), 6);
+        assert_eq!(position("(tag = a) )"), 10);
+        assert_eq!(show_position("tag > x", 4), "tag > x\n    ^");
+    }
+}
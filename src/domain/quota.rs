@@ -0,0 +1,161 @@
+use crate::{
+    domain::privacy::same_actor,
+    error::{HlaviError, Result},
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+};
+
+/// An operation that quotas can limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaOperation {
+    Create,
+    Update,
+    Delete,
+}
+
+impl fmt::Display for QuotaOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Create => write!(f, "create"),
+            Self::Update => write!(f, "update"),
+            Self::Delete => write!(f, "delete"),
+        }
+    }
+}
+
+/// Caps how often an actor may perform an operation, so a misbehaving
+/// automation cannot flood the board
+///
+/// For example, `Quota::new("agent", QuotaOperation::Create, 20, 60)` lets
+/// the agent create at most 20 tasks in any hour.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Quota {
+    /// Actor the quota applies to (case-insensitive), or `*` for everyone
+    pub actor: String,
+    pub operation: QuotaOperation,
+    /// Operations allowed within the window
+    pub limit: u32,
+    /// Length of the sliding window in minutes
+    pub window_minutes: u32,
+}
+
+impl Quota {
+    pub fn new(actor: &str, operation: QuotaOperation, limit: u32, window_minutes: u32) -> Self {
+        Self {
+            actor: actor.to_string(),
+            operation,
+            limit,
+            window_minutes,
+        }
+    }
+
+    pub fn applies_to(&self, actor: &str, operation: QuotaOperation) -> bool {
+        self.operation == operation && (self.actor == "*" || same_actor(&self.actor, actor))
+    }
+
+    fn window(&self) -> Duration {
+        Duration::minutes(i64::from(self.window_minutes))
+    }
+}
+
+/// Recent operations per actor, checked against quotas
+#[derive(Debug, Clone, Default)]
+pub struct QuotaTracker {
+    usage: HashMap<(String, QuotaOperation), VecDeque<DateTime<Utc>>>,
+}
+
+impl QuotaTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an operation by `actor` at `now` if every quota that applies
+    /// allows it, and fails with `HlaviError::QuotaExceeded` otherwise
+    ///
+    /// A refused operation is not recorded.
+    pub fn record(
+        &mut self,
+        quotas: &[Quota],
+        actor: &str,
+        operation: QuotaOperation,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        let applicable: Vec<&Quota> = quotas
+            .iter()
+            .filter(|quota| quota.applies_to(actor, operation))
+            .collect();
+        let Some(longest) = applicable.iter().map(|quota| quota.window()).max() else {
+            return Ok(());
+        };
+
+        let history = self
+            .usage
+            .entry((actor.trim().to_lowercase(), operation))
+            .or_default();
+        while history.front().is_some_and(|at| now - *at >= longest) {
+            history.pop_front();
+        }
+        for quota in applicable {
+            let used = history
+                .iter()
+                .filter(|at| now - **at < quota.window())
+                .count();
+            if used >= quota.limit as usize {
+                return Err(HlaviError::QuotaExceeded {
+                    actor: actor.to_string(),
+                    operation: operation.to_string(),
+                    limit: quota.limit,
+                    window_minutes: quota.window_minutes,
+                });
+            }
+        }
+        history.push_back(now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quota_window() {
+        let quotas = vec![
+            Quota::new("Agent", QuotaOperation::Create, 2, 60),
+            Quota::new("*", QuotaOperation::Delete, 0, 1),
+        ];
+        let mut tracker = QuotaTracker::new();
+        let now = Utc::now();
+
+        tracker
+            .record(&quotas, "agent", QuotaOperation::Create, now)
+            .unwrap();
+        tracker
+            .record(&quotas, "agent", QuotaOperation::Create, now)
+            .unwrap();
+        assert!(matches!(
+            tracker.record(&quotas, "agent", QuotaOperation::Create, now),
+            Err(HlaviError::QuotaExceeded { limit: 2, .. })
+        ));
+        // Other actors and operations are unaffected
+        tracker
+            .record(&quotas, "ana", QuotaOperation::Create, now)
+            .unwrap();
+        tracker
+            .record(&quotas, "agent", QuotaOperation::Update, now)
+            .unwrap();
+        assert!(tracker
+            .record(&quotas, "ana", QuotaOperation::Delete, now)
+            .is_err());
+
+        let later = now + Duration::minutes(60);
+        tracker
+            .record(&quotas, "agent", QuotaOperation::Create, later)
+            .unwrap();
+    }
+}
@@ -0,0 +1,69 @@
+use crate::domain::task::{Task, TaskId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// When one person last looked at each task
+///
+/// Read state is personal: it is kept next to the board in local storage,
+/// never in the shared task files, so viewing a task does not create a change
+/// for everyone else.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadState {
+    pub actor: String,
+    /// Last view per task, keyed by task ID
+    #[serde(default)]
+    pub viewed: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl ReadState {
+    pub fn new(actor: &str) -> Self {
+        Self {
+            actor: actor.to_string(),
+            viewed: BTreeMap::new(),
+        }
+    }
+
+    pub fn mark_viewed(&mut self, id: &TaskId, at: DateTime<Utc>) {
+        self.viewed.insert(id.as_str().to_string(), at);
+    }
+
+    pub fn last_viewed(&self, id: &TaskId) -> Option<DateTime<Utc>> {
+        self.viewed.get(id.as_str()).copied()
+    }
+
+    /// Checks whether the task changed since it was last viewed; tasks never
+    /// viewed count as unread
+    pub fn is_unread(&self, task: &Task) -> bool {
+        self.last_viewed(&task.id)
+            .map_or(true, |viewed| task.updated_at > viewed)
+    }
+
+    /// Forgets tasks that no longer exist
+    pub fn retain(&mut self, ids: &[TaskId]) {
+        self.viewed
+            .retain(|key, _| ids.iter().any(|id| id.as_str() == key));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_unread_tracking() {
+        let mut task = Task::new(TaskId::new(1), "Watch me".to_string());
+        let mut state = ReadState::new("ana");
+        assert!(state.is_unread(&task));
+
+        state.mark_viewed(&task.id, task.updated_at);
+        assert!(!state.is_unread(&task));
+
+        task.updated_at += Duration::minutes(5);
+        assert!(state.is_unread(&task));
+
+        state.retain(&[TaskId::new(2)]);
+        assert!(state.viewed.is_empty());
+    }
+}
@@ -0,0 +1,350 @@
+//! Task references in free text: `hlavi://` URIs and plain IDs like `HLA12`.
+//!
+//! Frontends use [`find_refs`] to linkify descriptions and comments; the
+//! byte ranges it returns point at the exact text to replace. Branch names
+//! like `hla12-fix-login-crash` are suggested by
+//! [`Task::suggest_branch_name`] and parsed by [`task_id_from_branch`].
+
+use crate::{
+    domain::task::{LinkKind, Task, TaskId},
+    error::{HlaviError, Result},
+};
+use std::{fmt, ops::Range, str::FromStr};
+
+/// URI scheme for task permalinks
+pub const URI_SCHEME: &str = "hlavi://";
+
+/// A reference to a task, optionally qualified by project
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TaskRef {
+    pub project: Option<String>,
+    pub id: TaskId,
+}
+
+/// A reference found in text, with its byte range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefMatch {
+    pub range: Range<usize>,
+    pub reference: TaskRef,
+}
+
+impl TaskRef {
+    pub fn new(id: TaskId) -> Self {
+        Self { project: None, id }
+    }
+
+    pub fn in_project(project: impl Into<String>, id: TaskId) -> Self {
+        Self {
+            project: Some(project.into()),
+            id,
+        }
+    }
+
+    /// Renders the reference as a `hlavi://` URI
+    pub fn to_uri(&self) -> String {
+        match &self.project {
+            Some(project) => format!("{}{}/{}", URI_SCHEME, project, self.id),
+            None => format!("{}{}", URI_SCHEME, self.id),
+        }
+    }
+}
+
+impl fmt::Display for TaskRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.project {
+            Some(project) => write!(f, "{}/{}", project, self.id),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
+impl FromStr for TaskRef {
+    type Err = HlaviError;
+
+    /// Accepts `hlavi://PROJECT/HLA12`, `hlavi://HLA12` and plain `HLA12`
+    fn from_str(s: &str) -> Result<Self> {
+        let Some(rest) = s.strip_prefix(URI_SCHEME) else {
+            return Ok(Self::new(TaskId::from_str(s)?));
+        };
+        match rest.rsplit_once('/') {
+            Some((project, id)) if !project.is_empty() => {
+                Ok(Self::in_project(project, TaskId::from_str(id)?))
+            }
+            Some(_) => Err(HlaviError::InvalidTaskId(s.to_string())),
+            None => Ok(Self::new(TaskId::from_str(rest)?)),
+        }
+    }
+}
+
+impl Task {
+    /// Stable link to this task under `base`, e.g. `hlavi://backend` or a web URL
+    pub fn permalink(&self, base: &str) -> String {
+        format!("{}/{}", base.trim_end_matches('/'), self.id)
+    }
+}
+
+/// Finds task references in text, in order of appearance
+///
+/// `hlavi://` URIs are always recognized. Plain IDs are matched
+/// case-insensitively for the given prefixes only (e.g. `["HLA"]`), and must
+/// stand alone as a word so that `HLA12x` or `XHLA12` are ignored.
+pub fn find_refs(text: &str, prefixes: &[&str]) -> Vec<RefMatch> {
+    let mut matches = Vec::new();
+    let mut pos = 0;
+
+    while pos < text.len() {
+        let rest = &text[pos..];
+        let at_boundary = !text[..pos].chars().next_back().is_some_and(is_word_char);
+
+        if at_boundary && rest.starts_with(URI_SCHEME) {
+            let len = rest
+                .find(|c: char| !(is_word_char(c) || c == '/' || c == '-' || c == '.' || c == ':'))
+                .unwrap_or(rest.len());
+            let candidate = rest[..len].trim_end_matches(['.', ':', '/']);
+            if let Ok(reference) = candidate.parse() {
+                matches.push(RefMatch {
+                    range: pos..pos + candidate.len(),
+                    reference,
+                });
+                pos += candidate.len();
+                continue;
+            }
+        }
+
+        if at_boundary {
+            if let Some(len) = plain_id_len(rest, prefixes) {
+                if let Ok(id) = TaskId::from_str(&rest[..len]) {
+                    matches.push(RefMatch {
+                        range: pos..pos + len,
+                        reference: TaskRef::new(id),
+                    });
+                    pos += len;
+                    continue;
+                }
+            }
+        }
+
+        pos += rest.chars().next().map_or(1, char::len_utf8);
+    }
+
+    matches
+}
+
+/// Distinct task references mentioned in text, in order of first mention
+///
+/// Plain IDs are recognized for the default `HLA` prefix; use
+/// `extract_task_refs_with_prefixes` for projects with a custom prefix.
+pub fn extract_task_refs(text: &str) -> Vec<TaskRef> {
+    extract_task_refs_with_prefixes(text, &[TaskId::DEFAULT_PREFIX])
+}
+
+/// Like `extract_task_refs`, recognizing plain IDs with the given prefixes
+pub fn extract_task_refs_with_prefixes(text: &str, prefixes: &[&str]) -> Vec<TaskRef> {
+    let mut refs: Vec<TaskRef> = Vec::new();
+    for found in find_refs(text, prefixes) {
+        if !refs.contains(&found.reference) {
+            refs.push(found.reference);
+        }
+    }
+    refs
+}
+
+impl Task {
+    /// Adds `RelatesTo` links for tasks mentioned in the description and
+    /// acceptance criteria
+    ///
+    /// Mentions of the task itself are ignored, and existing links are kept.
+    /// Returns true if any link was added.
+    pub fn link_mentions(&mut self, prefixes: &[&str]) -> bool {
+        let mut text = self.description.clone().unwrap_or_default();
+        for ac in &self.acceptance_criteria {
+            text.push('\n');
+            text.push_str(&ac.description);
+        }
+
+        let before = self.links.len();
+        for reference in extract_task_refs_with_prefixes(&text, prefixes) {
+            match reference.project {
+                Some(project) => self.add_external_link(LinkKind::RelatesTo, project, reference.id),
+                None if reference.id == self.id => {}
+                None if self
+                    .links
+                    .iter()
+                    .any(|l| l.target == reference.id && l.project.is_none()) => {}
+                None => self.add_link(LinkKind::RelatesTo, reference.id),
+            }
+        }
+        self.links.len() > before
+    }
+}
+
+/// Maximum length of a suggested branch name
+pub const MAX_BRANCH_LEN: usize = 50;
+
+impl Task {
+    /// Suggests a branch name such as `hla12-fix-login-crash`
+    ///
+    /// The title is slugged to lowercase ASCII words joined by `-`, and cut at
+    /// a word boundary so the whole name fits in `MAX_BRANCH_LEN`.
+    pub fn suggest_branch_name(&self) -> String {
+        let mut name = self.id.as_str().to_lowercase();
+        let slug = self
+            .title
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_ascii_lowercase());
+        for word in slug {
+            if name.len() + 1 + word.len() > MAX_BRANCH_LEN {
+                break;
+            }
+            name.push('-');
+            name.push_str(&word);
+        }
+        name
+    }
+}
+
+/// Extracts the task ID from a branch name like `feature/hla12-fix-login`
+///
+/// The ID must start the last path segment and use one of `prefixes`.
+pub fn task_id_from_branch(branch: &str, prefixes: &[&str]) -> Option<TaskId> {
+    let segment = branch.rsplit('/').next()?;
+    let head = segment.split(['-', '_']).next()?;
+    match find_refs(head, prefixes).as_slice() {
+        [only] if only.range.len() == head.len() => Some(only.reference.id.clone()),
+        _ => None,
+    }
+}
+
+/// Length of a plain ID at the start of `text`, if one is there
+fn plain_id_len(text: &str, prefixes: &[&str]) -> Option<usize> {
+    prefixes.iter().find_map(|prefix| {
+        let head = text.get(..prefix.len())?;
+        if !head.eq_ignore_ascii_case(prefix) {
+            return None;
+        }
+        let digits = text[prefix.len()..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(text.len() - prefix.len());
+        let len = prefix.len() + digits;
+        let followed_by_word = text[len..].chars().next().is_some_and(is_word_char);
+        (digits > 0 && !followed_by_word).then_some(len)
+    })
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_refs() {
+        let r: TaskRef = "hlavi://services/api/hla12".parse().unwrap();
+        assert_eq!(r, TaskRef::in_project("services/api", TaskId::new(12)));
+        assert_eq!(r.to_uri(), "hlavi://services/api/HLA12");
+
+        let r: TaskRef = "hlavi://HLA3".parse().unwrap();
+        assert_eq!(r, TaskRef::new(TaskId::new(3)));
+        assert_eq!(r.to_uri(), "hlavi://HLA3");
+
+        assert_eq!("HLA7".parse::<TaskRef>().unwrap().id, TaskId::new(7));
+        assert!("hlavi:///HLA7".parse::<TaskRef>().is_err());
+        assert!("hlavi://backend/".parse::<TaskRef>().is_err());
+    }
+
+    #[test]
+    fn test_permalink() {
+        let task = Task::new(TaskId::new(12), "Linked".to_string());
+        assert_eq!(task.permalink("hlavi://backend"), "hlavi://backend/HLA12");
+        assert_eq!(
+            task.permalink("https://example.com/board/"),
+            "https://example.com/board/HLA12"
+        );
+        assert_eq!(
+            task.permalink("hlavi://backend")
+                .parse::<TaskRef>()
+                .unwrap(),
+            TaskRef::in_project("backend", TaskId::new(12))
+        );
+    }
+
+    #[test]
+    fn test_find_refs() {
+        let text = "See HLA12, hla3 and hlavi://web/HLA4. Not HLA12x, XHLA5 or MP3.";
+        let found = find_refs(text, &["HLA"]);
+
+        let refs: Vec<String> = found.iter().map(|m| m.reference.to_string()).collect();
+        assert_eq!(refs, vec!["HLA12", "HLA3", "web/HLA4"]);
+        assert_eq!(&text[found[0].range.clone()], "HLA12");
+        assert_eq!(&text[found[2].range.clone()], "hlavi://web/HLA4");
+    }
+
+    #[test]
+    fn test_extract_task_refs_dedupes() {
+        let refs = extract_task_refs("Fixes HLA2 and HLA2 again; refs hla9, see hlavi://web/HLA2");
+        let refs: Vec<String> = refs.iter().map(|r| r.to_string()).collect();
+        assert_eq!(refs, vec!["HLA2", "HLA9", "web/HLA2"]);
+    }
+
+    #[test]
+    fn test_link_mentions() {
+        let mut task = Task::new(TaskId::new(1), "Parent".to_string());
+        task.set_description("Follow-up to HLA1 and HLA4, see hlavi://web/HLA2".to_string())
+            .unwrap();
+        task.add_acceptance_criterion("Coordinate with HLA5".to_string());
+        task.add_link(LinkKind::ClonedFrom, TaskId::new(4));
+
+        assert!(task.link_mentions(&["HLA"]));
+        let links: Vec<String> = task.links.iter().map(|l| l.to_string()).collect();
+        assert_eq!(
+            links,
+            vec!["cloned from HLA4", "relates to web/HLA2", "relates to HLA5"]
+        );
+        assert!(!task.link_mentions(&["HLA"]));
+    }
+
+    #[test]
+    fn test_find_refs_custom_prefix() {
+        let found = find_refs("Blocked by WEB2 — see HLA1", &["WEB"]);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].reference.id.as_str(), "WEB2");
+    }
+
+    #[test]
+    fn test_suggest_branch_name() {
+        let task = Task::new(TaskId::new(12), "Fix: login crash (iOS)!".to_string());
+        assert_eq!(task.suggest_branch_name(), "hla12-fix-login-crash-ios");
+
+        let long = Task::new(
+            TaskId::new(7),
+            "Investigate why the nightly export job occasionally times out on large boards"
+                .to_string(),
+        );
+        let name = long.suggest_branch_name();
+        assert!(name.len() <= MAX_BRANCH_LEN);
+        assert_eq!(name, "hla7-investigate-why-the-nightly-export-job");
+
+        let untitled = Task::new(TaskId::new(3), "???".to_string());
+        assert_eq!(untitled.suggest_branch_name(), "hla3");
+    }
+
+    #[test]
+    fn test_task_id_from_branch() {
+        let task = Task::new(TaskId::new(12), "Fix login".to_string());
+        let branch = format!("feature/{}", task.suggest_branch_name());
+        assert_eq!(
+            task_id_from_branch(&branch, &["HLA"]),
+            Some(TaskId::new(12))
+        );
+        assert_eq!(
+            task_id_from_branch("HLA4_hotfix", &["HLA"]),
+            Some(TaskId::new(4))
+        );
+        assert_eq!(task_id_from_branch("main", &["HLA"]), None);
+        assert_eq!(task_id_from_branch("v2-release", &["HLA"]), None);
+        assert_eq!(task_id_from_branch("hla12x-typo", &["HLA"]), None);
+    }
+}
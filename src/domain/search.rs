@@ -0,0 +1,223 @@
+use crate::domain::{
+    lock::TaskLock,
+    task::{Task, TaskId, TaskLocation, TaskStatus},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Structured limits applied before free-text matching in a search
+///
+/// Cheap checks run first so that storage backends can skip loading tasks
+/// that cannot match; an empty set of constraints matches every task on the
+/// board. Archived and trashed tasks are only searched when asked for.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchConstraints {
+    /// Task must be in one of these statuses (any status if empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub statuses: Vec<TaskStatus>,
+    /// Task must carry every one of these tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_before: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_after: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_before: Option<DateTime<Utc>>,
+    /// Also search the archive
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub include_archived: bool,
+    /// Also search the trash
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub include_trashed: bool,
+}
+
+/// The few fields of a task needed for listing and search pre-filtering
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskSummary {
+    pub id: TaskId,
+    pub title: String,
+    pub status: TaskStatus,
+    /// False once the task is archived or trashed
+    pub active: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub draft: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub blocked: bool,
+    /// Lowercased tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// When the task entered its current status
+    pub status_since: DateTime<Utc>,
+    /// Staleness as of when the summary was taken or last refreshed
+    #[serde(default)]
+    pub days_in_current_status: i64,
+    #[serde(default)]
+    pub days_since_update: i64,
+    /// Editing lock, which may have expired since the summary was taken
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock: Option<TaskLock>,
+}
+
+impl TaskSummary {
+    /// Summarizes the task, measuring its staleness against `now`
+    pub fn at(task: &Task, now: DateTime<Utc>) -> Self {
+        Self {
+            id: task.id.clone(),
+            title: task.title.clone(),
+            status: task.status.clone(),
+            active: task.is_active(),
+            draft: task.draft,
+            blocked: task.is_blocked(),
+            tags: task.tags.iter().map(|t| t.to_lowercase()).collect(),
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            status_since: task.status_since(),
+            days_in_current_status: task.days_in_current_status(now),
+            days_since_update: task.days_since_update(now),
+            lock: task.lock.clone(),
+        }
+    }
+
+    /// Recomputes the staleness counters against `now`, for summaries read
+    /// back from a cache
+    pub fn refresh_age(&mut self, now: DateTime<Utc>) {
+        self.days_in_current_status = (now - self.status_since).num_days().max(0);
+        self.days_since_update = (now - self.updated_at).num_days().max(0);
+    }
+
+    /// Who holds an unexpired lock on the task
+    pub fn locked_by(&self, now: DateTime<Utc>) -> Option<&str> {
+        self.lock
+            .as_ref()
+            .filter(|lock| !lock.is_expired(now))
+            .map(|lock| lock.actor.as_str())
+    }
+}
+
+impl From<&Task> for TaskSummary {
+    fn from(task: &Task) -> Self {
+        Self::at(task, Utc::now())
+    }
+}
+
+impl SearchConstraints {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    pub fn with_status(mut self, status: TaskStatus) -> Self {
+        self.statuses.push(status);
+        self
+    }
+
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    /// Searches the archive and trash as well as the board
+    pub fn everywhere(mut self) -> Self {
+        self.include_archived = true;
+        self.include_trashed = true;
+        self
+    }
+
+    pub fn allows_location(&self, location: TaskLocation) -> bool {
+        match location {
+            TaskLocation::Board => true,
+            TaskLocation::Archive => self.include_archived,
+            TaskLocation::Trash => self.include_trashed,
+        }
+    }
+
+    /// Checks whether a task in `status` could match, without loading it
+    pub fn allows_status(&self, status: &TaskStatus) -> bool {
+        self.statuses.is_empty() || self.statuses.contains(status)
+    }
+
+    /// Checks every constraint against the task
+    pub fn matches(&self, task: &Task) -> bool {
+        self.allows_location(task.location()) && self.matches_summary(&TaskSummary::from(task))
+    }
+
+    /// Checks every constraint against a summary
+    ///
+    /// This is exact except that a summary does not tell archived from
+    /// trashed tasks; an inactive task passes if either is included.
+    pub fn matches_summary(&self, summary: &TaskSummary) -> bool {
+        let within = |at, after: Option<DateTime<Utc>>, before: Option<DateTime<Utc>>| {
+            after.map_or(true, |after| at >= after) && before.map_or(true, |before| at < before)
+        };
+        (summary.active || self.include_archived || self.include_trashed)
+            && self.allows_status(&summary.status)
+            && self
+                .tags
+                .iter()
+                .all(|tag| summary.tags.contains(&tag.to_lowercase()))
+            && within(summary.created_at, self.created_after, self.created_before)
+            && within(summary.updated_at, self.updated_after, self.updated_before)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::task::TaskId;
+    use chrono::Duration;
+
+    #[test]
+    fn test_constraints() {
+        let mut task = Task::new(TaskId::new(1), "Crash".to_string());
+        task.add_tag("bug".to_string());
+        assert!(SearchConstraints::default().matches(&task));
+
+        let bugs = SearchConstraints::default()
+            .with_status(TaskStatus::New)
+            .with_tag("BUG");
+        assert!(bugs.matches(&task));
+        assert!(!bugs.clone().with_tag("ui").matches(&task));
+        assert!(!bugs.allows_status(&TaskStatus::Done));
+
+        let recent = SearchConstraints {
+            created_after: Some(task.created_at + Duration::seconds(1)),
+            ..SearchConstraints::default()
+        };
+        assert!(!recent.matches(&task));
+
+        task.archive(task.created_at);
+        assert_eq!(task.location(), TaskLocation::Archive);
+        assert!(!SearchConstraints::default().matches(&task));
+        let trash = SearchConstraints {
+            include_trashed: true,
+            ..SearchConstraints::default()
+        };
+        assert!(trash.matches_summary(&TaskSummary::from(&task)));
+        assert!(!trash.matches(&task));
+        assert!(SearchConstraints::default().everywhere().matches(&task));
+    }
+
+    #[test]
+    fn test_summary_age() {
+        let mut task = Task::new(TaskId::new(1), "Stale".to_string());
+        let created = task.created_at;
+        task.transition_to(TaskStatus::Open, None).unwrap();
+        task.status_changed_at = Some(created + Duration::days(2));
+        task.updated_at = created + Duration::days(5);
+
+        let mut summary = TaskSummary::at(&task, created + Duration::days(10));
+        assert_eq!(summary.days_in_current_status, 8);
+        assert_eq!(summary.days_since_update, 5);
+
+        summary.refresh_age(created + Duration::days(12));
+        assert_eq!(summary.days_in_current_status, 10);
+        assert_eq!(summary.days_since_update, 7);
+        // A clock behind the task never reports negative ages
+        summary.refresh_age(created);
+        assert_eq!(summary.days_since_update, 0);
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashSet;
+
+/// Words ignored when comparing titles
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "at", "for", "from", "in", "is", "it", "of", "on", "or", "the", "to", "when",
+    "with",
+];
+
+/// Splits text into lowercase alphanumeric tokens, dropping stopwords
+pub fn normalize_tokens(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .filter(|t| !STOPWORDS.contains(&t.as_str()))
+        .collect()
+}
+
+/// Jaccard overlap of the normalized token sets (0.0 – 1.0)
+pub fn token_overlap(a: &str, b: &str) -> f64 {
+    let a: HashSet<String> = normalize_tokens(a).into_iter().collect();
+    let b: HashSet<String> = normalize_tokens(b).into_iter().collect();
+    if a.is_empty() && b.is_empty() {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / a.union(&b).count() as f64
+}
+
+/// Sørensen–Dice coefficient over character bigrams of the normalized text
+///
+/// Tolerates typos and word-form differences ("crash" vs "crashes") that
+/// exact token overlap misses.
+pub fn bigram_similarity(a: &str, b: &str) -> f64 {
+    fn bigrams(text: &str) -> Vec<(char, char)> {
+        let joined: Vec<char> = normalize_tokens(text).join(" ").chars().collect();
+        joined.windows(2).map(|w| (w[0], w[1])).collect()
+    }
+
+    let a = bigrams(a);
+    let mut b = bigrams(b);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let total = a.len() + b.len();
+    let mut shared = 0;
+    for pair in &a {
+        if let Some(pos) = b.iter().position(|p| p == pair) {
+            b.swap_remove(pos);
+            shared += 1;
+        }
+    }
+    (2 * shared) as f64 / total as f64
+}
+
+/// Similarity of two titles (0.0 – 1.0): the mean of token overlap and bigram similarity
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    (token_overlap(a, b) + bigram_similarity(a, b)) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_tokens() {
+        assert_eq!(
+            normalize_tokens("Crash at LOGIN, on iOS!"),
+            vec!["crash", "login", "ios"]
+        );
+    }
+
+    #[test]
+    fn test_identical_and_reordered_titles() {
+        assert_eq!(title_similarity("Login crash", "Login crash"), 1.0);
+        assert_eq!(token_overlap("Login crash", "Crash at login"), 1.0);
+        assert!(title_similarity("Login crash", "Crash at login") > 0.7);
+    }
+
+    #[test]
+    fn test_typos_still_score() {
+        assert!(bigram_similarity("Fix login crash", "Fix logn crashes") > 0.6);
+        assert_eq!(token_overlap("Fix login crash", "Fix logn crashes"), 0.2);
+    }
+
+    #[test]
+    fn test_unrelated_titles() {
+        assert!(title_similarity("Login crash", "Update README badges") < 0.2);
+        assert_eq!(title_similarity("", "anything"), 0.0);
+    }
+}
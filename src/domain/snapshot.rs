@@ -0,0 +1,181 @@
+use crate::{
+    domain::{
+        board::Board,
+        diff::TaskDiff,
+        task::{Task, TaskId, TaskStatus},
+        view::ViewFilter,
+    },
+    error::{HlaviError, Result},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A frozen copy of the board and every task, e.g. taken at sprint start
+///
+/// Snapshots are never modified once saved; compare two of them with
+/// `delta` to see what changed in between.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardSnapshot {
+    pub label: String,
+    pub taken_at: DateTime<Utc>,
+    pub board: Board,
+    /// Tasks ordered by ID
+    pub tasks: Vec<Task>,
+}
+
+/// Changes between two snapshots
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotDelta {
+    /// Tasks present only in the later snapshot
+    pub added: Vec<TaskId>,
+    /// Tasks present only in the earlier snapshot
+    pub removed: Vec<TaskId>,
+    /// Tasks whose status changed, with the old and new status
+    pub moved: Vec<(TaskId, TaskStatus, TaskStatus)>,
+    /// Field-level changes for every task present in both that was edited
+    pub changed: Vec<TaskDiff>,
+}
+
+impl SnapshotDelta {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+impl BoardSnapshot {
+    /// Captures `board` and `tasks` under `label`
+    pub fn new(label: &str, board: Board, mut tasks: Vec<Task>) -> Result<Self> {
+        Self::validate_label(label)?;
+        tasks.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
+        Ok(Self {
+            label: label.to_string(),
+            taken_at: Utc::now(),
+            board,
+            tasks,
+        })
+    }
+
+    /// Labels double as file names, so they are limited to letters, digits,
+    /// `-`, `_` and `.`
+    pub fn validate_label(label: &str) -> Result<()> {
+        let valid = !label.is_empty()
+            && !label.starts_with('.')
+            && label
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if valid {
+            Ok(())
+        } else {
+            Err(HlaviError::ConfigError(format!(
+                "Invalid snapshot label '{}': use letters, digits, '-', '_' or '.'",
+                label
+            )))
+        }
+    }
+
+    pub fn task(&self, id: &TaskId) -> Option<&Task> {
+        self.tasks.iter().find(|t| &t.id == id)
+    }
+
+    pub fn tasks_in_status(&self, status: &TaskStatus) -> Vec<&Task> {
+        self.tasks.iter().filter(|t| &t.status == status).collect()
+    }
+
+    /// Tasks matching `filter` as they were when the snapshot was taken
+    pub fn query(&self, filter: &ViewFilter) -> Vec<&Task> {
+        self.tasks.iter().filter(|t| filter.matches(t)).collect()
+    }
+
+    /// Computes what changed between this snapshot and a later one
+    pub fn delta(&self, later: &BoardSnapshot) -> SnapshotDelta {
+        let before: HashMap<&TaskId, &Task> = self.tasks.iter().map(|t| (&t.id, t)).collect();
+        let mut delta = SnapshotDelta::default();
+
+        for task in &later.tasks {
+            match before.get(&task.id) {
+                None => delta.added.push(task.id.clone()),
+                Some(old) => {
+                    if old.status != task.status {
+                        delta.moved.push((
+                            task.id.clone(),
+                            old.status.clone(),
+                            task.status.clone(),
+                        ));
+                    }
+                    let diff = old.diff(task);
+                    if !diff.is_empty() {
+                        delta.changed.push(diff);
+                    }
+                }
+            }
+        }
+        delta.removed = self
+            .tasks
+            .iter()
+            .filter(|t| later.task(&t.id).is_none())
+            .map(|t| t.id.clone())
+            .collect();
+        delta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::board::BoardConfig;
+
+    fn snapshot(label: &str, tasks: Vec<Task>) -> BoardSnapshot {
+        BoardSnapshot::new(label, Board::new(BoardConfig::default()), tasks).unwrap()
+    }
+
+    #[test]
+    fn test_label_validation() {
+        assert!(BoardSnapshot::validate_label("sprint-12.start").is_ok());
+        assert!(BoardSnapshot::validate_label("").is_err());
+        assert!(BoardSnapshot::validate_label("../board").is_err());
+        assert!(BoardSnapshot::validate_label("a/b").is_err());
+    }
+
+    #[test]
+    fn test_delta() {
+        let kept = Task::new(TaskId::new(1), "Kept".to_string());
+        let dropped = Task::new(TaskId::new(2), "Dropped".to_string());
+        let start = snapshot("start", vec![kept.clone(), dropped]);
+
+        let mut moved = kept.clone();
+        moved.transition_to(TaskStatus::Open, None).unwrap();
+        let added = Task::new(TaskId::new(3), "Added".to_string());
+        let end = snapshot("end", vec![added, moved]);
+
+        let delta = start.delta(&end);
+        assert_eq!(delta.added, vec![TaskId::new(3)]);
+        assert_eq!(delta.removed, vec![TaskId::new(2)]);
+        assert_eq!(
+            delta.moved,
+            vec![(TaskId::new(1), TaskStatus::New, TaskStatus::Open)]
+        );
+        assert_eq!(delta.changed.len(), 1);
+        assert!(start.delta(&start).is_empty());
+    }
+
+    #[test]
+    fn test_query() {
+        let mut open = Task::new(TaskId::new(1), "Open".to_string());
+        open.status = TaskStatus::Open;
+        let snap = snapshot(
+            "s",
+            vec![open, Task::new(TaskId::new(2), "New".to_string())],
+        );
+
+        let filter = ViewFilter {
+            statuses: vec![TaskStatus::Open],
+            ..ViewFilter::default()
+        };
+        assert_eq!(snap.query(&filter).len(), 1);
+        assert_eq!(snap.tasks_in_status(&TaskStatus::New).len(), 1);
+    }
+}
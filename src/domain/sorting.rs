@@ -1,10 +1,12 @@
 use crate::domain::task::{Task, TaskStatus};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::str::FromStr;
 
 /// Fields available for sorting tasks
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum SortField {
     Id,
     Title,
@@ -15,14 +17,22 @@ pub enum SortField {
     End,
     AcProgress,
     AcCount,
+    /// Completion across acceptance criteria and checklists
+    Progress,
     /// Board column order: higher rank appears higher in the column
     Rank,
+    /// Task type name; untyped tasks sort last
+    Type,
+    /// Least to most severe; tasks without a severity sort last
+    Severity,
 }
 
 /// Sort order direction
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SortOrder {
+    #[serde(rename = "asc")]
     Ascending,
+    #[serde(rename = "desc")]
     Descending,
 }
 
@@ -40,9 +50,12 @@ impl FromStr for SortField {
             "end" => Ok(SortField::End),
             "ac-progress" => Ok(SortField::AcProgress),
             "ac-count" => Ok(SortField::AcCount),
+            "progress" => Ok(SortField::Progress),
             "rank" => Ok(SortField::Rank),
+            "type" => Ok(SortField::Type),
+            "severity" => Ok(SortField::Severity),
             _ => Err(format!(
-                "Invalid sort field '{}'. Valid fields: id, title, status, created, updated, start, end, ac-progress, ac-count, rank",
+                "Invalid sort field '{}'. Valid fields: id, title, status, created, updated, start, end, ac-progress, ac-count, progress, rank, type, severity",
                 s
             )),
         }
@@ -102,11 +115,24 @@ pub fn sort_tasks(tasks: &mut [Task], field: SortField, order: SortOrder) {
                 .acceptance_criteria
                 .len()
                 .cmp(&b.acceptance_criteria.len()),
+            SortField::Progress => compare_progress(a, b),
             // Rank: higher rank → earlier in list; tiebreak by updated_at desc
             SortField::Rank => b
                 .rank
                 .cmp(&a.rank)
                 .then_with(|| b.updated_at.cmp(&a.updated_at)),
+            SortField::Type => match (&a.task_type, &b.task_type) {
+                (Some(a), Some(b)) => a.to_lowercase().cmp(&b.to_lowercase()),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+            SortField::Severity => match (a.severity, b.severity) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
         };
 
         match order {
@@ -186,6 +212,22 @@ fn compare_ac_progress(a: &Task, b: &Task) -> Ordering {
         .unwrap_or(Ordering::Equal)
 }
 
+/// Compare by overall completion across acceptance criteria and checklists
+///
+/// Tasks with nothing to complete are treated as 0% complete.
+fn compare_progress(a: &Task, b: &Task) -> Ordering {
+    fn progress_pct(t: &Task) -> f64 {
+        match t.progress() {
+            (_, 0) => 0.0,
+            (completed, total) => (completed as f64) / (total as f64),
+        }
+    }
+
+    progress_pct(a)
+        .partial_cmp(&progress_pct(b))
+        .unwrap_or(Ordering::Equal)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -323,6 +365,33 @@ mod tests {
         assert_eq!(compare_ac_progress(&task2, &task1), Ordering::Greater);
     }
 
+    #[test]
+    fn test_compare_progress_includes_checklists() {
+        let mut task1 = Task::new(TaskId::new(1), "Task 1".to_string());
+        let mut task2 = Task::new(TaskId::new(2), "Task 2".to_string());
+
+        task1.add_acceptance_criterion("AC1".to_string());
+        task1.add_acceptance_criterion("AC2".to_string());
+        task1.acceptance_criteria[0].mark_completed();
+
+        task2.add_checklist("Steps".to_string()).unwrap();
+        let id = task2
+            .checklist_mut("Steps")
+            .unwrap()
+            .add_item("One".to_string());
+        task2
+            .checklist_mut("Steps")
+            .unwrap()
+            .toggle_item(id)
+            .unwrap();
+
+        assert_eq!(compare_progress(&task1, &task2), Ordering::Less);
+        assert_eq!(
+            SortField::from_str("progress").unwrap(),
+            SortField::Progress
+        );
+    }
+
     #[test]
     fn test_sort_by_ac_count() {
         let mut task1 = Task::new(TaskId::new(1), "Task 1".to_string());
@@ -344,6 +413,26 @@ mod tests {
         assert_eq!(tasks[2].acceptance_criteria.len(), 3);
     }
 
+    #[test]
+    fn test_sort_by_severity() {
+        use crate::domain::task::Severity;
+
+        let mut minor = Task::new(TaskId::new(1), "Typo".to_string());
+        minor.severity = Some(Severity::Minor);
+        let untriaged = Task::new(TaskId::new(2), "Untriaged".to_string());
+        let mut blocker = Task::new(TaskId::new(3), "Data loss".to_string());
+        blocker.severity = Some(Severity::Blocker);
+
+        let mut tasks = vec![minor, untriaged, blocker];
+        sort_tasks(
+            &mut tasks,
+            "severity".parse().unwrap(),
+            SortOrder::Descending,
+        );
+        let ids: Vec<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["HLA2", "HLA3", "HLA1"]);
+    }
+
     #[test]
     fn test_sort_by_dates_with_none_values() {
         let mut task1 = Task::new(TaskId::new(1), "Has both dates".to_string());
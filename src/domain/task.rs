@@ -1,45 +1,146 @@
+use crate::domain::{
+    agent_note::AgentNote,
+    blocked::BlockedInfo,
+    checklist::Checklist,
+    crdt::CrdtFields,
+    history::StatusTransition,
+    lock::TaskLock,
+    markdown::{self, DescriptionFormat},
+    text::{Description, Title},
+    worklog::WorkLogEntry,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::{fmt, str::FromStr};
+use std::{collections::HashMap, fmt, str::FromStr};
+use uuid::Uuid;
 
 /// Unique identifier for a task (e.g., HLA1, HLA2, HLA100)
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TaskId(String);
 
 impl TaskId {
-    // Default prefix for task IDs (could be made configurable in the future)
-    const DEFAULT_PREFIX: &'static str = "HLA";
+    // Default prefix for task IDs; projects can override it in config.toml
+    pub const DEFAULT_PREFIX: &'static str = "HLA";
 
     /// Creates a new TaskId from a counter
     pub fn new(counter: u32) -> Self {
-        Self(format!("{}{}", Self::DEFAULT_PREFIX, counter))
+        Self::with_prefix(Self::DEFAULT_PREFIX, counter)
+    }
+
+    /// Creates a TaskId with a project-specific prefix
+    pub fn with_prefix(prefix: &str, counter: u32) -> Self {
+        Self(format!("{}{}", prefix.to_uppercase(), counter))
     }
 
     /// Returns the string representation
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// The numeric part of the ID (`12` for `HLA12`)
+    pub fn number(&self) -> Option<u32> {
+        let start = self.0.find(|c: char| c.is_ascii_digit())?;
+        self.0[start..].parse().ok()
+    }
+
+    /// Parses user input against a project's prefixes
+    ///
+    /// Accepts any casing, an optional `#` or `-` (`#12`, `hla-12`), and bare
+    /// numbers, which get the first prefix. IDs with a prefix not in
+    /// `prefixes` are rejected.
+    pub fn parse_with_prefixes(
+        input: &str,
+        prefixes: &[&str],
+    ) -> Result<Self, crate::error::HlaviError> {
+        let invalid = || crate::error::HlaviError::InvalidTaskId(input.to_string());
+        let trimmed = input.trim().trim_start_matches('#');
+
+        if !trimmed.is_empty() && trimmed.chars().all(|c| c.is_ascii_digit()) {
+            let number = trimmed.parse::<u32>().map_err(|_| invalid())?;
+            let prefix = prefixes.first().ok_or_else(invalid)?;
+            return Ok(Self::with_prefix(prefix, number));
+        }
+
+        let id = Self::from_str(trimmed).map_err(|_| invalid())?;
+        let known = prefixes.iter().any(|prefix| {
+            id.0.len() > prefix.len()
+                && id.0[..prefix.len()].eq_ignore_ascii_case(prefix)
+                && id.0[prefix.len()..].chars().all(|c| c.is_ascii_digit())
+        });
+        if known {
+            Ok(id)
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+/// How task IDs are rendered for people and external tools
+///
+/// IDs are always stored in their compact form (`HLA42`); the format only
+/// changes how they are shown, e.g. `HLA-0042` with a `-` separator and a
+/// padding of 4. Parsing accepts either form.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IdFormat {
+    /// Placed between the prefix and the number: empty, `-` or `_`
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub separator: String,
+    /// Minimum number of digits, filled with leading zeros
+    #[serde(skip_serializing_if = "is_zero_u8")]
+    pub padding: u8,
+}
+
+fn is_zero_u8(n: &u8) -> bool {
+    *n == 0
+}
+
+impl IdFormat {
+    /// Longest padding accepted; `u32` numbers have at most 10 digits
+    pub const MAX_PADDING: u8 = 10;
+
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Renders `id` in this format
+    pub fn render(&self, id: &TaskId) -> String {
+        let Some(number) = id.number() else {
+            return id.to_string();
+        };
+        let prefix = &id.0[..id.0.len() - number.to_string().len()];
+        format!(
+            "{}{}{:0width$}",
+            prefix,
+            self.separator,
+            number,
+            width = self.padding as usize
+        )
+    }
 }
 
 impl FromStr for TaskId {
     type Err = crate::error::HlaviError;
 
+    /// Parses `HLA42`, also accepting lowercase, a `-` or `_` after the
+    /// prefix and zero-padded numbers (`hla-0042`)
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || crate::error::HlaviError::InvalidTaskId(s.to_string());
         // Convert to uppercase for case-insensitive comparison
         let normalized = s.to_uppercase();
-        let prefix = TaskId::DEFAULT_PREFIX;
-
-        if normalized.starts_with(prefix) && normalized.len() > prefix.len() {
-            // Verify the rest is a valid number
-            if normalized[prefix.len()..].parse::<u32>().is_ok() {
-                // Store the normalized (uppercase) form
-                Ok(Self(normalized))
-            } else {
-                Err(crate::error::HlaviError::InvalidTaskId(s.to_string()))
-            }
-        } else {
-            Err(crate::error::HlaviError::InvalidTaskId(s.to_string()))
+        // Any ASCII letter prefix is accepted, since projects may configure their own
+        let prefix_len = normalized
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(normalized.len());
+        let (prefix, rest) = normalized.split_at(prefix_len);
+        let digits = rest.strip_prefix(|c| c == '-' || c == '_').unwrap_or(rest);
+
+        if prefix.is_empty() || digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
         }
+        // Store the compact form, so padded and unpadded input give equal IDs
+        let number: u32 = digits.parse().map_err(|_| invalid())?;
+        Ok(Self::with_prefix(prefix, number))
     }
 }
 
@@ -50,11 +151,15 @@ impl fmt::Display for TaskId {
 }
 
 /// Status of a task on the kanban board
+///
+/// Serialized in lowercase (`inprogress`); other common spellings of
+/// In Progress are accepted when reading.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TaskStatus {
     New,
     Open,
+    #[serde(alias = "in_progress", alias = "in-progress", alias = "In Progress")]
     InProgress,
     Pending,
     Review,
@@ -76,7 +181,78 @@ impl fmt::Display for TaskStatus {
     }
 }
 
+impl FromStr for TaskStatus {
+    type Err = crate::error::HlaviError;
+
+    /// Accepts any casing with or without separators, e.g. `in-progress`,
+    /// `in_progress`, `In Progress` or `inprogress`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized: String = s
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_'))
+            .collect::<String>()
+            .to_lowercase();
+        match normalized.as_str() {
+            "new" => Ok(Self::New),
+            "open" => Ok(Self::Open),
+            "inprogress" => Ok(Self::InProgress),
+            "pending" => Ok(Self::Pending),
+            "review" => Ok(Self::Review),
+            "done" => Ok(Self::Done),
+            "closed" => Ok(Self::Closed),
+            _ => Err(crate::error::HlaviError::Other(format!(
+                "Invalid status '{}'. Valid statuses: new, open, in-progress, pending, review, done, closed",
+                s
+            ))),
+        }
+    }
+}
+
 impl TaskStatus {
+    /// Every status, in workflow order
+    pub const ALL: [TaskStatus; 7] = [
+        Self::New,
+        Self::Open,
+        Self::InProgress,
+        Self::Pending,
+        Self::Review,
+        Self::Done,
+        Self::Closed,
+    ];
+
+    /// Shortest sequence of valid transitions from this status to `target`
+    ///
+    /// The result excludes `self` and ends with `target`; it is empty when
+    /// already there, and `None` when the target is unreachable.
+    pub fn path_to(&self, target: &TaskStatus) -> Option<Vec<TaskStatus>> {
+        // (status, predecessor) for every status reached so far
+        let mut came_from: Vec<(TaskStatus, TaskStatus)> = Vec::new();
+        let mut queue = std::collections::VecDeque::from([self.clone()]);
+
+        while let Some(status) = queue.pop_front() {
+            if &status == target {
+                let mut path = Vec::new();
+                let mut current = status;
+                while &current != self {
+                    let (_, from) = came_from.iter().find(|(to, _)| to == &current)?;
+                    let from = from.clone();
+                    path.push(current);
+                    current = from;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for next in Self::ALL.iter() {
+                let reached = next == self || came_from.iter().any(|(to, _)| to == next);
+                if !reached && status.can_transition_to(next) {
+                    came_from.push((next.clone(), status.clone()));
+                    queue.push_back(next.clone());
+                }
+            }
+        }
+        None
+    }
+
     /// Checks if a status transition is valid
     pub fn can_transition_to(&self, target: &TaskStatus) -> bool {
         match (self, target) {
@@ -112,14 +288,154 @@ impl TaskStatus {
     }
 }
 
+/// Priority of a task, ordered from lowest to highest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Priority {
+    /// Every priority, lowest first
+    pub const ALL: [Priority; 4] = [Self::Low, Self::Medium, Self::High, Self::Critical];
+
+    /// Next priority up, or `None` if already critical
+    pub fn escalated(&self) -> Option<Priority> {
+        match self {
+            Self::Low => Some(Self::Medium),
+            Self::Medium => Some(Self::High),
+            Self::High => Some(Self::Critical),
+            Self::Critical => None,
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Low => write!(f, "Low"),
+            Self::Medium => write!(f, "Medium"),
+            Self::High => write!(f, "High"),
+            Self::Critical => write!(f, "Critical"),
+        }
+    }
+}
+
+impl FromStr for Priority {
+    type Err = crate::error::HlaviError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "low" => Ok(Self::Low),
+            "medium" => Ok(Self::Medium),
+            "high" => Ok(Self::High),
+            "critical" => Ok(Self::Critical),
+            _ => Err(crate::error::HlaviError::Other(format!(
+                "Invalid priority '{}'. Valid priorities: low, medium, high, critical",
+                s
+            ))),
+        }
+    }
+}
+
+/// Impact of a bug, ordered from least to most severe
+///
+/// Severity describes how bad the defect is; priority describes how soon it
+/// gets fixed. Only bug-type tasks carry a severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Trivial,
+    Minor,
+    Major,
+    Critical,
+    /// Blocks a release or stops people working
+    Blocker,
+}
+
+impl Severity {
+    /// Every severity, least severe first
+    pub const ALL: [Severity; 5] = [
+        Self::Trivial,
+        Self::Minor,
+        Self::Major,
+        Self::Critical,
+        Self::Blocker,
+    ];
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Trivial => write!(f, "Trivial"),
+            Self::Minor => write!(f, "Minor"),
+            Self::Major => write!(f, "Major"),
+            Self::Critical => write!(f, "Critical"),
+            Self::Blocker => write!(f, "Blocker"),
+        }
+    }
+}
+
+impl FromStr for Severity {
+    type Err = crate::error::HlaviError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "trivial" => Ok(Self::Trivial),
+            "minor" => Ok(Self::Minor),
+            "major" => Ok(Self::Major),
+            "critical" => Ok(Self::Critical),
+            "blocker" => Ok(Self::Blocker),
+            _ => Err(crate::error::HlaviError::Other(format!(
+                "Invalid severity '{}'. Valid severities: trivial, minor, major, critical, blocker",
+                s
+            ))),
+        }
+    }
+}
+
+/// Where a task lives: on the board, in the archive or in the trash
+///
+/// Drafts count as on the board; a task both archived and trashed is in
+/// the trash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskLocation {
+    Board,
+    Archive,
+    Trash,
+}
+
+impl fmt::Display for TaskLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Board => write!(f, "Board"),
+            Self::Archive => write!(f, "Archive"),
+            Self::Trash => write!(f, "Trash"),
+        }
+    }
+}
+
 /// Acceptance criteria for a task
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AcceptanceCriteria {
     pub id: usize,
     pub description: String,
     pub completed: bool,
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    /// Who is responsible for (or verified) this criterion
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    /// Free-form verification notes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Link to evidence (test run, screenshot, PR comment)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence_url: Option<String>,
 }
 
 impl AcceptanceCriteria {
@@ -130,6 +446,9 @@ impl AcceptanceCriteria {
             completed: false,
             created_at: Utc::now(),
             completed_at: None,
+            assignee: None,
+            notes: None,
+            evidence_url: None,
         }
     }
 
@@ -152,12 +471,152 @@ impl AcceptanceCriteria {
     }
 }
 
+/// Relationship between two tasks, recorded on the source task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    RelatesTo,
+    /// This task was created as a copy of the target
+    ClonedFrom,
+    /// This task was split off the target
+    SplitFrom,
+    /// Part of this task was split into the target
+    SplitInto,
+    /// This task is a duplicate of the target
+    Duplicates,
+    /// This task was moved to the target, usually on another board
+    MovedTo,
+    /// This task was moved here from the target
+    MovedFrom,
+}
+
+impl fmt::Display for LinkKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RelatesTo => write!(f, "relates to"),
+            Self::ClonedFrom => write!(f, "cloned from"),
+            Self::SplitFrom => write!(f, "split from"),
+            Self::SplitInto => write!(f, "split into"),
+            Self::Duplicates => write!(f, "duplicates"),
+            Self::MovedTo => write!(f, "moved to"),
+            Self::MovedFrom => write!(f, "moved from"),
+        }
+    }
+}
+
+/// A typed link to another task
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TaskLink {
+    pub kind: LinkKind,
+    pub target: TaskId,
+    /// Project holding the target when it lives on another board
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
+impl fmt::Display for TaskLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.project {
+            Some(project) => write!(f, "{} {}/{}", self.kind, project, self.target),
+            None => write!(f, "{} {}", self.kind, self.target),
+        }
+    }
+}
+
+/// A pointer to something outside Hlavi, such as a commit or an issue in
+/// another tracker
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExternalRef {
+    /// Where the reference lives (e.g. "git", "github")
+    pub system: String,
+    /// Identifier within that system (e.g. a commit hash)
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl ExternalRef {
+    pub fn new(system: impl Into<String>, id: impl Into<String>) -> Self {
+        Self {
+            system: system.into(),
+            id: id.into(),
+            url: None,
+        }
+    }
+}
+
+impl fmt::Display for ExternalRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.system, self.id)
+    }
+}
+
+/// Options for `Task::clone_as`
+#[derive(Debug, Clone)]
+pub struct CloneOptions {
+    /// Title for the copy; defaults to the original title
+    pub title: Option<String>,
+    pub include_description: bool,
+    pub include_acceptance_criteria: bool,
+    pub include_checklists: bool,
+    pub include_tags: bool,
+}
+
+impl Default for CloneOptions {
+    fn default() -> Self {
+        Self {
+            title: None,
+            include_description: true,
+            include_acceptance_criteria: true,
+            include_checklists: true,
+            include_tags: true,
+        }
+    }
+}
+
+/// Parses a Markdown task-list line (`- [ ] text`, `* [x] text`, `+ [X] text`)
+///
+/// Returns the completion state and trimmed text, or `None` for any other line.
+pub(crate) fn parse_checklist_line(line: &str) -> Option<(bool, &str)> {
+    let rest = line.trim_start().strip_prefix(['-', '*', '+'])?;
+    let rest = rest.strip_prefix(' ')?.trim_start();
+    let (completed, text) = if let Some(text) = rest.strip_prefix("[ ]") {
+        (false, text)
+    } else if let Some(text) = rest
+        .strip_prefix("[x]")
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        (true, text)
+    } else {
+        return None;
+    };
+
+    let text = text.trim();
+    if text.is_empty() {
+        None
+    } else {
+        Some((completed, text))
+    }
+}
+
+/// Renders a single Markdown task-list line
+pub(crate) fn render_checklist_line(completed: bool, text: &str) -> String {
+    format!("- [{}] {}", if completed { "x" } else { " " }, text)
+}
+
 /// A kanban task
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Task {
     pub id: TaskId,
+    /// Stable identity that survives renumbering and moves between projects;
+    /// nil only for tasks written before it existed
+    #[serde(default = "Uuid::nil", skip_serializing_if = "Uuid::is_nil")]
+    pub uuid: Uuid,
     pub title: String,
     pub description: Option<String>,
+    /// How `description` should be rendered
+    #[serde(default, skip_serializing_if = "DescriptionFormat::is_plain")]
+    pub description_format: DescriptionFormat,
     pub status: TaskStatus,
     pub acceptance_criteria: Vec<AcceptanceCriteria>,
     pub created_at: DateTime<Utc>,
@@ -174,23 +633,110 @@ pub struct Task {
     /// Task IDs that are blocked by this task (this task must complete before they can proceed)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub blocks: Vec<TaskId>,
+    /// Typed links to related tasks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<TaskLink>,
     /// Sort rank within the board — higher values appear higher in a column
     #[serde(default, skip_serializing_if = "is_zero_i64")]
     pub rank: i64,
+    /// Optional priority
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    /// Free-form labels (e.g., "bug", "backend")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Named checklists independent of acceptance criteria
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checklists: Vec<Checklist>,
+    /// Replicated state for conflict-free offline merging (opt-in)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crdt: Option<CrdtFields>,
+    /// Next acceptance criterion ID; IDs are never reused within a task
+    #[serde(default, skip_serializing_if = "is_zero_usize")]
+    pub next_criterion_id: usize,
+    /// References to commits, issues and other external records
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external_refs: Vec<ExternalRef>,
+    /// Set when the task was archived; archived tasks are kept but hidden from the board
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Set when the task was moved to the trash, pending purge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trashed_at: Option<DateTime<Utc>>,
+    /// Time logged against the task
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub work_log: Vec<WorkLogEntry>,
+    /// Decisions agents recorded while working the task, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agent_notes: Vec<AgentNote>,
+    /// Person responsible for the task
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    /// Size estimate in story points
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<u32>,
+    /// When the task last moved to Done; cleared if it is reopened
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When the task last changed status
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_changed_at: Option<DateTime<Utc>>,
+    /// Every status change since history was first recorded, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status_history: Vec<StatusTransition>,
+    /// Name of one of the board's task types, such as "bug"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_type: Option<String>,
+    /// Impact of a bug; only set on bug-type tasks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+    /// Parts of the product the task touches, such as "parser"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<String>,
+    /// Where a problem was seen, such as "staging"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+    /// Half-written task kept off the board and out of agent hands until
+    /// published
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub draft: bool,
+    /// Editing lock held by a person or agent session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock: Option<TaskLock>,
+    /// Set while the task cannot move forward, whatever its status
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked: Option<BlockedInfo>,
+    /// Times the error this task tracks has been reported
+    #[serde(default, skip_serializing_if = "is_zero_u32")]
+    pub occurrences: u32,
+    /// Set on tasks loaded as headers, whose description was left in
+    /// storage; saving such a task keeps the stored description
+    #[serde(skip)]
+    pub body_omitted: bool,
 }
 
 fn is_zero_i64(n: &i64) -> bool {
     *n == 0
 }
 
+fn is_zero_usize(n: &usize) -> bool {
+    *n == 0
+}
+
+fn is_zero_u32(n: &u32) -> bool {
+    *n == 0
+}
+
 impl Task {
     /// Creates a new task with the given ID and title
     pub fn new(id: TaskId, title: String) -> Self {
         let now = Utc::now();
         Self {
             id,
+            uuid: Uuid::new_v4(),
             title,
             description: None,
+            description_format: DescriptionFormat::Plain,
             status: TaskStatus::New,
             acceptance_criteria: Vec::new(),
             created_at: now,
@@ -201,20 +747,81 @@ impl Task {
             end_date: None,
             parent: None,
             blocks: Vec::new(),
+            links: Vec::new(),
             rank: 0,
+            priority: None,
+            tags: Vec::new(),
+            checklists: Vec::new(),
+            crdt: None,
+            next_criterion_id: 0,
+            external_refs: Vec::new(),
+            archived_at: None,
+            trashed_at: None,
+            work_log: Vec::new(),
+            agent_notes: Vec::new(),
+            assignee: None,
+            estimate: None,
+            completed_at: None,
+            status_changed_at: None,
+            status_history: Vec::new(),
+            task_type: None,
+            severity: None,
+            components: Vec::new(),
+            environment: None,
+            draft: false,
+            lock: None,
+            blocked: None,
+            occurrences: 0,
+            body_omitted: false,
         }
     }
 
-    /// Sets the title
-    pub fn set_title(&mut self, title: String) {
-        self.title = title;
+    /// Creates a new task, validating the title first
+    pub fn try_new(id: TaskId, title: &str) -> Result<Self, crate::error::HlaviError> {
+        Ok(Self::new(id, Title::new(title)?.into_string()))
+    }
+
+    /// Sets the title after cleaning and validating it
+    pub fn set_title(&mut self, title: String) -> Result<(), crate::error::HlaviError> {
+        self.title = Title::new(&title)?.into_string();
         self.updated_at = Utc::now();
+        Ok(())
     }
 
-    /// Sets the description
-    pub fn set_description(&mut self, description: String) {
-        self.description = Some(description);
+    /// Sets the description after cleaning and validating it
+    pub fn set_description(&mut self, description: String) -> Result<(), crate::error::HlaviError> {
+        self.description = Some(Description::new(&description)?.into_string());
+        self.body_omitted = false;
         self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Cleans the title and description in place, failing if either is
+    /// invalid
+    pub fn validate_text(&mut self) -> Result<(), crate::error::HlaviError> {
+        self.title = Title::new(&self.title)?.into_string();
+        if let Some(description) = &self.description {
+            self.description = Some(Description::new(description)?.into_string());
+        }
+        Ok(())
+    }
+
+    /// Like `validate_text`, but only for the title and description that
+    /// differ from `before`
+    ///
+    /// Tasks saved before validation existed may hold text that no longer
+    /// passes; they stay editable until that text itself is changed.
+    pub fn validate_changed_text(&mut self, before: &Task) -> Result<(), crate::error::HlaviError> {
+        if self.title != before.title {
+            self.title = Title::new(&self.title)?.into_string();
+        }
+        match &self.description {
+            Some(description) if self.description != before.description => {
+                self.description = Some(Description::new(description)?.into_string());
+            }
+            _ => {}
+        }
+        Ok(())
     }
 
     /// Sets the start date with validation against end_date
@@ -253,119 +860,864 @@ impl Task {
         self.updated_at = Utc::now();
     }
 
-    /// Clears the end date
-    pub fn clear_end_date(&mut self) {
-        self.end_date = None;
+    /// Clears the end date
+    pub fn clear_end_date(&mut self) {
+        self.end_date = None;
+        self.updated_at = Utc::now();
+    }
+
+    /// Sets both dates atomically with validation
+    pub fn set_date_range(
+        &mut self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<(), crate::error::HlaviError> {
+        if start > end {
+            return Err(crate::error::HlaviError::InvalidDateRange {
+                start: start.to_rfc3339(),
+                end: end.to_rfc3339(),
+            });
+        }
+        self.start_date = Some(start);
+        self.end_date = Some(end);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Time from start date to end date, if both are set
+    pub fn cycle_time(&self) -> Option<chrono::Duration> {
+        Some(self.end_date? - self.start_date?)
+    }
+
+    /// Adds an acceptance criterion, returning its stable ID
+    pub fn add_acceptance_criterion(&mut self, description: String) -> usize {
+        let id = self.allocate_criterion_id();
+        self.acceptance_criteria
+            .push(AcceptanceCriteria::new(id, description));
+        self.updated_at = Utc::now();
+        id
+    }
+
+    /// Finds an acceptance criterion by its stable ID
+    pub fn find_criterion(&self, id: usize) -> Option<&AcceptanceCriteria> {
+        self.acceptance_criteria.iter().find(|ac| ac.id == id)
+    }
+
+    fn find_criterion_mut(
+        &mut self,
+        id: usize,
+    ) -> Result<&mut AcceptanceCriteria, crate::error::HlaviError> {
+        self.acceptance_criteria
+            .iter_mut()
+            .find(|ac| ac.id == id)
+            .ok_or(crate::error::HlaviError::AcceptanceCriteriaNotFound)
+    }
+
+    /// Replaces the text of an acceptance criterion
+    pub fn update_criterion(
+        &mut self,
+        id: usize,
+        description: String,
+    ) -> Result<(), crate::error::HlaviError> {
+        self.find_criterion_mut(id)?.description = description;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Sets or clears the assignee of an acceptance criterion
+    pub fn assign_criterion(
+        &mut self,
+        id: usize,
+        assignee: Option<String>,
+    ) -> Result<(), crate::error::HlaviError> {
+        self.find_criterion_mut(id)?.assignee = assignee;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Sets or clears the verification notes of an acceptance criterion
+    pub fn set_criterion_notes(
+        &mut self,
+        id: usize,
+        notes: Option<String>,
+    ) -> Result<(), crate::error::HlaviError> {
+        self.find_criterion_mut(id)?.notes = notes;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Sets or clears the evidence link of an acceptance criterion
+    pub fn set_criterion_evidence(
+        &mut self,
+        id: usize,
+        evidence_url: Option<String>,
+    ) -> Result<(), crate::error::HlaviError> {
+        self.find_criterion_mut(id)?.evidence_url = evidence_url;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Toggles completion of an acceptance criterion, returning the new state
+    pub fn toggle_criterion(&mut self, id: usize) -> Result<bool, crate::error::HlaviError> {
+        let ac = self.find_criterion_mut(id)?;
+        ac.toggle();
+        let completed = ac.completed;
+        self.updated_at = Utc::now();
+        Ok(completed)
+    }
+
+    /// Removes an acceptance criterion by stable ID or exact description
+    pub fn remove_acceptance_criterion(
+        &mut self,
+        identifier: &str,
+    ) -> Result<(), crate::error::HlaviError> {
+        // Try to parse as ID first
+        let by_id = identifier
+            .parse::<usize>()
+            .ok()
+            .and_then(|id| self.acceptance_criteria.iter().position(|ac| ac.id == id));
+
+        // Fall back to matching the description
+        let pos = by_id.or_else(|| {
+            self.acceptance_criteria
+                .iter()
+                .position(|ac| ac.description == identifier)
+        });
+
+        if let Some(pos) = pos {
+            self.acceptance_criteria.remove(pos);
+            self.updated_at = Utc::now();
+            return Ok(());
+        }
+
+        Err(crate::error::HlaviError::AcceptanceCriteriaNotFound)
+    }
+
+    /// Replaces the acceptance criteria with those in a Markdown checklist
+    ///
+    /// Only task-list lines (`- [ ] text` / `- [x] text`) are read; other lines
+    /// are ignored. Criteria whose text matches an existing criterion keep its
+    /// ID and timestamps. Returns the number of criteria parsed.
+    pub fn set_acceptance_criteria_from_markdown(&mut self, markdown: &str) -> usize {
+        let mut existing = std::mem::take(&mut self.acceptance_criteria);
+        let mut criteria = Vec::new();
+
+        for (completed, text) in markdown.lines().filter_map(parse_checklist_line) {
+            let mut ac = match existing.iter().position(|ac| ac.description == text) {
+                Some(pos) => existing.remove(pos),
+                None => AcceptanceCriteria::new(0, text.to_string()),
+            };
+            if completed && !ac.completed {
+                ac.mark_completed();
+            } else if !completed && ac.completed {
+                ac.mark_incomplete();
+            }
+            criteria.push(ac);
+        }
+
+        // Retained criteria keep their IDs; new ones get fresh IDs afterwards
+        self.acceptance_criteria = criteria.iter().filter(|ac| ac.id != 0).cloned().collect();
+        for ac in criteria.iter_mut().filter(|ac| ac.id == 0) {
+            ac.id = self.allocate_criterion_id();
+        }
+        self.acceptance_criteria = criteria;
+        self.updated_at = Utc::now();
+        self.acceptance_criteria.len()
+    }
+
+    /// Renders the acceptance criteria as a Markdown checklist
+    pub fn acceptance_criteria_to_markdown(&self) -> String {
+        self.acceptance_criteria
+            .iter()
+            .map(|ac| render_checklist_line(ac.completed, &ac.description))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Moves the acceptance criterion at position `from` to position `to`
+    ///
+    /// Positions are zero-based; criteria in between shift by one. The list
+    /// order is what gets persisted, so the new order survives save/load.
+    pub fn move_criterion(
+        &mut self,
+        from: usize,
+        to: usize,
+    ) -> Result<(), crate::error::HlaviError> {
+        let len = self.acceptance_criteria.len();
+        if from >= len || to >= len {
+            return Err(crate::error::HlaviError::AcceptanceCriteriaNotFound);
+        }
+        if from != to {
+            let ac = self.acceptance_criteria.remove(from);
+            self.acceptance_criteria.insert(to, ac);
+            self.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+
+    /// Reassigns IDs of criteria that share an ID with an earlier criterion
+    ///
+    /// Files written before IDs were stable can contain duplicates (IDs used to
+    /// be `len() + 1`). Returns true if any ID was changed.
+    pub fn repair_criterion_ids(&mut self) -> bool {
+        let mut seen = std::collections::HashSet::new();
+        let mut duplicates = Vec::new();
+        for (pos, ac) in self.acceptance_criteria.iter().enumerate() {
+            if !seen.insert(ac.id) {
+                duplicates.push(pos);
+            }
+        }
+
+        for pos in &duplicates {
+            let id = self.allocate_criterion_id();
+            self.acceptance_criteria[*pos].id = id;
+        }
+        !duplicates.is_empty()
+    }
+
+    pub(crate) fn allocate_criterion_id(&mut self) -> usize {
+        let max_existing = self
+            .acceptance_criteria
+            .iter()
+            .map(|ac| ac.id)
+            .max()
+            .unwrap_or(0);
+        let id = self.next_criterion_id.max(max_existing + 1);
+        self.next_criterion_id = id + 1;
+        id
+    }
+
+    /// Adds an empty checklist; names are unique (case-insensitive)
+    pub fn add_checklist(&mut self, name: String) -> Result<(), crate::error::HlaviError> {
+        if self.checklist(&name).is_some() {
+            return Err(crate::error::HlaviError::Other(format!(
+                "Checklist '{}' already exists",
+                name
+            )));
+        }
+        self.checklists.push(Checklist::new(name));
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Finds a checklist by name (case-insensitive)
+    pub fn checklist(&self, name: &str) -> Option<&Checklist> {
+        self.checklists
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Finds a checklist by name for editing; marks the task as updated
+    pub fn checklist_mut(
+        &mut self,
+        name: &str,
+    ) -> Result<&mut Checklist, crate::error::HlaviError> {
+        let checklist = self
+            .checklists
+            .iter_mut()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| crate::error::HlaviError::ChecklistNotFound(name.to_string()))?;
+        self.updated_at = Utc::now();
+        Ok(checklist)
+    }
+
+    /// Removes a checklist by name
+    pub fn remove_checklist(&mut self, name: &str) -> Result<Checklist, crate::error::HlaviError> {
+        let pos = self
+            .checklists
+            .iter()
+            .position(|c| c.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| crate::error::HlaviError::ChecklistNotFound(name.to_string()))?;
+        self.updated_at = Utc::now();
+        Ok(self.checklists.remove(pos))
+    }
+
+    /// Returns `(completed, total)` across acceptance criteria and all checklist items
+    pub fn progress(&self) -> (usize, usize) {
+        let ac_completed = self
+            .acceptance_criteria
+            .iter()
+            .filter(|ac| ac.completed)
+            .count();
+        self.checklists.iter().map(Checklist::progress).fold(
+            (ac_completed, self.acceptance_criteria.len()),
+            |(done, total), (d, t)| (done + d, total + t),
+        )
+    }
+
+    /// Checks if the query matches the title, description, acceptance criteria,
+    /// or checklists (case-insensitive)
+    pub fn matches_query(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        let contains = |text: &str| text.to_lowercase().contains(&query);
+
+        contains(&self.title)
+            || self.description.as_deref().map(contains).unwrap_or(false)
+            || contains(&self.description_text())
+            || self.acceptance_criteria.iter().any(|ac| {
+                contains(&ac.description) || ac.notes.as_deref().map(contains).unwrap_or(false)
+            })
+            || self
+                .checklists
+                .iter()
+                .any(|c| contains(&c.name) || c.items.iter().any(|i| contains(&i.text)))
+    }
+
+    /// The description with formatting removed, or an empty string
+    pub fn description_text(&self) -> String {
+        self.description
+            .as_deref()
+            .map(|d| markdown::strip_formatting(d, self.description_format))
+            .unwrap_or_default()
+    }
+
+    /// First paragraph of the description as plain text, for list views
+    pub fn description_summary(&self) -> String {
+        self.description
+            .as_deref()
+            .map(|d| markdown::summary(d, self.description_format))
+            .unwrap_or_default()
+    }
+
+    /// Number of words in the description
+    pub fn description_word_count(&self) -> usize {
+        self.description
+            .as_deref()
+            .map(|d| markdown::word_count(d, self.description_format))
+            .unwrap_or(0)
+    }
+
+    /// Changes the task status
+    pub fn transition_to(
+        &mut self,
+        new_status: TaskStatus,
+        rejection_reason: Option<String>,
+    ) -> Result<(), crate::error::HlaviError> {
+        if !self.status.can_transition_to(&new_status) {
+            return Err(crate::error::HlaviError::InvalidStatusTransition {
+                from: self.status.to_string(),
+                to: new_status.to_string(),
+            });
+        }
+
+        self.enter_status(new_status, rejection_reason);
+        Ok(())
+    }
+
+    /// Sets the status without checking the workflow
+    pub(crate) fn enter_status(
+        &mut self,
+        new_status: TaskStatus,
+        rejection_reason: Option<String>,
+    ) {
+        let now = Utc::now();
+        self.stamp_completion(&new_status, now);
+        self.record_status(new_status, now);
+        self.rejection_reason = rejection_reason;
+        self.updated_at = now;
+    }
+
+    /// Updates `completed_at` for a move from the current status into `to`
+    ///
+    /// Entering Done records `at` and closing a finished task keeps its
+    /// completion time; any other move clears it.
+    pub(crate) fn stamp_completion(&mut self, to: &TaskStatus, at: DateTime<Utc>) {
+        let finished = matches!(self.status, TaskStatus::Done | TaskStatus::Closed);
+        self.completed_at = match to {
+            TaskStatus::Done if self.status != TaskStatus::Done => Some(at),
+            TaskStatus::Done => self.completed_at.or(Some(at)),
+            TaskStatus::Closed if finished => self.completed_at,
+            _ => None,
+        };
+    }
+
+    /// When the task was finished, if it is Done or was closed after being
+    /// done
+    ///
+    /// Done tasks completed before `completed_at` was recorded fall back to
+    /// their last update. Tasks closed without being done were rejected
+    /// rather than completed.
+    pub fn completion_time(&self) -> Option<DateTime<Utc>> {
+        match self.status {
+            TaskStatus::Done => Some(self.completed_at.unwrap_or(self.updated_at)),
+            TaskStatus::Closed => self.completed_at,
+            _ => None,
+        }
+    }
+
+    /// When the task entered its current status
+    ///
+    /// Tasks that have not moved since creation, or moved before
+    /// `status_changed_at` was recorded, fall back to their creation time.
+    pub fn status_since(&self) -> DateTime<Utc> {
+        self.status_changed_at.unwrap_or(self.created_at)
+    }
+
+    /// Whole days the task has spent in its current status as of `now`
+    pub fn days_in_current_status(&self, now: DateTime<Utc>) -> i64 {
+        (now - self.status_since()).num_days().max(0)
+    }
+
+    /// Whole days since the task was last updated as of `now`
+    pub fn days_since_update(&self, now: DateTime<Utc>) -> i64 {
+        (now - self.updated_at).num_days().max(0)
+    }
+
+    /// Checks if all acceptance criteria are completed
+    pub fn all_acceptance_criteria_completed(&self) -> bool {
+        !self.acceptance_criteria.is_empty()
+            && self.acceptance_criteria.iter().all(|ac| ac.completed)
+    }
+
+    /// Checks if the task can be marked as done
+    pub fn can_mark_done(&self) -> bool {
+        self.status == TaskStatus::Review && self.all_acceptance_criteria_completed()
+    }
+
+    /// Sets the parent task
+    pub fn set_parent(&mut self, task_id: TaskId) {
+        self.parent = Some(task_id);
+        self.updated_at = Utc::now();
+    }
+
+    /// Clears the parent task
+    pub fn clear_parent(&mut self) {
+        self.parent = None;
+        self.updated_at = Utc::now();
+    }
+
+    /// Marks another task as blocked by this task
+    pub fn add_block(&mut self, task_id: TaskId) {
+        if !self.blocks.contains(&task_id) {
+            self.blocks.push(task_id);
+            self.updated_at = Utc::now();
+        }
+    }
+
+    /// Adds a typed link to another task, ignoring duplicates
+    pub fn add_link(&mut self, kind: LinkKind, target: TaskId) {
+        self.push_link(TaskLink {
+            kind,
+            target,
+            project: None,
+        });
+    }
+
+    /// Records an external reference; returns false if it was already present
+    pub fn add_external_ref(&mut self, external: ExternalRef) -> bool {
+        if self
+            .external_refs
+            .iter()
+            .any(|r| r.system == external.system && r.id == external.id)
+        {
+            return false;
+        }
+        self.external_refs.push(external);
+        self.updated_at = Utc::now();
+        true
+    }
+
+    /// Adds a link to a task on another project's board
+    pub fn add_external_link(&mut self, kind: LinkKind, project: String, target: TaskId) {
+        self.push_link(TaskLink {
+            kind,
+            target,
+            project: Some(project),
+        });
+    }
+
+    fn push_link(&mut self, link: TaskLink) {
+        if !self.links.contains(&link) {
+            self.links.push(link);
+            self.updated_at = Utc::now();
+        }
+    }
+
+    /// Returns the targets of all links of the given kind
+    pub fn linked(&self, kind: LinkKind) -> impl Iterator<Item = &TaskId> {
+        self.links
+            .iter()
+            .filter(move |l| l.kind == kind)
+            .map(|l| &l.target)
+    }
+
+    /// Creates a fresh copy of this task under a new ID
+    ///
+    /// The copy starts in `New` with no dates, incomplete criteria and
+    /// checklist items, and a `ClonedFrom` link back to this task. Priority and
+    /// parent are carried over; blocking relations and rank are not.
+    pub fn clone_as(&self, id: TaskId, options: &CloneOptions) -> Task {
+        let title = options.title.clone().unwrap_or_else(|| self.title.clone());
+        let mut copy = Task::new(id, title);
+        copy.priority = self.priority;
+        copy.parent = self.parent.clone();
+
+        if options.include_description {
+            copy.description = self.description.clone();
+        }
+        if options.include_acceptance_criteria {
+            for ac in &self.acceptance_criteria {
+                copy.add_acceptance_criterion(ac.description.clone());
+            }
+        }
+        if options.include_checklists {
+            copy.checklists = self
+                .checklists
+                .iter()
+                .map(|c| {
+                    let mut fresh = Checklist::new(c.name.clone());
+                    for item in &c.items {
+                        fresh.add_item(item.text.clone());
+                    }
+                    fresh
+                })
+                .collect();
+        }
+        if options.include_tags {
+            copy.tags = self.tags.clone();
+        }
+
+        copy.add_link(LinkKind::ClonedFrom, self.id.clone());
+        copy
+    }
+
+    /// Moves the selected acceptance criteria into a new task
+    ///
+    /// The new task keeps the criteria's completion state, inherits tags,
+    /// priority and parent, and the two tasks are linked with
+    /// `SplitFrom`/`SplitInto`.
+    pub fn split_off_criteria(
+        &mut self,
+        id: TaskId,
+        criterion_ids: &[usize],
+    ) -> Result<Task, crate::error::HlaviError> {
+        if criterion_ids.is_empty()
+            || criterion_ids
+                .iter()
+                .any(|c| self.find_criterion(*c).is_none())
+        {
+            return Err(crate::error::HlaviError::AcceptanceCriteriaNotFound);
+        }
+
+        let mut split = Task::new(id, format!("{} (split)", self.title));
+        split.priority = self.priority;
+        split.parent = self.parent.clone();
+        split.tags = self.tags.clone();
+
+        let (moved, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.acceptance_criteria)
+            .into_iter()
+            .partition(|ac| criterion_ids.contains(&ac.id));
+        self.acceptance_criteria = kept;
+        for ac in moved {
+            let new_id = split.add_acceptance_criterion(ac.description.clone());
+            let copied = split.find_criterion_mut(new_id)?;
+            *copied = AcceptanceCriteria { id: new_id, ..ac };
+        }
+
+        split.add_link(LinkKind::SplitFrom, self.id.clone());
+        self.add_link(LinkKind::SplitInto, split.id.clone());
+        Ok(split)
+    }
+
+    /// Folds the content of a duplicate task into this one
+    ///
+    /// Appends the duplicate's description, adds criteria and checklists not
+    /// already present (by text/name), and unions tags, links and blocking
+    /// relations. Priority becomes the higher of the two.
+    pub fn absorb(&mut self, duplicate: &Task) {
+        if let Some(dup_description) = duplicate.description.as_deref() {
+            self.description = match self.description.take() {
+                None => Some(dup_description.to_string()),
+                Some(own) if own == dup_description => Some(own),
+                Some(own) => Some(format!(
+                    "{}\n\n---\nMerged from {}:\n\n{}",
+                    own, duplicate.id, dup_description
+                )),
+            };
+        }
+
+        for ac in &duplicate.acceptance_criteria {
+            if !self
+                .acceptance_criteria
+                .iter()
+                .any(|own| own.description == ac.description)
+            {
+                let id = self.allocate_criterion_id();
+                self.acceptance_criteria
+                    .push(AcceptanceCriteria { id, ..ac.clone() });
+            }
+        }
+        for checklist in &duplicate.checklists {
+            if self.checklist(&checklist.name).is_none() {
+                self.checklists.push(checklist.clone());
+            }
+        }
+        for tag in &duplicate.tags {
+            self.add_tag(tag.clone());
+        }
+        for link in &duplicate.links {
+            if link.target != self.id {
+                self.add_link(link.kind, link.target.clone());
+            }
+        }
+        for blocked in &duplicate.blocks {
+            if blocked != &self.id {
+                self.add_block(blocked.clone());
+            }
+        }
+        self.priority = self.priority.max(duplicate.priority);
+        self.updated_at = Utc::now();
+    }
+
+    /// Returns true unless the task is archived or in the trash
+    pub fn is_active(&self) -> bool {
+        self.archived_at.is_none() && self.trashed_at.is_none()
+    }
+
+    pub fn location(&self) -> TaskLocation {
+        if self.trashed_at.is_some() {
+            TaskLocation::Trash
+        } else if self.archived_at.is_some() {
+            TaskLocation::Archive
+        } else {
+            TaskLocation::Board
+        }
+    }
+
+    /// Returns true if the task belongs in a board column: active and not a
+    /// draft
+    pub fn is_on_board(&self) -> bool {
+        self.is_active() && !self.draft
+    }
+
+    /// Turns a draft into a regular task after checking it is complete
+    ///
+    /// The title and description are cleaned and validated as for any
+    /// task; publishing a task that is not a draft is a no-op.
+    pub fn publish(&mut self) -> Result<(), crate::error::HlaviError> {
+        if !self.draft {
+            return Ok(());
+        }
+        self.validate_text()?;
+        self.draft = false;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn archive(&mut self, at: DateTime<Utc>) {
+        self.archived_at = Some(at);
+        self.updated_at = at;
+    }
+
+    pub fn trash(&mut self, at: DateTime<Utc>) {
+        self.trashed_at = Some(at);
+        self.updated_at = at;
+    }
+
+    /// Brings an archived or trashed task back onto the board
+    pub fn restore(&mut self) {
+        self.archived_at = None;
+        self.trashed_at = None;
         self.updated_at = Utc::now();
     }
 
-    /// Sets both dates atomically with validation
-    pub fn set_date_range(
-        &mut self,
-        start: DateTime<Utc>,
-        end: DateTime<Utc>,
-    ) -> Result<(), crate::error::HlaviError> {
-        if start > end {
-            return Err(crate::error::HlaviError::InvalidDateRange {
-                start: start.to_rfc3339(),
-                end: end.to_rfc3339(),
+    /// Closes this task as a duplicate of `primary`, bypassing the workflow
+    pub fn close_as_duplicate(&mut self, primary: TaskId) {
+        let now = Utc::now();
+        self.rejection_reason = Some(format!("Duplicate of {}", primary));
+        self.record_status(TaskStatus::Closed, now);
+        self.add_link(LinkKind::Duplicates, primary);
+        self.updated_at = now;
+    }
+
+    /// Copies this task under an ID minted on another project's board
+    ///
+    /// Content, status, timestamps, criteria and the UUID are preserved. References to
+    /// tasks on the source board (parent, blocks, links) cannot be resolved
+    /// from the destination, so they become external links to `from_project`,
+    /// and a `MovedFrom` link records where the task came from.
+    pub fn relocate_as(&self, id: TaskId, from_project: &str) -> Task {
+        let mut moved = self.clone();
+        moved.id = id;
+        moved.parent = None;
+        moved.blocks.clear();
+        moved.links.clear();
+
+        for link in &self.links {
+            moved.push_link(TaskLink {
+                project: link
+                    .project
+                    .clone()
+                    .or_else(|| Some(from_project.to_string())),
+                ..link.clone()
             });
         }
-        self.start_date = Some(start);
-        self.end_date = Some(end);
-        self.updated_at = Utc::now();
-        Ok(())
+        for related in self.parent.iter().chain(&self.blocks) {
+            moved.add_external_link(
+                LinkKind::RelatesTo,
+                from_project.to_string(),
+                related.clone(),
+            );
+        }
+        moved.add_external_link(
+            LinkKind::MovedFrom,
+            from_project.to_string(),
+            self.id.clone(),
+        );
+        moved
     }
 
-    /// Adds an acceptance criterion
-    pub fn add_acceptance_criterion(&mut self, description: String) {
-        let id = self.acceptance_criteria.len() + 1;
-        self.acceptance_criteria
-            .push(AcceptanceCriteria::new(id, description));
-        self.updated_at = Utc::now();
+    /// Turns this task into a closed tombstone redirecting to its new location
+    pub fn mark_moved(&mut self, to_project: String, new_id: TaskId) {
+        let now = Utc::now();
+        self.rejection_reason = Some(format!("Moved to {}/{}", to_project, new_id));
+        self.record_status(TaskStatus::Closed, now);
+        self.add_external_link(LinkKind::MovedTo, to_project, new_id);
+        self.updated_at = now;
     }
 
-    /// Removes an acceptance criterion by description or index
-    pub fn remove_acceptance_criterion(
-        &mut self,
-        identifier: &str,
-    ) -> Result<(), crate::error::HlaviError> {
-        // Try to parse as index first
-        if let Ok(index) = identifier.parse::<usize>() {
-            if index > 0 && index <= self.acceptance_criteria.len() {
-                self.acceptance_criteria.remove(index - 1);
-                self.updated_at = Utc::now();
-                return Ok(());
+    /// Rewrites parent, blocking and link references from `old` to `new`
+    ///
+    /// Returns true if anything changed.
+    pub fn replace_references(&mut self, old: &TaskId, new: &TaskId) -> bool {
+        self.remap_references(&HashMap::from([(old.clone(), new.clone())]))
+    }
+
+    /// Rewrites parent, blocking and link references using `mapping`, all at once
+    ///
+    /// Links to tasks in other projects are left alone. Returns true if
+    /// anything changed.
+    pub fn remap_references(&mut self, mapping: &HashMap<TaskId, TaskId>) -> bool {
+        let mut changed = false;
+        let mut remap = |id: &mut TaskId| {
+            if let Some(new) = mapping.get(id) {
+                *id = new.clone();
+                changed = true;
             }
+        };
+        if let Some(parent) = self.parent.as_mut() {
+            remap(parent);
         }
-
-        // Try to find by description
-        if let Some(pos) = self
-            .acceptance_criteria
-            .iter()
-            .position(|ac| ac.description == identifier)
-        {
-            self.acceptance_criteria.remove(pos);
+        self.blocks.iter_mut().for_each(&mut remap);
+        self.links
+            .iter_mut()
+            .filter(|l| l.project.is_none())
+            .for_each(|l| remap(&mut l.target));
+
+        if changed {
+            let mut seen = std::collections::HashSet::new();
+            self.blocks.retain(|b| seen.insert(b.clone()));
+            let mut seen = std::collections::HashSet::new();
+            self.links.retain(|l| seen.insert(l.clone()));
             self.updated_at = Utc::now();
-            return Ok(());
         }
+        changed
+    }
 
-        Err(crate::error::HlaviError::AcceptanceCriteriaNotFound)
+    /// Sets the sort rank for board ordering
+    pub fn set_rank(&mut self, rank: i64) {
+        self.rank = rank;
+        self.updated_at = Utc::now();
     }
 
-    /// Changes the task status
-    pub fn transition_to(
-        &mut self,
-        new_status: TaskStatus,
-        rejection_reason: Option<String>,
-    ) -> Result<(), crate::error::HlaviError> {
-        if !self.status.can_transition_to(&new_status) {
-            return Err(crate::error::HlaviError::InvalidStatusTransition {
-                from: self.status.to_string(),
-                to: new_status.to_string(),
-            });
-        }
+    /// Sets the priority
+    pub fn set_priority(&mut self, priority: Priority) {
+        self.priority = Some(priority);
+        self.updated_at = Utc::now();
+    }
 
-        self.status = new_status;
-        self.rejection_reason = rejection_reason;
+    /// Sets or clears the person responsible for the task
+    pub fn set_assignee(&mut self, assignee: Option<String>) {
+        self.assignee = assignee;
         self.updated_at = Utc::now();
-        Ok(())
     }
 
-    /// Checks if all acceptance criteria are completed
-    pub fn all_acceptance_criteria_completed(&self) -> bool {
-        !self.acceptance_criteria.is_empty()
-            && self.acceptance_criteria.iter().all(|ac| ac.completed)
+    /// Checks whether the task is assigned to `actor` (case-insensitive)
+    pub fn is_assigned_to(&self, actor: &str) -> bool {
+        self.assignee
+            .as_deref()
+            .is_some_and(|assignee| crate::domain::privacy::same_actor(assignee, actor))
     }
 
-    /// Checks if the task can be marked as done
-    pub fn can_mark_done(&self) -> bool {
-        self.status == TaskStatus::Review && self.all_acceptance_criteria_completed()
+    /// Sets or clears the story point estimate
+    pub fn set_estimate(&mut self, estimate: Option<u32>) {
+        self.estimate = estimate;
+        self.updated_at = Utc::now();
     }
 
-    /// Sets the parent task
-    pub fn set_parent(&mut self, task_id: TaskId) {
-        self.parent = Some(task_id);
+    /// Sets or clears the severity
+    ///
+    /// Whether the task's type allows a severity is checked by the service
+    /// against the board's task types.
+    pub fn set_severity(&mut self, severity: Option<Severity>) {
+        self.severity = severity;
         self.updated_at = Utc::now();
     }
 
-    /// Clears the parent task
-    pub fn clear_parent(&mut self) {
-        self.parent = None;
+    /// Clears the priority
+    pub fn clear_priority(&mut self) {
+        self.priority = None;
         self.updated_at = Utc::now();
     }
 
-    /// Marks another task as blocked by this task
-    pub fn add_block(&mut self, task_id: TaskId) {
-        if !self.blocks.contains(&task_id) {
-            self.blocks.push(task_id);
+    /// Checks if the task carries a tag (case-insensitive)
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Adds a tag, ignoring duplicates. Returns true if the tag was added.
+    pub fn add_tag(&mut self, tag: String) -> bool {
+        if self.has_tag(&tag) {
+            return false;
+        }
+        self.tags.push(tag);
+        self.updated_at = Utc::now();
+        true
+    }
+
+    /// Removes a tag (case-insensitive). Returns true if the tag was present.
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        if let Some(pos) = self.tags.iter().position(|t| t.eq_ignore_ascii_case(tag)) {
+            self.tags.remove(pos);
             self.updated_at = Utc::now();
+            true
+        } else {
+            false
         }
     }
 
-    /// Sets the sort rank for board ordering
-    pub fn set_rank(&mut self, rank: i64) {
-        self.rank = rank;
+    /// Checks if the task touches a component (case-insensitive)
+    pub fn has_component(&self, component: &str) -> bool {
+        self.components
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(component))
+    }
+
+    /// Adds a component, ignoring duplicates. Returns true if it was added.
+    pub fn add_component(&mut self, component: String) -> bool {
+        if self.has_component(&component) {
+            return false;
+        }
+        self.components.push(component);
+        self.updated_at = Utc::now();
+        true
+    }
+
+    /// Removes a component (case-insensitive). Returns true if it was present.
+    pub fn remove_component(&mut self, component: &str) -> bool {
+        let before = self.components.len();
+        self.components
+            .retain(|c| !c.eq_ignore_ascii_case(component));
+        if self.components.len() == before {
+            return false;
+        }
+        self.updated_at = Utc::now();
+        true
+    }
+
+    /// Sets or clears the environment
+    pub fn set_environment(&mut self, environment: Option<String>) {
+        self.environment = environment;
         self.updated_at = Utc::now();
     }
 
@@ -408,6 +1760,115 @@ mod tests {
         assert!(TaskId::from_str("INVALID").is_err());
         assert!(TaskId::from_str("HLA").is_err());
         assert!(TaskId::from_str("HLAabc").is_err());
+        assert!(TaskId::from_str("42").is_err());
+        assert!(TaskId::from_str("HLA-").is_err());
+        assert!(TaskId::from_str("HLA+5").is_err());
+    }
+
+    #[test]
+    fn test_task_id_formatting() {
+        let id = TaskId::from_str("hla-0042").unwrap();
+        assert_eq!(id, TaskId::new(42));
+        assert_eq!(TaskId::from_str("HLA_7").unwrap(), TaskId::new(7));
+
+        let format = IdFormat {
+            separator: "-".to_string(),
+            padding: 4,
+        };
+        assert_eq!(format.render(&id), "HLA-0042");
+        assert_eq!(format.render(&TaskId::new(123456)), "HLA-123456");
+        assert_eq!(IdFormat::default().render(&id), "HLA42");
+    }
+
+    #[test]
+    fn test_relocate_and_mark_moved() {
+        let mut task = Task::new(TaskId::new(3), "Move me".to_string());
+        task.parent = Some(TaskId::new(1));
+        task.blocks.push(TaskId::new(4));
+        task.add_link(LinkKind::ClonedFrom, TaskId::new(2));
+        task.add_acceptance_criterion("Keep me".to_string());
+
+        let moved = task.relocate_as(TaskId::with_prefix("WEB", 1), "backend");
+        assert_eq!(moved.id.as_str(), "WEB1");
+        assert_eq!(moved.created_at, task.created_at);
+        assert_eq!(moved.uuid, task.uuid);
+        assert_eq!(moved.acceptance_criteria.len(), 1);
+        assert!(moved.parent.is_none() && moved.blocks.is_empty());
+        assert!(moved
+            .links
+            .iter()
+            .all(|l| l.project.as_deref() == Some("backend")));
+        assert_eq!(
+            moved.linked(LinkKind::MovedFrom).next(),
+            Some(&TaskId::new(3))
+        );
+        assert_eq!(moved.linked(LinkKind::RelatesTo).count(), 2);
+
+        task.mark_moved("frontend".to_string(), moved.id.clone());
+        assert_eq!(task.status, TaskStatus::Closed);
+        assert_eq!(
+            task.links.last().unwrap().to_string(),
+            "moved to frontend/WEB1"
+        );
+    }
+
+    #[test]
+    fn test_task_id_parse_with_prefixes() {
+        let prefixes = ["WEB", "HLA"];
+        let parse = |s| TaskId::parse_with_prefixes(s, &prefixes);
+
+        assert_eq!(parse("12").unwrap().as_str(), "WEB12");
+        assert_eq!(parse("#12").unwrap().as_str(), "WEB12");
+        assert_eq!(parse(" web-3 ").unwrap().as_str(), "WEB3");
+        assert_eq!(parse("hla7").unwrap().as_str(), "HLA7");
+        assert!(parse("API7").is_err());
+        assert!(parse("WEBX7").is_err());
+        assert!(parse("").is_err());
+        assert!(TaskId::parse_with_prefixes("5", &[]).is_err());
+    }
+
+    #[test]
+    fn test_remap_references_is_simultaneous() {
+        let mut task = Task::new(TaskId::new(9), "Refs".to_string());
+        task.parent = Some(TaskId::new(3));
+        task.blocks = vec![TaskId::new(2)];
+        task.add_external_link(LinkKind::RelatesTo, "web".to_string(), TaskId::new(3));
+
+        let mapping = HashMap::from([
+            (TaskId::new(3), TaskId::new(2)),
+            (TaskId::new(2), TaskId::new(1)),
+        ]);
+        assert!(task.remap_references(&mapping));
+
+        assert_eq!(task.parent, Some(TaskId::new(2)));
+        assert_eq!(task.blocks, vec![TaskId::new(1)]);
+        assert_eq!(task.links[0].target, TaskId::new(3));
+        assert_eq!(TaskId::new(12).number(), Some(12));
+    }
+
+    #[test]
+    fn test_markdown_description_helpers() {
+        let mut task = Task::new(TaskId::new(1), "Docs".to_string());
+        task.set_description("Make the **install guide** clearer.\n\nMore later.".to_string())
+            .unwrap();
+        task.description_format = DescriptionFormat::Markdown;
+
+        assert_eq!(
+            task.description_summary(),
+            "Make the install guide clearer."
+        );
+        assert_eq!(task.description_word_count(), 7);
+        assert!(task.matches_query("install guide"));
+
+        let json = serde_json::to_string(&task).unwrap();
+        assert!(json.contains(r#""description_format":"markdown""#));
+    }
+
+    #[test]
+    fn test_task_id_custom_prefix() {
+        let id = TaskId::with_prefix("web", 7);
+        assert_eq!(id.as_str(), "WEB7");
+        assert_eq!(TaskId::from_str("web7").unwrap(), id);
     }
 
     #[test]
@@ -440,6 +1901,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_status_parsing() {
+        for spelling in ["in-progress", "in_progress", "In Progress", "INPROGRESS"] {
+            assert_eq!(
+                TaskStatus::from_str(spelling).unwrap(),
+                TaskStatus::InProgress
+            );
+        }
+        assert_eq!(TaskStatus::from_str(" Done ").unwrap(), TaskStatus::Done);
+        assert!(TaskStatus::from_str("doing").is_err());
+
+        for status in TaskStatus::ALL {
+            assert_eq!(TaskStatus::from_str(&status.to_string()).unwrap(), status);
+        }
+    }
+
+    #[test]
+    fn test_status_serde_aliases() {
+        for json in [
+            r#""inprogress""#,
+            r#""in_progress""#,
+            r#""in-progress""#,
+            r#""In Progress""#,
+        ] {
+            let status: TaskStatus = serde_json::from_str(json).unwrap();
+            assert_eq!(status, TaskStatus::InProgress);
+        }
+        assert_eq!(
+            serde_json::to_string(&TaskStatus::InProgress).unwrap(),
+            r#""inprogress""#
+        );
+    }
+
+    #[test]
+    fn test_status_path_to() {
+        assert_eq!(
+            TaskStatus::New.path_to(&TaskStatus::Review),
+            Some(vec![
+                TaskStatus::Open,
+                TaskStatus::InProgress,
+                TaskStatus::Review
+            ])
+        );
+        assert_eq!(
+            TaskStatus::Review.path_to(&TaskStatus::Review),
+            Some(vec![])
+        );
+        assert_eq!(TaskStatus::Closed.path_to(&TaskStatus::Open), None);
+    }
+
+    #[test]
+    fn test_completion_time() {
+        let mut task = Task::new(TaskId::new(1), "Finish".to_string());
+        assert!(task.completion_time().is_none());
+        task.transition_to(TaskStatus::Open, None).unwrap();
+        task.transition_to(TaskStatus::InProgress, None).unwrap();
+        task.transition_to(TaskStatus::Review, None).unwrap();
+        task.transition_to(TaskStatus::Done, None).unwrap();
+        assert_eq!(task.completion_time(), task.completed_at);
+        assert!(task.completed_at.is_some());
+
+        let done_at = task.completed_at;
+        task.transition_to(TaskStatus::Closed, None).unwrap();
+        assert_eq!(task.completed_at, done_at);
+        assert_eq!(task.completion_time(), done_at);
+
+        let mut rejected = Task::new(TaskId::new(2), "Won't do".to_string());
+        rejected.transition_to(TaskStatus::Open, None).unwrap();
+        rejected
+            .transition_to(TaskStatus::Closed, Some("Out of scope".to_string()))
+            .unwrap();
+        assert!(rejected.completion_time().is_none());
+    }
+
     #[test]
     fn test_status_transitions() {
         assert!(TaskStatus::New.can_transition_to(&TaskStatus::Open));
@@ -534,7 +2069,7 @@ mod tests {
         let mut task = Task::new(TaskId::new(1), "Original Title".to_string());
         assert_eq!(task.title, "Original Title");
 
-        task.set_title("Updated Title".to_string());
+        task.set_title("Updated Title".to_string()).unwrap();
         assert_eq!(task.title, "Updated Title");
     }
 
@@ -544,7 +2079,7 @@ mod tests {
         let initial_updated_at = task.updated_at;
 
         std::thread::sleep(std::time::Duration::from_millis(10));
-        task.set_title("New Title".to_string());
+        task.set_title("New Title".to_string()).unwrap();
 
         assert!(task.updated_at > initial_updated_at);
     }
@@ -679,5 +2214,330 @@ mod tests {
         assert!(task.start_date.is_none());
         assert!(task.end_date.is_none());
         assert_eq!(task.rank, 0);
+        assert!(task.priority.is_none());
+        assert!(task.tags.is_empty());
+        assert!(task.uuid.is_nil());
+    }
+
+    #[test]
+    fn test_criterion_ids_are_stable_after_removal() {
+        let mut task = Task::new(TaskId::new(1), "Test".to_string());
+        let first = task.add_acceptance_criterion("AC 1".to_string());
+        let second = task.add_acceptance_criterion("AC 2".to_string());
+        assert_eq!((first, second), (1, 2));
+
+        task.remove_acceptance_criterion("2").unwrap();
+        let third = task.add_acceptance_criterion("AC 3".to_string());
+        assert_eq!(third, 3);
+
+        task.remove_acceptance_criterion("1").unwrap();
+        assert_eq!(task.acceptance_criteria.len(), 1);
+        assert_eq!(task.acceptance_criteria[0].id, 3);
+    }
+
+    #[test]
+    fn test_update_and_toggle_criterion() {
+        let mut task = Task::new(TaskId::new(1), "Test".to_string());
+        let id = task.add_acceptance_criterion("Draft".to_string());
+
+        task.update_criterion(id, "Final".to_string()).unwrap();
+        assert_eq!(task.find_criterion(id).unwrap().description, "Final");
+
+        assert!(task.toggle_criterion(id).unwrap());
+        assert!(!task.toggle_criterion(id).unwrap());
+
+        assert!(task.update_criterion(99, "x".to_string()).is_err());
+        assert!(task.toggle_criterion(99).is_err());
+    }
+
+    #[test]
+    fn test_criterion_verification_fields() {
+        let mut task = Task::new(TaskId::new(1), "Test".to_string());
+        let id = task.add_acceptance_criterion("Login works".to_string());
+
+        task.assign_criterion(id, Some("alice".to_string()))
+            .unwrap();
+        task.set_criterion_notes(id, Some("Checked on staging".to_string()))
+            .unwrap();
+        task.set_criterion_evidence(id, Some("https://ci.example/run/1".to_string()))
+            .unwrap();
+
+        let ac = task.find_criterion(id).unwrap();
+        assert_eq!(ac.assignee.as_deref(), Some("alice"));
+        assert_eq!(ac.evidence_url.as_deref(), Some("https://ci.example/run/1"));
+        assert!(task.matches_query("staging"));
+
+        let json = serde_json::to_string(&task).unwrap();
+        let loaded: Task = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            loaded.find_criterion(id).unwrap().notes.as_deref(),
+            Some("Checked on staging")
+        );
+
+        task.assign_criterion(id, None).unwrap();
+        assert!(task.find_criterion(id).unwrap().assignee.is_none());
+        assert!(task.assign_criterion(42, None).is_err());
+    }
+
+    #[test]
+    fn test_clone_as_resets_state() {
+        let mut task = Task::new(TaskId::new(1), "Original".to_string());
+        task.set_description("Details".to_string()).unwrap();
+        task.add_tag("backend".to_string());
+        task.set_priority(Priority::High);
+        let ac = task.add_acceptance_criterion("Works".to_string());
+        task.toggle_criterion(ac).unwrap();
+        task.transition_to(TaskStatus::Open, None).unwrap();
+        task.set_start_date(Utc::now()).unwrap();
+
+        let copy = task.clone_as(TaskId::new(2), &CloneOptions::default());
+
+        assert_eq!(copy.title, "Original");
+        assert_eq!(copy.status, TaskStatus::New);
+        assert!(copy.start_date.is_none());
+        assert_eq!(copy.description.as_deref(), Some("Details"));
+        assert!(copy.has_tag("backend"));
+        assert_eq!(copy.priority, Some(Priority::High));
+        assert!(!copy.acceptance_criteria[0].completed);
+        assert_eq!(
+            copy.linked(LinkKind::ClonedFrom).collect::<Vec<_>>(),
+            vec![&task.id]
+        );
+
+        let bare = task.clone_as(
+            TaskId::new(3),
+            &CloneOptions {
+                title: Some("Bare".to_string()),
+                include_acceptance_criteria: false,
+                include_tags: false,
+                ..CloneOptions::default()
+            },
+        );
+        assert_eq!(bare.title, "Bare");
+        assert!(bare.acceptance_criteria.is_empty());
+        assert!(bare.tags.is_empty());
+    }
+
+    #[test]
+    fn test_split_off_criteria() {
+        let mut task = Task::new(TaskId::new(1), "Big".to_string());
+        task.add_acceptance_criterion("a".to_string());
+        let b = task.add_acceptance_criterion("b".to_string());
+        let c = task.add_acceptance_criterion("c".to_string());
+        task.toggle_criterion(c).unwrap();
+
+        let split = task.split_off_criteria(TaskId::new(2), &[b, c]).unwrap();
+
+        assert_eq!(task.acceptance_criteria.len(), 1);
+        assert_eq!(split.acceptance_criteria.len(), 2);
+        assert_eq!(split.acceptance_criteria[0].id, 1);
+        assert!(split.acceptance_criteria[1].completed);
+        assert_eq!(split.linked(LinkKind::SplitFrom).next(), Some(&task.id));
+        assert_eq!(task.linked(LinkKind::SplitInto).next(), Some(&split.id));
+
+        assert!(task.split_off_criteria(TaskId::new(3), &[]).is_err());
+        assert!(task.split_off_criteria(TaskId::new(3), &[b]).is_err());
+    }
+
+    #[test]
+    fn test_absorb_duplicate() {
+        let mut primary = Task::new(TaskId::new(1), "Login crash".to_string());
+        primary
+            .set_description("Crashes on login".to_string())
+            .unwrap();
+        primary.add_acceptance_criterion("No crash".to_string());
+        primary.set_priority(Priority::Low);
+
+        let mut duplicate = Task::new(TaskId::new(2), "Crash at login".to_string());
+        duplicate
+            .set_description("Stack trace attached".to_string())
+            .unwrap();
+        duplicate.add_acceptance_criterion("No crash".to_string());
+        duplicate.add_acceptance_criterion("Regression test".to_string());
+        duplicate.add_tag("bug".to_string());
+        duplicate.set_priority(Priority::High);
+        duplicate.add_block(TaskId::new(1));
+        duplicate.add_block(TaskId::new(5));
+
+        primary.absorb(&duplicate);
+
+        let description = primary.description.as_deref().unwrap();
+        assert!(description.contains("Crashes on login"));
+        assert!(description.contains("Merged from HLA2"));
+        assert_eq!(primary.acceptance_criteria.len(), 2);
+        assert_eq!(primary.acceptance_criteria[1].id, 2);
+        assert!(primary.has_tag("bug"));
+        assert_eq!(primary.priority, Some(Priority::High));
+        assert_eq!(primary.blocks, vec![TaskId::new(5)]);
+
+        duplicate.close_as_duplicate(primary.id.clone());
+        assert_eq!(duplicate.status, TaskStatus::Closed);
+        assert_eq!(
+            duplicate.linked(LinkKind::Duplicates).next(),
+            Some(&primary.id)
+        );
+    }
+
+    #[test]
+    fn test_replace_references() {
+        let mut task = Task::new(TaskId::new(3), "Other".to_string());
+        task.set_parent(TaskId::new(2));
+        task.add_block(TaskId::new(2));
+        task.add_block(TaskId::new(1));
+        task.add_link(LinkKind::RelatesTo, TaskId::new(2));
+
+        assert!(task.replace_references(&TaskId::new(2), &TaskId::new(1)));
+        assert_eq!(task.parent, Some(TaskId::new(1)));
+        assert_eq!(task.blocks, vec![TaskId::new(1)]);
+        assert_eq!(task.links[0].target, TaskId::new(1));
+        assert!(!task.replace_references(&TaskId::new(2), &TaskId::new(1)));
+    }
+
+    #[test]
+    fn test_move_criterion() {
+        let mut task = Task::new(TaskId::new(1), "Test".to_string());
+        for ac in ["a", "b", "c"] {
+            task.add_acceptance_criterion(ac.to_string());
+        }
+
+        task.move_criterion(2, 0).unwrap();
+        let order: Vec<&str> = task
+            .acceptance_criteria
+            .iter()
+            .map(|ac| ac.description.as_str())
+            .collect();
+        assert_eq!(order, vec!["c", "a", "b"]);
+
+        // IDs travel with their criteria
+        assert_eq!(task.acceptance_criteria[0].id, 3);
+
+        task.move_criterion(0, 2).unwrap();
+        assert_eq!(task.acceptance_criteria[2].description, "c");
+
+        assert!(task.move_criterion(0, 3).is_err());
+        assert!(task.move_criterion(5, 0).is_err());
+
+        let json = serde_json::to_string(&task).unwrap();
+        let loaded: Task = serde_json::from_str(&json).unwrap();
+        let ids: Vec<usize> = loaded.acceptance_criteria.iter().map(|ac| ac.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_acceptance_criteria_from_markdown() {
+        let mut task = Task::new(TaskId::new(1), "Test".to_string());
+        let count = task.set_acceptance_criteria_from_markdown(
+            "## Checklist\n- [ ] a\n- [x] b\n  * [X] c\nnot a task\n- [ ]   \n",
+        );
+
+        assert_eq!(count, 3);
+        assert!(!task.acceptance_criteria[0].completed);
+        assert!(task.acceptance_criteria[1].completed);
+        assert!(task.acceptance_criteria[1].completed_at.is_some());
+        assert_eq!(task.acceptance_criteria[2].description, "c");
+        assert_eq!(
+            task.acceptance_criteria_to_markdown(),
+            "- [ ] a\n- [x] b\n- [x] c"
+        );
+    }
+
+    #[test]
+    fn test_markdown_import_keeps_ids_of_matching_criteria() {
+        let mut task = Task::new(TaskId::new(1), "Test".to_string());
+        task.add_acceptance_criterion("keep".to_string());
+        task.add_acceptance_criterion("drop".to_string());
+
+        task.set_acceptance_criteria_from_markdown("- [ ] new\n- [x] keep");
+
+        assert_eq!(task.acceptance_criteria[0].id, 3);
+        assert_eq!(task.acceptance_criteria[1].id, 1);
+        assert!(task.acceptance_criteria[1].completed);
+
+        // Round-trip is stable
+        let markdown = task.acceptance_criteria_to_markdown();
+        let mut copy = task.clone();
+        copy.set_acceptance_criteria_from_markdown(&markdown);
+        assert_eq!(copy.acceptance_criteria_to_markdown(), markdown);
+    }
+
+    #[test]
+    fn test_checklists_on_task() {
+        let mut task = Task::new(TaskId::new(1), "Test".to_string());
+        task.add_acceptance_criterion("AC".to_string());
+        task.add_checklist("Deployment steps".to_string()).unwrap();
+        assert!(task.add_checklist("deployment STEPS".to_string()).is_err());
+
+        let checklist = task.checklist_mut("deployment steps").unwrap();
+        let id = checklist.add_item("Run migrations".to_string());
+        checklist.add_item("Flip flag".to_string());
+        checklist.toggle_item(id).unwrap();
+
+        assert_eq!(task.progress(), (1, 3));
+        assert!(task.matches_query("MIGRATIONS"));
+        assert!(task.matches_query("deployment"));
+        assert!(!task.matches_query("rollback"));
+
+        task.remove_checklist("Deployment steps").unwrap();
+        assert!(task.checklist_mut("Deployment steps").is_err());
+        assert_eq!(task.progress(), (0, 1));
+    }
+
+    #[test]
+    fn test_remove_criterion_by_description() {
+        let mut task = Task::new(TaskId::new(1), "Test".to_string());
+        task.add_acceptance_criterion("Keep".to_string());
+        task.add_acceptance_criterion("Drop".to_string());
+
+        task.remove_acceptance_criterion("Drop").unwrap();
+        assert_eq!(task.acceptance_criteria.len(), 1);
+        assert!(task.remove_acceptance_criterion("Missing").is_err());
+    }
+
+    #[test]
+    fn test_repair_duplicate_criterion_ids_from_old_files() {
+        let old_json = r#"{
+        "id": "HLA1",
+        "title": "Old Task",
+        "description": null,
+        "status": "new",
+        "acceptance_criteria": [
+            {"id": 1, "description": "a", "completed": false, "created_at": "2024-01-01T00:00:00Z", "completed_at": null},
+            {"id": 2, "description": "b", "completed": false, "created_at": "2024-01-01T00:00:00Z", "completed_at": null},
+            {"id": 2, "description": "c", "completed": false, "created_at": "2024-01-01T00:00:00Z", "completed_at": null}
+        ],
+        "created_at": "2024-01-01T00:00:00Z",
+        "updated_at": "2024-01-01T00:00:00Z",
+        "agent_assigned": false,
+        "rejection_reason": null
+    }"#;
+
+        let mut task: Task = serde_json::from_str(old_json).unwrap();
+        assert!(task.repair_criterion_ids());
+        let ids: Vec<usize> = task.acceptance_criteria.iter().map(|ac| ac.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert!(!task.repair_criterion_ids());
+        assert_eq!(task.add_acceptance_criterion("d".to_string()), 4);
+    }
+
+    #[test]
+    fn test_priority_ordering_and_parsing() {
+        assert!(Priority::Low < Priority::Medium);
+        assert!(Priority::High < Priority::Critical);
+        assert_eq!(Priority::from_str("HIGH").unwrap(), Priority::High);
+        assert!(Priority::from_str("urgent").is_err());
+    }
+
+    #[test]
+    fn test_tags_are_case_insensitive_and_deduplicated() {
+        let mut task = Task::new(TaskId::new(1), "Test".to_string());
+
+        assert!(task.add_tag("bug".to_string()));
+        assert!(!task.add_tag("BUG".to_string()));
+        assert!(task.has_tag("Bug"));
+        assert_eq!(task.tags.len(), 1);
+
+        assert!(task.remove_tag("bug"));
+        assert!(!task.remove_tag("bug"));
+        assert!(task.tags.is_empty());
     }
 }
@@ -0,0 +1,182 @@
+use crate::domain::task::{Priority, Task, TaskStatus};
+use serde::{Deserialize, Serialize};
+
+/// A kind of task configured on the board, such as bug, feature or spike
+///
+/// Each type can pre-fill new tasks and trim the workflow: statuses in
+/// `skip_statuses` are never entered, and transitions may pass straight
+/// through them (a spike can go from In Progress to Done without Review).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskType {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Description given to new tasks of this type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Tags added to new tasks of this type
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub default_tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_priority: Option<Priority>,
+    /// Statuses this type bypasses
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skip_statuses: Vec<TaskStatus>,
+    /// Tasks of this type may carry a severity (bugs)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub severity: bool,
+}
+
+impl TaskType {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: None,
+            template: None,
+            default_tags: Vec::new(),
+            default_priority: None,
+            skip_statuses: Vec::new(),
+            severity: false,
+        }
+    }
+
+    pub fn with_template(mut self, template: &str) -> Self {
+        self.template = Some(template.to_string());
+        self
+    }
+
+    pub fn skipping(mut self, status: TaskStatus) -> Self {
+        self.skip_statuses.push(status);
+        self
+    }
+
+    /// Bug, feature, chore and spike, a starting point for new boards
+    pub fn defaults() -> Vec<TaskType> {
+        let mut bug =
+            Self::new("bug").with_template("## Steps to reproduce\n\n## Expected\n\n## Actual\n");
+        bug.default_priority = Some(Priority::High);
+        bug.severity = true;
+        vec![
+            bug,
+            Self::new("feature"),
+            Self::new("chore").skipping(TaskStatus::Review),
+            Self::new("spike").skipping(TaskStatus::Review),
+        ]
+    }
+
+    /// Checks whether a task of this type may move from `from` to `target`
+    ///
+    /// The move is allowed if the target is not skipped and is reachable
+    /// directly or through skipped statuses only.
+    pub fn can_transition(&self, from: &TaskStatus, target: &TaskStatus) -> bool {
+        if self.skip_statuses.contains(target) {
+            return false;
+        }
+        let mut seen = vec![from.clone()];
+        let mut frontier = vec![from.clone()];
+        while let Some(status) = frontier.pop() {
+            for next in TaskStatus::ALL.iter() {
+                if !status.can_transition_to(next) || seen.contains(next) {
+                    continue;
+                }
+                if next == target {
+                    return true;
+                }
+                seen.push(next.clone());
+                if self.skip_statuses.contains(next) {
+                    frontier.push(next.clone());
+                }
+            }
+        }
+        from == target
+    }
+
+    /// Fills in the template, tags and priority on a freshly created task
+    pub fn apply_defaults(&self, task: &mut Task) {
+        task.task_type = Some(self.name.clone());
+        if task.description.is_none() {
+            task.description = self.template.clone();
+        }
+        for tag in &self.default_tags {
+            task.add_tag(tag.clone());
+        }
+        if task.priority.is_none() {
+            task.priority = self.default_priority;
+        }
+    }
+}
+
+impl Task {
+    /// Checks whether the task is of the named type (case-insensitive)
+    pub fn is_type(&self, name: &str) -> bool {
+        self.task_type
+            .as_deref()
+            .is_some_and(|t| t.eq_ignore_ascii_case(name))
+    }
+
+    /// Sets or clears the task's type
+    pub fn set_task_type(&mut self, task_type: Option<String>) {
+        self.task_type = task_type;
+        self.updated_at = chrono::Utc::now();
+    }
+
+    /// Moves the task to `new_status` following its type's workflow
+    ///
+    /// Without a type this is the same as `transition_to`.
+    pub fn transition_as(
+        &mut self,
+        task_type: Option<&TaskType>,
+        new_status: TaskStatus,
+        rejection_reason: Option<String>,
+    ) -> Result<(), crate::error::HlaviError> {
+        let Some(task_type) = task_type else {
+            return self.transition_to(new_status, rejection_reason);
+        };
+        if !task_type.can_transition(&self.status, &new_status) {
+            return Err(crate::error::HlaviError::InvalidStatusTransition {
+                from: self.status.to_string(),
+                to: new_status.to_string(),
+            });
+        }
+        self.enter_status(new_status, rejection_reason);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::task::TaskId;
+
+    #[test]
+    fn test_workflow_skips_statuses() {
+        let spike = TaskType::new("spike").skipping(TaskStatus::Review);
+        assert!(spike.can_transition(&TaskStatus::InProgress, &TaskStatus::Done));
+        assert!(spike.can_transition(&TaskStatus::InProgress, &TaskStatus::Pending));
+        assert!(!spike.can_transition(&TaskStatus::InProgress, &TaskStatus::Review));
+        assert!(!spike.can_transition(&TaskStatus::New, &TaskStatus::Done));
+
+        let mut task = Task::new(TaskId::new(1), "Try a parser".to_string());
+        spike.apply_defaults(&mut task);
+        for status in [TaskStatus::Open, TaskStatus::InProgress, TaskStatus::Done] {
+            task.transition_as(Some(&spike), status, None).unwrap();
+        }
+        assert!(task.completed_at.is_some());
+        assert!(task.transition_as(None, TaskStatus::Review, None).is_err());
+    }
+
+    #[test]
+    fn test_apply_defaults() {
+        let bug = &TaskType::defaults()[0];
+        let mut task = Task::new(TaskId::new(1), "Crash".to_string());
+        bug.apply_defaults(&mut task);
+
+        assert!(task.is_type("Bug"));
+        assert_eq!(task.priority, Some(Priority::High));
+        assert!(task
+            .description
+            .as_deref()
+            .unwrap()
+            .contains("Steps to reproduce"));
+    }
+}
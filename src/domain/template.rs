@@ -0,0 +1,193 @@
+use crate::{
+    domain::task::{Priority, Task, TaskId},
+    error::{HlaviError, Result},
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Templates shipped with the crate, by name
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[(
+    "rust-service",
+    include_str!("../../templates/rust-service.toml"),
+)];
+
+/// A bundle of starter tasks used to seed a new project
+///
+/// Templates are TOML files. Tasks with a `key` can be referenced as the
+/// `parent` of later tasks, which is how epics are expressed:
+///
+/// ```toml
+/// name = "example"
+///
+/// [[tasks]]
+/// key = "setup"
+/// title = "Project setup"
+///
+/// [[tasks]]
+/// parent = "setup"
+/// title = "Set up CI"
+/// priority = "high"
+/// acceptance_criteria = ["Tests run on every push"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectTemplate {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tasks: Vec<TemplateTask>,
+}
+
+/// A task in a template
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateTask {
+    /// Name other template tasks use to refer to this one as their parent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Key of an earlier task that is this task's parent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub acceptance_criteria: Vec<String>,
+}
+
+impl ProjectTemplate {
+    /// Parses and validates a template document
+    pub fn from_toml(contents: &str) -> Result<Self> {
+        let template: Self = toml::from_str(contents)
+            .map_err(|e| HlaviError::ConfigError(format!("Invalid template: {}", e)))?;
+        template.validate()?;
+        Ok(template)
+    }
+
+    /// Reads a template from a user-supplied file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_toml(&std::fs::read_to_string(path)?)
+    }
+
+    /// Looks up a template shipped with the crate
+    pub fn builtin(name: &str) -> Option<Self> {
+        BUILTIN_TEMPLATES
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|(_, contents)| Self::from_toml(contents).expect("built-in templates are valid"))
+    }
+
+    /// Names of the templates shipped with the crate
+    pub fn builtin_names() -> impl Iterator<Item = &'static str> {
+        BUILTIN_TEMPLATES.iter().map(|(name, _)| *name)
+    }
+
+    /// Checks that keys are unique and parents refer to earlier tasks
+    pub fn validate(&self) -> Result<()> {
+        let mut keys: Vec<&str> = Vec::new();
+        for task in &self.tasks {
+            if task.title.trim().is_empty() {
+                return Err(HlaviError::ConfigError(format!(
+                    "Template '{}' has a task without a title",
+                    self.name
+                )));
+            }
+            if let Some(parent) = &task.parent {
+                if !keys.contains(&parent.as_str()) {
+                    return Err(HlaviError::ConfigError(format!(
+                        "Task '{}' refers to unknown or later parent '{}'",
+                        task.title, parent
+                    )));
+                }
+            }
+            if let Some(key) = &task.key {
+                if keys.contains(&key.as_str()) {
+                    return Err(HlaviError::ConfigError(format!(
+                        "Duplicate template key '{}'",
+                        key
+                    )));
+                }
+                keys.push(key);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TemplateTask {
+    /// Builds the task under the given ID; `parent` is the already-created
+    /// task for this task's `parent` key
+    pub fn instantiate(&self, id: TaskId, parent: Option<&TaskId>) -> Task {
+        let mut task = Task::new(id, self.title.clone());
+        task.description = self.description.clone();
+        task.priority = self.priority;
+        for tag in &self.tags {
+            task.add_tag(tag.clone());
+        }
+        for criterion in &self.acceptance_criteria {
+            task.add_acceptance_criterion(criterion.clone());
+        }
+        task.parent = parent.cloned();
+        task
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_templates_parse() {
+        for name in ProjectTemplate::builtin_names() {
+            let template = ProjectTemplate::builtin(name).unwrap();
+            assert!(!template.tasks.is_empty());
+        }
+        assert!(ProjectTemplate::builtin("missing").is_none());
+    }
+
+    #[test]
+    fn test_template_validation() {
+        let unknown_parent = r#"
+            name = "bad"
+            [[tasks]]
+            parent = "epic"
+            title = "Orphan"
+        "#;
+        assert!(ProjectTemplate::from_toml(unknown_parent).is_err());
+
+        let duplicate_key = r#"
+            name = "bad"
+            [[tasks]]
+            key = "a"
+            title = "One"
+            [[tasks]]
+            key = "a"
+            title = "Two"
+        "#;
+        assert!(ProjectTemplate::from_toml(duplicate_key).is_err());
+    }
+
+    #[test]
+    fn test_instantiate() {
+        let template = ProjectTemplate::from_toml(
+            r#"
+            name = "t"
+            [[tasks]]
+            title = "Set up CI"
+            priority = "high"
+            tags = ["ci"]
+            acceptance_criteria = ["Runs on push"]
+            "#,
+        )
+        .unwrap();
+
+        let task = template.tasks[0].instantiate(TaskId::new(2), Some(&TaskId::new(1)));
+        assert_eq!(task.priority, Some(Priority::High));
+        assert!(task.has_tag("ci"));
+        assert_eq!(task.acceptance_criteria[0].description, "Runs on push");
+        assert_eq!(task.parent, Some(TaskId::new(1)));
+    }
+}
@@ -0,0 +1,153 @@
+use crate::error::{HlaviError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A validated task title: trimmed, non-empty, single-line and at most
+/// `Title::MAX_LEN` characters
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Title(String);
+
+impl Title {
+    pub const MAX_LEN: usize = 200;
+
+    /// Turns line breaks and tabs into spaces, strips other control
+    /// characters and surrounding whitespace, then checks what is left
+    pub fn new(title: &str) -> Result<Self> {
+        let cleaned: String = title
+            .chars()
+            .filter_map(|c| match c {
+                '\n' | '\r' | '\t' => Some(' '),
+                c if c.is_control() => None,
+                c => Some(c),
+            })
+            .collect();
+        let cleaned = cleaned.trim();
+        if cleaned.is_empty() {
+            return Err(HlaviError::Validation("Title cannot be empty".to_string()));
+        }
+        let len = cleaned.chars().count();
+        if len > Self::MAX_LEN {
+            return Err(HlaviError::Validation(format!(
+                "Title is {} characters long; the limit is {}",
+                len,
+                Self::MAX_LEN
+            )));
+        }
+        Ok(Self(cleaned.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+/// A validated task description of at most `Description::MAX_LEN`
+/// characters
+///
+/// Control characters other than newlines and tabs are stripped, and line
+/// endings are normalized to `\n`; other whitespace is kept because it can be
+/// significant in Markdown.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Description(String);
+
+impl Description {
+    pub const MAX_LEN: usize = 100_000;
+
+    pub fn new(description: &str) -> Result<Self> {
+        let cleaned: String = description
+            .replace("\r\n", "\n")
+            .chars()
+            .filter(|c| !c.is_control() || matches!(c, '\n' | '\t'))
+            .collect();
+        let len = cleaned.chars().count();
+        if len > Self::MAX_LEN {
+            return Err(HlaviError::Validation(format!(
+                "Description is {} characters long; the limit is {}",
+                len,
+                Self::MAX_LEN
+            )));
+        }
+        Ok(Self(cleaned))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl TryFrom<String> for Title {
+    type Error = HlaviError;
+
+    fn try_from(value: String) -> Result<Self> {
+        Self::new(&value)
+    }
+}
+
+impl TryFrom<String> for Description {
+    type Error = HlaviError;
+
+    fn try_from(value: String) -> Result<Self> {
+        Self::new(&value)
+    }
+}
+
+impl From<Title> for String {
+    fn from(title: Title) -> Self {
+        title.0
+    }
+}
+
+impl From<Description> for String {
+    fn from(description: Description) -> Self {
+        description.0
+    }
+}
+
+impl fmt::Display for Title {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl fmt::Display for Description {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_validation() {
+        assert_eq!(
+            Title::new("  Fix\u{7} the\nparser ").unwrap().as_str(),
+            "Fix the parser"
+        );
+        assert!(matches!(
+            Title::new(" \t\n "),
+            Err(HlaviError::Validation(_))
+        ));
+        assert!(Title::new(&"x".repeat(Title::MAX_LEN)).is_ok());
+        assert!(Title::new(&"x".repeat(Title::MAX_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_description_validation() {
+        let description = Description::new("Steps:\r\n\t1. Run\u{0}\n").unwrap();
+        assert_eq!(description.as_str(), "Steps:\n\t1. Run\n");
+        assert_eq!(Description::new("").unwrap().as_str(), "");
+        assert!(Description::new(&"x".repeat(Description::MAX_LEN + 1)).is_err());
+    }
+}
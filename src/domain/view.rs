@@ -0,0 +1,241 @@
+use crate::domain::{
+    automation::RuleCondition,
+    privacy::same_actor,
+    sorting::{sort_tasks, SortField, SortOrder},
+    task::{Task, TaskStatus},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A named, shared filter over the board's tasks, such as "My open bugs"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedView {
+    pub name: String,
+    #[serde(default)]
+    pub filter: ViewFilter,
+    /// Falls back to the project's default sort when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<ViewSort>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_by: Option<GroupBy>,
+    /// People notified when a task starts or stops matching the filter
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subscribers: Vec<String>,
+}
+
+/// Which tasks a view shows; an empty filter shows everything
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewFilter {
+    /// Task must be in one of these statuses (any status if empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub statuses: Vec<TaskStatus>,
+    /// Every condition must hold
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<RuleCondition>,
+    /// Free-text query, matched like `Task::matches_query`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewSort {
+    pub field: SortField,
+    pub order: SortOrder,
+}
+
+/// How a view buckets its tasks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    Status,
+    Priority,
+    /// A task appears under each of its tags
+    Tag,
+    Parent,
+    Type,
+    Severity,
+    /// A task appears under each of its components
+    Component,
+    Environment,
+}
+
+/// Group label for tasks without a value for the grouped field
+pub const UNGROUPED: &str = "None";
+
+impl SavedView {
+    pub fn new(name: &str, filter: ViewFilter) -> Self {
+        Self {
+            name: name.to_string(),
+            filter,
+            sort: None,
+            group_by: None,
+            subscribers: Vec::new(),
+        }
+    }
+
+    pub fn sorted_by(mut self, field: SortField, order: SortOrder) -> Self {
+        self.sort = Some(ViewSort { field, order });
+        self
+    }
+
+    pub fn grouped_by(mut self, group_by: GroupBy) -> Self {
+        self.group_by = Some(group_by);
+        self
+    }
+
+    pub fn is_subscribed(&self, actor: &str) -> bool {
+        self.subscribers.iter().any(|s| same_actor(s, actor))
+    }
+
+    /// Adds `actor` to the subscribers; returns false if already there
+    pub fn subscribe(&mut self, actor: &str) -> bool {
+        if self.is_subscribed(actor) {
+            return false;
+        }
+        self.subscribers.push(actor.trim().to_string());
+        true
+    }
+
+    /// Removes `actor` from the subscribers; returns false if not there
+    pub fn unsubscribe(&mut self, actor: &str) -> bool {
+        let before = self.subscribers.len();
+        self.subscribers.retain(|s| !same_actor(s, actor));
+        self.subscribers.len() < before
+    }
+
+    /// Filters and sorts `tasks` according to the view
+    pub fn apply(&self, tasks: Vec<Task>) -> Vec<Task> {
+        let mut tasks: Vec<Task> = tasks
+            .into_iter()
+            .filter(|task| self.filter.matches(task))
+            .collect();
+        if let Some(sort) = self.sort {
+            sort_tasks(&mut tasks, sort.field, sort.order);
+        }
+        tasks
+    }
+
+    /// Buckets already-filtered tasks by the view's `group_by`, keeping their
+    /// order within each group
+    ///
+    /// Without a `group_by` everything lands in a single group named after
+    /// the view.
+    pub fn group(&self, tasks: Vec<Task>) -> BTreeMap<String, Vec<Task>> {
+        let mut groups: BTreeMap<String, Vec<Task>> = BTreeMap::new();
+        let Some(group_by) = self.group_by else {
+            groups.insert(self.name.clone(), tasks);
+            return groups;
+        };
+
+        for task in tasks {
+            for key in group_by.keys(&task) {
+                groups.entry(key).or_default().push(task.clone());
+            }
+        }
+        groups
+    }
+}
+
+impl ViewFilter {
+    pub fn matches(&self, task: &Task) -> bool {
+        (self.statuses.is_empty() || self.statuses.contains(&task.status))
+            && self.conditions.iter().all(|c| c.holds(task))
+            && self
+                .query
+                .as_deref()
+                .map_or(true, |query| task.matches_query(query))
+    }
+}
+
+impl GroupBy {
+    fn keys(&self, task: &Task) -> Vec<String> {
+        let keys = match self {
+            Self::Status => vec![task.status.to_string()],
+            Self::Priority => task.priority.iter().map(|p| p.to_string()).collect(),
+            Self::Tag => task.tags.clone(),
+            Self::Parent => task.parent.iter().map(|p| p.to_string()).collect(),
+            Self::Type => task.task_type.iter().cloned().collect(),
+            Self::Severity => task.severity.iter().map(|s| s.to_string()).collect(),
+            Self::Component => task.components.clone(),
+            Self::Environment => task.environment.iter().cloned().collect(),
+        };
+        if keys.is_empty() {
+            vec![UNGROUPED.to_string()]
+        } else {
+            keys
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::task::{Priority, TaskId};
+
+    fn tasks() -> Vec<Task> {
+        let mut bug = Task::new(TaskId::new(1), "Crash on save".to_string());
+        bug.add_tag("bug".to_string());
+        bug.set_priority(Priority::High);
+
+        let mut review = Task::new(TaskId::new(2), "Docs".to_string());
+        review.status = TaskStatus::Review;
+
+        let mut closed_bug = Task::new(TaskId::new(3), "Old crash".to_string());
+        closed_bug.add_tag("bug".to_string());
+        closed_bug.status = TaskStatus::Closed;
+
+        vec![bug, review, closed_bug]
+    }
+
+    #[test]
+    fn test_filter_and_sort() {
+        let open_bugs = SavedView::new(
+            "Open bugs",
+            ViewFilter {
+                statuses: vec![TaskStatus::New, TaskStatus::Open],
+                conditions: vec![RuleCondition::HasTag {
+                    tag: "bug".to_string(),
+                }],
+                query: None,
+            },
+        );
+        let ids: Vec<_> = open_bugs
+            .apply(tasks())
+            .into_iter()
+            .map(|t| t.id.to_string())
+            .collect();
+        assert_eq!(ids, ["HLA1"]);
+
+        let all = SavedView::new("All", ViewFilter::default())
+            .sorted_by(SortField::Id, SortOrder::Descending);
+        let ids: Vec<_> = all
+            .apply(tasks())
+            .into_iter()
+            .map(|t| t.id.to_string())
+            .collect();
+        assert_eq!(ids, ["HLA3", "HLA2", "HLA1"]);
+    }
+
+    #[test]
+    fn test_group_by() {
+        let view = SavedView::new("All", ViewFilter::default()).grouped_by(GroupBy::Priority);
+        let groups = view.group(tasks());
+        assert_eq!(groups["High"].len(), 1);
+        assert_eq!(groups[UNGROUPED].len(), 2);
+
+        let view = SavedView::new("All", ViewFilter::default());
+        assert_eq!(view.group(tasks())["All"].len(), 3);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let view = SavedView::new("Review queue", ViewFilter::default())
+            .sorted_by(SortField::AcProgress, SortOrder::Ascending)
+            .grouped_by(GroupBy::Tag);
+        let json = serde_json::to_string(&view).unwrap();
+        assert!(json.contains("\"ac-progress\""));
+        assert!(json.contains("\"asc\""));
+        let loaded: SavedView = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, view);
+    }
+}
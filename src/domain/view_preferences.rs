@@ -0,0 +1,131 @@
+use crate::domain::board::{BoardConfig, Column};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// One person's overrides of a column's board-wide visibility
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnPreference {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collapsed: Option<bool>,
+}
+
+impl ColumnPreference {
+    fn is_empty(&self) -> bool {
+        self.hidden.is_none() && self.collapsed.is_none()
+    }
+}
+
+/// How one person likes to see the board
+///
+/// Like read state, preferences are personal and kept in local storage, not
+/// in the shared board config. Columns without a preference follow their
+/// `hidden` and `collapsed_by_default` flags.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewPreferences {
+    pub actor: String,
+    /// Overrides keyed by column name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub columns: BTreeMap<String, ColumnPreference>,
+}
+
+impl ViewPreferences {
+    pub fn new(actor: &str) -> Self {
+        Self {
+            actor: actor.to_string(),
+            columns: BTreeMap::new(),
+        }
+    }
+
+    /// Overrides whether the column is hidden; `None` goes back to the
+    /// board's setting
+    pub fn set_hidden(&mut self, column: &str, hidden: Option<bool>) {
+        self.update(column, |pref| pref.hidden = hidden);
+    }
+
+    /// Overrides whether the column is collapsed; `None` goes back to the
+    /// board's setting
+    pub fn set_collapsed(&mut self, column: &str, collapsed: Option<bool>) {
+        self.update(column, |pref| pref.collapsed = collapsed);
+    }
+
+    fn update(&mut self, column: &str, change: impl FnOnce(&mut ColumnPreference)) {
+        let pref = self.columns.entry(column.to_string()).or_default();
+        change(pref);
+        if pref.is_empty() {
+            self.columns.remove(column);
+        }
+    }
+
+    pub fn is_hidden(&self, column: &Column) -> bool {
+        self.columns
+            .get(&column.name)
+            .and_then(|pref| pref.hidden)
+            .unwrap_or(column.hidden)
+    }
+
+    pub fn is_collapsed(&self, column: &Column) -> bool {
+        self.columns
+            .get(&column.name)
+            .and_then(|pref| pref.collapsed)
+            .unwrap_or(column.collapsed_by_default)
+    }
+
+    /// The board's columns this person sees, in board order
+    pub fn visible_columns<'a>(&self, config: &'a BoardConfig) -> Vec<&'a Column> {
+        config
+            .columns
+            .iter()
+            .filter(|column| !self.is_hidden(column))
+            .collect()
+    }
+
+    /// Drops preferences for columns the board no longer has
+    pub fn retain(&mut self, config: &BoardConfig) {
+        self.columns
+            .retain(|name, _| config.columns.iter().any(|c| &c.name == name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TaskStatus;
+
+    #[test]
+    fn test_preferences_override_board_flags() {
+        let config = BoardConfig {
+            columns: vec![
+                Column::new("Open".to_string(), TaskStatus::Open),
+                Column::new("Done".to_string(), TaskStatus::Done).collapsed(),
+                Column::new("Closed".to_string(), TaskStatus::Closed).hidden(),
+            ],
+            ..BoardConfig::default()
+        };
+        let mut prefs = ViewPreferences::new("ana");
+        let names = |prefs: &ViewPreferences| -> Vec<String> {
+            prefs
+                .visible_columns(&config)
+                .iter()
+                .map(|c| c.name.clone())
+                .collect()
+        };
+        assert_eq!(names(&prefs), vec!["Open", "Done"]);
+        assert!(prefs.is_collapsed(&config.columns[1]));
+
+        prefs.set_hidden("Closed", Some(false));
+        prefs.set_hidden("Open", Some(true));
+        prefs.set_collapsed("Done", Some(false));
+        assert_eq!(names(&prefs), vec!["Done", "Closed"]);
+        assert!(!prefs.is_collapsed(&config.columns[1]));
+
+        prefs.set_collapsed("Done", None);
+        assert!(!prefs.columns.contains_key("Done"));
+        prefs
+            .columns
+            .insert("Gone".to_string(), ColumnPreference::default());
+        prefs.retain(&config);
+        assert_eq!(prefs.columns.len(), 2);
+    }
+}
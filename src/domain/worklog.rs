@@ -0,0 +1,71 @@
+use crate::{domain::task::Task, error::HlaviError};
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Time someone spent on a task on a given day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkLogEntry {
+    pub actor: String,
+    /// Day the work was done (not when it was logged)
+    pub date: NaiveDate,
+    pub minutes: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl WorkLogEntry {
+    pub fn new(actor: &str, date: NaiveDate, minutes: u32) -> Self {
+        Self {
+            actor: actor.to_string(),
+            date,
+            minutes,
+            note: None,
+        }
+    }
+
+    pub fn with_note(mut self, note: &str) -> Self {
+        self.note = Some(note.to_string());
+        self
+    }
+}
+
+impl Task {
+    /// Records time spent on the task
+    pub fn log_work(&mut self, entry: WorkLogEntry) -> Result<(), HlaviError> {
+        if entry.minutes == 0 {
+            return Err(HlaviError::Other(
+                "Logged time must be at least one minute".to_string(),
+            ));
+        }
+        if entry.actor.trim().is_empty() {
+            return Err(HlaviError::Other("Logged time needs an actor".to_string()));
+        }
+        self.work_log.push(entry);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Total minutes logged by everyone
+    pub fn logged_minutes(&self) -> u32 {
+        self.work_log.iter().map(|e| e.minutes).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::task::TaskId;
+
+    #[test]
+    fn test_log_work() {
+        let mut task = Task::new(TaskId::new(1), "Invoice me".to_string());
+        let day = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        task.log_work(WorkLogEntry::new("dana", day, 90).with_note("setup"))
+            .unwrap();
+        task.log_work(WorkLogEntry::new("dana", day, 30)).unwrap();
+        assert_eq!(task.logged_minutes(), 120);
+
+        assert!(task.log_work(WorkLogEntry::new("dana", day, 0)).is_err());
+        assert!(task.log_work(WorkLogEntry::new(" ", day, 5)).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::domain::{board::RequiredField, merge::MergeConflict};
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, HlaviError>;
@@ -22,6 +23,7 @@ pub enum HlaviError {
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
+    #[cfg(feature = "storage")]
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
 
@@ -31,12 +33,81 @@ pub enum HlaviError {
     #[error("Acceptance criteria not found")]
     AcceptanceCriteriaNotFound,
 
+    #[error("Checklist not found: {0}")]
+    ChecklistNotFound(String),
+
+    #[error("Checklist item not found: {0}")]
+    ChecklistItemNotFound(usize),
+
+    #[error("View not found: {0}")]
+    ViewNotFound(String),
+
     #[error("Invalid date range: start date {start} must be before or equal to end date {end}")]
     InvalidDateRange { start: String, end: String },
 
     #[error("Project not initialized. Run 'hlavi init' first.")]
     ProjectNotInitialized,
 
+    #[error("Merge conflict on {task_id}: {} conflicting field(s)", conflicts.len())]
+    MergeConflict {
+        task_id: String,
+        conflicts: Vec<MergeConflict>,
+    },
+
+    #[error("Script error: {0}")]
+    ScriptError(String),
+
+    #[error("Invalid value: {0}")]
+    Validation(String),
+
+    #[error("{task_id} cannot enter {column} without: {}", list_fields(fields))]
+    MissingRequiredFields {
+        task_id: String,
+        column: String,
+        fields: Vec<RequiredField>,
+    },
+
+    #[error("Task {task_id} is locked by {actor} until {until}")]
+    TaskLocked {
+        task_id: String,
+        actor: String,
+        until: String,
+    },
+
+    #[error("Quota exceeded: {actor} may {operation} at most {limit} task(s) per {window_minutes} minute(s)")]
+    QuotaExceeded {
+        actor: String,
+        operation: String,
+        limit: u32,
+        window_minutes: u32,
+    },
+
+    #[error("Unsupported format version {found}: this build reads versions {oldest} to {newest}")]
+    UnsupportedFormatVersion {
+        found: u32,
+        oldest: u32,
+        newest: u32,
+    },
+
+    #[error("{task_id} appears to contain a {kind} in {field}; remove it before saving")]
+    SecretDetected {
+        task_id: String,
+        field: String,
+        kind: String,
+    },
+
+    /// `position` is the character offset of the problem in the query
+    #[error("Invalid query at position {position}: {message}")]
+    InvalidQuery { position: usize, message: String },
+
     #[error("{0}")]
     Other(String),
 }
+
+fn list_fields(fields: &[RequiredField]) -> String {
+    fields
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
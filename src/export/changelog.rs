@@ -0,0 +1,222 @@
+//! Release notes built from the tasks finished under an epic or milestone.
+
+use crate::{
+    domain::{Task, TaskId},
+    error::{HlaviError, Result},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Which finished tasks a changelog covers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangelogScope {
+    /// Everything under an epic, at any depth
+    Epic(TaskId),
+    /// Everything carrying a milestone tag, such as `v2.1`
+    Milestone(String),
+}
+
+/// A heading tasks are sorted under, by type or tag
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangelogCategory {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub task_types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl ChangelogCategory {
+    pub fn new(name: &str, task_types: &[&str], tags: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            task_types: task_types.iter().map(|t| t.to_string()).collect(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    fn matches(&self, task: &Task) -> bool {
+        self.task_types.iter().any(|t| task.is_type(t)) || self.tags.iter().any(|t| task.has_tag(t))
+    }
+}
+
+/// How a changelog is laid out
+///
+/// Templates are Markdown with placeholders. `section` takes `{title}`;
+/// `category` takes `{name}`; `item` takes `{id}`, `{title}`, `{type}`,
+/// `{tags}` and `{assignee}`. Tasks go under the first category they match,
+/// or under `other`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChangelogTemplate {
+    pub section: String,
+    pub category: String,
+    pub item: String,
+    pub categories: Vec<ChangelogCategory>,
+    pub other: String,
+}
+
+impl Default for ChangelogTemplate {
+    fn default() -> Self {
+        Self {
+            section: "## {title}".to_string(),
+            category: "### {name}".to_string(),
+            item: "- {title} ({id})".to_string(),
+            categories: vec![
+                ChangelogCategory::new("Features", &["feature"], &["feature", "enhancement"]),
+                ChangelogCategory::new("Bug fixes", &["bug"], &["bug", "fix"]),
+                ChangelogCategory::new("Maintenance", &["chore"], &["chore"]),
+            ],
+            other: "Other changes".to_string(),
+        }
+    }
+}
+
+fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |text, (key, value)| {
+            text.replace(&format!("{{{}}}", key), value)
+        })
+}
+
+/// Renders a changelog section for the tasks completed within `scope`
+///
+/// Tasks are listed in the order they were completed. An epic that is not
+/// among `tasks` fails with `HlaviError::TaskNotFound`.
+pub fn changelog(
+    tasks: &[Task],
+    scope: &ChangelogScope,
+    template: &ChangelogTemplate,
+) -> Result<String> {
+    let (title, mut included): (&str, Vec<&Task>) = match scope {
+        ChangelogScope::Epic(epic) => {
+            let epic_task = tasks
+                .iter()
+                .find(|t| &t.id == epic)
+                .ok_or_else(|| HlaviError::TaskNotFound(epic.to_string()))?;
+            let under_epic = |task: &Task| {
+                let mut parent = task.parent.as_ref();
+                // Bounded so a parent cycle cannot loop forever
+                for _ in 0..tasks.len() {
+                    match parent {
+                        Some(id) if id == epic => return true,
+                        Some(id) => {
+                            parent = tasks
+                                .iter()
+                                .find(|t| &t.id == id)
+                                .and_then(|t| t.parent.as_ref())
+                        }
+                        None => return false,
+                    }
+                }
+                false
+            };
+            let under: Vec<&Task> = tasks.iter().filter(|t| under_epic(t)).collect();
+            (&epic_task.title, under)
+        }
+        ChangelogScope::Milestone(tag) => (tag, tasks.iter().filter(|t| t.has_tag(tag)).collect()),
+    };
+    included.retain(|t| t.is_active() && t.completion_time().is_some());
+    included.sort_by_key(|t| (t.completion_time(), t.id.number()));
+
+    let mut groups: Vec<(&str, Vec<&Task>)> = template
+        .categories
+        .iter()
+        .map(|c| (c.name.as_str(), Vec::new()))
+        .chain(std::iter::once((template.other.as_str(), Vec::new())))
+        .collect();
+    for task in included {
+        let index = template
+            .categories
+            .iter()
+            .position(|c| c.matches(task))
+            .unwrap_or(template.categories.len());
+        groups[index].1.push(task);
+    }
+
+    let mut out = fill(&template.section, &[("title", title)]);
+    out.push('\n');
+    for (name, tasks) in groups.iter().filter(|(_, tasks)| !tasks.is_empty()) {
+        let _ = writeln!(out, "\n{}\n", fill(&template.category, &[("name", name)]));
+        for task in tasks {
+            let id = task.id.to_string();
+            let tags = task.tags.join(", ");
+            let line = fill(
+                &template.item,
+                &[
+                    ("id", &id),
+                    ("title", &task.title),
+                    ("type", task.task_type.as_deref().unwrap_or_default()),
+                    ("tags", &tags),
+                    ("assignee", task.assignee.as_deref().unwrap_or_default()),
+                ],
+            );
+            let _ = writeln!(out, "{}", line);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TaskStatus;
+
+    fn done(number: u32, title: &str, parent: Option<u32>) -> Task {
+        let mut task = Task::new(TaskId::new(number), title.to_string());
+        task.parent = parent.map(TaskId::new);
+        task.enter_status(TaskStatus::Done, None);
+        task
+    }
+
+    #[test]
+    fn test_epic_changelog() {
+        let epic = Task::new(TaskId::new(1), "Payments v2".to_string());
+        let mut feature = done(2, "Card form", Some(1));
+        feature.task_type = Some("feature".to_string());
+        let mut fix = done(3, "Rounding error", Some(2));
+        fix.add_tag("bug".to_string());
+        let docs = done(4, "Update docs", Some(1));
+        let mut open = Task::new(TaskId::new(5), "Refunds".to_string());
+        open.parent = Some(TaskId::new(1));
+        let elsewhere = done(6, "Unrelated", None);
+        let tasks = vec![epic, feature, fix, docs, open, elsewhere];
+
+        let text = changelog(
+            &tasks,
+            &ChangelogScope::Epic(TaskId::new(1)),
+            &ChangelogTemplate::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            text,
+            "## Payments v2\n\n### Features\n\n- Card form (HLA2)\n\n\
+             ### Bug fixes\n\n- Rounding error (HLA3)\n\n\
+             ### Other changes\n\n- Update docs (HLA4)\n"
+        );
+
+        let template = ChangelogTemplate {
+            item: "* [{id}] {title}".to_string(),
+            ..ChangelogTemplate::default()
+        };
+        let mut tagged = done(7, "Dark mode", None);
+        tagged.add_tag("v2.1".to_string());
+        let text = changelog(
+            &[tagged],
+            &ChangelogScope::Milestone("v2.1".to_string()),
+            &template,
+        )
+        .unwrap();
+        assert!(text.starts_with("## v2.1\n"));
+        assert!(text.contains("* [HLA7] Dark mode"));
+
+        assert!(changelog(
+            &tasks,
+            &ChangelogScope::Epic(TaskId::new(99)),
+            &ChangelogTemplate::default()
+        )
+        .is_err());
+    }
+}
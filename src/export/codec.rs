@@ -0,0 +1,334 @@
+//! Reading and writing tasks, boards and exports in any supported encoding.
+//!
+//! Every document goes through one canonical form before it is written: the
+//! JSON value of the data with object keys sorted and `null` members left
+//! out. TOML has no `null`, and a missing optional field reads back as
+//! `None` either way, so dropping them is what lets a task or board read
+//! back identically from JSON, TOML and YAML. [`canonical`] exposes the form
+//! so callers can compare documents across encodings.
+//!
+//! Documents are stamped with the [`format`] version they were written in
+//! and upgraded on read, like exports. [`negotiate`] picks the version to
+//! write for a reader that only understands a range of versions.
+
+use crate::{
+    error::{HlaviError, Result},
+    export::format::{self, FORMAT_VERSION, OLDEST_SUPPORTED_VERSION},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{fmt, str::FromStr};
+
+/// A text encoding documents can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    Json,
+    Toml,
+    /// Requires the `yaml` feature
+    Yaml,
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 3] = [Encoding::Json, Encoding::Toml, Encoding::Yaml];
+
+    /// File extension, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Encoding::Json => "json",
+            Encoding::Toml => "toml",
+            Encoding::Yaml => "yaml",
+        }
+    }
+
+    /// The encoding a file extension names, if any
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(Encoding::Json),
+            "toml" => Some(Encoding::Toml),
+            "yaml" | "yml" => Some(Encoding::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Whether this build can read and write the encoding
+    pub fn is_available(&self) -> bool {
+        !matches!(self, Encoding::Yaml) || cfg!(feature = "yaml")
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Encoding::Json => "JSON",
+            Encoding::Toml => "TOML",
+            Encoding::Yaml => "YAML",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = HlaviError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_extension(s)
+            .ok_or_else(|| HlaviError::Validation(format!("Unknown encoding: {}", s)))
+    }
+}
+
+/// The canonical form of `value`: its JSON value without `null` members
+pub fn canonical<T: Serialize>(value: &T) -> Result<Value> {
+    let mut value = serde_json::to_value(value)?;
+    drop_nulls(&mut value);
+    Ok(value)
+}
+
+fn drop_nulls(value: &mut Value) {
+    match value {
+        Value::Object(members) => {
+            members.retain(|_, member| !member.is_null());
+            members.values_mut().for_each(drop_nulls);
+        }
+        Value::Array(items) => items.iter_mut().for_each(drop_nulls),
+        _ => {}
+    }
+}
+
+/// The newest version both this build and a reader of versions `oldest` to
+/// `newest` understand
+pub fn negotiate(oldest: u32, newest: u32) -> Result<u32> {
+    let version = newest.min(FORMAT_VERSION);
+    if version < oldest.max(OLDEST_SUPPORTED_VERSION) {
+        return Err(HlaviError::UnsupportedFormatVersion {
+            found: newest,
+            oldest: OLDEST_SUPPORTED_VERSION,
+            newest: FORMAT_VERSION,
+        });
+    }
+    Ok(version)
+}
+
+/// Writes `value` in the current format version
+pub fn encode<T: Serialize>(value: &T, encoding: Encoding) -> Result<String> {
+    encode_as(value, encoding, FORMAT_VERSION)
+}
+
+/// Writes `value` in an older format version, for readers that have not
+/// caught up
+///
+/// `value` must serialize to a map, as tasks, boards and exports do.
+pub fn encode_as<T: Serialize>(value: &T, encoding: Encoding, version: u32) -> Result<String> {
+    format::check(version)?;
+    let mut value = canonical(value)?;
+    let members = value.as_object_mut().ok_or_else(|| {
+        HlaviError::Validation("Only maps can be written as documents".to_string())
+    })?;
+    members.insert("format_version".to_string(), FORMAT_VERSION.into());
+    format::downgrade(&mut value, version)?;
+    write(&value, encoding)
+}
+
+/// Reads a document written in any supported encoding and format version
+///
+/// Documents from a newer build, or too old to convert, fail with
+/// `HlaviError::UnsupportedFormatVersion`.
+pub fn decode<T: DeserializeOwned>(text: &str, encoding: Encoding) -> Result<T> {
+    let mut value = read(text, encoding)?;
+    format::upgrade(&mut value)?;
+    Ok(serde_json::from_value(value)?)
+}
+
+fn write(value: &Value, encoding: Encoding) -> Result<String> {
+    let failed = |e: &dyn fmt::Display| {
+        HlaviError::StorageError(format!("Cannot write {}: {}", encoding, e))
+    };
+    match encoding {
+        Encoding::Json => {
+            let mut text = serde_json::to_string_pretty(value)?;
+            text.push('\n');
+            Ok(text)
+        }
+        Encoding::Toml => toml::to_string_pretty(value).map_err(|e| failed(&e)),
+        #[cfg(feature = "yaml")]
+        Encoding::Yaml => serde_yaml::to_string(value).map_err(|e| failed(&e)),
+        #[cfg(not(feature = "yaml"))]
+        Encoding::Yaml => Err(failed(&"the yaml feature is not enabled")),
+    }
+}
+
+fn read(text: &str, encoding: Encoding) -> Result<Value> {
+    let invalid =
+        |e: &dyn fmt::Display| HlaviError::Validation(format!("Invalid {}: {}", encoding, e));
+    match encoding {
+        Encoding::Json => Ok(serde_json::from_str(text)?),
+        Encoding::Toml => toml::from_str(text).map_err(|e| invalid(&e)),
+        #[cfg(feature = "yaml")]
+        Encoding::Yaml => serde_yaml::from_str(text).map_err(|e| invalid(&e)),
+        #[cfg(not(feature = "yaml"))]
+        Encoding::Yaml => Err(invalid(&"the yaml feature is not enabled")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::{
+            history::StatusTransition, Board, BoardConfig, Priority, Severity, Task, TaskId,
+            TaskStatus,
+        },
+        export::BoardExport,
+    };
+    use chrono::{DateTime, TimeZone, Utc};
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, hour, 0, 0).unwrap()
+    }
+
+    /// A task using most optional fields, with every timestamp pinned
+    fn full_task() -> Task {
+        let mut task = Task::new(TaskId::new(7), "Ship the importer".to_string());
+        task.uuid = Uuid::from_u64_pair(7, 7);
+        task.description = Some("Reads \"quoted\" text,\nover two lines".to_string());
+        task.priority = Some(Priority::High);
+        task.severity = Some(Severity::Major);
+        task.task_type = Some("feature".to_string());
+        task.assignee = Some("ana".to_string());
+        task.estimate = Some(5);
+        task.rank = -3;
+        task.tags = vec!["backend".to_string(), "yes".to_string()];
+        task.parent = Some(TaskId::new(2));
+        task.start_date = Some(at(9));
+        task.add_acceptance_criterion("Parses CSV".to_string());
+        task.add_acceptance_criterion("Reports errors".to_string());
+        task.acceptance_criteria[0].completed = true;
+        task.acceptance_criteria[0].completed_at = Some(at(11));
+        for ac in &mut task.acceptance_criteria {
+            ac.created_at = at(9);
+        }
+        task.status = TaskStatus::Open;
+        task.status_history = vec![StatusTransition {
+            from: TaskStatus::New,
+            to: TaskStatus::Open,
+            at: at(10),
+        }];
+        task.status_changed_at = Some(at(10));
+        task.created_at = at(9);
+        task.updated_at = at(11);
+        task
+    }
+
+    /// A task with every optional field empty
+    fn bare_task() -> Task {
+        let mut task = Task::new(TaskId::new(8), "Bare".to_string());
+        task.uuid = Uuid::nil();
+        task.created_at = at(9);
+        task.updated_at = at(9);
+        task
+    }
+
+    fn board() -> Board {
+        let mut board = Board::new(BoardConfig::default());
+        board.add_task(&full_task());
+        board.add_task(&bare_task());
+        board
+    }
+
+    fn bundle() -> BoardExport {
+        let mut export = BoardExport::new(board(), vec![full_task(), bare_task()]);
+        export.exported_at = at(12);
+        export
+    }
+
+    /// Compares `text` with the golden file, or rewrites the file when
+    /// `HLAVI_UPDATE_GOLDEN` is set, returning the file's contents
+    fn check_golden(name: &str, encoding: Encoding, text: &str) -> String {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(format!("{}.{}", name, encoding.extension()));
+        if std::env::var_os("HLAVI_UPDATE_GOLDEN").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, text).unwrap();
+        }
+        let golden = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            text,
+            golden,
+            "{} changed; rerun with HLAVI_UPDATE_GOLDEN=1 if intended",
+            path.display()
+        );
+        golden
+    }
+
+    fn encodings() -> impl Iterator<Item = Encoding> {
+        Encoding::ALL.into_iter().filter(Encoding::is_available)
+    }
+
+    #[test]
+    fn test_tasks_round_trip_in_every_encoding() {
+        for task in [full_task(), bare_task()] {
+            let expected = canonical(&task).unwrap();
+            for encoding in encodings() {
+                let text = encode(&task, encoding).unwrap();
+                let read: Task = decode(&text, encoding).unwrap();
+                assert_eq!(read, task, "{}", encoding);
+                assert_eq!(canonical(&read).unwrap(), expected, "{}", encoding);
+            }
+        }
+    }
+
+    #[test]
+    fn test_documents_match_golden_files() {
+        for encoding in encodings() {
+            let task = check_golden("task", encoding, &encode(&full_task(), encoding).unwrap());
+            assert_eq!(decode::<Task>(&task, encoding).unwrap(), full_task());
+
+            let board = check_golden("board", encoding, &encode(&board(), encoding).unwrap());
+            let board: Board = decode(&board, encoding).unwrap();
+            assert_eq!(
+                canonical(&board).unwrap(),
+                canonical(&self::board()).unwrap()
+            );
+
+            let export = check_golden("bundle", encoding, &bundle().encode(encoding).unwrap());
+            let export = BoardExport::decode(&export, encoding).unwrap();
+            assert_eq!(export.tasks, bundle().tasks);
+        }
+    }
+
+    #[test]
+    fn test_bundle_carries_the_same_documents() {
+        let export = BoardExport::from_json(&bundle().to_json().unwrap()).unwrap();
+        assert_eq!(
+            canonical(&export.board).unwrap(),
+            canonical(&board()).unwrap()
+        );
+        assert_eq!(export.tasks, vec![full_task(), bare_task()]);
+    }
+
+    #[test]
+    fn test_version_negotiation() {
+        assert_eq!(negotiate(1, FORMAT_VERSION + 3).unwrap(), FORMAT_VERSION);
+        assert_eq!(negotiate(1, 1).unwrap(), 1);
+        assert!(matches!(
+            negotiate(FORMAT_VERSION + 1, FORMAT_VERSION + 2),
+            Err(HlaviError::UnsupportedFormatVersion { .. })
+        ));
+
+        // Version 1 readers get an unstamped document, which reads back
+        let old = encode_as(&full_task(), Encoding::Toml, 1).unwrap();
+        assert!(!old.contains("format_version"));
+        assert_eq!(decode::<Task>(&old, Encoding::Toml).unwrap(), full_task());
+
+        let future = encode(&full_task(), Encoding::Json).unwrap().replace(
+            &format!("\"format_version\": {}", FORMAT_VERSION),
+            &format!("\"format_version\": {}", FORMAT_VERSION + 1),
+        );
+        assert!(matches!(
+            decode::<Task>(&future, Encoding::Json),
+            Err(HlaviError::UnsupportedFormatVersion { .. })
+        ));
+    }
+}
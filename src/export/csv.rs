@@ -0,0 +1,250 @@
+//! CSV reports with user-chosen columns, for spreadsheets and BI tools.
+
+use crate::{domain::Task, error::HlaviError};
+use std::str::FromStr;
+
+/// A task field that can become a CSV column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldSelector {
+    Id,
+    Uuid,
+    Title,
+    Description,
+    Status,
+    Priority,
+    Type,
+    Severity,
+    /// Tags joined with `;`
+    Tags,
+    /// Components joined with `;`
+    Components,
+    Environment,
+    Parent,
+    Assignee,
+    /// Story points
+    Estimate,
+    Rank,
+    Created,
+    Updated,
+    Start,
+    End,
+    /// Completed acceptance criteria as `done/total`
+    AcProgress,
+    /// Completion across criteria and checklists as a whole percentage
+    Progress,
+    /// Whole days from start date to end date, empty unless both are set
+    CycleTime,
+    AgentAssigned,
+}
+
+impl FieldSelector {
+    /// Every selector, in a sensible default column order
+    pub const ALL: [FieldSelector; 23] = [
+        Self::Id,
+        Self::Uuid,
+        Self::Title,
+        Self::Description,
+        Self::Status,
+        Self::Priority,
+        Self::Type,
+        Self::Severity,
+        Self::Tags,
+        Self::Components,
+        Self::Environment,
+        Self::Parent,
+        Self::Assignee,
+        Self::Estimate,
+        Self::Rank,
+        Self::Created,
+        Self::Updated,
+        Self::Start,
+        Self::End,
+        Self::AcProgress,
+        Self::Progress,
+        Self::CycleTime,
+        Self::AgentAssigned,
+    ];
+
+    /// Column header, also accepted by `FromStr`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Uuid => "uuid",
+            Self::Title => "title",
+            Self::Description => "description",
+            Self::Status => "status",
+            Self::Priority => "priority",
+            Self::Type => "type",
+            Self::Severity => "severity",
+            Self::Tags => "tags",
+            Self::Components => "components",
+            Self::Environment => "environment",
+            Self::Parent => "parent",
+            Self::Assignee => "assignee",
+            Self::Estimate => "estimate",
+            Self::Rank => "rank",
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::Start => "start",
+            Self::End => "end",
+            Self::AcProgress => "ac-progress",
+            Self::Progress => "progress",
+            Self::CycleTime => "cycle-time",
+            Self::AgentAssigned => "agent-assigned",
+        }
+    }
+
+    /// Renders this field of `task` as unquoted cell text
+    pub fn value(&self, task: &Task) -> String {
+        let date = |d: Option<chrono::DateTime<chrono::Utc>>| {
+            d.map(|d| d.to_rfc3339()).unwrap_or_default()
+        };
+        match self {
+            Self::Id => task.id.to_string(),
+            Self::Uuid => task.uuid.to_string(),
+            Self::Title => task.title.clone(),
+            Self::Description => task.description.clone().unwrap_or_default(),
+            Self::Status => task.status.to_string(),
+            Self::Priority => task.priority.map(|p| p.to_string()).unwrap_or_default(),
+            Self::Type => task.task_type.clone().unwrap_or_default(),
+            Self::Severity => task.severity.map(|s| s.to_string()).unwrap_or_default(),
+            Self::Tags => task.tags.join(";"),
+            Self::Components => task.components.join(";"),
+            Self::Environment => task.environment.clone().unwrap_or_default(),
+            Self::Parent => task
+                .parent
+                .as_ref()
+                .map(|p| p.to_string())
+                .unwrap_or_default(),
+            Self::Assignee => task.assignee.clone().unwrap_or_default(),
+            Self::Estimate => task.estimate.map(|e| e.to_string()).unwrap_or_default(),
+            Self::Rank => task.rank.to_string(),
+            Self::Created => task.created_at.to_rfc3339(),
+            Self::Updated => task.updated_at.to_rfc3339(),
+            Self::Start => date(task.start_date),
+            Self::End => date(task.end_date),
+            Self::AcProgress => format!(
+                "{}/{}",
+                task.acceptance_criteria
+                    .iter()
+                    .filter(|ac| ac.completed)
+                    .count(),
+                task.acceptance_criteria.len()
+            ),
+            Self::Progress => match task.progress() {
+                (_, 0) => String::new(),
+                (done, total) => (done * 100 / total).to_string(),
+            },
+            Self::CycleTime => task
+                .cycle_time()
+                .map(|d| d.num_days().to_string())
+                .unwrap_or_default(),
+            Self::AgentAssigned => task.agent_assigned.to_string(),
+        }
+    }
+}
+
+impl FromStr for FieldSelector {
+    type Err = HlaviError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace('_', "-");
+        Self::ALL
+            .into_iter()
+            .find(|field| field.name() == name)
+            .ok_or_else(|| {
+                let valid: Vec<&str> = Self::ALL.iter().map(|f| f.name()).collect();
+                HlaviError::ConfigError(format!(
+                    "Invalid CSV field '{}'. Valid fields: {}",
+                    s,
+                    valid.join(", ")
+                ))
+            })
+    }
+}
+
+/// Renders `tasks` as CSV with a header row and one column per selector
+///
+/// Cells are quoted per RFC 4180 when needed. Text starting with `=`, `+`,
+/// `-` or `@` is prefixed with `'` so spreadsheets do not evaluate it as a
+/// formula.
+pub fn csv(tasks: &[Task], fields: &[FieldSelector]) -> String {
+    let mut out = String::new();
+    let header: Vec<&str> = fields.iter().map(|f| f.name()).collect();
+    push_row(&mut out, header.iter().map(|h| h.to_string()));
+    for task in tasks {
+        push_row(&mut out, fields.iter().map(|f| f.value(task)));
+    }
+    out
+}
+
+fn push_row(out: &mut String, cells: impl Iterator<Item = String>) {
+    let cells: Vec<String> = cells.map(|cell| escape(&cell)).collect();
+    out.push_str(&cells.join(","));
+    out.push_str("\r\n");
+}
+
+/// Quotes a cell per RFC 4180 and defuses spreadsheet formulas
+pub(crate) fn escape(cell: &str) -> String {
+    let cell = if cell.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", cell)
+    } else {
+        cell.to_string()
+    };
+    if cell.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Priority, TaskId};
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_selected_columns() {
+        let mut task = Task::new(TaskId::new(4), "Parse \"quoted\", titles".to_string());
+        task.set_priority(Priority::High);
+        task.add_tag("bug".to_string());
+        task.add_tag("ui".to_string());
+        let id = task.add_acceptance_criterion("a".to_string());
+        task.toggle_criterion(id).unwrap();
+        task.add_acceptance_criterion("b".to_string());
+        let start = Utc::now();
+        task.set_date_range(start, start + Duration::days(3))
+            .unwrap();
+
+        let fields: Vec<FieldSelector> = [
+            "id",
+            "title",
+            "priority",
+            "tags",
+            "ac_progress",
+            "cycle-time",
+        ]
+        .iter()
+        .map(|f| f.parse().unwrap())
+        .collect();
+        let out = csv(&[task], &fields);
+        let lines: Vec<&str> = out.split("\r\n").collect();
+        assert_eq!(lines[0], "id,title,priority,tags,ac-progress,cycle-time");
+        assert_eq!(
+            lines[1],
+            "HLA4,\"Parse \"\"quoted\"\", titles\",High,bug;ui,1/2,3"
+        );
+    }
+
+    #[test]
+    fn test_formula_guard() {
+        assert_eq!(escape("=SUM(A1)"), "'=SUM(A1)");
+        assert_eq!(escape("plain"), "plain");
+    }
+
+    #[test]
+    fn test_unknown_field() {
+        assert!("owner".parse::<FieldSelector>().is_err());
+    }
+}
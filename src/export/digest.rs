@@ -0,0 +1,158 @@
+//! Standup digest: what happened on the board over a few days, as Markdown.
+
+use crate::{
+    domain::{Task, TaskStatus},
+    metrics::DateRange,
+};
+use chrono::{DateTime, Utc};
+use std::{collections::BTreeMap, fmt::Write};
+
+/// Heading under which tasks without an assignee are listed
+const UNASSIGNED: &str = "Unassigned";
+
+#[derive(Default)]
+struct Sections<'a> {
+    completed: Vec<&'a Task>,
+    created: Vec<&'a Task>,
+    in_review: Vec<&'a Task>,
+    overdue: Vec<&'a Task>,
+    blocked: Vec<&'a Task>,
+}
+
+impl Sections<'_> {
+    fn is_empty(&self) -> bool {
+        self.completed.is_empty()
+            && self.created.is_empty()
+            && self.in_review.is_empty()
+            && self.overdue.is_empty()
+            && self.blocked.is_empty()
+    }
+}
+
+/// Summarizes `range` per assignee: tasks completed, created and moved to
+/// Review during it, plus tasks overdue or blocked as of `now`
+///
+/// Drafts and trashed tasks are left out. Assignees are listed
+/// alphabetically, with unassigned tasks last.
+pub fn digest(tasks: &[Task], range: DateRange, now: DateTime<Utc>) -> String {
+    let within = |at: DateTime<Utc>| range.contains(at.date_naive());
+    let mut by_assignee: BTreeMap<Option<&str>, Sections> = BTreeMap::new();
+
+    for task in tasks.iter().filter(|t| !t.draft && t.trashed_at.is_none()) {
+        let sections = by_assignee.entry(task.assignee.as_deref()).or_default();
+        let finished = matches!(task.status, TaskStatus::Done | TaskStatus::Closed);
+
+        if task.completion_time().is_some_and(within) {
+            sections.completed.push(task);
+        }
+        if within(task.created_at) {
+            sections.created.push(task);
+        }
+        if task
+            .status_history
+            .iter()
+            .any(|t| t.to == TaskStatus::Review && within(t.at))
+        {
+            sections.in_review.push(task);
+        }
+        if task.is_on_board() && !finished {
+            if task.end_date.is_some_and(|end| end < now) {
+                sections.overdue.push(task);
+            }
+            if task.is_blocked() {
+                sections.blocked.push(task);
+            }
+        }
+    }
+    by_assignee.retain(|_, sections| !sections.is_empty());
+
+    let mut out = format!("# Digest {} to {}\n", range.start, range.end);
+    if by_assignee.is_empty() {
+        out.push_str("\n_Nothing to report._\n");
+        return out;
+    }
+    // `None` sorts first in the map, but unassigned work reads best last
+    let unassigned = by_assignee.remove(&None);
+    let groups = by_assignee
+        .iter()
+        .map(|(assignee, sections)| (assignee.unwrap_or(UNASSIGNED), sections))
+        .chain(unassigned.as_ref().map(|sections| (UNASSIGNED, sections)));
+    for (assignee, sections) in groups {
+        let _ = write!(out, "\n## {}\n", assignee);
+        section(&mut out, "Completed", &sections.completed, |_| None);
+        section(&mut out, "Created", &sections.created, |_| None);
+        section(&mut out, "Moved to Review", &sections.in_review, |_| None);
+        section(&mut out, "Overdue", &sections.overdue, |task| {
+            task.end_date.map(|end| format!("due {}", end.date_naive()))
+        });
+        section(&mut out, "Blocked", &sections.blocked, |task| {
+            task.blocked.as_ref().map(ToString::to_string)
+        });
+    }
+    out
+}
+
+fn section(
+    out: &mut String,
+    heading: &str,
+    tasks: &[&Task],
+    note: impl Fn(&Task) -> Option<String>,
+) {
+    if tasks.is_empty() {
+        return;
+    }
+    let _ = write!(out, "\n**{} ({})**\n", heading, tasks.len());
+    for task in tasks {
+        let _ = write!(out, "- {} {}", task.id, task.title);
+        if let Some(note) = note(task) {
+            let _ = write!(out, " ({})", note);
+        }
+        out.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TaskId;
+    use chrono::{Duration, NaiveDate, TimeZone};
+
+    #[test]
+    fn test_digest_groups_by_assignee() {
+        let day = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 9, 0, 0).unwrap();
+        let range = DateRange::new(
+            NaiveDate::from_ymd_opt(2026, 3, 9).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+        )
+        .unwrap();
+
+        let mut shipped = Task::new(TaskId::new(1), "Ship login".to_string());
+        shipped.created_at = day(2);
+        shipped.assignee = Some("ana".to_string());
+        shipped.record_status(TaskStatus::Review, day(10));
+        shipped.record_status(TaskStatus::Done, day(11));
+        shipped.completed_at = Some(day(11));
+
+        let mut stuck = Task::new(TaskId::new(2), "Vendor API".to_string());
+        stuck.created_at = day(10);
+        stuck.end_date = Some(day(12));
+        stuck.block("Waiting on keys", None).unwrap();
+
+        let mut old = Task::new(TaskId::new(3), "Old news".to_string());
+        old.created_at = day(2) - Duration::days(30);
+
+        let text = digest(&[stuck, shipped, old], range, day(13));
+        assert!(text.starts_with("# Digest 2026-03-09 to 2026-03-13\n"));
+        let ana = text.find("## ana").unwrap();
+        let unassigned = text.find("## Unassigned").unwrap();
+        assert!(ana < unassigned);
+        assert!(text.contains("**Completed (1)**\n- HLA1 Ship login\n"));
+        assert!(text.contains("**Moved to Review (1)**"));
+        assert!(text.contains("- HLA2 Vendor API (due 2026-03-12)"));
+        assert!(text.contains("- HLA2 Vendor API (Waiting on keys)"));
+        assert!(!text.contains("Old news"));
+
+        let quiet = digest(&[], range, day(13));
+        assert!(quiet.contains("_Nothing to report._"));
+    }
+}
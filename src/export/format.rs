@@ -0,0 +1,173 @@
+//! Versioning of the export format.
+//!
+//! Every export is stamped with the [`FORMAT_VERSION`] it was written in.
+//! Reading an export checks the stamp against [`compatibility`]: the current
+//! version loads as-is, older supported versions are upgraded one step at a
+//! time by the converters below, and anything else is refused rather than
+//! half-read. Writers can step back down with [`downgrade`] for readers that
+//! have not caught up.
+//!
+//! A change to the exported shape that a `#[serde(default)]` cannot absorb
+//! bumps the version and adds a converter from the previous one.
+
+use crate::error::{HlaviError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Version written by this build
+pub const FORMAT_VERSION: u32 = 2;
+
+/// Oldest version this build can still read
+pub const OLDEST_SUPPORTED_VERSION: u32 = 1;
+
+/// How this build relates to an export's format version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Compatibility {
+    /// Written in the current format
+    Current,
+    /// Written in an older format that can be converted
+    Upgradable,
+    /// Too old to convert
+    TooOld,
+    /// Written by a newer build
+    TooNew,
+}
+
+impl Compatibility {
+    pub fn is_readable(self) -> bool {
+        matches!(self, Self::Current | Self::Upgradable)
+    }
+}
+
+/// Where `version` falls in the compatibility matrix
+pub fn compatibility(version: u32) -> Compatibility {
+    match version {
+        v if v == FORMAT_VERSION => Compatibility::Current,
+        v if v > FORMAT_VERSION => Compatibility::TooNew,
+        v if v < OLDEST_SUPPORTED_VERSION => Compatibility::TooOld,
+        _ => Compatibility::Upgradable,
+    }
+}
+
+/// Fails unless exports written in `version` can be read
+pub fn check(version: u32) -> Result<Compatibility> {
+    let compatibility = compatibility(version);
+    if compatibility.is_readable() {
+        Ok(compatibility)
+    } else {
+        Err(HlaviError::UnsupportedFormatVersion {
+            found: version,
+            oldest: OLDEST_SUPPORTED_VERSION,
+            newest: FORMAT_VERSION,
+        })
+    }
+}
+
+/// Version an export was written in; exports from before versioning carry
+/// no stamp and are version 1
+#[cfg(feature = "storage")]
+pub fn version_of(value: &serde_json::Value) -> Result<u32> {
+    match value.get("format_version") {
+        None => Ok(1),
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| HlaviError::Validation(format!("Invalid format version: {}", version))),
+    }
+}
+
+/// Converts a raw export to the current format, returning the version it
+/// was written in
+#[cfg(feature = "storage")]
+pub fn upgrade(value: &mut serde_json::Value) -> Result<u32> {
+    let found = version_of(value)?;
+    check(found)?;
+    for version in found..FORMAT_VERSION {
+        match version {
+            1 => upgrade_v1(value)?,
+            _ => unreachable!("no converter from format version {}", version),
+        }
+    }
+    Ok(found)
+}
+
+/// Converts a current-format export back to `version`, for readers that
+/// have not been upgraded
+#[cfg(feature = "storage")]
+pub fn downgrade(value: &mut serde_json::Value, version: u32) -> Result<()> {
+    check(version)?;
+    for from in (version + 1..=FORMAT_VERSION).rev() {
+        match from {
+            2 => downgrade_v2(value)?,
+            _ => unreachable!("no converter from format version {}", from),
+        }
+    }
+    Ok(())
+}
+
+/// Version 1 is the unstamped layout. Everything added to tasks and boards
+/// since has a default, so stamping the version is all that is needed.
+#[cfg(feature = "storage")]
+fn upgrade_v1(value: &mut serde_json::Value) -> Result<()> {
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| HlaviError::Validation("An export must be a JSON object".to_string()))?;
+    object.insert("format_version".to_string(), 2.into());
+    Ok(())
+}
+
+/// Readers of version 1 ignore unknown fields, so dropping the stamp is
+/// all that is needed
+#[cfg(feature = "storage")]
+fn downgrade_v2(value: &mut serde_json::Value) -> Result<()> {
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| HlaviError::Validation("An export must be a JSON object".to_string()))?;
+    object.remove("format_version");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compatibility_matrix() {
+        assert_eq!(compatibility(FORMAT_VERSION), Compatibility::Current);
+        assert_eq!(compatibility(1), Compatibility::Upgradable);
+        assert_eq!(compatibility(0), Compatibility::TooOld);
+        assert_eq!(compatibility(FORMAT_VERSION + 1), Compatibility::TooNew);
+        assert!(matches!(
+            check(FORMAT_VERSION + 1),
+            Err(HlaviError::UnsupportedFormatVersion { found: 3, .. })
+        ));
+    }
+
+    #[cfg(feature = "storage")]
+    #[test]
+    fn test_upgrade_unstamped_export() {
+        use crate::{
+            domain::{Board, BoardConfig, Task, TaskId},
+            export::BoardExport,
+        };
+
+        let export = BoardExport::new(
+            Board::new(BoardConfig::default()),
+            vec![Task::new(TaskId::new(1), "Carried over".to_string())],
+        );
+        let mut value = serde_json::to_value(&export).unwrap();
+        value.as_object_mut().unwrap().remove("format_version");
+        let legacy = serde_json::to_string(&value).unwrap();
+
+        let read = BoardExport::from_json(&legacy).unwrap();
+        assert_eq!(read.format_version, FORMAT_VERSION);
+        assert_eq!(read.tasks[0].title, "Carried over");
+
+        value["format_version"] = (FORMAT_VERSION + 1).into();
+        let future = serde_json::to_string(&value).unwrap();
+        assert!(matches!(
+            BoardExport::from_json(&future),
+            Err(HlaviError::UnsupportedFormatVersion { .. })
+        ));
+    }
+}
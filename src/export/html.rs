@@ -0,0 +1,207 @@
+//! A static, self-contained HTML page of the board.
+//!
+//! The page uses inline CSS only, so it renders in mail clients and can be
+//! published as a CI artifact without any scripts.
+
+use crate::{
+    domain::{BoardConfig, Column, Task},
+    export::BoardExport,
+};
+use std::fmt::Write;
+
+const STYLE: &str = "\
+body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;margin:24px;color:#1f2328;background:#f6f8fa}\
+h1{font-size:20px;margin:0 0 4px}\
+.meta{color:#656d76;font-size:12px;margin-bottom:16px}\
+.board{display:flex;gap:12px;align-items:flex-start;overflow-x:auto}\
+.column{flex:0 0 240px;background:#eaeef2;border-radius:6px;padding:8px;border-top:4px solid #8c959f}\
+.column h2{font-size:14px;margin:0 0 8px;display:flex;justify-content:space-between}\
+.column .count{color:#656d76;font-weight:normal}\
+.column .over{color:#cf222e;font-weight:bold}\
+.column p{font-size:12px;color:#656d76;margin:0 0 8px}\
+.card{background:#fff;border:1px solid #d0d7de;border-radius:6px;padding:8px;margin-bottom:8px;font-size:13px}\
+.id{color:#656d76;font-size:11px}\
+.badge{display:inline-block;border-radius:10px;padding:0 6px;margin:4px 4px 0 0;font-size:11px;background:#ddf4ff;color:#1f2328}\
+.bar{height:4px;background:#eaeef2;border-radius:2px;margin-top:6px}\
+.bar div{height:4px;background:#1f883d;border-radius:2px}\
+.blocked{color:#cf222e;font-size:12px;margin-top:4px}";
+
+/// Renders the board's columns and task cards as a standalone HTML page
+///
+/// Column and badge colors come from the board's theme. Archived and trashed
+/// tasks in the export are skipped.
+pub fn html(export: &BoardExport) -> String {
+    let config = &export.board.config;
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(out, "<title>{}</title>", escape(&config.name));
+    let _ = writeln!(out, "<style>{}</style>\n</head>\n<body>", STYLE);
+    let _ = writeln!(out, "<h1>{}</h1>", escape(&config.name));
+    let _ = writeln!(
+        out,
+        "<div class=\"meta\">Exported {}</div>",
+        export.exported_at.format("%Y-%m-%d %H:%M UTC")
+    );
+
+    out.push_str("<div class=\"board\">\n");
+    for column in &config.columns {
+        let mut tasks: Vec<&Task> = export
+            .tasks
+            .iter()
+            .filter(|t| t.is_on_board() && column.holds(&t.status))
+            .collect();
+        tasks.sort_by(|a, b| {
+            b.rank
+                .cmp(&a.rank)
+                .then_with(|| a.id.as_str().cmp(b.id.as_str()))
+        });
+        render_column(&mut out, column, &tasks, config);
+    }
+    out.push_str("</div>\n</body>\n</html>\n");
+    out
+}
+
+fn render_column(out: &mut String, column: &Column, tasks: &[&Task], config: &BoardConfig) {
+    let style = column
+        .color
+        .as_deref()
+        .map(|c| format!(" style=\"border-top-color:{}\"", escape(c)))
+        .unwrap_or_default();
+    let _ = writeln!(out, "<section class=\"column\"{}>", style);
+
+    let icon = column
+        .icon
+        .as_deref()
+        .map(|i| format!("{} ", escape(i)))
+        .unwrap_or_default();
+    let count = match column.wip_limit {
+        Some(limit) if tasks.len() > limit as usize => {
+            format!("<span class=\"over\">{}/{}</span>", tasks.len(), limit)
+        }
+        Some(limit) => format!("<span class=\"count\">{}/{}</span>", tasks.len(), limit),
+        None => format!("<span class=\"count\">{}</span>", tasks.len()),
+    };
+    let _ = writeln!(
+        out,
+        "<h2><span>{}{}</span>{}</h2>",
+        icon,
+        escape(&column.name),
+        count
+    );
+    if let Some(description) = &column.description {
+        let _ = writeln!(out, "<p>{}</p>", escape(description));
+    }
+
+    for task in tasks {
+        render_card(out, task, config);
+    }
+    out.push_str("</section>\n");
+}
+
+fn render_card(out: &mut String, task: &Task, config: &BoardConfig) {
+    let theme = &config.theme;
+    out.push_str("<div class=\"card\">\n");
+    let _ = writeln!(
+        out,
+        "<div class=\"id\">{}</div>",
+        escape(&config.id_format.render(&task.id))
+    );
+    let _ = writeln!(out, "<div>{}</div>", escape(&task.title));
+    if let Some(info) = &task.blocked {
+        let _ = writeln!(
+            out,
+            "<div class=\"blocked\">Blocked: {}</div>",
+            escape(&info.reason)
+        );
+    }
+
+    if let Some(priority) = task.priority {
+        let _ = writeln!(
+            out,
+            "<span class=\"badge\"{}>{}</span>",
+            badge_style(theme.priority_color(priority)),
+            priority
+        );
+    }
+    if let Some(severity) = task.severity {
+        let _ = writeln!(out, "<span class=\"badge\">{}</span>", severity);
+    }
+    for tag in &task.tags {
+        let _ = writeln!(
+            out,
+            "<span class=\"badge\"{}>{}</span>",
+            badge_style(theme.tag_color(tag)),
+            escape(tag)
+        );
+    }
+
+    let (done, total) = task.progress();
+    if total > 0 {
+        let _ = writeln!(
+            out,
+            "<div class=\"bar\" title=\"{}/{}\"><div style=\"width:{}%\"></div></div>",
+            done,
+            total,
+            done * 100 / total
+        );
+    }
+    out.push_str("</div>\n");
+}
+
+fn badge_style(color: Option<&str>) -> String {
+    color
+        .map(|c| format!(" style=\"background:{};color:#fff\"", escape(c)))
+        .unwrap_or_default()
+}
+
+/// Escapes text for use in HTML content and attribute values
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Board, BoardConfig, Priority, TaskId, TaskStatus};
+
+    #[test]
+    fn test_board_page() {
+        let mut config = BoardConfig::default();
+        config.columns[1].wip_limit = Some(1);
+        config.theme.set_tag_color("bug", "#d73a49".to_string());
+        config.id_format.separator = "-".to_string();
+        config.id_format.padding = 3;
+
+        let mut first = Task::new(TaskId::new(1), "<script>alert(1)</script>".to_string());
+        first.status = TaskStatus::Open;
+        first.add_tag("bug".to_string());
+        first.set_priority(Priority::High);
+        let id = first.add_acceptance_criterion("a".to_string());
+        first.toggle_criterion(id).unwrap();
+        first.add_acceptance_criterion("b".to_string());
+        let mut second = Task::new(TaskId::new(2), "Second".to_string());
+        second.status = TaskStatus::Open;
+        second.block("Needs <design>", None).unwrap();
+
+        let page = html(&BoardExport::new(Board::new(config), vec![first, second]));
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(!page.contains("<script>"));
+        assert!(page.contains("&lt;script&gt;"));
+        assert!(page.contains("<span class=\"over\">2/1</span>"));
+        assert!(page.contains("background:#d73a49"));
+        assert!(page.contains("width:50%"));
+        assert!(page.contains("<div class=\"id\">HLA-002</div>"));
+        assert!(page.contains("Blocked: Needs &lt;design&gt;"));
+    }
+}
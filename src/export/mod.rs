@@ -0,0 +1,86 @@
+//! Getting boards out of Hlavi, whole or in part.
+
+pub mod changelog;
+#[cfg(feature = "storage")]
+pub mod codec;
+pub mod csv;
+pub mod digest;
+pub mod format;
+pub mod html;
+#[cfg(feature = "print")]
+pub mod print;
+pub mod scrub;
+
+use crate::domain::{Board, Task};
+#[cfg(feature = "storage")]
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub use self::csv::{csv, FieldSelector};
+pub use changelog::{changelog, ChangelogCategory, ChangelogScope, ChangelogTemplate};
+#[cfg(feature = "storage")]
+pub use codec::Encoding;
+pub use digest::digest;
+pub use html::html;
+pub use scrub::ScrubOptions;
+
+/// A self-contained copy of a board and its tasks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardExport {
+    /// Format the export was written in; see [`format`]
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub board: Board,
+    /// Tasks ordered by ID
+    pub tasks: Vec<Task>,
+}
+
+/// What `BoardService::export` includes and how
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// Also export archived and trashed tasks
+    pub include_inactive: bool,
+    /// Redact the export before returning it
+    pub scrub: Option<ScrubOptions>,
+}
+
+impl BoardExport {
+    pub fn new(board: Board, mut tasks: Vec<Task>) -> Self {
+        tasks.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
+        Self {
+            format_version: format::FORMAT_VERSION,
+            exported_at: Utc::now(),
+            board,
+            tasks,
+        }
+    }
+
+    #[cfg(feature = "storage")]
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Writes the export in any encoding; see [`codec`]
+    #[cfg(feature = "storage")]
+    pub fn encode(&self, encoding: Encoding) -> Result<String> {
+        codec::encode(self, encoding)
+    }
+
+    /// Reads an export written by [`BoardExport::encode`] or
+    /// [`BoardExport::to_json`], converting it to the current format
+    #[cfg(feature = "storage")]
+    pub fn decode(text: &str, encoding: Encoding) -> Result<Self> {
+        codec::decode(text, encoding)
+    }
+
+    /// Reads an export written by this or an earlier supported version,
+    /// converting it to the current format
+    ///
+    /// Exports from a newer build, or too old to convert, fail with
+    /// `HlaviError::UnsupportedFormatVersion`.
+    #[cfg(feature = "storage")]
+    pub fn from_json(json: &str) -> Result<Self> {
+        Self::decode(json, Encoding::Json)
+    }
+}
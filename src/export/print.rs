@@ -0,0 +1,290 @@
+//! Print-ready status packs, for stakeholders who want the board on paper.
+//!
+//! A [`PrintDocument`] is a layout-neutral model of what goes on the page:
+//! one table per column for the whole board, or a single table for a
+//! filtered list. It renders to HTML with print styles (open it and print
+//! to PDF) or to Typst markup for `typst compile`.
+
+use crate::{
+    domain::{BoardConfig, Task, ViewFilter},
+    export::{html::escape, BoardExport},
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+const PRINT_STYLE: &str = "\
+@page{size:A4 landscape;margin:15mm}\
+body{font-family:Helvetica,Arial,sans-serif;font-size:10pt;color:#000}\
+h1{font-size:16pt;margin:0}\
+.meta{color:#555;margin:2pt 0 12pt}\
+section{break-inside:avoid-page;margin-bottom:12pt}\
+h2{font-size:12pt;border-bottom:1pt solid #000;margin:0 0 4pt}\
+table{width:100%;border-collapse:collapse}\
+th,td{text-align:left;padding:2pt 4pt;border-bottom:0.5pt solid #bbb;vertical-align:top}\
+tr{break-inside:avoid}\
+.blocked{font-style:italic}";
+
+/// One task as a table row
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrintRow {
+    /// ID as the board displays it
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+    /// Blocked reason, if blocked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked: Option<String>,
+}
+
+impl PrintRow {
+    fn new(task: &Task, config: &BoardConfig) -> Self {
+        Self {
+            id: config.id_format.render(&task.id),
+            title: task.title.clone(),
+            status: task.status.to_string(),
+            priority: task.priority.map(|p| p.to_string()),
+            assignee: task.assignee.clone(),
+            due: task.end_date.map(|end| end.date_naive()),
+            blocked: task.blocked.as_ref().map(|b| b.reason.clone()),
+        }
+    }
+
+    fn cells(&self) -> [String; 5] {
+        let mut title = self.title.clone();
+        if let Some(reason) = &self.blocked {
+            let _ = write!(title, " (blocked: {})", reason);
+        }
+        [
+            self.id.clone(),
+            title,
+            self.priority.clone().unwrap_or_default(),
+            self.assignee.clone().unwrap_or_default(),
+            self.due.map(|d| d.to_string()).unwrap_or_default(),
+        ]
+    }
+}
+
+const HEADERS: [&str; 5] = ["ID", "Title", "Priority", "Assignee", "Due"];
+
+/// A titled table of tasks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrintSection {
+    pub heading: String,
+    pub rows: Vec<PrintRow>,
+}
+
+/// Everything that goes on the printed pages
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrintDocument {
+    pub title: String,
+    /// Line under the title, such as when the export was taken
+    pub subtitle: String,
+    pub sections: Vec<PrintSection>,
+}
+
+fn ordered<'a>(tasks: impl Iterator<Item = &'a Task>) -> Vec<&'a Task> {
+    let mut tasks: Vec<&Task> = tasks.collect();
+    tasks.sort_by(|a, b| {
+        b.rank
+            .cmp(&a.rank)
+            .then_with(|| a.id.number().cmp(&b.id.number()))
+    });
+    tasks
+}
+
+impl PrintDocument {
+    /// The whole board, one section per column, skipping empty columns
+    pub fn board(export: &BoardExport) -> Self {
+        let config = &export.board.config;
+        let sections = config
+            .columns
+            .iter()
+            .map(|column| PrintSection {
+                heading: column.name.clone(),
+                rows: ordered(
+                    export
+                        .tasks
+                        .iter()
+                        .filter(|t| t.is_on_board() && column.holds(&t.status)),
+                )
+                .into_iter()
+                .map(|task| PrintRow::new(task, config))
+                .collect(),
+            })
+            .filter(|section| !section.rows.is_empty())
+            .collect();
+        Self {
+            title: config.name.clone(),
+            subtitle: Self::stamp(export),
+            sections,
+        }
+    }
+
+    /// Tasks on the board matching `filter`, as one section
+    pub fn list(export: &BoardExport, title: &str, filter: &ViewFilter) -> Self {
+        let config = &export.board.config;
+        let rows = ordered(
+            export
+                .tasks
+                .iter()
+                .filter(|t| t.is_on_board() && filter.matches(t)),
+        )
+        .into_iter()
+        .map(|task| PrintRow::new(task, config))
+        .collect();
+        Self {
+            title: config.name.clone(),
+            subtitle: Self::stamp(export),
+            sections: vec![PrintSection {
+                heading: title.to_string(),
+                rows,
+            }],
+        }
+    }
+
+    fn stamp(export: &BoardExport) -> String {
+        format!(
+            "Status as of {}",
+            export.exported_at.format("%Y-%m-%d %H:%M UTC")
+        )
+    }
+
+    /// Standalone HTML page styled for printing
+    pub fn to_html(&self) -> String {
+        let mut out =
+            String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        let _ = writeln!(out, "<title>{}</title>", escape(&self.title));
+        let _ = writeln!(out, "<style>{}</style>\n</head>\n<body>", PRINT_STYLE);
+        let _ = writeln!(out, "<h1>{}</h1>", escape(&self.title));
+        let _ = writeln!(out, "<div class=\"meta\">{}</div>", escape(&self.subtitle));
+        for section in &self.sections {
+            let _ = writeln!(
+                out,
+                "<section>\n<h2>{} ({})</h2>\n<table>\n<tr>{}</tr>",
+                escape(&section.heading),
+                section.rows.len(),
+                HEADERS
+                    .iter()
+                    .map(|h| format!("<th>{}</th>", h))
+                    .collect::<String>()
+            );
+            for row in &section.rows {
+                let class = if row.blocked.is_some() {
+                    " class=\"blocked\""
+                } else {
+                    ""
+                };
+                let cells: String = row
+                    .cells()
+                    .iter()
+                    .map(|cell| format!("<td>{}</td>", escape(cell)))
+                    .collect();
+                let _ = writeln!(out, "<tr{}>{}</tr>", class, cells);
+            }
+            out.push_str("</table>\n</section>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    /// Typst source laying out the same pages
+    pub fn to_typst(&self) -> String {
+        let mut out = String::from(
+            "#set page(paper: \"a4\", flipped: true, margin: 15mm)\n#set text(size: 10pt)\n\n",
+        );
+        let _ = writeln!(out, "#heading(level: 1, {})", typst_str(&self.title));
+        let _ = writeln!(out, "#text(fill: gray, {})\n", typst_str(&self.subtitle));
+        for section in &self.sections {
+            let _ = writeln!(
+                out,
+                "#heading(level: 2, {})",
+                typst_str(&format!("{} ({})", section.heading, section.rows.len()))
+            );
+            out.push_str(
+                "#table(\n  columns: (auto, 1fr, auto, auto, auto),\n  stroke: 0.5pt + gray,\n",
+            );
+            let headers: Vec<String> = HEADERS.iter().map(|h| format!("[*{}*]", h)).collect();
+            let _ = writeln!(out, "  {},", headers.join(", "));
+            for row in &section.rows {
+                let cells: Vec<String> = row.cells().iter().map(|c| typst_str(c)).collect();
+                let _ = writeln!(out, "  {},", cells.join(", "));
+            }
+            out.push_str(")\n\n");
+        }
+        out
+    }
+}
+
+/// A Typst string literal, which tables and headings accept as content
+/// without interpreting markup in it
+fn typst_str(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len() + 2);
+    literal.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            _ => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Board, Priority, TaskId, TaskStatus};
+
+    fn export() -> BoardExport {
+        let mut urgent = Task::new(TaskId::new(1), "Fix \"login\" <now>".to_string());
+        urgent.priority = Some(Priority::Critical);
+        urgent.block("Waiting on vendor", None).unwrap();
+        let mut done = Task::new(TaskId::new(2), "Ship docs".to_string());
+        done.status = TaskStatus::Done;
+        BoardExport::new(Board::new(BoardConfig::default()), vec![urgent, done])
+    }
+
+    #[test]
+    fn test_board_document() {
+        let document = PrintDocument::board(&export());
+        let headings: Vec<&str> = document
+            .sections
+            .iter()
+            .map(|s| s.heading.as_str())
+            .collect();
+        assert_eq!(headings, vec!["New", "Done"]);
+        assert_eq!(
+            document.sections[0].rows[0].priority.as_deref(),
+            Some("Critical")
+        );
+
+        let html = document.to_html();
+        assert!(html.contains("@page{size:A4 landscape"));
+        assert!(html.contains("Fix &quot;login&quot; &lt;now&gt; (blocked: Waiting on vendor)"));
+
+        let typst = document.to_typst();
+        assert!(typst.contains("#heading(level: 2, \"New (1)\")"));
+        assert!(typst.contains("\"Fix \\\"login\\\" <now> (blocked: Waiting on vendor)\""));
+    }
+
+    #[test]
+    fn test_filtered_list() {
+        let filter = ViewFilter {
+            statuses: vec![TaskStatus::Done],
+            ..ViewFilter::default()
+        };
+        let document = PrintDocument::list(&export(), "Shipped", &filter);
+        assert_eq!(document.sections.len(), 1);
+        assert_eq!(document.sections[0].heading, "Shipped");
+        assert_eq!(document.sections[0].rows[0].id, "HLA2");
+    }
+}
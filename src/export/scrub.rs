@@ -0,0 +1,207 @@
+//! Redacting exports so boards can be shared as reproduction cases or demos.
+//!
+//! Scrubbing keeps the structure that matters for debugging — IDs, statuses,
+//! dates, priorities, tags, links and criterion/checklist counts — and replaces
+//! free text and people's names with placeholders.
+
+use crate::{domain::Task, export::BoardExport};
+use std::collections::HashMap;
+
+/// Placeholder for redacted free text
+pub const REDACTED: &str = "[redacted]";
+
+/// Which parts of an export to redact
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrubOptions {
+    /// Replace titles with `Task <ID>`
+    pub titles: bool,
+    /// Redact descriptions, criterion and checklist text, notes and rejection reasons
+    pub descriptions: bool,
+    /// Replace assignees and work log actors with stable pseudonyms (`actor-1`, `actor-2`, ...)
+    pub actors: bool,
+    /// Redact external ref IDs and URLs
+    pub external_refs: bool,
+}
+
+impl Default for ScrubOptions {
+    /// Redacts everything except titles
+    fn default() -> Self {
+        Self {
+            titles: false,
+            descriptions: true,
+            actors: true,
+            external_refs: true,
+        }
+    }
+}
+
+impl ScrubOptions {
+    /// Redacts everything, titles included
+    pub fn all() -> Self {
+        Self {
+            titles: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// Hands out one pseudonym per distinct actor, in order of first appearance
+#[derive(Default)]
+struct Pseudonyms(HashMap<String, String>);
+
+impl Pseudonyms {
+    fn get(&mut self, actor: &str) -> String {
+        let next = self.0.len() + 1;
+        self.0
+            .entry(actor.trim().to_lowercase())
+            .or_insert_with(|| format!("actor-{}", next))
+            .clone()
+    }
+}
+
+impl BoardExport {
+    /// Redacts the export in place
+    pub fn scrub(&mut self, options: &ScrubOptions) {
+        let mut pseudonyms = Pseudonyms::default();
+        for task in &mut self.tasks {
+            scrub_task(task, options, &mut pseudonyms);
+        }
+        if options.descriptions {
+            for view in &mut self.board.config.views {
+                if view.filter.query.is_some() {
+                    view.filter.query = Some(REDACTED.to_string());
+                }
+            }
+        }
+    }
+
+    /// Returns a redacted copy of the export
+    pub fn scrubbed(&self, options: &ScrubOptions) -> BoardExport {
+        let mut export = self.clone();
+        export.scrub(options);
+        export
+    }
+}
+
+fn scrub_task(task: &mut Task, options: &ScrubOptions, pseudonyms: &mut Pseudonyms) {
+    // Replicated state duplicates tags, criteria text and replica names
+    task.crdt = None;
+
+    if options.titles {
+        task.title = format!("Task {}", task.id);
+    }
+
+    if options.descriptions {
+        redact(&mut task.description);
+        redact(&mut task.rejection_reason);
+        for ac in &mut task.acceptance_criteria {
+            ac.description = format!("Criterion {}", ac.id);
+            redact(&mut ac.notes);
+            redact(&mut ac.evidence_url);
+        }
+        for entry in &mut task.work_log {
+            redact(&mut entry.note);
+        }
+        for note in &mut task.agent_notes {
+            note.decision = REDACTED.to_string();
+            redact(&mut note.rationale);
+            redact(&mut note.prompt_ref);
+            note.artifacts.clear();
+        }
+        for (index, checklist) in task.checklists.iter_mut().enumerate() {
+            checklist.name = format!("Checklist {}", index + 1);
+            for item in &mut checklist.items {
+                item.text = format!("Item {}", item.id);
+            }
+        }
+    }
+
+    if options.actors {
+        if let Some(assignee) = task.assignee.as_mut() {
+            *assignee = pseudonyms.get(assignee);
+        }
+        for ac in &mut task.acceptance_criteria {
+            if let Some(assignee) = ac.assignee.as_mut() {
+                *assignee = pseudonyms.get(assignee);
+            }
+        }
+        for entry in &mut task.work_log {
+            entry.actor = pseudonyms.get(&entry.actor);
+        }
+        for note in &mut task.agent_notes {
+            note.agent = pseudonyms.get(&note.agent);
+        }
+    }
+
+    if options.external_refs {
+        for external in &mut task.external_refs {
+            external.id = REDACTED.to_string();
+            external.url = None;
+        }
+    }
+}
+
+fn redact(field: &mut Option<String>) {
+    if field.is_some() {
+        *field = Some(REDACTED.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Board, BoardConfig, ExternalRef, TaskId, TaskStatus};
+
+    fn export() -> BoardExport {
+        let mut task = Task::new(TaskId::new(1), "Fix billing for ACME".to_string());
+        task.set_description("Customer ACME is double charged".to_string())
+            .unwrap();
+        task.status = TaskStatus::Open;
+        let id = task.add_acceptance_criterion("Refund ACME".to_string());
+        task.assign_criterion(id, Some("Alice".to_string()))
+            .unwrap();
+        task.add_external_ref(ExternalRef::new("zendesk", "4411"));
+
+        let mut other = Task::new(TaskId::new(2), "Follow up".to_string());
+        let id = other.add_acceptance_criterion("Call back".to_string());
+        other
+            .assign_criterion(id, Some("alice".to_string()))
+            .unwrap();
+
+        BoardExport::new(Board::new(BoardConfig::default()), vec![task, other])
+    }
+
+    #[test]
+    fn test_scrub_keeps_structure() {
+        let original = export();
+        let scrubbed = original.scrubbed(&ScrubOptions::default());
+
+        let task = &scrubbed.tasks[0];
+        assert_eq!(task.title, "Fix billing for ACME");
+        assert_eq!(task.description.as_deref(), Some(REDACTED));
+        assert_eq!(task.status, TaskStatus::Open);
+        assert_eq!(task.created_at, original.tasks[0].created_at);
+        assert_eq!(task.acceptance_criteria[0].description, "Criterion 1");
+        assert_eq!(task.external_refs[0].system, "zendesk");
+        assert_eq!(task.external_refs[0].id, REDACTED);
+
+        // The same person gets the same pseudonym across tasks
+        assert_eq!(
+            task.acceptance_criteria[0].assignee.as_deref(),
+            Some("actor-1")
+        );
+        assert_eq!(
+            scrubbed.tasks[1].acceptance_criteria[0].assignee.as_deref(),
+            Some("actor-1")
+        );
+    }
+
+    #[test]
+    fn test_scrub_all() {
+        let scrubbed = export().scrubbed(&ScrubOptions::all());
+        let json = serde_json::to_string(&scrubbed).unwrap();
+        assert!(!json.contains("ACME"));
+        assert!(!json.to_lowercase().contains("alice"));
+        assert_eq!(scrubbed.tasks[0].title, "Task HLA1");
+    }
+}
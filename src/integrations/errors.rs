@@ -0,0 +1,240 @@
+//! Turning error reports from tools like Sentry into bug tasks.
+//!
+//! Each report carries a fingerprint identifying the underlying error. The
+//! first report of a fingerprint creates a bug task that keeps the
+//! fingerprint as an external ref; later reports only count another
+//! occurrence. A report of an error whose task was already finished is a
+//! regression, and reopens the task.
+
+use crate::{
+    domain::{text::Title, ExternalRef, Severity, Task, TaskStatus},
+    error::{HlaviError, Result},
+    service::BoardService,
+    storage::Storage,
+};
+use serde::{Deserialize, Serialize};
+
+/// One error report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorEvent {
+    /// Identifies the error across reports, such as Sentry's issue
+    /// fingerprint
+    pub fingerprint: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Reported level: `fatal`, `error`, `warning`, `info` or `debug`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+    /// Link to the error in the reporting tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl ErrorEvent {
+    pub fn new(fingerprint: &str, title: &str) -> Self {
+        Self {
+            fingerprint: fingerprint.to_string(),
+            title: title.to_string(),
+            message: None,
+            level: None,
+            environment: None,
+            url: None,
+        }
+    }
+
+    /// The report's title cut to fit a task title, and whether it was cut
+    fn short_title(&self) -> (String, bool) {
+        let title = self.title.trim();
+        if title.chars().count() <= Title::MAX_LEN {
+            return (title.to_string(), false);
+        }
+        let mut short: String = title.chars().take(Title::MAX_LEN - 1).collect();
+        short.push('…');
+        (short, true)
+    }
+
+    /// The message, led by the full title when the task's title was cut
+    fn description(&self) -> Option<String> {
+        match (self.short_title().1, &self.message) {
+            (true, Some(message)) => Some(format!("{}\n\n{}", self.title.trim(), message)),
+            (true, None) => Some(self.title.trim().to_string()),
+            (false, message) => message.clone(),
+        }
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        match self.level.as_deref()?.to_ascii_lowercase().as_str() {
+            "fatal" => Some(Severity::Critical),
+            "error" => Some(Severity::Major),
+            "warning" => Some(Severity::Minor),
+            "info" | "debug" => Some(Severity::Trivial),
+            _ => None,
+        }
+    }
+}
+
+/// What ingesting an error report did
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ingested {
+    /// First report of the error; a bug task was created
+    Created(Task),
+    /// The error's open task counted another occurrence
+    Repeated(Task),
+    /// The error's task was finished and has been reopened
+    Regressed(Task),
+}
+
+impl Ingested {
+    pub fn task(&self) -> &Task {
+        match self {
+            Self::Created(task) | Self::Repeated(task) | Self::Regressed(task) => task,
+        }
+    }
+}
+
+/// Records an error report from `system`, such as "sentry", deduplicating
+/// by fingerprint
+///
+/// New tasks get the board's `bug` task type when it has one (or has no
+/// task types at all), and otherwise a `bug` tag. Titles too long for a
+/// task are cut short, with the full title kept in the description. Reopening a finished task
+/// moves it back to Open directly, whatever the workflow.
+pub async fn ingest_error<S: Storage>(
+    service: &BoardService<S>,
+    system: &str,
+    event: &ErrorEvent,
+) -> Result<Ingested> {
+    if event.fingerprint.trim().is_empty() {
+        return Err(HlaviError::Validation(
+            "An error event needs a fingerprint".to_string(),
+        ));
+    }
+
+    let existing = service
+        .storage()
+        .load_all_tasks()
+        .await?
+        .into_iter()
+        .find(|task| {
+            task.external_refs
+                .iter()
+                .any(|r| r.system == system && r.id == event.fingerprint)
+        });
+    if let Some(mut task) = existing {
+        task.occurrences = task.occurrences.max(1) + 1;
+        task.updated_at = chrono::Utc::now();
+        let regressed = matches!(task.status, TaskStatus::Done | TaskStatus::Closed);
+        if regressed {
+            task.enter_status(TaskStatus::Open, None);
+        }
+        let task = service.update_task(task).await?;
+        return Ok(if regressed {
+            Ingested::Regressed(task)
+        } else {
+            Ingested::Repeated(task)
+        });
+    }
+
+    let config = service.storage().load_board().await?.config;
+    let bug_type = config.task_type("bug").cloned();
+    let typed = bug_type.is_some() || config.task_types.is_empty();
+    let severity_allowed = bug_type
+        .as_ref()
+        .map_or(config.task_types.is_empty(), |t| t.severity);
+    let environment = event.environment.clone().filter(|env| {
+        config.environments.is_empty() || config.environments.iter().any(|e| e == env)
+    });
+
+    let task = service
+        .create_task_with(|id| {
+            let mut task = Task::try_new(id, &event.short_title().0)?;
+            if let Some(description) = event.description() {
+                task.set_description(description)?;
+            }
+            match &bug_type {
+                Some(bug) => bug.apply_defaults(&mut task),
+                None if typed => task.task_type = Some("bug".to_string()),
+                None => {
+                    task.add_tag("bug".to_string());
+                }
+            }
+            if severity_allowed {
+                task.severity = event.severity();
+            }
+            task.environment = environment;
+            task.occurrences = 1;
+            let mut external = ExternalRef::new(system, event.fingerprint.as_str());
+            external.url = event.url.clone();
+            task.add_external_ref(external);
+            Ok(task)
+        })
+        .await?;
+    Ok(Ingested::Created(task))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::file_storage::FileStorage;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_ingest_deduplicates_and_reopens() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        let service = BoardService::new(storage);
+
+        let mut event = ErrorEvent::new("a1b2", "TypeError in checkout");
+        event.level = Some("fatal".to_string());
+        let Ingested::Created(task) = ingest_error(&service, "sentry", &event).await.unwrap()
+        else {
+            panic!("expected a new task");
+        };
+        assert!(task.is_type("bug"));
+        assert_eq!(task.severity, Some(Severity::Critical));
+        assert_eq!(task.occurrences, 1);
+
+        let repeated = ingest_error(&service, "sentry", &event).await.unwrap();
+        assert!(matches!(repeated, Ingested::Repeated(_)));
+        assert_eq!(repeated.task().occurrences, 2);
+        assert!(repeated.task().updated_at >= task.updated_at);
+
+        let mut closed = repeated.task().clone();
+        closed.transition_to(TaskStatus::Open, None).unwrap();
+        closed.transition_to(TaskStatus::Closed, None).unwrap();
+        service.update_task(closed).await.unwrap();
+        let regressed = ingest_error(&service, "sentry", &event).await.unwrap();
+        assert!(matches!(regressed, Ingested::Regressed(_)));
+        assert_eq!(regressed.task().status, TaskStatus::Open);
+        assert_eq!(regressed.task().occurrences, 3);
+        assert_eq!(service.list_tasks().await.unwrap().len(), 1);
+
+        assert!(ingest_error(&service, "sentry", &ErrorEvent::new(" ", "x"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ingest_long_title() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        let service = BoardService::new(storage);
+
+        let title = format!("Error: {}", "x".repeat(300));
+        let mut event = ErrorEvent::new("c3d4", &title);
+        event.message = Some("at checkout.js:12".to_string());
+        let task = ingest_error(&service, "sentry", &event).await.unwrap();
+
+        let task = task.task();
+        assert_eq!(task.title.chars().count(), Title::MAX_LEN);
+        assert!(task.title.ends_with('…'));
+        let description = task.description.as_deref().unwrap();
+        assert!(description.starts_with(&title));
+        assert!(description.ends_with("at checkout.js:12"));
+    }
+}
@@ -0,0 +1,286 @@
+//! Keeping the board current from commit messages.
+//!
+//! A `commit-msg` or `post-commit` hook can pass each message to
+//! [`apply_commit_message`]. Closing keywords (`fixes HLA12`, `closes`,
+//! `resolves`) move the task to Review, and reference keywords (`refs HLA7`,
+//! `see`, `part of`) move it to In Progress. Tasks only ever move forward, and
+//! the commit hash is recorded on each referenced task as an external ref.
+//!
+//! Branch names follow the `hla12-fix-login-crash` convention produced by
+//! [`Task::suggest_branch_name`](crate::domain::Task::suggest_branch_name)
+//! and read back by [`task_id_from_branch`].
+
+use crate::{
+    domain::{refs::find_refs, ExternalRef, TaskId, TaskStatus},
+    error::{HlaviError, Result},
+    service::BoardService,
+    storage::Storage,
+};
+
+pub use crate::domain::refs::{task_id_from_branch, MAX_BRANCH_LEN};
+
+/// External ref system used for commit hashes
+pub const GIT_SYSTEM: &str = "git";
+
+/// What a commit says about a task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitKeyword {
+    /// `fixes`, `closes`, `resolves` and their inflections
+    Fixes,
+    /// `refs`, `references`, `see`, `part of`
+    Refs,
+}
+
+impl CommitKeyword {
+    fn parse(word: &str) -> Option<Self> {
+        match word.to_lowercase().as_str() {
+            "fix" | "fixes" | "fixed" | "close" | "closes" | "closed" | "resolve" | "resolves"
+            | "resolved" => Some(Self::Fixes),
+            "ref" | "refs" | "references" | "see" | "part-of" => Some(Self::Refs),
+            _ => None,
+        }
+    }
+
+    /// Status the keyword moves a task towards
+    pub fn target_status(&self) -> TaskStatus {
+        match self {
+            Self::Fixes => TaskStatus::Review,
+            Self::Refs => TaskStatus::InProgress,
+        }
+    }
+}
+
+/// A task referenced by a commit keyword
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitRef {
+    pub id: TaskId,
+    pub keyword: CommitKeyword,
+}
+
+/// What happened to one referenced task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommitEffect {
+    /// The task moved to the given status
+    Moved(TaskStatus),
+    /// The task was already at or past the keyword's target; only the commit was recorded
+    Recorded,
+    /// No task with that ID exists on this board
+    NotFound,
+}
+
+/// Result for one task referenced by a commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitOutcome {
+    pub id: TaskId,
+    pub keyword: CommitKeyword,
+    pub effect: CommitEffect,
+    /// Display name of the commit's author, when given
+    pub author: Option<String>,
+}
+
+/// Finds keyword references in a commit message
+///
+/// A keyword applies to the IDs that follow it, so `fixes HLA1, HLA2 and
+/// HLA3` references all three. Bare mentions without a keyword are ignored.
+pub fn parse_commit_message(message: &str, prefixes: &[&str]) -> Vec<CommitRef> {
+    let mut refs: Vec<CommitRef> = Vec::new();
+    let mut keyword = None;
+
+    let normalized = message.to_lowercase().replace("part of", "part-of");
+    for raw in normalized.split_whitespace() {
+        let word = raw.trim_matches(|c: char| !c.is_alphanumeric() && c != '-');
+        if let Some(found) = CommitKeyword::parse(word) {
+            keyword = Some(found);
+            continue;
+        }
+        let id = match find_refs(word, prefixes).as_slice() {
+            [only] if only.range.len() == word.len() && only.reference.project.is_none() => {
+                Some(only.reference.id.clone())
+            }
+            _ => None,
+        };
+        match (id, keyword) {
+            (Some(id), Some(keyword)) => {
+                if !refs.iter().any(|r| r.id == id) {
+                    refs.push(CommitRef { id, keyword });
+                }
+            }
+            (None, _) if word == "and" || word.is_empty() => {}
+            _ => keyword = None,
+        }
+    }
+    refs
+}
+
+/// Applies a commit's keywords to the board and records the commit on each task
+pub async fn apply_commit_message<S: Storage>(
+    service: &BoardService<S>,
+    commit_hash: &str,
+    message: &str,
+) -> Result<Vec<CommitOutcome>> {
+    apply_commit(service, commit_hash, message, None).await
+}
+
+/// Like [`apply_commit_message`], attributing the outcomes to the commit's
+/// author
+///
+/// `author` is the author email or name as git records it; it is mapped
+/// through the project's identities to the person's display name.
+pub async fn apply_commit_message_by<S: Storage>(
+    service: &BoardService<S>,
+    commit_hash: &str,
+    message: &str,
+    author: &str,
+) -> Result<Vec<CommitOutcome>> {
+    apply_commit(service, commit_hash, message, Some(author)).await
+}
+
+async fn apply_commit<S: Storage>(
+    service: &BoardService<S>,
+    commit_hash: &str,
+    message: &str,
+    author: Option<&str>,
+) -> Result<Vec<CommitOutcome>> {
+    let config = service.storage().load_project_config().await?;
+    let author = author.map(|author| config.identities.canonical(author));
+    let mut outcomes = Vec::new();
+
+    for CommitRef { id, keyword } in parse_commit_message(message, &config.id_prefixes()) {
+        let mut task = match service.load_task(&id).await {
+            Ok(task) => task,
+            Err(HlaviError::TaskNotFound(_)) => {
+                outcomes.push(CommitOutcome {
+                    id,
+                    keyword,
+                    effect: CommitEffect::NotFound,
+                    author: author.clone(),
+                });
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        let effect = match forward_path(&task.status, &keyword.target_status()) {
+            Some(path) => {
+                for status in path {
+                    task.transition_to(status, None)?;
+                }
+                CommitEffect::Moved(task.status.clone())
+            }
+            None => CommitEffect::Recorded,
+        };
+        task.add_external_ref(ExternalRef::new(GIT_SYSTEM, commit_hash));
+        service.update_task(task).await?;
+
+        outcomes.push(CommitOutcome {
+            id,
+            keyword,
+            effect,
+            author: author.clone(),
+        });
+    }
+    Ok(outcomes)
+}
+
+/// Path to `target` if it lies ahead of `current` in the workflow
+fn forward_path(current: &TaskStatus, target: &TaskStatus) -> Option<Vec<TaskStatus>> {
+    let position = |status: &TaskStatus| TaskStatus::ALL.iter().position(|s| s == status);
+    if position(target) <= position(current) {
+        return None;
+    }
+    current.path_to(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::file_storage::FileStorage;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_commit_message() {
+        let refs = parse_commit_message(
+            "Handle empty input\n\nFixes HLA1, hla2 and HLA3. Refs: HLA7\nMentions HLA9",
+            &["HLA"],
+        );
+        let parsed: Vec<_> = refs
+            .iter()
+            .map(|r| (r.id.as_str().to_string(), r.keyword))
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                ("HLA1".to_string(), CommitKeyword::Fixes),
+                ("HLA2".to_string(), CommitKeyword::Fixes),
+                ("HLA3".to_string(), CommitKeyword::Fixes),
+                ("HLA7".to_string(), CommitKeyword::Refs),
+            ]
+        );
+
+        let refs = parse_commit_message("Part of HLA4", &["HLA"]);
+        assert_eq!(refs[0].keyword, CommitKeyword::Refs);
+    }
+
+    #[tokio::test]
+    async fn test_apply_commit_message() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        let service = BoardService::new(storage);
+        service.create_task("Crash".to_string()).await.unwrap();
+        service.create_task("Refactor".to_string()).await.unwrap();
+        let mut done = service.create_task("Shipped".to_string()).await.unwrap();
+        for status in TaskStatus::New.path_to(&TaskStatus::Done).unwrap() {
+            done.transition_to(status, None).unwrap();
+        }
+        service.update_task(done).await.unwrap();
+
+        let outcomes = apply_commit_message(
+            &service,
+            "abc123",
+            "Fix crash (fixes HLA1, HLA3; refs HLA2, HLA99)",
+        )
+        .await
+        .unwrap();
+
+        let effects: Vec<_> = outcomes.iter().map(|o| o.effect.clone()).collect();
+        assert_eq!(
+            effects,
+            vec![
+                CommitEffect::Moved(TaskStatus::Review),
+                CommitEffect::Recorded,
+                CommitEffect::Moved(TaskStatus::InProgress),
+                CommitEffect::NotFound,
+            ]
+        );
+
+        let fixed = service.load_task(&TaskId::new(1)).await.unwrap();
+        assert_eq!(fixed.status, TaskStatus::Review);
+        assert_eq!(
+            fixed.external_refs,
+            vec![ExternalRef::new(GIT_SYSTEM, "abc123")]
+        );
+        let shipped = service.load_task(&TaskId::new(3)).await.unwrap();
+        assert_eq!(shipped.status, TaskStatus::Done);
+        assert_eq!(outcomes[0].author, None);
+    }
+
+    #[tokio::test]
+    async fn test_commit_author_identity() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        let mut config = crate::domain::ProjectConfig::default();
+        config
+            .identities
+            .add(crate::domain::Identity::new("Ana Lee").with_email("ana@example.com"));
+        storage.save_project_config(&config).await.unwrap();
+        let service = BoardService::new(storage);
+        service.create_task("Crash".to_string()).await.unwrap();
+
+        let outcomes = apply_commit_message_by(&service, "abc123", "fixes HLA1", "ANA@example.com")
+            .await
+            .unwrap();
+        assert_eq!(outcomes[0].author.as_deref(), Some("Ana Lee"));
+    }
+}
@@ -0,0 +1,292 @@
+//! Importing from and reporting back to GitLab Issues.
+//!
+//! [`GitLabImporter`] reads the JSON returned by GitLab's issues API
+//! (`GET /projects/:id/issues`) for use with
+//! [`plan_import`](super::import::plan_import). Board columns in GitLab are
+//! usually scoped labels, so a label such as `workflow::In Progress` becomes
+//! the status; otherwise the issue state (`opened`, `closed`) is used.
+//! Epics, where the instance has them, become parent tasks.
+//!
+//! Push-back is optional. [`issue_update`] describes the API call that would
+//! bring an imported issue in line with its task's status and tags; sending
+//! it is left to the caller, which keeps this crate free of an HTTP client
+//! and of GitLab credentials.
+
+use crate::{
+    domain::{Task, TaskStatus},
+    error::{HlaviError, Result},
+    integrations::import::{ExternalTask, ImportMapping, Importer},
+    service::BoardService,
+    storage::Storage,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// External ref system used for GitLab issues
+pub const GITLAB_SYSTEM: &str = "gitlab";
+
+/// Where issues come from and how statuses are written as labels
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitLabConfig {
+    /// Project path, such as `group/app`
+    pub project: String,
+    /// Scope of the labels holding the status, such as `workflow`
+    #[serde(default = "default_status_scope")]
+    pub status_scope: String,
+}
+
+fn default_status_scope() -> String {
+    "workflow".to_string()
+}
+
+impl GitLabConfig {
+    pub fn new(project: &str) -> Self {
+        Self {
+            project: project.to_string(),
+            status_scope: default_status_scope(),
+        }
+    }
+
+    fn status_prefix(&self) -> String {
+        format!("{}::", self.status_scope)
+    }
+}
+
+/// Importer for GitLab issues
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitLabImporter {
+    pub config: GitLabConfig,
+}
+
+impl GitLabImporter {
+    pub fn new(config: GitLabConfig) -> Self {
+        Self { config }
+    }
+
+    /// Mapping for issue states; scoped status labels that spell Hlavi
+    /// statuses need no entry
+    pub fn default_mapping() -> ImportMapping {
+        let mut mapping = ImportMapping::default();
+        mapping
+            .statuses
+            .insert("opened".to_string(), TaskStatus::Open);
+        mapping
+            .statuses
+            .insert("closed".to_string(), TaskStatus::Done);
+        mapping
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Issue {
+    iid: u64,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    state: String,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    assignees: Vec<User>,
+    #[serde(default)]
+    milestone: Option<Milestone>,
+    #[serde(default)]
+    epic: Option<Epic>,
+    #[serde(default)]
+    weight: Option<u32>,
+    #[serde(default)]
+    due_date: Option<String>,
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    web_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Milestone {
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Epic {
+    iid: u64,
+    title: String,
+}
+
+impl Importer for GitLabImporter {
+    fn system(&self) -> &str {
+        GITLAB_SYSTEM
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<ExternalTask>> {
+        let issues: Vec<Issue> = serde_json::from_str(input)?;
+        let prefix = self.config.status_prefix();
+        let mut records: Vec<ExternalTask> = Vec::new();
+
+        for issue in issues {
+            let parent = issue.epic.map(|epic| {
+                let external_id = format!("{}&{}", self.config.project, epic.iid);
+                if !records.iter().any(|r| r.external_id == external_id) {
+                    records.push(ExternalTask {
+                        external_id: external_id.clone(),
+                        title: epic.title,
+                        ..ExternalTask::default()
+                    });
+                }
+                external_id
+            });
+            let (status_labels, tags): (Vec<String>, Vec<String>) = issue
+                .labels
+                .into_iter()
+                .partition(|label| label.starts_with(&prefix));
+            let status = match (issue.state.as_str(), status_labels.first()) {
+                ("opened", Some(label)) => label[prefix.len()..].to_string(),
+                (state, _) => state.to_string(),
+            };
+
+            let mut record = ExternalTask {
+                external_id: format!("{}#{}", self.config.project, issue.iid),
+                title: issue.title,
+                description: issue.description.filter(|d| !d.trim().is_empty()),
+                status: Some(status),
+                tags,
+                assignee: issue.assignees.into_iter().next().map(|u| u.username),
+                estimate: issue.weight,
+                created_at: issue.created_at,
+                url: issue.web_url,
+                parent,
+                ..ExternalTask::default()
+            };
+            if let Some(milestone) = issue.milestone {
+                record
+                    .extra
+                    .insert("milestone".to_string(), milestone.title);
+            }
+            if let Some(due) = issue.due_date {
+                record.extra.insert("due_date".to_string(), due);
+            }
+            records.push(record);
+        }
+        Ok(records)
+    }
+}
+
+/// An edit to one GitLab issue, as a call to `PUT /projects/:id/issues/:iid`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueUpdate {
+    pub project: String,
+    pub iid: u64,
+    /// `close` or `reopen`
+    pub state_event: String,
+    /// The issue's full label set: the task's tags plus a scoped status label
+    pub labels: Vec<String>,
+}
+
+impl IssueUpdate {
+    /// API path relative to `/api/v4`, with the project path encoded
+    pub fn path(&self) -> String {
+        format!(
+            "/projects/{}/issues/{}",
+            self.project.replace('/', "%2F"),
+            self.iid
+        )
+    }
+
+    /// Request body
+    pub fn body(&self) -> serde_json::Value {
+        serde_json::json!({
+            "state_event": self.state_event,
+            "labels": self.labels.join(","),
+        })
+    }
+}
+
+/// The update that would bring `task`'s GitLab issue in line with it, or
+/// `None` if the task was not imported from `config.project`
+pub fn issue_update(task: &Task, config: &GitLabConfig) -> Result<Option<IssueUpdate>> {
+    let issue_prefix = format!("{}#", config.project);
+    let Some(external) = task
+        .external_refs
+        .iter()
+        .find(|r| r.system == GITLAB_SYSTEM && r.id.starts_with(&issue_prefix))
+    else {
+        return Ok(None);
+    };
+    let iid = external.id[issue_prefix.len()..].parse().map_err(|_| {
+        HlaviError::Validation(format!("Invalid GitLab issue ref: {}", external.id))
+    })?;
+
+    let finished = matches!(task.status, TaskStatus::Done | TaskStatus::Closed);
+    let mut labels = task.tags.clone();
+    if !finished {
+        labels.push(format!("{}{}", config.status_prefix(), task.status));
+    }
+    Ok(Some(IssueUpdate {
+        project: config.project.clone(),
+        iid,
+        state_event: if finished { "close" } else { "reopen" }.to_string(),
+        labels,
+    }))
+}
+
+/// Updates for every active task imported from `config.project`, for
+/// pushing the board's state back to GitLab
+pub async fn issue_updates<S: Storage>(
+    service: &BoardService<S>,
+    config: &GitLabConfig,
+) -> Result<Vec<IssueUpdate>> {
+    let mut updates = Vec::new();
+    for task in service.list_tasks().await? {
+        if let Some(update) = issue_update(&task, config)? {
+            updates.push(update);
+        }
+    }
+    Ok(updates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ExternalRef, TaskId};
+
+    #[test]
+    fn test_parse_and_push_back() {
+        let importer = GitLabImporter::new(GitLabConfig::new("group/app"));
+        let input = r#"[
+            {"iid": 12, "title": "Fix login", "state": "opened",
+             "labels": ["bug", "workflow::In Progress"],
+             "assignees": [{"username": "ana"}], "weight": 2,
+             "epic": {"iid": 3, "title": "Auth"}, "milestone": {"title": "v2"}},
+            {"iid": 13, "title": "Old thing", "state": "closed",
+             "labels": ["workflow::Review"]}
+        ]"#;
+        let records = importer.parse(input).unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].external_id, "group/app&3");
+        assert_eq!(records[1].external_id, "group/app#12");
+        assert_eq!(records[1].status.as_deref(), Some("In Progress"));
+        assert_eq!(records[1].tags, vec!["bug".to_string()]);
+        assert_eq!(records[1].parent.as_deref(), Some("group/app&3"));
+        assert_eq!(records[2].status.as_deref(), Some("closed"));
+        assert_eq!(
+            GitLabImporter::default_mapping().map_status("closed"),
+            Some(TaskStatus::Done)
+        );
+
+        let mut task = Task::new(TaskId::new(1), "Fix login".to_string());
+        assert_eq!(issue_update(&task, &importer.config).unwrap(), None);
+        task.add_external_ref(ExternalRef::new(GITLAB_SYSTEM, "group/app#12"));
+        task.add_tag("bug".to_string());
+        task.status = TaskStatus::Review;
+        let update = issue_update(&task, &importer.config).unwrap().unwrap();
+        assert_eq!(update.path(), "/projects/group%2Fapp/issues/12");
+        assert_eq!(update.state_event, "reopen");
+        assert_eq!(update.body()["labels"], "bug,workflow::Review");
+    }
+}
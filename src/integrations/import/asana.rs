@@ -0,0 +1,193 @@
+//! Importing tasks from Asana.
+//!
+//! Reads Asana's JSON export of a project, or the response of its tasks
+//! API (`{"data": [...]}`), with these fields present:
+//!
+//! ```text
+//! gid name notes completed created_at permalink_url due_on assignee.name
+//! memberships.(project.gid|project.name|section.name) tags.name
+//! parent.gid custom_fields.(name|display_value)
+//! ```
+//!
+//! A task's status is the name of its board section, or `Completed` once it
+//! is marked complete. Asana has no built-in priority, so a custom field
+//! named "Priority" is used when present; other custom fields are reported
+//! as unmapped. Projects become epics, the parent of their top-level tasks;
+//! subtasks keep their parent task.
+
+use super::{project_epic, unwrap_items, ExternalTask, ImportMapping, Importer};
+use crate::{domain::TaskStatus, error::Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+
+/// Importer for Asana tasks
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AsanaImporter;
+
+impl AsanaImporter {
+    /// Mapping for the sections of Asana's default board template
+    pub fn default_mapping() -> ImportMapping {
+        let mut mapping = ImportMapping::default();
+        for (section, status) in [
+            ("Untitled section", TaskStatus::New),
+            ("To do", TaskStatus::Open),
+            ("Doing", TaskStatus::InProgress),
+            ("Done", TaskStatus::Done),
+            ("Completed", TaskStatus::Done),
+        ] {
+            mapping.statuses.insert(section.to_string(), status);
+        }
+        mapping
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AsanaTask {
+    gid: String,
+    name: String,
+    #[serde(default)]
+    notes: Option<String>,
+    #[serde(default)]
+    completed: bool,
+    #[serde(default)]
+    assignee: Option<Named>,
+    #[serde(default)]
+    memberships: Vec<Membership>,
+    #[serde(default)]
+    tags: Vec<Named>,
+    #[serde(default)]
+    parent: Option<Parent>,
+    #[serde(default)]
+    custom_fields: Vec<CustomField>,
+    #[serde(default)]
+    due_on: Option<String>,
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    permalink_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Named {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Membership {
+    #[serde(default)]
+    project: Option<Project>,
+    #[serde(default)]
+    section: Option<Named>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    gid: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Parent {
+    gid: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CustomField {
+    name: String,
+    #[serde(default)]
+    display_value: Option<String>,
+}
+
+impl Importer for AsanaImporter {
+    fn system(&self) -> &str {
+        "asana"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<ExternalTask>> {
+        let tasks: Vec<AsanaTask> = unwrap_items(input, &["data"])?;
+        let mut projects = BTreeSet::new();
+        let mut records = Vec::new();
+
+        for task in tasks {
+            let membership = task.memberships.into_iter().find(|m| m.project.is_some());
+            let (project, section) = match membership {
+                Some(m) => (m.project, m.section.map(|s| s.name)),
+                None => (None, None),
+            };
+            let project = project.map(|project| {
+                let external_id = format!("project:{}", project.gid);
+                if projects.insert(external_id.clone()) {
+                    records.push(project_epic(external_id.clone(), &project.name));
+                }
+                external_id
+            });
+
+            let mut record = ExternalTask {
+                external_id: task.gid,
+                title: task.name,
+                description: task.notes.filter(|notes| !notes.trim().is_empty()),
+                status: if task.completed {
+                    Some("Completed".to_string())
+                } else {
+                    section
+                },
+                tags: task.tags.into_iter().map(|tag| tag.name).collect(),
+                assignee: task.assignee.map(|assignee| assignee.name),
+                created_at: task.created_at,
+                url: task.permalink_url,
+                parent: task.parent.map(|parent| parent.gid).or(project),
+                ..ExternalTask::default()
+            };
+            for field in task.custom_fields {
+                let Some(value) = field.display_value else {
+                    continue;
+                };
+                if field.name.eq_ignore_ascii_case("priority") {
+                    record.priority = Some(value);
+                } else {
+                    record.extra.insert(field.name, value);
+                }
+            }
+            if let Some(due) = task.due_on {
+                record.extra.insert("due_on".to_string(), due);
+            }
+            records.push(record);
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_project_export() {
+        let input = r#"{"data": [
+            {"gid": "1201", "name": "Launch site", "completed": false,
+             "memberships": [{"project": {"gid": "900", "name": "Website"},
+                              "section": {"name": "Doing"}}],
+             "custom_fields": [{"name": "Priority", "display_value": "High"},
+                               {"name": "Team", "display_value": "Web"}]},
+            {"gid": "1202", "name": "Write copy", "completed": true,
+             "parent": {"gid": "1201"}, "tags": [{"name": "content"}]}
+        ]}"#;
+        let records = AsanaImporter.parse(input).unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].external_id, "project:900");
+        assert_eq!(records[1].status.as_deref(), Some("Doing"));
+        assert_eq!(records[1].priority.as_deref(), Some("High"));
+        assert_eq!(records[1].parent.as_deref(), Some("project:900"));
+        assert_eq!(
+            records[1].extra.get("Team").map(String::as_str),
+            Some("Web")
+        );
+        assert_eq!(records[2].status.as_deref(), Some("Completed"));
+        assert_eq!(records[2].parent.as_deref(), Some("1201"));
+
+        let mapping = AsanaImporter::default_mapping();
+        assert_eq!(mapping.map_status("completed"), Some(TaskStatus::Done));
+    }
+}
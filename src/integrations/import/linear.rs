@@ -0,0 +1,209 @@
+//! Importing issues from Linear.
+//!
+//! Reads the `issues` result of Linear's GraphQL API, either the whole
+//! response (`{"data": {"issues": {"nodes": [...]}}}`) or the bare list of
+//! issue nodes. The query should select:
+//!
+//! ```graphql
+//! identifier title description priority estimate createdAt url dueDate
+//! state { name } labels { nodes { name } } assignee { name }
+//! project { id name } parent { identifier } cycle { number }
+//! ```
+//!
+//! Projects become epics: each project is imported as a task of its own and
+//! becomes the parent of its issues. Sub-issues keep their parent issue.
+
+use super::{project_epic, unwrap_items, ExternalTask, ImportMapping, Importer};
+use crate::{
+    domain::{Priority, TaskStatus},
+    error::Result,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+
+/// Importer for Linear issues
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LinearImporter;
+
+impl LinearImporter {
+    /// Mapping for Linear's default workflow states and priorities
+    pub fn default_mapping() -> ImportMapping {
+        let mut mapping = ImportMapping::default();
+        for (state, status) in [
+            ("Triage", TaskStatus::New),
+            ("Backlog", TaskStatus::New),
+            ("Todo", TaskStatus::Open),
+            ("In Progress", TaskStatus::InProgress),
+            ("In Review", TaskStatus::Review),
+            ("Done", TaskStatus::Done),
+            ("Canceled", TaskStatus::Closed),
+            ("Duplicate", TaskStatus::Closed),
+        ] {
+            mapping.statuses.insert(state.to_string(), status);
+        }
+        for (label, priority) in [
+            ("Urgent", Priority::Critical),
+            ("High", Priority::High),
+            ("Medium", Priority::Medium),
+            ("Low", Priority::Low),
+        ] {
+            mapping.priorities.insert(label.to_string(), priority);
+        }
+        mapping
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Issue {
+    identifier: String,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    state: Option<Named>,
+    /// 0 is no priority, then 1 (urgent) to 4 (low)
+    #[serde(default)]
+    priority: Option<u8>,
+    #[serde(default)]
+    estimate: Option<f64>,
+    #[serde(default)]
+    labels: Option<Nodes<Named>>,
+    #[serde(default)]
+    assignee: Option<Named>,
+    #[serde(default)]
+    project: Option<Project>,
+    #[serde(default)]
+    parent: Option<ParentIssue>,
+    #[serde(default)]
+    cycle: Option<Cycle>,
+    #[serde(default)]
+    due_date: Option<String>,
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Named {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParentIssue {
+    identifier: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Cycle {
+    number: u32,
+}
+
+fn priority_label(priority: u8) -> Option<&'static str> {
+    match priority {
+        1 => Some("Urgent"),
+        2 => Some("High"),
+        3 => Some("Medium"),
+        4 => Some("Low"),
+        _ => None,
+    }
+}
+
+impl Importer for LinearImporter {
+    fn system(&self) -> &str {
+        "linear"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<ExternalTask>> {
+        let issues: Vec<Issue> = unwrap_items(input, &["data", "issues", "nodes"])?;
+        let mut projects = BTreeSet::new();
+        let mut records = Vec::new();
+
+        for issue in issues {
+            let project = issue.project.map(|project| {
+                let external_id = format!("project:{}", project.id);
+                if projects.insert(external_id.clone()) {
+                    records.push(project_epic(external_id.clone(), &project.name));
+                }
+                external_id
+            });
+            let mut record = ExternalTask {
+                external_id: issue.identifier,
+                title: issue.title,
+                description: issue.description.filter(|d| !d.trim().is_empty()),
+                status: issue.state.map(|state| state.name),
+                priority: issue.priority.and_then(priority_label).map(str::to_string),
+                tags: issue
+                    .labels
+                    .map(|labels| labels.nodes.into_iter().map(|l| l.name).collect())
+                    .unwrap_or_default(),
+                assignee: issue.assignee.map(|assignee| assignee.name),
+                estimate: issue.estimate.map(|points| points.round() as u32),
+                created_at: issue.created_at,
+                url: issue.url,
+                parent: issue.parent.map(|parent| parent.identifier).or(project),
+                ..ExternalTask::default()
+            };
+            if let Some(cycle) = issue.cycle {
+                record
+                    .extra
+                    .insert("cycle".to_string(), cycle.number.to_string());
+            }
+            if let Some(due) = issue.due_date {
+                record.extra.insert("dueDate".to_string(), due);
+            }
+            records.push(record);
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_graphql_response() {
+        let input = r#"{"data": {"issues": {"nodes": [
+            {"identifier": "ENG-1", "title": "Checkout revamp", "priority": 2,
+             "state": {"name": "In Progress"}, "project": {"id": "p1", "name": "Payments"},
+             "labels": {"nodes": [{"name": "frontend"}]}, "estimate": 3,
+             "cycle": {"number": 12}},
+            {"identifier": "ENG-2", "title": "Card form", "priority": 0,
+             "state": {"name": "Todo"}, "project": {"id": "p1", "name": "Payments"},
+             "parent": {"identifier": "ENG-1"}}
+        ]}}}"#;
+        let records = LinearImporter.parse(input).unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].external_id, "project:p1");
+        assert_eq!(records[0].title, "Payments");
+        assert_eq!(records[1].parent.as_deref(), Some("project:p1"));
+        assert_eq!(records[1].priority.as_deref(), Some("High"));
+        assert_eq!(records[1].tags, vec!["frontend".to_string()]);
+        assert_eq!(records[1].estimate, Some(3));
+        assert_eq!(
+            records[1].extra.get("cycle").map(String::as_str),
+            Some("12")
+        );
+        assert_eq!(records[2].parent.as_deref(), Some("ENG-1"));
+        assert_eq!(records[2].priority, None);
+
+        let mapping = LinearImporter::default_mapping();
+        assert_eq!(mapping.map_status("todo"), Some(TaskStatus::Open));
+        assert_eq!(mapping.map_priority("Urgent"), Some(Priority::Critical));
+    }
+}
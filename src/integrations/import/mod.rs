@@ -0,0 +1,669 @@
+//! Bringing tasks over from other trackers.
+//!
+//! Each source tool has an [`Importer`] that turns the tool's export into
+//! neutral [`ExternalTask`] records. Nothing is written straight away:
+//! [`plan_import`] maps the records onto the board and returns an
+//! [`ImportPlan`] listing the tasks to create, how statuses were mapped,
+//! which source fields have nowhere to go, which records collide with
+//! tasks already on the board and which would fail the board's checks. Once reviewed, the plan is carried out with
+//! [`apply_import`].
+//!
+//! Imported tasks carry an external ref to their source record, so running
+//! the same import twice reports collisions instead of creating duplicates.
+//!
+//! Importers for specific tools live in the submodules.
+
+pub mod asana;
+pub mod linear;
+
+use crate::{
+    domain::{Board, ExternalRef, Priority, Task, TaskId, TaskStatus},
+    error::{HlaviError, Result},
+    service::BoardService,
+    storage::Storage,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+/// Reads a source tool's export
+pub trait Importer {
+    /// External ref system recorded on imported tasks, such as "linear"
+    fn system(&self) -> &str;
+
+    /// Parses an export into neutral records
+    fn parse(&self, input: &str) -> Result<Vec<ExternalTask>>;
+}
+
+/// A task as read from another tracker, before mapping
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExternalTask {
+    /// Identifier in the source tool, such as `ENG-42`
+    pub external_id: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Status name as the source tool spells it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// External ID of the parent record, such as the epic a story belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Source fields with no Hlavi equivalent, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, String>,
+}
+
+/// How source values translate to Hlavi
+///
+/// Status and priority names are matched case-insensitively. Names without
+/// an entry fall back to Hlavi's own spelling (`In Progress`, `high`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImportMapping {
+    pub statuses: BTreeMap<String, TaskStatus>,
+    pub priorities: BTreeMap<String, Priority>,
+    /// Status given to tasks whose source status cannot be mapped
+    pub default_status: TaskStatus,
+    /// Keep the number of source IDs that fall in a reserved range, so
+    /// `ENG-42` becomes `HLA42`
+    pub keep_numbers: bool,
+}
+
+impl Default for ImportMapping {
+    fn default() -> Self {
+        Self {
+            statuses: BTreeMap::new(),
+            priorities: BTreeMap::new(),
+            default_status: TaskStatus::New,
+            keep_numbers: false,
+        }
+    }
+}
+
+impl ImportMapping {
+    pub fn map_status(&self, status: &str) -> Option<TaskStatus> {
+        lookup(&self.statuses, status).or_else(|| TaskStatus::from_str(status).ok())
+    }
+
+    pub fn map_priority(&self, priority: &str) -> Option<Priority> {
+        lookup(&self.priorities, priority).or_else(|| Priority::from_str(priority).ok())
+    }
+}
+
+fn lookup<T: Clone>(map: &BTreeMap<String, T>, key: &str) -> Option<T> {
+    map.iter()
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case(key.trim()))
+        .map(|(_, value)| value.clone())
+}
+
+/// A task the import will create
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedTask {
+    pub external_id: String,
+    /// The ID kept from the source, or `None` to mint a fresh one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<TaskId>,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_status: Option<String>,
+    pub status: TaskStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// External ID of the parent, which is either earlier in the plan or
+    /// already imported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+}
+
+impl PlannedTask {
+    /// Builds the task under `id`, with an external ref back to the source
+    ///
+    /// The task moves to its status directly, whatever the workflow, and the
+    /// move is recorded in its history.
+    pub fn build(&self, system: &str, id: TaskId) -> Result<Task> {
+        let mut task = Task::try_new(id, &self.title)?;
+        if let Some(description) = &self.description {
+            task.set_description(description.clone())?;
+        }
+        if task.status != self.status {
+            task.enter_status(self.status.clone(), None);
+        }
+        task.priority = self.priority;
+        for tag in &self.tags {
+            task.add_tag(tag.clone());
+        }
+        task.assignee = self.assignee.clone();
+        task.estimate = self.estimate;
+        if let Some(created_at) = self.created_at {
+            task.created_at = created_at;
+        }
+        let mut external = ExternalRef::new(system, self.external_id.as_str());
+        external.url = self.url.clone();
+        task.add_external_ref(external);
+        Ok(task)
+    }
+}
+
+/// A source record left out of the import
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportCollision {
+    pub external_id: String,
+    /// The task already standing in the record's way
+    pub existing: TaskId,
+    pub reason: String,
+}
+
+/// A source record left out of the import because the board would reject it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvalidRecord {
+    pub external_id: String,
+    pub reason: String,
+}
+
+/// What an import would do, for review before anything is written
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportPlan {
+    /// External ref system of the source tool
+    pub system: String,
+    pub tasks: Vec<PlannedTask>,
+    /// Every source status seen and what it maps to
+    pub status_mappings: BTreeMap<String, TaskStatus>,
+    /// Source statuses with no mapping; their tasks get the default status
+    pub unmapped_statuses: Vec<String>,
+    /// Source priorities with no mapping; their tasks get no priority
+    pub unmapped_priorities: Vec<String>,
+    /// Source fields that will not be carried over
+    pub unmapped_fields: Vec<String>,
+    /// Parents referenced by records but found neither in the import nor on
+    /// the board; their children are imported without a parent
+    pub missing_parents: Vec<String>,
+    pub collisions: Vec<ImportCollision>,
+    /// Records with an invalid title or description, or missing fields
+    /// their column requires
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub invalid: Vec<InvalidRecord>,
+}
+
+impl ImportPlan {
+    /// Maps `records` onto the board without touching it
+    pub fn build(
+        system: &str,
+        records: Vec<ExternalTask>,
+        mapping: &ImportMapping,
+        board: &Board,
+        existing: &[Task],
+        prefix: &str,
+    ) -> Self {
+        let mut plan = Self {
+            system: system.to_string(),
+            tasks: Vec::new(),
+            status_mappings: BTreeMap::new(),
+            unmapped_statuses: Vec::new(),
+            unmapped_priorities: Vec::new(),
+            unmapped_fields: Vec::new(),
+            missing_parents: Vec::new(),
+            collisions: Vec::new(),
+            invalid: Vec::new(),
+        };
+        let imported_as = |external_id: &str| {
+            existing.iter().find(|task| {
+                task.external_refs
+                    .iter()
+                    .any(|r| r.system.eq_ignore_ascii_case(system) && r.id == external_id)
+            })
+        };
+
+        for record in records {
+            if let Some(task) = imported_as(&record.external_id) {
+                plan.collisions.push(ImportCollision {
+                    external_id: record.external_id,
+                    existing: task.id.clone(),
+                    reason: "already imported".to_string(),
+                });
+                continue;
+            }
+
+            let id = mapping
+                .keep_numbers
+                .then(|| kept_id(&record.external_id, board, prefix))
+                .flatten();
+            if let Some(taken) = id
+                .as_ref()
+                .and_then(|id| existing.iter().find(|task| &task.id == id))
+            {
+                plan.collisions.push(ImportCollision {
+                    external_id: record.external_id,
+                    existing: taken.id.clone(),
+                    reason: "ID already in use".to_string(),
+                });
+                continue;
+            }
+
+            let status = match &record.status {
+                Some(source) => match mapping.map_status(source) {
+                    Some(status) => {
+                        plan.status_mappings.insert(source.clone(), status.clone());
+                        status
+                    }
+                    None => {
+                        push_unique(&mut plan.unmapped_statuses, source);
+                        mapping.default_status.clone()
+                    }
+                },
+                None => mapping.default_status.clone(),
+            };
+            let priority = record.priority.as_deref().and_then(|source| {
+                let priority = mapping.map_priority(source);
+                if priority.is_none() {
+                    push_unique(&mut plan.unmapped_priorities, source);
+                }
+                priority
+            });
+            for field in record.extra.keys() {
+                push_unique(&mut plan.unmapped_fields, field);
+            }
+
+            let planned = PlannedTask {
+                external_id: record.external_id,
+                id,
+                title: record.title,
+                description: record.description,
+                source_status: record.status,
+                status,
+                priority,
+                tags: record.tags,
+                assignee: record.assignee,
+                estimate: record.estimate,
+                created_at: record.created_at,
+                url: record.url,
+                parent: record.parent,
+            };
+            // Checked the way creating the task will check it
+            let check_id = planned
+                .id
+                .clone()
+                .unwrap_or_else(|| TaskId::with_prefix(prefix, board.next_task_number));
+            let checked = planned
+                .build(system, check_id)
+                .and_then(|task| board.config.check_required_fields(&task, None));
+            if let Err(err) = checked {
+                plan.invalid.push(InvalidRecord {
+                    external_id: planned.external_id,
+                    reason: err.to_string(),
+                });
+                continue;
+            }
+            plan.tasks.push(planned);
+        }
+        plan.resolve_parents(|external_id| imported_as(external_id).is_some());
+        plan
+    }
+
+    /// Drops parents that cannot be found and moves parents ahead of their
+    /// children, so each task's parent exists by the time it is created
+    fn resolve_parents(&mut self, on_board: impl Fn(&str) -> bool) {
+        let planned: Vec<String> = self.tasks.iter().map(|t| t.external_id.clone()).collect();
+        for task in &mut self.tasks {
+            let Some(parent) = &task.parent else {
+                continue;
+            };
+            if !planned.contains(parent) && !on_board(parent) {
+                push_unique(&mut self.missing_parents, parent);
+                task.parent = None;
+            }
+        }
+
+        let parents: HashMap<&str, &str> = self
+            .tasks
+            .iter()
+            .filter_map(|t| Some((t.external_id.as_str(), t.parent.as_deref()?)))
+            .collect();
+        let depth = |external_id: &str| {
+            let mut depth = 0;
+            let mut current = external_id;
+            // Bounded so a cycle in the source cannot loop forever
+            while let Some(parent) = parents.get(current) {
+                if depth > parents.len() {
+                    break;
+                }
+                depth += 1;
+                current = parent;
+            }
+            depth
+        };
+        let depths: HashMap<String, usize> =
+            planned.iter().map(|id| (id.clone(), depth(id))).collect();
+        self.tasks.sort_by_key(|t| depths[&t.external_id]);
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.unmapped_statuses.is_empty()
+            && self.unmapped_priorities.is_empty()
+            && self.unmapped_fields.is_empty()
+            && self.missing_parents.is_empty()
+            && self.collisions.is_empty()
+            && self.invalid.is_empty()
+    }
+}
+
+/// The source ID's number under `prefix`, if it falls in a reserved range
+fn kept_id(external_id: &str, board: &Board, prefix: &str) -> Option<TaskId> {
+    let start = external_id
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    let number: u32 = external_id[start..].parse().ok()?;
+    board
+        .reserved_range(number)
+        .map(|_| TaskId::with_prefix(prefix, number))
+}
+
+/// Record standing in for a source project, imported as the epic its
+/// tasks hang under
+fn project_epic(external_id: String, name: &str) -> ExternalTask {
+    ExternalTask {
+        external_id,
+        title: name.to_string(),
+        ..ExternalTask::default()
+    }
+}
+
+/// Items of an export that is either a bare JSON array or wrapped in an API
+/// response envelope at `path`
+fn unwrap_items<T: serde::de::DeserializeOwned>(input: &str, path: &[&str]) -> Result<Vec<T>> {
+    let mut value: serde_json::Value = serde_json::from_str(input)?;
+    if !value.is_array() {
+        for key in path {
+            value = value
+                .get_mut(*key)
+                .map(serde_json::Value::take)
+                .ok_or_else(|| HlaviError::Validation(format!("Export has no '{}' field", key)))?;
+        }
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+fn push_unique(list: &mut Vec<String>, value: &str) {
+    if !list.iter().any(|v| v.eq_ignore_ascii_case(value)) {
+        list.push(value.to_string());
+    }
+}
+
+/// Parses `input` with `importer` and plans the import without writing
+/// anything
+///
+/// Assignees are mapped through the project's identities, so a source
+/// username becomes the person's display name.
+pub async fn plan_import<S: Storage>(
+    service: &BoardService<S>,
+    importer: &dyn Importer,
+    input: &str,
+    mapping: &ImportMapping,
+) -> Result<ImportPlan> {
+    let mut records = importer.parse(input)?;
+    let storage = service.storage();
+    let config = storage.load_project_config().await?;
+    for record in &mut records {
+        if let Some(assignee) = &record.assignee {
+            record.assignee = Some(
+                config
+                    .identities
+                    .canonical_account(importer.system(), assignee),
+            );
+        }
+    }
+    let board = storage.load_board().await?;
+    let existing = storage.load_all_tasks().await?;
+    Ok(ImportPlan::build(
+        importer.system(),
+        records,
+        mapping,
+        &board,
+        &existing,
+        &config.id_prefix,
+    ))
+}
+
+/// Creates the tasks in a reviewed plan, in order, returning them
+///
+/// Tasks with a kept ID go through `BoardService::import_task`; the rest get
+/// fresh IDs. Parents are linked to the tasks created for them, or to tasks
+/// imported earlier. The first failure stops the import, leaving earlier
+/// tasks in place; since they carry external refs, planning again skips
+/// them.
+pub async fn apply_import<S: Storage>(
+    service: &BoardService<S>,
+    plan: &ImportPlan,
+) -> Result<Vec<Task>> {
+    let mut ids: HashMap<String, TaskId> = HashMap::new();
+    for task in service.storage().load_all_tasks().await? {
+        for external in &task.external_refs {
+            if external.system.eq_ignore_ascii_case(&plan.system) {
+                ids.insert(external.id.clone(), task.id.clone());
+            }
+        }
+    }
+
+    let mut created = Vec::new();
+    for planned in &plan.tasks {
+        let parent = planned.parent.as_ref().and_then(|p| ids.get(p)).cloned();
+        let build = |id: TaskId| {
+            let mut task = planned.build(&plan.system, id)?;
+            task.parent = parent.clone();
+            Ok(task)
+        };
+        let task = match &planned.id {
+            Some(id) => service.import_task(build(id.clone())?).await?,
+            None => service.create_task_with(build).await?,
+        };
+        ids.insert(planned.external_id.clone(), task.id.clone());
+        created.push(task);
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::file_storage::FileStorage;
+    use tempfile::TempDir;
+
+    /// Reads `id|title|status|priority|points|assignee` lines
+    struct LineImporter;
+
+    impl Importer for LineImporter {
+        fn system(&self) -> &str {
+            "lines"
+        }
+
+        fn parse(&self, input: &str) -> Result<Vec<ExternalTask>> {
+            Ok(input
+                .lines()
+                .map(|line| {
+                    let fields: Vec<&str> = line.split('|').collect();
+                    let optional = |i: usize| {
+                        fields
+                            .get(i)
+                            .filter(|f| !f.is_empty())
+                            .map(|f| f.to_string())
+                    };
+                    let mut record = ExternalTask {
+                        external_id: fields[0].to_string(),
+                        title: fields[1].to_string(),
+                        status: optional(2),
+                        priority: optional(3),
+                        assignee: optional(5),
+                        ..ExternalTask::default()
+                    };
+                    if let Some(points) = optional(4) {
+                        record.extra.insert("story_points".to_string(), points);
+                    }
+                    record
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plan_then_apply() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        let mut config = crate::domain::ProjectConfig::default();
+        config
+            .identities
+            .add(crate::domain::Identity::new("Ana Lee").with_account("lines", "ana.l"));
+        storage.save_project_config(&config).await.unwrap();
+        let service = BoardService::new(storage);
+        service.reserve_ids(1, 100, "lines").await.unwrap();
+
+        let input =
+            "ENG-7|Login crash|Triage|Urgent|3|ana.l\nENG-500|Dark mode|In Progress|high||bo";
+        let mut mapping = ImportMapping {
+            keep_numbers: true,
+            ..ImportMapping::default()
+        };
+        mapping
+            .priorities
+            .insert("urgent".to_string(), Priority::Critical);
+
+        let plan = plan_import(&service, &LineImporter, input, &mapping)
+            .await
+            .unwrap();
+        assert!(service.list_tasks().await.unwrap().is_empty());
+        assert_eq!(plan.tasks.len(), 2);
+        assert_eq!(plan.tasks[0].id, Some(TaskId::new(7)));
+        assert_eq!(plan.tasks[0].status, TaskStatus::New);
+        assert_eq!(plan.tasks[0].priority, Some(Priority::Critical));
+        assert_eq!(plan.tasks[0].assignee.as_deref(), Some("Ana Lee"));
+        assert_eq!(plan.tasks[1].assignee.as_deref(), Some("bo"));
+        assert_eq!(plan.tasks[1].id, None);
+        assert_eq!(plan.unmapped_statuses, vec!["Triage".to_string()]);
+        assert_eq!(plan.unmapped_fields, vec!["story_points".to_string()]);
+        assert_eq!(
+            plan.status_mappings.get("In Progress"),
+            Some(&TaskStatus::InProgress)
+        );
+        assert!(!plan.is_clean());
+
+        let created = apply_import(&service, &plan).await.unwrap();
+        assert_eq!(created[0].id, TaskId::new(7));
+        assert_eq!(created[1].id, TaskId::new(101));
+        assert_eq!(created[1].external_refs[0].id, "ENG-500");
+
+        let again = plan_import(&service, &LineImporter, input, &mapping)
+            .await
+            .unwrap();
+        assert!(again.tasks.is_empty());
+        assert_eq!(again.collisions.len(), 2);
+        assert_eq!(again.collisions[0].existing, TaskId::new(7));
+    }
+
+    #[tokio::test]
+    async fn test_apply_links_parents() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        let service = BoardService::new(storage);
+
+        let input = r#"[
+            {"identifier": "ENG-2", "title": "Card form", "parent": {"identifier": "ENG-1"}},
+            {"identifier": "ENG-1", "title": "Checkout", "project": {"id": "p1", "name": "Payments"}},
+            {"identifier": "ENG-3", "title": "Orphan", "parent": {"identifier": "ENG-99"}}
+        ]"#;
+        let plan = plan_import(
+            &service,
+            &linear::LinearImporter,
+            input,
+            &linear::LinearImporter::default_mapping(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(plan.missing_parents, vec!["ENG-99".to_string()]);
+        let order: Vec<&str> = plan.tasks.iter().map(|t| t.external_id.as_str()).collect();
+        assert_eq!(order, vec!["project:p1", "ENG-3", "ENG-1", "ENG-2"]);
+
+        let created = apply_import(&service, &plan).await.unwrap();
+        let epic = &created[0];
+        let checkout = &created[2];
+        assert_eq!(checkout.parent.as_ref(), Some(&epic.id));
+        assert_eq!(created[3].parent.as_ref(), Some(&checkout.id));
+        assert_eq!(created[1].parent, None);
+    }
+
+    #[test]
+    fn test_plan_lists_invalid_records() {
+        let mut board = Board::new(crate::domain::BoardConfig::default());
+        let column = board
+            .config
+            .columns
+            .iter_mut()
+            .find(|c| c.statuses().any(|s| *s == TaskStatus::InProgress))
+            .unwrap();
+        column
+            .required_fields
+            .push(crate::domain::RequiredField::Assignee);
+        let record = |id: &str, title: &str, status: &str| ExternalTask {
+            external_id: id.to_string(),
+            title: title.to_string(),
+            status: Some(status.to_string()),
+            ..ExternalTask::default()
+        };
+        let records = vec![
+            record("ENG-1", "Fine", "Done"),
+            record("ENG-2", &"x".repeat(300), "New"),
+            record("ENG-3", "Unowned", "In Progress"),
+        ];
+
+        let plan = ImportPlan::build(
+            "lines",
+            records,
+            &ImportMapping::default(),
+            &board,
+            &[],
+            "HLA",
+        );
+
+        assert!(!plan.is_clean());
+        let invalid: Vec<&str> = plan
+            .invalid
+            .iter()
+            .map(|r| r.external_id.as_str())
+            .collect();
+        assert_eq!(invalid, vec!["ENG-2", "ENG-3"]);
+        assert_eq!(plan.tasks.len(), 1);
+
+        // The imported task's status move is recorded
+        let task = plan.tasks[0].build("lines", TaskId::new(1)).unwrap();
+        assert_eq!(task.status, TaskStatus::Done);
+        assert_eq!(task.status_history.len(), 1);
+        assert!(task.completed_at.is_some());
+    }
+}
@@ -0,0 +1,7 @@
+//! Glue between Hlavi boards and external tools.
+
+pub mod errors;
+pub mod git;
+#[cfg(feature = "gitlab")]
+pub mod gitlab;
+pub mod import;
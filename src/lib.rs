@@ -5,16 +5,37 @@
 //! This crate provides the fundamental types and operations for managing
 //! kanban boards, tasks, and workflows without any dependency on
 //! specific UI implementations or storage backends.
+//!
+//! With default features disabled only the domain models, metrics and
+//! CSV/HTML export are built, with no async runtime; the `storage` feature
+//! adds storage backends, services and sync.
 
 pub mod domain;
 pub mod error;
+pub mod export;
+#[cfg(feature = "storage")]
+pub mod integrations;
+pub mod metrics;
+#[cfg(feature = "storage")]
+pub mod service;
+#[cfg(feature = "storage")]
 pub mod storage;
+#[cfg(feature = "storage")]
+pub mod sync;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "storage")]
+pub mod workspace;
 
 // Re-export commonly used types
 pub use domain::{
+    automation::{AutomationRule, RuleAction, RuleCondition},
     board::{Board, BoardConfig, Column},
     sorting::{sort_tasks, sort_tasks_for_board, SortField, SortOrder},
-    task::{AcceptanceCriteria, Task, TaskId, TaskStatus},
+    task::{AcceptanceCriteria, Priority, Task, TaskId, TaskStatus},
 };
 pub use error::{HlaviError, Result};
+#[cfg(feature = "storage")]
+pub use service::BoardService;
+#[cfg(feature = "storage")]
 pub use storage::Storage;
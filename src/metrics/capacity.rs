@@ -0,0 +1,157 @@
+//! Who is carrying how much work, for planning meetings.
+
+use crate::domain::{Task, TaskStatus};
+use serde::{Deserialize, Serialize};
+
+/// Which tasks count as load and when someone is overloaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapacityOptions {
+    /// Statuses that count towards load
+    pub statuses: Vec<TaskStatus>,
+    /// More tasks than this flags the assignee as overloaded
+    pub max_tasks: Option<usize>,
+    /// More story points than this flags the assignee as overloaded
+    pub max_estimate: Option<u32>,
+}
+
+impl Default for CapacityOptions {
+    fn default() -> Self {
+        Self {
+            statuses: vec![TaskStatus::Open, TaskStatus::InProgress],
+            max_tasks: None,
+            max_estimate: None,
+        }
+    }
+}
+
+/// Current load of one assignee
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssigneeLoad {
+    pub assignee: String,
+    /// Tasks in any counted status
+    pub tasks: usize,
+    pub in_progress: usize,
+    /// Sum of story points of estimated tasks
+    pub estimate: u32,
+    /// Counted tasks without an estimate
+    pub unestimated: usize,
+    pub overloaded: bool,
+}
+
+/// Load per assignee, busiest first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapacityReport {
+    pub loads: Vec<AssigneeLoad>,
+    /// Counted tasks nobody is assigned to
+    pub unassigned: usize,
+}
+
+impl CapacityReport {
+    pub fn overloaded(&self) -> impl Iterator<Item = &AssigneeLoad> {
+        self.loads.iter().filter(|load| load.overloaded)
+    }
+}
+
+/// Sums the load of every assignee over `tasks`
+///
+/// Assignee names are grouped case-insensitively. To limit the report to a
+/// sprint or view, filter the tasks before passing them in.
+pub fn capacity<'a>(
+    tasks: impl IntoIterator<Item = &'a Task>,
+    options: &CapacityOptions,
+) -> CapacityReport {
+    let mut report = CapacityReport::default();
+
+    for task in tasks {
+        if !task.is_on_board() || !options.statuses.contains(&task.status) {
+            continue;
+        }
+        let Some(assignee) = task.assignee.as_deref() else {
+            report.unassigned += 1;
+            continue;
+        };
+
+        let index = match report
+            .loads
+            .iter()
+            .position(|l| l.assignee.eq_ignore_ascii_case(assignee.trim()))
+        {
+            Some(index) => index,
+            None => {
+                report.loads.push(AssigneeLoad {
+                    assignee: assignee.trim().to_string(),
+                    tasks: 0,
+                    in_progress: 0,
+                    estimate: 0,
+                    unestimated: 0,
+                    overloaded: false,
+                });
+                report.loads.len() - 1
+            }
+        };
+        let load = &mut report.loads[index];
+        load.tasks += 1;
+        if task.status == TaskStatus::InProgress {
+            load.in_progress += 1;
+        }
+        match task.estimate {
+            Some(points) => load.estimate += points,
+            None => load.unestimated += 1,
+        }
+    }
+
+    for load in &mut report.loads {
+        load.overloaded = options.max_tasks.is_some_and(|max| load.tasks > max)
+            || options.max_estimate.is_some_and(|max| load.estimate > max);
+    }
+    report.loads.sort_by(|a, b| {
+        b.estimate
+            .cmp(&a.estimate)
+            .then(b.tasks.cmp(&a.tasks))
+            .then_with(|| a.assignee.cmp(&b.assignee))
+    });
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TaskId;
+
+    fn task(n: u32, assignee: Option<&str>, status: TaskStatus, estimate: Option<u32>) -> Task {
+        let mut task = Task::new(TaskId::new(n), format!("Task {}", n));
+        task.status = status;
+        task.assignee = assignee.map(str::to_string);
+        task.estimate = estimate;
+        task
+    }
+
+    #[test]
+    fn test_capacity() {
+        let tasks = vec![
+            task(1, Some("ana"), TaskStatus::InProgress, Some(5)),
+            task(2, Some("Ana"), TaskStatus::Open, Some(8)),
+            task(3, Some("ben"), TaskStatus::Open, None),
+            task(4, Some("ben"), TaskStatus::Done, Some(13)),
+            task(5, None, TaskStatus::Open, Some(1)),
+        ];
+        let options = CapacityOptions {
+            max_estimate: Some(10),
+            ..CapacityOptions::default()
+        };
+
+        let report = capacity(&tasks, &options);
+        assert_eq!(report.unassigned, 1);
+        assert_eq!(report.loads.len(), 2);
+
+        let ana = &report.loads[0];
+        assert_eq!(ana.assignee, "ana");
+        assert_eq!((ana.tasks, ana.in_progress, ana.estimate), (2, 1, 13));
+        assert!(ana.overloaded);
+
+        let ben = &report.loads[1];
+        assert_eq!((ben.tasks, ben.unestimated), (1, 1));
+        assert!(!ben.overloaded);
+        assert_eq!(report.overloaded().count(), 1);
+    }
+}
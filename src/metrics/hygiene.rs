@@ -0,0 +1,140 @@
+//! How well tasks are written: missing descriptions, work started without
+//! acceptance criteria, and how much detail each task type gets.
+
+use crate::domain::{Task, TaskId, TaskStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Content figures for the tasks of one type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeHygiene {
+    /// `None` for tasks without a type
+    pub task_type: Option<String>,
+    pub tasks: usize,
+    pub average_criteria: f64,
+    /// Mean description length in words, over tasks with a description
+    pub average_words: Option<f64>,
+}
+
+/// Content quality across a set of tasks
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HygieneReport {
+    pub total: usize,
+    /// Tasks with no description, or only whitespace
+    pub empty_description: Vec<TaskId>,
+    /// Tasks that reached In Progress with no acceptance criteria
+    pub started_without_criteria: Vec<TaskId>,
+    /// Mean description length in words, over tasks with a description
+    pub average_words: Option<f64>,
+    /// Per task type, typed tasks first by name, untyped last
+    pub by_type: Vec<TypeHygiene>,
+}
+
+#[derive(Default)]
+struct Totals {
+    tasks: usize,
+    criteria: usize,
+    described: usize,
+    words: usize,
+}
+
+impl Totals {
+    fn add(&mut self, task: &Task, words: Option<usize>) {
+        self.tasks += 1;
+        self.criteria += task.acceptance_criteria.len();
+        if let Some(words) = words {
+            self.described += 1;
+            self.words += words;
+        }
+    }
+
+    fn average_words(&self) -> Option<f64> {
+        (self.described > 0).then(|| self.words as f64 / self.described as f64)
+    }
+}
+
+/// Checks the content of `tasks`
+///
+/// Tasks loaded as headers, with their description left out, are not
+/// counted as having an empty one.
+pub fn hygiene_report<'a>(tasks: impl IntoIterator<Item = &'a Task>) -> HygieneReport {
+    let mut report = HygieneReport::default();
+    let mut overall = Totals::default();
+    let mut by_type: BTreeMap<Option<String>, Totals> = BTreeMap::new();
+
+    for task in tasks {
+        report.total += 1;
+        let words = task
+            .description
+            .as_deref()
+            .map(|d| d.split_whitespace().count())
+            .filter(|&words| words > 0);
+        if words.is_none() && !task.body_omitted {
+            report.empty_description.push(task.id.clone());
+        }
+        let started = task.status == TaskStatus::InProgress
+            || task
+                .status_history
+                .iter()
+                .any(|t| t.to == TaskStatus::InProgress);
+        if started && task.acceptance_criteria.is_empty() {
+            report.started_without_criteria.push(task.id.clone());
+        }
+        overall.add(task, words);
+        by_type
+            .entry(task.task_type.as_ref().map(|t| t.to_lowercase()))
+            .or_default()
+            .add(task, words);
+    }
+
+    report.average_words = overall.average_words();
+    // `None` sorts first in the map; untyped tasks read best last
+    let untyped = by_type.remove(&None);
+    report.by_type = by_type
+        .into_iter()
+        .chain(untyped.map(|totals| (None, totals)))
+        .map(|(task_type, totals)| TypeHygiene {
+            task_type,
+            tasks: totals.tasks,
+            average_criteria: totals.criteria as f64 / totals.tasks as f64,
+            average_words: totals.average_words(),
+        })
+        .collect();
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hygiene_report() {
+        let mut bug = Task::new(TaskId::new(1), "Crash".to_string());
+        bug.task_type = Some("bug".to_string());
+        bug.set_description("Crashes on an empty password".to_string())
+            .unwrap();
+        bug.add_acceptance_criterion("Rejected".to_string());
+        bug.add_acceptance_criterion("Tested".to_string());
+
+        let mut rushed = Task::new(TaskId::new(2), "Rushed".to_string());
+        rushed.task_type = Some("Bug".to_string());
+        rushed.transition_to(TaskStatus::Open, None).unwrap();
+        rushed.transition_to(TaskStatus::InProgress, None).unwrap();
+        rushed.transition_to(TaskStatus::Review, None).unwrap();
+
+        let mut header = Task::new(TaskId::new(3), "Header".to_string());
+        header.body_omitted = true;
+
+        let report = hygiene_report(&[bug, rushed, header]);
+        assert_eq!(report.total, 3);
+        assert_eq!(report.empty_description, vec![TaskId::new(2)]);
+        assert_eq!(report.started_without_criteria, vec![TaskId::new(2)]);
+        assert_eq!(report.average_words, Some(5.0));
+        assert_eq!(report.by_type.len(), 2);
+        assert_eq!(report.by_type[0].task_type.as_deref(), Some("bug"));
+        assert_eq!(report.by_type[0].tasks, 2);
+        assert_eq!(report.by_type[0].average_criteria, 1.0);
+        assert_eq!(report.by_type[1].task_type, None);
+        assert_eq!(report.by_type[1].average_words, None);
+    }
+}
@@ -0,0 +1,13 @@
+//! Reports computed over a set of tasks.
+
+pub mod capacity;
+pub mod hygiene;
+pub mod stats;
+pub mod throughput;
+pub mod timesheet;
+
+pub use capacity::{capacity, AssigneeLoad, CapacityOptions, CapacityReport};
+pub use hygiene::{hygiene_report, HygieneReport, TypeHygiene};
+pub use stats::{stats, BoardStats};
+pub use throughput::{forecast, throughput, Forecast, ForecastOptions, WeeklyThroughput};
+pub use timesheet::{timesheet, DateRange, Timesheet, TimesheetRow};
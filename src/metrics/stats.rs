@@ -0,0 +1,140 @@
+//! A one-call summary of the board for dashboards.
+
+use crate::domain::{Priority, Task, TaskId, TaskStatus};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Headline numbers for a set of tasks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardStats {
+    pub total: usize,
+    /// Every status in workflow order, including empty ones
+    pub by_status: Vec<(TaskStatus, usize)>,
+    pub by_priority: BTreeMap<Priority, usize>,
+    pub unprioritized: usize,
+    /// Tag counts, keyed by lowercased tag
+    pub by_tag: BTreeMap<String, usize>,
+    /// Unfinished tasks whose end date has passed
+    pub overdue: usize,
+    /// Unfinished tasks flagged as blocked
+    pub blocked: usize,
+    /// Mean fraction of completed acceptance criteria over tasks that have
+    /// any, or `None` if no task has criteria
+    pub average_ac_completion: Option<f64>,
+    /// The in-progress task created earliest
+    pub oldest_in_progress: Option<TaskId>,
+}
+
+impl BoardStats {
+    pub fn count(&self, status: &TaskStatus) -> usize {
+        self.by_status
+            .iter()
+            .find(|(s, _)| s == status)
+            .map_or(0, |(_, count)| *count)
+    }
+}
+
+/// Summarizes `tasks` as of `now`
+pub fn stats<'a>(tasks: impl IntoIterator<Item = &'a Task>, now: DateTime<Utc>) -> BoardStats {
+    let mut stats = BoardStats {
+        total: 0,
+        by_status: TaskStatus::ALL.iter().map(|s| (s.clone(), 0)).collect(),
+        by_priority: BTreeMap::new(),
+        unprioritized: 0,
+        by_tag: BTreeMap::new(),
+        overdue: 0,
+        blocked: 0,
+        average_ac_completion: None,
+        oldest_in_progress: None,
+    };
+    let mut ac_fractions = Vec::new();
+    let mut oldest: Option<&Task> = None;
+
+    for task in tasks {
+        stats.total += 1;
+        if let Some((_, count)) = stats.by_status.iter_mut().find(|(s, _)| s == &task.status) {
+            *count += 1;
+        }
+        match task.priority {
+            Some(priority) => *stats.by_priority.entry(priority).or_default() += 1,
+            None => stats.unprioritized += 1,
+        }
+        for tag in &task.tags {
+            *stats.by_tag.entry(tag.to_lowercase()).or_default() += 1;
+        }
+
+        let finished = matches!(task.status, TaskStatus::Done | TaskStatus::Closed);
+        if !finished && task.end_date.is_some_and(|end| end < now) {
+            stats.overdue += 1;
+        }
+        if !finished && task.is_blocked() {
+            stats.blocked += 1;
+        }
+        if !task.acceptance_criteria.is_empty() {
+            let done = task
+                .acceptance_criteria
+                .iter()
+                .filter(|ac| ac.completed)
+                .count();
+            ac_fractions.push(done as f64 / task.acceptance_criteria.len() as f64);
+        }
+        if task.status == TaskStatus::InProgress
+            && oldest.map_or(true, |o| task.created_at < o.created_at)
+        {
+            oldest = Some(task);
+        }
+    }
+
+    if !ac_fractions.is_empty() {
+        stats.average_ac_completion =
+            Some(ac_fractions.iter().sum::<f64>() / ac_fractions.len() as f64);
+    }
+    stats.oldest_in_progress = oldest.map(|task| task.id.clone());
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_stats() {
+        let now = Utc::now();
+        let mut late = Task::new(TaskId::new(1), "Late".to_string());
+        late.status = TaskStatus::InProgress;
+        late.created_at = now - Duration::days(10);
+        late.end_date = Some(now - Duration::days(1));
+        late.set_priority(Priority::High);
+        late.add_tag("Bug".to_string());
+        let id = late.add_acceptance_criterion("a".to_string());
+        late.toggle_criterion(id).unwrap();
+        late.add_acceptance_criterion("b".to_string());
+
+        let mut newer = Task::new(TaskId::new(2), "Newer".to_string());
+        newer.status = TaskStatus::InProgress;
+        newer.add_tag("bug".to_string());
+        let id = newer.add_acceptance_criterion("a".to_string());
+        newer.toggle_criterion(id).unwrap();
+
+        newer.block("Waiting on review", None).unwrap();
+
+        let mut done = Task::new(TaskId::new(3), "Shipped late".to_string());
+        done.status = TaskStatus::Done;
+        done.block("Stale flag", None).unwrap();
+        done.end_date = Some(now - Duration::days(5));
+
+        let stats = stats(&[late, newer, done], now);
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.count(&TaskStatus::InProgress), 2);
+        assert_eq!(stats.count(&TaskStatus::Review), 0);
+        assert_eq!(stats.by_priority[&Priority::High], 1);
+        assert_eq!(stats.unprioritized, 2);
+        assert_eq!(stats.by_tag["bug"], 2);
+        assert_eq!(stats.overdue, 1);
+        assert_eq!(stats.blocked, 1);
+        assert_eq!(stats.average_ac_completion, Some(0.75));
+        assert_eq!(stats.oldest_in_progress, Some(TaskId::new(1)));
+    }
+}
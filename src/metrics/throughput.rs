@@ -0,0 +1,212 @@
+//! Weekly throughput and Monte Carlo completion forecasts.
+//!
+//! The forecaster replays randomly sampled historical weeks until the
+//! remaining work is done, many times over, and reads dates off the
+//! distribution of outcomes: "80% of trials finished within 6 weeks".
+
+use crate::{domain::Task, metrics::timesheet::DateRange};
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Trials that never finish are cut off after this many weeks
+pub const MAX_FORECAST_WEEKS: usize = 520;
+
+/// Tasks completed in the week starting `week_start` (a Monday)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeeklyThroughput {
+    pub week_start: NaiveDate,
+    pub completed: usize,
+}
+
+/// Counts tasks completed per week for every week overlapping `window`,
+/// including weeks with no completions
+pub fn throughput(tasks: &[Task], window: DateRange) -> Vec<WeeklyThroughput> {
+    let first = week_start(window.start);
+    let mut weeks: Vec<WeeklyThroughput> =
+        std::iter::successors(Some(first), |w| Some(*w + Duration::weeks(1)))
+            .take_while(|w| *w <= window.end)
+            .map(|week_start| WeeklyThroughput {
+                week_start,
+                completed: 0,
+            })
+            .collect();
+
+    for task in tasks {
+        let Some(day) = task.completion_time().map(|t| t.date_naive()) else {
+            continue;
+        };
+        if !window.contains(day) {
+            continue;
+        }
+        let index = ((week_start(day) - first).num_days() / 7) as usize;
+        weeks[index].completed += 1;
+    }
+    weeks
+}
+
+fn week_start(day: NaiveDate) -> NaiveDate {
+    day - Duration::days(day.weekday().num_days_from_monday().into())
+}
+
+/// Settings for `forecast`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForecastOptions {
+    pub trials: usize,
+    /// Seed for the sampler, so forecasts are reproducible
+    pub seed: u64,
+}
+
+impl Default for ForecastOptions {
+    fn default() -> Self {
+        Self {
+            trials: 10_000,
+            seed: 0x5eed,
+        }
+    }
+}
+
+/// Distribution of weeks needed to finish the remaining work
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forecast {
+    pub remaining: usize,
+    pub start: NaiveDate,
+    /// Weeks needed by each trial, sorted ascending
+    pub trial_weeks: Vec<usize>,
+}
+
+impl Forecast {
+    /// Weeks within which `confidence` (0.0-1.0) of the trials finished
+    pub fn weeks_at(&self, confidence: f64) -> usize {
+        let confidence = confidence.clamp(0.0, 1.0);
+        let index = ((self.trial_weeks.len() as f64 * confidence).ceil() as usize)
+            .saturating_sub(1)
+            .min(self.trial_weeks.len() - 1);
+        self.trial_weeks[index]
+    }
+
+    /// Date by which the work finishes with the given confidence
+    pub fn date_at(&self, confidence: f64) -> NaiveDate {
+        self.start + Duration::weeks(self.weeks_at(confidence) as i64)
+    }
+}
+
+/// Simulates finishing `remaining` tasks from `start` by resampling the
+/// weekly history
+///
+/// Returns `None` when the history is empty, has no completions at all, or
+/// `options.trials` is zero.
+pub fn forecast(
+    history: &[WeeklyThroughput],
+    remaining: usize,
+    start: NaiveDate,
+    options: &ForecastOptions,
+) -> Option<Forecast> {
+    if options.trials == 0 || history.iter().all(|w| w.completed == 0) {
+        return None;
+    }
+
+    let mut rng = SplitMix64(options.seed);
+    let mut trial_weeks: Vec<usize> = (0..options.trials)
+        .map(|_| {
+            let mut done = 0;
+            let mut weeks = 0;
+            while done < remaining && weeks < MAX_FORECAST_WEEKS {
+                let sample = rng.next() % history.len() as u64;
+                done += history[sample as usize].completed;
+                weeks += 1;
+            }
+            weeks
+        })
+        .collect();
+    trial_weeks.sort_unstable();
+
+    Some(Forecast {
+        remaining,
+        start,
+        trial_weeks,
+    })
+}
+
+/// Small deterministic PRNG; statistical quality is ample for resampling
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{TaskId, TaskStatus};
+    use chrono::{TimeZone, Utc};
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 7, d).unwrap()
+    }
+
+    fn done_on(n: u32, d: u32) -> Task {
+        let mut task = Task::new(TaskId::new(n), "Done".to_string());
+        task.status = TaskStatus::Done;
+        task.completed_at = Some(Utc.from_utc_datetime(&day(d).and_hms_opt(12, 0, 0).unwrap()));
+        task
+    }
+
+    #[test]
+    fn test_weekly_throughput() {
+        // 2024-07-01 is a Monday
+        let tasks = vec![done_on(1, 2), done_on(2, 5), done_on(3, 16), done_on(4, 30)];
+        let weeks = throughput(&tasks, DateRange::new(day(3), day(21)).unwrap());
+
+        let counts: Vec<usize> = weeks.iter().map(|w| w.completed).collect();
+        assert_eq!(weeks[0].week_start, day(1));
+        assert_eq!(counts, vec![1, 0, 1]);
+    }
+
+    #[test]
+    fn test_closed_tasks_count_when_they_were_done() {
+        let mut closed = done_on(1, 10);
+        closed.status = TaskStatus::Closed;
+        let mut rejected = Task::new(TaskId::new(2), "Rejected".to_string());
+        rejected.status = TaskStatus::Closed;
+        let weeks = throughput(
+            &[closed, rejected],
+            DateRange::new(day(8), day(14)).unwrap(),
+        );
+
+        assert_eq!(weeks[0].completed, 1);
+    }
+
+    #[test]
+    fn test_forecast() {
+        let history: Vec<WeeklyThroughput> = [2, 4, 3, 3]
+            .into_iter()
+            .enumerate()
+            .map(|(i, completed)| WeeklyThroughput {
+                week_start: day(1) + Duration::weeks(i as i64),
+                completed,
+            })
+            .collect();
+
+        let forecast = forecast(&history, 24, day(29), &ForecastOptions::default()).unwrap();
+        // Between 6 weeks at four a week and 12 weeks at two a week
+        assert!(forecast.weeks_at(0.0) >= 6);
+        assert!(forecast.weeks_at(1.0) <= 12);
+        assert!(forecast.weeks_at(0.5) <= forecast.weeks_at(0.8));
+        assert_eq!(
+            forecast.date_at(0.8),
+            day(29) + Duration::weeks(forecast.weeks_at(0.8) as i64)
+        );
+
+        let idle = vec![WeeklyThroughput {
+            week_start: day(1),
+            completed: 0,
+        }];
+        assert!(super::forecast(&idle, 5, day(29), &ForecastOptions::default()).is_none());
+    }
+}
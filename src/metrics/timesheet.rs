@@ -0,0 +1,148 @@
+//! Logged time per task and day, for invoicing.
+
+use crate::{
+    domain::{privacy::same_actor, Task, TaskId},
+    error::{HlaviError, Result},
+    export::csv::escape,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Inclusive range of days
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Result<Self> {
+        if start > end {
+            return Err(HlaviError::InvalidDateRange {
+                start: start.to_string(),
+                end: end.to_string(),
+            });
+        }
+        Ok(Self { start, end })
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+/// Minutes one actor logged on one task on one day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimesheetRow {
+    pub date: NaiveDate,
+    pub task_id: TaskId,
+    pub title: String,
+    pub minutes: u32,
+}
+
+/// An actor's logged time within a date range, ordered by day then task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timesheet {
+    pub actor: String,
+    pub range: DateRange,
+    pub rows: Vec<TimesheetRow>,
+}
+
+impl Timesheet {
+    pub fn total_minutes(&self) -> u32 {
+        self.rows.iter().map(|r| r.minutes).sum()
+    }
+
+    /// Minutes per task across the whole range
+    pub fn minutes_by_task(&self) -> BTreeMap<&str, u32> {
+        let mut totals = BTreeMap::new();
+        for row in &self.rows {
+            *totals.entry(row.task_id.as_str()).or_insert(0) += row.minutes;
+        }
+        totals
+    }
+
+    /// Renders the rows as CSV with hours to two decimals
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("date,task,title,hours\r\n");
+        for row in &self.rows {
+            out.push_str(&format!(
+                "{},{},{},{:.2}\r\n",
+                row.date,
+                row.task_id,
+                escape(&row.title),
+                f64::from(row.minutes) / 60.0
+            ));
+        }
+        out
+    }
+}
+
+/// Collects the time `actor` logged on `tasks` within `range`, summed per
+/// task and day
+pub fn timesheet(tasks: &[Task], actor: &str, range: DateRange) -> Timesheet {
+    let mut minutes: BTreeMap<(NaiveDate, &str), (&Task, u32)> = BTreeMap::new();
+    for task in tasks {
+        for entry in &task.work_log {
+            if same_actor(&entry.actor, actor) && range.contains(entry.date) {
+                minutes
+                    .entry((entry.date, task.id.as_str()))
+                    .or_insert((task, 0))
+                    .1 += entry.minutes;
+            }
+        }
+    }
+
+    Timesheet {
+        actor: actor.to_string(),
+        range,
+        rows: minutes
+            .into_iter()
+            .map(|((date, _), (task, minutes))| TimesheetRow {
+                date,
+                task_id: task.id.clone(),
+                title: task.title.clone(),
+                minutes,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::WorkLogEntry;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, d).unwrap()
+    }
+
+    #[test]
+    fn test_timesheet_groups_per_task_and_day() {
+        let mut api = Task::new(TaskId::new(1), "API, v2".to_string());
+        api.log_work(WorkLogEntry::new("dana", day(2), 60)).unwrap();
+        api.log_work(WorkLogEntry::new("Dana", day(2), 30)).unwrap();
+        api.log_work(WorkLogEntry::new("eli", day(2), 45)).unwrap();
+        api.log_work(WorkLogEntry::new("dana", day(9), 15)).unwrap();
+        let mut docs = Task::new(TaskId::new(2), "Docs".to_string());
+        docs.log_work(WorkLogEntry::new("dana", day(1), 120))
+            .unwrap();
+
+        let range = DateRange::new(day(1), day(7)).unwrap();
+        let sheet = timesheet(&[api, docs], "dana", range);
+        assert_eq!(sheet.rows.len(), 2);
+        assert_eq!(sheet.rows[0].task_id, TaskId::new(2));
+        assert_eq!(sheet.rows[1].minutes, 90);
+        assert_eq!(sheet.total_minutes(), 210);
+        assert_eq!(sheet.minutes_by_task()["HLA1"], 90);
+
+        let csv = sheet.to_csv();
+        assert!(csv.contains("2024-05-02,HLA1,\"API, v2\",1.50"));
+    }
+
+    #[test]
+    fn test_invalid_range() {
+        assert!(DateRange::new(day(5), day(1)).is_err());
+    }
+}
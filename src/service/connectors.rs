@@ -0,0 +1,376 @@
+//! Custom integrations written against a stable interface.
+//!
+//! A [`Connector`] turns task events into outbound calls, such as a webhook
+//! POST, and turns payloads received from another tool into operations on
+//! the board. Implementations are registered with a [`ConnectorRegistry`]
+//! under their kind; the board config's `connectors` list says which ones are
+//! enabled, for which events and with which settings.
+//!
+//! Outbound calls are collected rather than sent: the application drains
+//! them with [`ConnectorRegistry::take_outbound`] and delivers them with
+//! whatever HTTP client and retry policy it uses.
+
+use crate::{
+    domain::{
+        automation::RuleAction, BoardConfig, ConnectorConfig, ExternalRef, Task, TaskEvent, TaskId,
+        TaskStatus,
+    },
+    error::{HlaviError, Result},
+    service::{BoardService, EventHandler},
+    storage::Storage,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+/// A request for the application to send on a connector's behalf
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutboundCall {
+    /// Name of the connector config that produced the call
+    pub connector: String,
+    pub method: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+impl OutboundCall {
+    pub fn post(url: &str, body: String) -> Self {
+        Self {
+            connector: String::new(),
+            method: "POST".to_string(),
+            url: url.to_string(),
+            headers: BTreeMap::new(),
+            body: Some(body),
+        }
+    }
+}
+
+/// A change an inbound payload asks for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InboundOperation {
+    CreateTask {
+        title: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        tags: Vec<String>,
+        /// Record in the other tool the task stands for
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        external_ref: Option<ExternalRef>,
+    },
+    /// Moves a task following its type's workflow
+    MoveTask {
+        id: TaskId,
+        status: TaskStatus,
+    },
+    AddTag {
+        id: TaskId,
+        tag: String,
+    },
+    Block {
+        id: TaskId,
+        reason: String,
+    },
+    Unblock {
+        id: TaskId,
+    },
+}
+
+/// An integration with another tool
+pub trait Connector: Send + Sync {
+    /// Kind this implementation handles, matched against
+    /// `ConnectorConfig::kind`
+    fn kind(&self) -> &str;
+
+    /// Calls to make for `event`. `task` is the task after the change, or
+    /// `None` when it was deleted.
+    fn outbound(
+        &self,
+        config: &ConnectorConfig,
+        event: &TaskEvent,
+        task: Option<&Task>,
+    ) -> Result<Vec<OutboundCall>>;
+
+    /// Operations requested by a payload the other tool sent. Connectors
+    /// that only send refuse inbound payloads.
+    fn inbound(&self, config: &ConnectorConfig, _payload: &str) -> Result<Vec<InboundOperation>> {
+        Err(HlaviError::Validation(format!(
+            "Connector '{}' does not accept inbound payloads",
+            config.name
+        )))
+    }
+}
+
+/// The connector implementations an application provides, by kind
+#[derive(Default, Clone)]
+pub struct ConnectorRegistry {
+    connectors: Vec<Arc<dyn Connector>>,
+    outbox: Arc<Mutex<Vec<OutboundCall>>>,
+}
+
+impl ConnectorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an implementation, replacing any earlier one of the same
+    /// kind
+    pub fn register(&mut self, connector: impl Connector + 'static) {
+        self.connectors.retain(|c| c.kind() != connector.kind());
+        self.connectors.push(Arc::new(connector));
+    }
+
+    pub fn get(&self, kind: &str) -> Option<&dyn Connector> {
+        self.connectors
+            .iter()
+            .find(|c| c.kind() == kind)
+            .map(|c| c.as_ref())
+    }
+
+    /// Fails if the board enables a connector kind that is not registered
+    pub fn check(&self, config: &BoardConfig) -> Result<()> {
+        match config
+            .connectors
+            .iter()
+            .find(|c| self.get(&c.kind).is_none())
+        {
+            Some(missing) => Err(HlaviError::ConfigError(format!(
+                "Connector '{}' has kind '{}', which is not registered",
+                missing.name, missing.kind
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Calls every enabled connector subscribed to `event`
+    pub fn outbound(
+        &self,
+        configs: &[ConnectorConfig],
+        event: &TaskEvent,
+        task: Option<&Task>,
+    ) -> Result<Vec<OutboundCall>> {
+        let mut calls = Vec::new();
+        for config in configs.iter().filter(|c| c.wants(&event.kind)) {
+            let Some(connector) = self.get(&config.kind) else {
+                continue;
+            };
+            for mut call in connector.outbound(config, event, task)? {
+                call.connector = config.name.clone();
+                calls.push(call);
+            }
+        }
+        Ok(calls)
+    }
+
+    /// Subscribes the board's connectors to `service`'s events
+    ///
+    /// The connector list is read once, here; attach again after changing it.
+    pub async fn attach<S: Storage>(&self, service: &mut BoardService<S>) -> Result<()> {
+        let board = service.storage().load_board().await?;
+        self.check(&board.config)?;
+        service.subscribe(ConnectorHandler {
+            registry: self.clone(),
+            configs: board.config.connectors,
+        });
+        Ok(())
+    }
+
+    /// Outbound calls produced since the last call, oldest first
+    pub fn take_outbound(&self) -> Vec<OutboundCall> {
+        std::mem::take(&mut *self.outbox.lock().expect("connector outbox lock poisoned"))
+    }
+
+    /// Applies a payload sent to the connector named `name`, returning the
+    /// tasks it created or changed
+    ///
+    /// Operations run in order and the first failure stops the rest.
+    pub async fn receive<S: Storage>(
+        &self,
+        service: &BoardService<S>,
+        name: &str,
+        payload: &str,
+    ) -> Result<Vec<Task>> {
+        let board = service.storage().load_board().await?;
+        let config = board
+            .config
+            .connectors
+            .iter()
+            .find(|c| c.name == name && c.enabled)
+            .ok_or_else(|| {
+                HlaviError::ConfigError(format!("No enabled connector named '{}'", name))
+            })?;
+        let connector = self.get(&config.kind).ok_or_else(|| {
+            HlaviError::ConfigError(format!(
+                "Connector '{}' has kind '{}', which is not registered",
+                config.name, config.kind
+            ))
+        })?;
+
+        let mut touched = Vec::new();
+        for operation in connector.inbound(config, payload)? {
+            touched.push(apply(service, &board.config, operation).await?);
+        }
+        Ok(touched)
+    }
+}
+
+async fn apply<S: Storage>(
+    service: &BoardService<S>,
+    config: &BoardConfig,
+    operation: InboundOperation,
+) -> Result<Task> {
+    match operation {
+        InboundOperation::CreateTask {
+            title,
+            description,
+            tags,
+            external_ref,
+        } => {
+            service
+                .create_task_with(|id| {
+                    let mut task = Task::try_new(id, &title)?;
+                    if let Some(description) = description {
+                        task.set_description(description)?;
+                    }
+                    for tag in tags {
+                        task.add_tag(tag);
+                    }
+                    if let Some(external) = external_ref {
+                        task.add_external_ref(external);
+                    }
+                    Ok(task)
+                })
+                .await
+        }
+        InboundOperation::MoveTask { id, status } => {
+            let mut task = service.load_task(&id).await?;
+            let task_type = task
+                .task_type
+                .as_deref()
+                .and_then(|name| config.task_type(name));
+            task.transition_as(task_type, status, None)?;
+            service.update_task(task).await
+        }
+        InboundOperation::AddTag { id, tag } => {
+            let mut task = service.load_task(&id).await?;
+            task.add_tag(tag);
+            service.update_task(task).await
+        }
+        InboundOperation::Block { id, reason } => service.block_task(&id, &reason).await,
+        InboundOperation::Unblock { id } => service.unblock_task(&id).await,
+    }
+}
+
+/// Forwards events to connectors, queueing their calls in the registry's
+/// outbox
+struct ConnectorHandler {
+    registry: ConnectorRegistry,
+    configs: Vec<ConnectorConfig>,
+}
+
+impl EventHandler for ConnectorHandler {
+    fn handle(&self, event: &TaskEvent, task: Option<&Task>) -> Result<Vec<RuleAction>> {
+        let calls = self.registry.outbound(&self.configs, event, task)?;
+        if !calls.is_empty() {
+            self.registry
+                .outbox
+                .lock()
+                .expect("connector outbox lock poisoned")
+                .extend(calls);
+        }
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::file_storage::FileStorage;
+    use tempfile::TempDir;
+
+    /// Posts event descriptions to a URL and takes `title` lines as new tasks
+    struct Webhook;
+
+    impl Connector for Webhook {
+        fn kind(&self) -> &str {
+            "webhook"
+        }
+
+        fn outbound(
+            &self,
+            config: &ConnectorConfig,
+            event: &TaskEvent,
+            _task: Option<&Task>,
+        ) -> Result<Vec<OutboundCall>> {
+            let url = config.setting("url").unwrap_or_default();
+            Ok(vec![OutboundCall::post(url, event.to_string())])
+        }
+
+        fn inbound(
+            &self,
+            _config: &ConnectorConfig,
+            payload: &str,
+        ) -> Result<Vec<InboundOperation>> {
+            Ok(payload
+                .lines()
+                .map(|title| InboundOperation::CreateTask {
+                    title: title.to_string(),
+                    description: None,
+                    tags: vec!["inbound".to_string()],
+                    external_ref: None,
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connector_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        let mut board = storage.load_board().await.unwrap();
+        let mut config = ConnectorConfig::new("chat", "webhook");
+        config.events = vec!["created".to_string()];
+        config
+            .settings
+            .insert("url".to_string(), "https://chat.example/hook".to_string());
+        board.config.connectors.push(config);
+        storage.save_board(&board).await.unwrap();
+        let mut service = BoardService::new(storage);
+
+        let empty = ConnectorRegistry::new();
+        assert!(matches!(
+            empty.attach(&mut service).await,
+            Err(HlaviError::ConfigError(_))
+        ));
+
+        let mut registry = ConnectorRegistry::new();
+        registry.register(Webhook);
+        registry.attach(&mut service).await.unwrap();
+
+        let task = service.create_task("Outbound".to_string()).await.unwrap();
+        let mut moved = task.clone();
+        moved.transition_to(TaskStatus::Open, None).unwrap();
+        service.update_task(moved).await.unwrap();
+        let calls = registry.take_outbound();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].connector, "chat");
+        assert_eq!(calls[0].url, "https://chat.example/hook");
+        assert!(registry.take_outbound().is_empty());
+
+        let created = registry
+            .receive(&service, "chat", "From chat\nAnother")
+            .await
+            .unwrap();
+        assert_eq!(created.len(), 2);
+        assert!(created[0].has_tag("inbound"));
+        assert_eq!(registry.take_outbound().len(), 2);
+        assert!(registry.receive(&service, "missing", "").await.is_err());
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    domain::{automation::RuleAction, event::TaskEvent, Task},
+    error::Result,
+};
+
+/// Receives task events published by the service layer
+///
+/// Handlers may request follow-up actions on the task. The service applies
+/// them once after all handlers have run, without re-dispatching the event.
+pub trait EventHandler: Send + Sync {
+    /// Handles an event. `task` is the task state after the mutation, or
+    /// `None` when the task was deleted.
+    fn handle(&self, event: &TaskEvent, task: Option<&Task>) -> Result<Vec<RuleAction>>;
+}
+
+/// Dispatches task events to registered handlers in subscription order
+#[derive(Default)]
+pub struct EventBus {
+    handlers: Vec<Box<dyn EventHandler>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a handler
+    pub fn subscribe(&mut self, handler: impl EventHandler + 'static) {
+        self.handlers.push(Box::new(handler));
+    }
+
+    /// Returns true if no handlers are registered
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Publishes an event to every handler, collecting requested follow-up actions
+    pub fn publish(&self, event: &TaskEvent, task: Option<&Task>) -> Result<Vec<RuleAction>> {
+        let mut actions = Vec::new();
+        for handler in &self.handlers {
+            actions.extend(handler.handle(event, task)?);
+        }
+        Ok(actions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{event::TaskEventKind, TaskId};
+    use std::sync::{Arc, Mutex};
+
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl EventHandler for Recorder {
+        fn handle(&self, event: &TaskEvent, _task: Option<&Task>) -> Result<Vec<RuleAction>> {
+            self.0.lock().unwrap().push(event.to_string());
+            Ok(vec![RuleAction::AssignAgent])
+        }
+    }
+
+    #[test]
+    fn test_publish_reaches_all_handlers() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut bus = EventBus::new();
+        bus.subscribe(Recorder(log.clone()));
+        bus.subscribe(Recorder(log.clone()));
+
+        let event = TaskEvent::new(TaskId::new(1), TaskEventKind::Created);
+        let actions = bus.publish(&event, None).unwrap();
+
+        assert_eq!(log.lock().unwrap().len(), 2);
+        assert_eq!(actions.len(), 2);
+    }
+}
@@ -0,0 +1,18 @@
+pub mod board_service;
+pub mod connectors;
+pub mod events;
+pub mod notifications;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+pub mod simulation;
+pub mod view_cache;
+
+pub use board_service::{
+    ActorPurgeReport, BoardService, ConflictResolution, MergeReport, ReconcileReport,
+    RenumberReport, RenumberStrategy, RetentionReport, SimilarTask,
+};
+pub use connectors::{Connector, ConnectorRegistry, InboundOperation, OutboundCall};
+pub use events::{EventBus, EventHandler};
+pub use notifications::NotificationScheduler;
+pub use simulation::{SimulatedOperation, Simulation, SimulationReport};
+pub use view_cache::{CacheStats, ViewCache};
@@ -0,0 +1,299 @@
+//! Delivering task events to people as digests.
+//!
+//! A [`NotificationScheduler`] subscribes to the service's events and queues
+//! each one for the task's assignee. It also tracks which tasks match each
+//! saved view that has subscribers, queueing a `view_match` event for them
+//! whenever a task starts or stops matching. The application calls
+//! [`NotificationScheduler::take_due`] on a timer and sends the digests it
+//! returns; what is still waiting is written back to storage, so a restart
+//! does not lose it.
+
+use crate::{
+    domain::{
+        automation::RuleAction, privacy::same_actor, Digest, NotificationQueue,
+        NotificationSettings, SavedView, Task, TaskEvent, TaskEventKind, TaskId, TaskLocation,
+    },
+    error::Result,
+    service::{BoardService, EventHandler},
+    storage::Storage,
+};
+use chrono::{DateTime, Utc};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+/// Queues task events per person and releases them as digests
+#[derive(Clone)]
+pub struct NotificationScheduler {
+    queue: Arc<Mutex<NotificationQueue>>,
+    settings: NotificationSettings,
+}
+
+impl NotificationScheduler {
+    /// Loads the pending queue and subscribes to `service`'s events
+    ///
+    /// Changes made by the service's actor are not sent to that actor. The
+    /// board's notification settings and view subscriptions are read once,
+    /// here, along with which tasks currently match each subscribed view;
+    /// from then on matches are updated one event at a time.
+    pub async fn attach<S: Storage>(service: &mut BoardService<S>) -> Result<Self> {
+        let queue = service.storage().load_notification_queue().await?;
+        let board = service.storage().load_board().await?;
+        let watched: Vec<SavedView> = board
+            .config
+            .views
+            .into_iter()
+            .filter(|view| !view.subscribers.is_empty())
+            .collect();
+        let tasks = if watched.is_empty() {
+            Vec::new()
+        } else {
+            service.list_tasks().await?
+        };
+        let watches = watched
+            .into_iter()
+            .map(|view| ViewWatch {
+                matching: tasks
+                    .iter()
+                    .filter(|task| in_view(&view, task))
+                    .map(|task| task.id.clone())
+                    .collect(),
+                view,
+            })
+            .collect();
+
+        let scheduler = Self {
+            queue: Arc::new(Mutex::new(queue)),
+            settings: board.config.notifications,
+        };
+        service.subscribe(NotificationHandler {
+            queue: scheduler.queue.clone(),
+            actor: service.actor().map(str::to_string),
+            watches: Mutex::new(watches),
+        });
+        Ok(scheduler)
+    }
+
+    /// Number of notifications waiting
+    pub fn pending(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Removes the digests due at `now` and saves what is left
+    pub async fn take_due<S: Storage>(
+        &self,
+        service: &BoardService<S>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Digest>> {
+        let (due, remaining) = {
+            let mut queue = self.lock();
+            (queue.take_due(&self.settings, now), queue.clone())
+        };
+        service
+            .storage()
+            .save_notification_queue(&remaining)
+            .await?;
+        Ok(due)
+    }
+
+    /// Saves the waiting notifications, as before shutting down
+    pub async fn save<S: Storage>(&self, service: &BoardService<S>) -> Result<()> {
+        let queue = self.lock().clone();
+        service.storage().save_notification_queue(&queue).await
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, NotificationQueue> {
+        self.queue.lock().expect("notification queue lock poisoned")
+    }
+}
+
+/// A subscribed view and the tasks that matched it as of the last event
+struct ViewWatch {
+    view: SavedView,
+    matching: HashSet<TaskId>,
+}
+
+/// Whether `task` belongs in `view`; tasks off the board and drafts never do
+fn in_view(view: &SavedView, task: &Task) -> bool {
+    task.location() == TaskLocation::Board && !task.draft && view.filter.matches(task)
+}
+
+/// Queues events for the assignee of the task they concern, and view
+/// matches for the view's subscribers
+struct NotificationHandler {
+    queue: Arc<Mutex<NotificationQueue>>,
+    actor: Option<String>,
+    watches: Mutex<Vec<ViewWatch>>,
+}
+
+impl NotificationHandler {
+    /// Whether `recipient` should hear about the change
+    fn notifies(&self, recipient: &str) -> bool {
+        !self
+            .actor
+            .as_deref()
+            .is_some_and(|actor| same_actor(actor, recipient))
+    }
+}
+
+impl EventHandler for NotificationHandler {
+    fn handle(&self, event: &TaskEvent, task: Option<&Task>) -> Result<Vec<RuleAction>> {
+        let mut queue = self.queue.lock().expect("notification queue lock poisoned");
+        if let Some(assignee) = task.and_then(|task| task.assignee.as_deref()) {
+            if self.notifies(assignee) {
+                queue.push(assignee, event.clone());
+            }
+        }
+
+        let mut watches = self.watches.lock().expect("view watch lock poisoned");
+        for watch in watches.iter_mut() {
+            let matching = task.is_some_and(|task| in_view(&watch.view, task));
+            let changed = if matching {
+                watch.matching.insert(event.task_id.clone())
+            } else {
+                watch.matching.remove(&event.task_id)
+            };
+            if !changed {
+                continue;
+            }
+            let change = TaskEvent {
+                task_id: event.task_id.clone(),
+                kind: TaskEventKind::ViewMatch {
+                    view: watch.view.name.clone(),
+                    matching,
+                },
+                at: event.at,
+            };
+            for subscriber in &watch.view.subscribers {
+                if self.notifies(subscriber) {
+                    queue.push(subscriber, change.clone());
+                }
+            }
+        }
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::{DigestFrequency, TaskStatus},
+        storage::file_storage::FileStorage,
+    };
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_digests_survive_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        let mut board = storage.load_board().await.unwrap();
+        board.config.notifications.default = DigestFrequency::Hourly;
+        storage.save_board(&board).await.unwrap();
+
+        let mut service = BoardService::new(storage);
+        let scheduler = NotificationScheduler::attach(&mut service).await.unwrap();
+        let mut task = service.create_task("Review".to_string()).await.unwrap();
+        task.assignee = Some("ana".to_string());
+        let mut task = service.update_task(task).await.unwrap();
+        task.transition_to(TaskStatus::Open, None).unwrap();
+        service.update_task(task).await.unwrap();
+        assert_eq!(scheduler.pending(), 3);
+        assert!(scheduler
+            .take_due(&service, Utc::now())
+            .await
+            .unwrap()
+            .is_empty());
+
+        // A fresh service, as after a restart, picks up the saved queue
+        let mut service = BoardService::new(FileStorage::new(temp_dir.path()));
+        service.set_actor(Some("Ana".to_string()));
+        let scheduler = NotificationScheduler::attach(&mut service).await.unwrap();
+        assert_eq!(scheduler.pending(), 3);
+        let mut task = service
+            .load_task(&crate::domain::TaskId::new(1))
+            .await
+            .unwrap();
+        task.add_tag("own-change".to_string());
+        service.update_task(task).await.unwrap();
+        assert_eq!(scheduler.pending(), 3);
+
+        let due = scheduler
+            .take_due(&service, Utc::now() + Duration::hours(1))
+            .await
+            .unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].actor, "ana");
+        assert_eq!(due[0].events.len(), 3);
+        assert_eq!(scheduler.pending(), 0);
+        assert!(service
+            .storage()
+            .load_notification_queue()
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_view_subscription_reports_match_changes() {
+        use crate::domain::{automation::RuleCondition, ViewFilter};
+
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        let mut service = BoardService::new(storage);
+        let mut existing = service.create_task("Old".to_string()).await.unwrap();
+        existing.add_tag("backend".to_string());
+        service.update_task(existing).await.unwrap();
+        let filter = ViewFilter {
+            conditions: vec![RuleCondition::HasTag {
+                tag: "backend".to_string(),
+            }],
+            ..ViewFilter::default()
+        };
+        service
+            .add_view(SavedView::new("Backend", filter))
+            .await
+            .unwrap();
+        service.subscribe_to_view("Backend", "ben").await.unwrap();
+        service.subscribe_to_view("Backend", "Ben").await.unwrap();
+        assert_eq!(service.list_views().await.unwrap()[0].subscribers.len(), 1);
+        assert!(service.subscribe_to_view("Missing", "ben").await.is_err());
+
+        let scheduler = NotificationScheduler::attach(&mut service).await.unwrap();
+        // Already matching when attached, so editing it changes nothing
+        let mut old = service.load_task(&TaskId::new(1)).await.unwrap();
+        old.title = "Old, renamed".to_string();
+        let old = service.update_task(old).await.unwrap();
+        assert_eq!(scheduler.pending(), 0);
+
+        let mut task = service.create_task("New".to_string()).await.unwrap();
+        task.add_tag("backend".to_string());
+        let mut task = service.update_task(task).await.unwrap();
+        task.tags.clear();
+        service.update_task(task).await.unwrap();
+        service.delete_task(&old.id).await.unwrap();
+
+        let due = scheduler.take_due(&service, Utc::now()).await.unwrap();
+        let changes: Vec<String> = due[0].events.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            changes,
+            vec![
+                "HLA2 now matches view 'Backend'",
+                "HLA2 no longer matches view 'Backend'",
+                "HLA1 no longer matches view 'Backend'",
+            ]
+        );
+
+        service
+            .unsubscribe_from_view("Backend", "BEN")
+            .await
+            .unwrap();
+        assert!(service.list_views().await.unwrap()[0]
+            .subscribers
+            .is_empty());
+    }
+}
@@ -0,0 +1,312 @@
+use crate::{
+    domain::{
+        automation::RuleAction,
+        event::{TaskEvent, TaskEventKind},
+        Priority, Task, TaskStatus,
+    },
+    error::{HlaviError, Result},
+    service::events::EventHandler,
+};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use std::{
+    path::Path,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use tokio::fs;
+
+/// Event handler that runs user-supplied rhai scripts
+///
+/// Each script sees two variables: `event` (a map with `type`, `task_id` and,
+/// for status changes, `from`/`to`) and `task` (a read-only map of the task's
+/// fields, or `()` for deleted tasks). Scripts request changes by calling
+/// `add_tag`, `remove_tag`, `set_priority`, `move_to` and `assign_agent`; the
+/// service applies them like automation rule actions.
+///
+/// The engine is sandboxed: there is no file or network access, `eval` is
+/// disabled, and operation counts and data sizes are capped.
+pub struct ScriptHook {
+    scripts: Vec<(String, AST)>,
+}
+
+impl ScriptHook {
+    const EXTENSION: &'static str = "rhai";
+    const MAX_OPERATIONS: u64 = 100_000;
+    const MAX_STRING_SIZE: usize = 64 * 1024;
+    const MAX_COLLECTION_SIZE: usize = 10_000;
+
+    /// Compiles scripts from `(name, source)` pairs
+    pub fn from_sources(sources: Vec<(String, String)>) -> Result<Self> {
+        let engine = Self::engine(Arc::new(Mutex::new(Vec::new())));
+        let scripts = sources
+            .into_iter()
+            .map(|(name, source)| {
+                engine
+                    .compile(&source)
+                    .map(|ast| (name.clone(), ast))
+                    .map_err(|e| HlaviError::ScriptError(format!("{}: {}", name, e)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { scripts })
+    }
+
+    /// Loads every `*.rhai` file in the directory, in file name order
+    ///
+    /// A missing directory yields a hook with no scripts.
+    pub async fn load_dir(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        if !dir.exists() {
+            return Ok(Self {
+                scripts: Vec::new(),
+            });
+        }
+
+        let mut entries = fs::read_dir(dir).await?;
+        let mut sources = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) == Some(Self::EXTENSION) {
+                let name = path
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or_default()
+                    .to_string();
+                sources.push((name, fs::read_to_string(&path).await?));
+            }
+        }
+        sources.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Self::from_sources(sources)
+    }
+
+    /// Returns the names of the loaded scripts
+    pub fn script_names(&self) -> Vec<&str> {
+        self.scripts.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    fn engine(actions: Arc<Mutex<Vec<RuleAction>>>) -> Engine {
+        let mut engine = Engine::new();
+        engine.set_max_operations(Self::MAX_OPERATIONS);
+        engine.set_max_string_size(Self::MAX_STRING_SIZE);
+        engine.set_max_array_size(Self::MAX_COLLECTION_SIZE);
+        engine.set_max_map_size(Self::MAX_COLLECTION_SIZE);
+        engine.disable_symbol("eval");
+        engine.on_print(|_| {});
+        engine.on_debug(|_, _, _| {});
+
+        let sink = actions.clone();
+        engine.register_fn("add_tag", move |tag: &str| {
+            push(
+                &sink,
+                RuleAction::AddTag {
+                    tag: tag.to_string(),
+                },
+            )
+        });
+        let sink = actions.clone();
+        engine.register_fn("remove_tag", move |tag: &str| {
+            push(
+                &sink,
+                RuleAction::RemoveTag {
+                    tag: tag.to_string(),
+                },
+            )
+        });
+        let sink = actions.clone();
+        engine.register_fn(
+            "set_priority",
+            move |priority: &str| -> std::result::Result<(), Box<EvalAltResult>> {
+                let priority = Priority::from_str(priority).map_err(|e| e.to_string())?;
+                push(&sink, RuleAction::SetPriority { priority });
+                Ok(())
+            },
+        );
+        let sink = actions.clone();
+        engine.register_fn(
+            "move_to",
+            move |status: &str| -> std::result::Result<(), Box<EvalAltResult>> {
+                let status = TaskStatus::from_str(status).map_err(|e| e.to_string())?;
+                push(&sink, RuleAction::TransitionTo { status });
+                Ok(())
+            },
+        );
+        let sink = actions;
+        engine.register_fn("assign_agent", move || push(&sink, RuleAction::AssignAgent));
+
+        engine
+    }
+}
+
+fn push(sink: &Mutex<Vec<RuleAction>>, action: RuleAction) {
+    sink.lock()
+        .expect("script action sink poisoned")
+        .push(action);
+}
+
+fn status_name(status: &TaskStatus) -> String {
+    serde_json::to_value(status)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn event_map(event: &TaskEvent) -> Map {
+    let mut map = Map::new();
+    map.insert("type".into(), event.kind.name().into());
+    map.insert("task_id".into(), event.task_id.to_string().into());
+    if let TaskEventKind::StatusChanged { from, to } = &event.kind {
+        map.insert("from".into(), status_name(from).into());
+        map.insert("to".into(), status_name(to).into());
+    }
+    map
+}
+
+fn task_map(task: &Task) -> Map {
+    let completed = task
+        .acceptance_criteria
+        .iter()
+        .filter(|ac| ac.completed)
+        .count();
+
+    let mut map = Map::new();
+    map.insert("id".into(), task.id.to_string().into());
+    map.insert("title".into(), task.title.clone().into());
+    map.insert("status".into(), status_name(&task.status).into());
+    map.insert(
+        "priority".into(),
+        task.priority
+            .map(|p| Dynamic::from(p.to_string().to_lowercase()))
+            .unwrap_or(Dynamic::UNIT),
+    );
+    map.insert(
+        "tags".into(),
+        task.tags
+            .iter()
+            .cloned()
+            .map(Dynamic::from)
+            .collect::<Array>()
+            .into(),
+    );
+    map.insert("agent_assigned".into(), task.agent_assigned.into());
+    map.insert(
+        "acceptance_total".into(),
+        (task.acceptance_criteria.len() as i64).into(),
+    );
+    map.insert("acceptance_completed".into(), (completed as i64).into());
+    map
+}
+
+impl EventHandler for ScriptHook {
+    fn handle(&self, event: &TaskEvent, task: Option<&Task>) -> Result<Vec<RuleAction>> {
+        if self.scripts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let actions = Arc::new(Mutex::new(Vec::new()));
+        let engine = Self::engine(actions.clone());
+
+        for (name, ast) in &self.scripts {
+            let mut scope = Scope::new();
+            scope.push_constant("event", event_map(event));
+            scope.push_constant(
+                "task",
+                task.map(|t| Dynamic::from(task_map(t)))
+                    .unwrap_or(Dynamic::UNIT),
+            );
+            engine
+                .run_ast_with_scope(&mut scope, ast)
+                .map_err(|e| HlaviError::ScriptError(format!("{}: {}", name, e)))?;
+        }
+
+        let actions = std::mem::take(&mut *actions.lock().expect("script action sink poisoned"));
+        Ok(actions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TaskId;
+    use tempfile::TempDir;
+
+    fn hook(source: &str) -> ScriptHook {
+        ScriptHook::from_sources(vec![("test.rhai".to_string(), source.to_string())]).unwrap()
+    }
+
+    #[test]
+    fn test_script_requests_actions() {
+        let hook = hook(
+            r#"
+            if event.type == "created" && task.title.contains("crash") {
+                add_tag("bug");
+                set_priority("high");
+            }
+            "#,
+        );
+        let task = Task::new(TaskId::new(1), "App crash on start".to_string());
+        let event = TaskEvent::new(task.id.clone(), TaskEventKind::Created);
+
+        let actions = hook.handle(&event, Some(&task)).unwrap();
+        assert_eq!(
+            actions,
+            vec![
+                RuleAction::AddTag {
+                    tag: "bug".to_string()
+                },
+                RuleAction::SetPriority {
+                    priority: Priority::High
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_script_sees_status_change() {
+        let hook = hook(r#"if event.to == "review" { assign_agent(); }"#);
+        let task = Task::new(TaskId::new(1), "Work".to_string());
+        let event = TaskEvent::new(
+            task.id.clone(),
+            TaskEventKind::StatusChanged {
+                from: TaskStatus::InProgress,
+                to: TaskStatus::Review,
+            },
+        );
+
+        let actions = hook.handle(&event, Some(&task)).unwrap();
+        assert_eq!(actions, vec![RuleAction::AssignAgent]);
+    }
+
+    #[test]
+    fn test_runaway_script_is_stopped() {
+        let hook = hook("loop {}");
+        let task = Task::new(TaskId::new(1), "Work".to_string());
+        let event = TaskEvent::new(task.id.clone(), TaskEventKind::Updated);
+
+        assert!(matches!(
+            hook.handle(&event, Some(&task)),
+            Err(HlaviError::ScriptError(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_script_fails_to_compile() {
+        let result = ScriptHook::from_sources(vec![("bad.rhai".to_string(), "if {".to_string())]);
+        assert!(matches!(result, Err(HlaviError::ScriptError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_load_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("b.rhai"), "assign_agent();").unwrap();
+        std::fs::write(temp_dir.path().join("a.rhai"), "add_tag(\"x\");").unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let hook = ScriptHook::load_dir(temp_dir.path()).await.unwrap();
+        assert_eq!(hook.script_names(), vec!["a.rhai", "b.rhai"]);
+
+        let missing = ScriptHook::load_dir(temp_dir.path().join("missing"))
+            .await
+            .unwrap();
+        assert!(missing.script_names().is_empty());
+    }
+}
@@ -0,0 +1,224 @@
+//! Dry runs of workflow changes against a throwaway copy of the board.
+
+use crate::{
+    domain::{AutomationRule, TaskId, TaskStatus},
+    error::{HlaviError, Result},
+    metrics::{stats, BoardStats},
+    service::BoardService,
+    storage::{MemoryStorage, Storage},
+};
+use serde::{Deserialize, Serialize};
+
+/// A hypothetical change to play out in a simulation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SimulatedOperation {
+    CreateTask {
+        title: String,
+    },
+    /// Moves a task following its type's workflow
+    MoveTask {
+        id: TaskId,
+        status: TaskStatus,
+    },
+    /// Changes the WIP limit of the column holding `status`
+    SetWipLimit {
+        status: TaskStatus,
+        limit: Option<u32>,
+    },
+    /// Adds an automation rule and re-evaluates every task against it
+    AddRule {
+        rule: AutomationRule,
+    },
+}
+
+/// Where a simulation left the board
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulationReport {
+    pub stats: BoardStats,
+    /// Columns holding more tasks than their WIP limit, with the count and
+    /// the limit
+    pub over_limit: Vec<(String, usize, u32)>,
+    /// Operations that were refused, by position in the sequence, with the
+    /// reason
+    pub rejected: Vec<(usize, String)>,
+}
+
+/// A copy of a board held in memory, for trying out changes before adopting
+/// them
+///
+/// The copy has no event handlers and no actor, so hooks never fire and
+/// quotas never apply; nothing done here reaches the real board.
+pub struct Simulation {
+    service: BoardService<MemoryStorage>,
+    rejected: Vec<(usize, String)>,
+    /// Operations run so far, across calls to `run`
+    played: usize,
+}
+
+impl Simulation {
+    /// Copies the board, project config and every task behind `source`
+    pub async fn from_service<S: Storage>(source: &BoardService<S>) -> Result<Self> {
+        let storage = source.storage();
+        let board = storage.load_board().await?;
+        let config = storage.load_project_config().await?;
+        let tasks = storage.load_all_tasks().await?;
+        Ok(Self {
+            service: BoardService::new(MemoryStorage::with_contents(config, board, tasks)),
+            rejected: Vec::new(),
+            played: 0,
+        })
+    }
+
+    /// The service over the simulated board, for operations not covered by
+    /// `SimulatedOperation`
+    pub fn service(&self) -> &BoardService<MemoryStorage> {
+        &self.service
+    }
+
+    /// Plays out `operations` in order and reports the outcome
+    ///
+    /// A refused operation, such as a move the workflow forbids, is recorded
+    /// in the report and the rest still run.
+    pub async fn run(&mut self, operations: &[SimulatedOperation]) -> Result<SimulationReport> {
+        for operation in operations {
+            if let Err(err) = self.apply(operation).await {
+                self.rejected.push((self.played, err.to_string()));
+            }
+            self.played += 1;
+        }
+        self.report().await
+    }
+
+    async fn apply(&self, operation: &SimulatedOperation) -> Result<()> {
+        let storage = self.service.storage();
+        match operation {
+            SimulatedOperation::CreateTask { title } => {
+                self.service.create_task(title.clone()).await?;
+            }
+            SimulatedOperation::MoveTask { id, status } => {
+                let mut task = self.service.load_task(id).await?;
+                let board = storage.load_board().await?;
+                let task_type = task
+                    .task_type
+                    .as_deref()
+                    .and_then(|name| board.config.task_type(name));
+                task.transition_as(task_type, status.clone(), None)?;
+                self.service.update_task(task).await?;
+            }
+            SimulatedOperation::SetWipLimit { status, limit } => {
+                let mut board = storage.load_board().await?;
+                let column = board
+                    .config
+                    .columns
+                    .iter_mut()
+                    .find(|col| col.holds(status))
+                    .ok_or_else(|| {
+                        HlaviError::Validation(format!("No column holds status {}", status))
+                    })?;
+                column.wip_limit = *limit;
+                storage.save_board(&board).await?;
+            }
+            SimulatedOperation::AddRule { rule } => {
+                let mut board = storage.load_board().await?;
+                board.config.rules.push(rule.clone());
+                storage.save_board(&board).await?;
+                for task in self.service.list_tasks().await? {
+                    self.service.update_task(task).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Metrics and WIP-limit breaches for the board as it stands now
+    pub async fn report(&self) -> Result<SimulationReport> {
+        let board = self.service.storage().load_board().await?;
+        let tasks = self.service.list_tasks().await?;
+        let over_limit = board
+            .config
+            .columns
+            .iter()
+            .filter_map(|column| {
+                let limit = column.wip_limit?;
+                let count = tasks
+                    .iter()
+                    .filter(|task| task.is_on_board() && column.holds(&task.status))
+                    .count();
+                (count > limit as usize).then(|| (column.name.clone(), count, limit))
+            })
+            .collect();
+        Ok(SimulationReport {
+            stats: stats(&tasks, chrono::Utc::now()),
+            over_limit,
+            rejected: self.rejected.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::{RuleAction, RuleCondition},
+        storage::file_storage::FileStorage,
+    };
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_simulation_leaves_real_board_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path().join(".hlavi"));
+        storage.initialize().await.unwrap();
+        let service = BoardService::new(storage);
+        let task = service.create_task("Real".to_string()).await.unwrap();
+
+        let mut simulation = Simulation::from_service(&service).await.unwrap();
+        let report = simulation
+            .run(&[
+                SimulatedOperation::CreateTask {
+                    title: "Hypothetical".to_string(),
+                },
+                SimulatedOperation::CreateTask {
+                    title: "Another".to_string(),
+                },
+                SimulatedOperation::MoveTask {
+                    id: task.id.clone(),
+                    status: TaskStatus::Open,
+                },
+                SimulatedOperation::MoveTask {
+                    id: task.id.clone(),
+                    status: TaskStatus::Done,
+                },
+                SimulatedOperation::SetWipLimit {
+                    status: TaskStatus::New,
+                    limit: Some(1),
+                },
+                SimulatedOperation::AddRule {
+                    rule: AutomationRule {
+                        name: "triage".to_string(),
+                        enabled: true,
+                        conditions: vec![RuleCondition::StatusIs {
+                            status: TaskStatus::New,
+                        }],
+                        actions: vec![RuleAction::AddTag {
+                            tag: "triage".to_string(),
+                        }],
+                    },
+                },
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(report.stats.total, 3);
+        assert_eq!(report.stats.by_tag.get("triage"), Some(&2));
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.rejected[0].0, 3);
+        assert_eq!(report.over_limit, vec![("New".to_string(), 2, 1)]);
+
+        assert_eq!(service.list_tasks().await.unwrap().len(), 1);
+        let real = service.load_task(&task.id).await.unwrap();
+        assert_eq!(real.status, TaskStatus::New);
+        assert!(real.tags.is_empty());
+    }
+}
@@ -0,0 +1,276 @@
+//! Cached results for saved views.
+//!
+//! Running a view filters and sorts every task on the board, which adds up
+//! for dashboards that refresh several views on a large board. A
+//! [`ViewCache`] keeps each view's results under a hash of its filter and
+//! sort, and drops them when an event on the service's bus touches a task
+//! the results hold or a task that now matches.
+//!
+//! The cache only sees changes made through the service it is attached to;
+//! call [`ViewCache::clear`] after other writers change the board, or after
+//! changing the project's default sort.
+
+use crate::{
+    domain::{
+        automation::{RuleAction, RuleCondition},
+        SavedView, Task, TaskEvent, TaskId,
+    },
+    error::{HlaviError, Result},
+    service::{BoardService, EventHandler},
+    storage::Storage,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+};
+
+/// How long results of views that compare against the current time, such as
+/// `updated > -7d`, stay cached
+pub const DEFAULT_CLOCK_TTL_SECONDS: i64 = 60;
+
+/// Cache effectiveness counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped because a task changed
+    pub invalidations: u64,
+    /// Entries dropped because they were too old
+    pub expirations: u64,
+    /// Entries currently held
+    pub entries: usize,
+}
+
+impl CacheStats {
+    /// Share of lookups served from the cache, if there were any
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+struct Entry {
+    view: SavedView,
+    tasks: Vec<Task>,
+    ids: HashSet<TaskId>,
+    cached_at: DateTime<Utc>,
+    /// Set for views whose results change with the clock
+    expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+struct State {
+    entries: HashMap<u64, Entry>,
+    stats: CacheStats,
+}
+
+/// Results of saved views, kept until a relevant task changes
+#[derive(Clone)]
+pub struct ViewCache {
+    state: Arc<Mutex<State>>,
+    clock_ttl: Duration,
+}
+
+impl ViewCache {
+    /// Creates a cache and subscribes it to `service`'s events
+    pub fn attach<S: Storage>(service: &mut BoardService<S>) -> Self {
+        let cache = Self {
+            state: Arc::new(Mutex::new(State::default())),
+            clock_ttl: Duration::seconds(DEFAULT_CLOCK_TTL_SECONDS),
+        };
+        service.subscribe(Invalidator {
+            state: cache.state.clone(),
+        });
+        cache
+    }
+
+    /// Sets how long results of clock-dependent views stay cached
+    pub fn with_clock_ttl(mut self, ttl: Duration) -> Self {
+        self.clock_ttl = ttl;
+        self
+    }
+
+    /// Tasks shown by the view called `name`, like `BoardService::run_view`
+    pub async fn run_view<S: Storage>(
+        &self,
+        service: &BoardService<S>,
+        name: &str,
+    ) -> Result<Vec<Task>> {
+        let view = service
+            .list_views()
+            .await?
+            .into_iter()
+            .find(|view| view.name == name)
+            .ok_or_else(|| HlaviError::ViewNotFound(name.to_string()))?;
+        self.run(service, &view).await
+    }
+
+    /// Tasks shown by `view`, from the cache when its results are current
+    pub async fn run<S: Storage>(
+        &self,
+        service: &BoardService<S>,
+        view: &SavedView,
+    ) -> Result<Vec<Task>> {
+        let key = view_key(view);
+        let now = Utc::now();
+        {
+            let mut state = self.lock();
+            let expired = state
+                .entries
+                .get(&key)
+                .and_then(|entry| entry.expires_at)
+                .is_some_and(|expires_at| expires_at <= now);
+            if expired {
+                state.entries.remove(&key);
+                state.stats.expirations += 1;
+            }
+            if let Some(entry) = state.entries.get(&key) {
+                let tasks = entry.tasks.clone();
+                state.stats.hits += 1;
+                return Ok(tasks);
+            }
+            state.stats.misses += 1;
+        }
+
+        let tasks = view.apply(service.list_tasks().await?);
+        let entry = Entry {
+            view: view.clone(),
+            ids: tasks.iter().map(|task| task.id.clone()).collect(),
+            tasks: tasks.clone(),
+            cached_at: now,
+            expires_at: view
+                .filter
+                .conditions
+                .iter()
+                .any(depends_on_clock)
+                .then(|| now + self.clock_ttl),
+        };
+        self.lock().entries.insert(key, entry);
+        Ok(tasks)
+    }
+
+    /// When the cached results for `view` were computed, if it is cached
+    pub fn cached_at(&self, view: &SavedView) -> Option<DateTime<Utc>> {
+        self.lock()
+            .entries
+            .get(&view_key(view))
+            .map(|entry| entry.cached_at)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let state = self.lock();
+        CacheStats {
+            entries: state.entries.len(),
+            ..state.stats
+        }
+    }
+
+    /// Drops every entry, keeping the counters
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("view cache lock poisoned")
+    }
+}
+
+/// Hash of the parts of a view that decide its results: filter and sort
+pub fn view_key(view: &SavedView) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    // Both serialize without fail; the JSON is a stable, hashable form
+    serde_json::to_string(&(&view.filter, &view.sort))
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+fn depends_on_clock(condition: &RuleCondition) -> bool {
+    match condition {
+        RuleCondition::UpdatedWithin { .. } | RuleCondition::CreatedWithin { .. } => true,
+        RuleCondition::All { conditions } | RuleCondition::Any { conditions } => {
+            conditions.iter().any(depends_on_clock)
+        }
+        RuleCondition::Not { condition } => depends_on_clock(condition),
+        _ => false,
+    }
+}
+
+/// Drops entries an event may have changed
+struct Invalidator {
+    state: Arc<Mutex<State>>,
+}
+
+impl EventHandler for Invalidator {
+    fn handle(&self, event: &TaskEvent, task: Option<&Task>) -> Result<Vec<RuleAction>> {
+        let mut state = self.state.lock().expect("view cache lock poisoned");
+        let before = state.entries.len();
+        state.entries.retain(|_, entry| {
+            let held = entry.ids.contains(&event.task_id);
+            let matches =
+                task.is_some_and(|task| task.is_on_board() && entry.view.filter.matches(task));
+            !held && !matches
+        });
+        state.stats.invalidations += (before - state.entries.len()) as u64;
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::{parse_query, Priority},
+        storage::file_storage::FileStorage,
+    };
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_hits_and_invalidation() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        let mut service = BoardService::new(storage);
+        let cache = ViewCache::attach(&mut service);
+
+        let urgent = SavedView::new("Urgent", parse_query("priority >= high").unwrap());
+        let recent = SavedView::new("Recent", parse_query("updated > -1d").unwrap());
+        service.add_view(urgent.clone()).await.unwrap();
+        let mut task = service.create_task("Outage".to_string()).await.unwrap();
+        task.priority = Some(Priority::Critical);
+        service.update_task(task).await.unwrap();
+        let other = service.create_task("Docs".to_string()).await.unwrap();
+
+        assert_eq!(cache.run_view(&service, "Urgent").await.unwrap().len(), 1);
+        assert_eq!(cache.run(&service, &urgent).await.unwrap().len(), 1);
+        assert_eq!(cache.stats().hits, 1);
+        assert_eq!(cache.stats().misses, 1);
+
+        // A task outside the results that still does not match leaves them
+        let mut other = other;
+        other.title = "Docs, revised".to_string();
+        let mut other = service.update_task(other).await.unwrap();
+        cache.run(&service, &urgent).await.unwrap();
+        assert_eq!(cache.stats().hits, 2);
+
+        // One that starts matching drops them
+        other.priority = Some(Priority::High);
+        service.update_task(other).await.unwrap();
+        assert!(cache.cached_at(&urgent).is_none());
+        assert_eq!(cache.run(&service, &urgent).await.unwrap().len(), 2);
+        assert_eq!(cache.stats().invalidations, 1);
+
+        // Views that depend on the clock expire
+        let cache = cache.with_clock_ttl(Duration::zero());
+        cache.run(&service, &recent).await.unwrap();
+        cache.run(&service, &recent).await.unwrap();
+        let stats = cache.stats();
+        assert_eq!(stats.expirations, 1);
+        assert_eq!(stats.entries, 2);
+        assert_eq!((stats.hits, stats.misses), (2, 4));
+        assert_eq!(stats.hit_rate(), Some(2.0 / 6.0));
+    }
+}
@@ -0,0 +1,55 @@
+//! Content hashes for cheap change detection.
+//!
+//! FNV-1a is used rather than `DefaultHasher` because hashes are persisted
+//! and compared across processes, so they must not change between runs.
+
+use crate::{domain::Task, error::Result};
+
+/// 64-bit FNV-1a
+pub(crate) struct Fnv1a(u64);
+
+impl Fnv1a {
+    pub fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// The ETag of stored content
+pub fn etag(bytes: &[u8]) -> String {
+    let mut hasher = Fnv1a::new();
+    hasher.write(bytes);
+    format!("{:016x}", hasher.finish())
+}
+
+/// The ETag of a task in its canonical stored form (pretty-printed JSON)
+pub fn task_etag(task: &Task) -> Result<String> {
+    Ok(etag(serde_json::to_string_pretty(task)?.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TaskId;
+
+    #[test]
+    fn test_task_etag_tracks_content() {
+        let mut task = Task::new(TaskId::new(1), "Cache me".to_string());
+        let before = task_etag(&task).unwrap();
+        assert_eq!(before, task_etag(&task.clone()).unwrap());
+
+        task.title = "Cache me twice".to_string();
+        assert_ne!(before, task_etag(&task).unwrap());
+        assert_eq!(etag(b""), "cbf29ce484222325");
+    }
+}
@@ -1,10 +1,19 @@
 use crate::{
-    domain::{Board, Task, TaskId},
+    domain::{
+        Board, BoardConfig, BoardSnapshot, NotificationQueue, ProjectConfig, ReadState,
+        SearchConstraints, Task, TaskId, TaskSummary, ViewPreferences,
+    },
     error::{HlaviError, Result},
-    storage::Storage,
+    storage::{
+        self,
+        index::{self, TaskIndex},
+        Storage,
+    },
 };
 use async_trait::async_trait;
+use futures::stream::{self, StreamExt, TryStreamExt};
 use std::{
+    ffi::OsString,
     path::{Path, PathBuf},
     str::FromStr,
 };
@@ -15,32 +24,306 @@ pub struct FileStorage {
     root_path: PathBuf,
 }
 
+/// A task file's name, size and modification time, as fingerprinted
+type ListingEntry = (String, u64, u128);
+
+fn listing_entry(name: String, metadata: &std::fs::Metadata) -> ListingEntry {
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_nanos());
+    (name, metadata.len(), modified)
+}
+
 impl FileStorage {
     const HLAVI_DIR: &'static str = ".hlavi";
+    /// Environment variable overriding the storage directory name
+    pub const DIR_ENV_VAR: &'static str = "HLAVI_DIR";
     const TASKS_DIR: &'static str = "tasks";
     const BOARD_FILE: &'static str = "board.json";
-    #[allow(dead_code)]
+    const SCRIPTS_DIR: &'static str = "scripts";
     const CONFIG_FILE: &'static str = "config.toml";
+    const SNAPSHOTS_DIR: &'static str = "snapshots";
+    /// Per-user state that is git-ignored and never shared
+    const LOCAL_DIR: &'static str = "local";
+    /// Task files read at once when loading many tasks
+    const LOAD_CONCURRENCY: usize = 32;
+    /// Descriptions longer than this many bytes are kept in a separate body
+    /// file, so the board can be listed without reading them
+    const BODY_THRESHOLD: usize = 4 * 1024;
+    /// Summary index; a local cache rebuilt whenever it goes stale
+    const INDEX_FILE: &'static str = "index.json";
+    const GITIGNORE: &'static str = "# Local caches\n*.db\n*.db-*\nindex.json\nlocal/\n";
 
     /// Creates a new FileStorage instance for the given project root
+    ///
+    /// The storage directory is taken from `HLAVI_DIR` if set, otherwise `.hlavi`.
     pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self::with_dir(project_root, Self::default_dir())
+    }
+
+    /// Storage directory used by `new`: `HLAVI_DIR` if set, otherwise `.hlavi`
+    pub fn default_dir() -> PathBuf {
+        Self::dir_from_env(std::env::var_os(Self::DIR_ENV_VAR))
+    }
+
+    /// Creates a FileStorage using an explicit storage directory
+    ///
+    /// A relative `dir` is resolved against the project root; an absolute one
+    /// is used as-is, so boards can live outside the repository.
+    pub fn with_dir(project_root: impl AsRef<Path>, dir: impl AsRef<Path>) -> Self {
         Self {
-            root_path: project_root.as_ref().join(Self::HLAVI_DIR),
+            root_path: project_root.as_ref().join(dir),
         }
     }
 
+    /// The directory holding the board and task files
+    pub fn root_path(&self) -> &Path {
+        &self.root_path
+    }
+
+    fn dir_from_env(value: Option<OsString>) -> PathBuf {
+        value
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(Self::HLAVI_DIR))
+    }
+
     fn tasks_dir(&self) -> PathBuf {
         self.root_path.join(Self::TASKS_DIR)
     }
 
+    /// Directory holding automation scripts (`.hlavi/scripts/`)
+    pub fn scripts_dir(&self) -> PathBuf {
+        self.root_path.join(Self::SCRIPTS_DIR)
+    }
+
     fn board_file(&self) -> PathBuf {
         self.root_path.join(Self::BOARD_FILE)
     }
 
+    fn config_file(&self) -> PathBuf {
+        self.root_path.join(Self::CONFIG_FILE)
+    }
+
+    /// Validates and writes `config.toml`
+    pub async fn save_project_config(&self, config: &ProjectConfig) -> Result<()> {
+        config.validate()?;
+        self.ensure_directory_exists(&self.root_path).await?;
+        fs::write(self.config_file(), config.to_toml()?).await?;
+        Ok(())
+    }
+
+    fn snapshots_dir(&self) -> PathBuf {
+        self.root_path.join(Self::SNAPSHOTS_DIR)
+    }
+
+    fn snapshot_file(&self, label: &str) -> PathBuf {
+        self.snapshots_dir().join(format!("{}.json", label))
+    }
+
+    /// Read state file for an actor (`.hlavi/local/read/<actor>.json`)
+    fn read_state_file(&self, actor: &str) -> PathBuf {
+        self.actor_file("read", actor)
+    }
+
+    /// View preferences file for an actor (`.hlavi/local/views/<actor>.json`)
+    fn view_preferences_file(&self, actor: &str) -> PathBuf {
+        self.actor_file("views", actor)
+    }
+
+    /// Notifications waiting for delivery (`.hlavi/local/notifications.json`)
+    fn notification_queue_file(&self) -> PathBuf {
+        self.root_path.join("local").join("notifications.json")
+    }
+
+    /// A per-actor file under `local/<kind>`
+    ///
+    /// Actor names are lowercased and anything outside `[a-z0-9._@-]` is
+    /// replaced, so names map to safe file names.
+    fn actor_file(&self, kind: &str, actor: &str) -> PathBuf {
+        let name: String = actor
+            .trim()
+            .to_lowercase()
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | '0'..='9' | '.' | '_' | '@' | '-' => c,
+                _ => '_',
+            })
+            .collect();
+        self.root_path
+            .join(Self::LOCAL_DIR)
+            .join(kind)
+            .join(format!("{}.json", name.trim_start_matches('.')))
+    }
+
+    /// Makes sure `pattern` is git-ignored, including in boards created
+    /// before it was added to the default `.gitignore`
+    async fn ensure_ignored(&self, pattern: &str) -> Result<()> {
+        let gitignore_path = self.root_path.join(".gitignore");
+        if !gitignore_path.exists() {
+            fs::write(gitignore_path, Self::GITIGNORE).await?;
+            return Ok(());
+        }
+        let mut contents = fs::read_to_string(&gitignore_path).await?;
+        if !contents.lines().any(|line| line.trim() == pattern) {
+            if !contents.is_empty() && !contents.ends_with('\n') {
+                contents.push('\n');
+            }
+            contents.push_str(pattern);
+            contents.push('\n');
+            fs::write(gitignore_path, contents).await?;
+        }
+        Ok(())
+    }
+
+    fn index_file(&self) -> PathBuf {
+        self.root_path.join(Self::INDEX_FILE)
+    }
+
+    /// Fingerprint of the task files as they are on disk now
+    async fn tasks_fingerprint(&self) -> Result<String> {
+        Ok(index::fingerprint(self.tasks_listing().await?))
+    }
+
+    /// Name, size and modification time of every task file
+    async fn tasks_listing(&self) -> Result<Vec<ListingEntry>> {
+        let tasks_dir = self.tasks_dir();
+        let mut files = Vec::new();
+        if tasks_dir.exists() {
+            let mut entries = fs::read_dir(&tasks_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if !name.ends_with(".json") {
+                    continue;
+                }
+                files.push(listing_entry(name, &entry.metadata().await?));
+            }
+        }
+        Ok(files)
+    }
+
+    /// Reads `index.json` if it exists and parses
+    async fn read_index(&self) -> Option<TaskIndex> {
+        let contents = fs::read_to_string(self.index_file()).await.ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    async fn write_index(&self, index: &TaskIndex) -> Result<()> {
+        self.ensure_ignored(Self::INDEX_FILE).await?;
+        fs::write(self.index_file(), serde_json::to_string(index)?).await?;
+        Ok(())
+    }
+
+    /// Loads the summary index, rebuilding it from the task files if they
+    /// changed since it was written
+    async fn load_index(&self) -> Result<TaskIndex> {
+        let fingerprint = self.tasks_fingerprint().await?;
+        if let Some(index) = self.read_index().await {
+            if index.fingerprint == fingerprint {
+                return Ok(index);
+            }
+        }
+
+        let tasks = self.load_all_tasks().await?;
+        // Loading may have upgraded legacy files, so fingerprint afterwards
+        let index = TaskIndex::build(&tasks, self.tasks_fingerprint().await?);
+        self.write_index(&index).await?;
+        Ok(index)
+    }
+
+    /// Applies a change made to one task file to the index
+    ///
+    /// `listing` is the task files as listed just before `path` was
+    /// changed; the new fingerprint is derived from it and the changed file,
+    /// so the directory is only listed once per save. If the index was
+    /// already stale it is dropped instead, to be rebuilt on the next read.
+    async fn update_index(
+        &self,
+        mut listing: Vec<ListingEntry>,
+        path: &Path,
+        change: impl FnOnce(&mut TaskIndex),
+    ) -> Result<()> {
+        let before = index::fingerprint(listing.clone());
+        match self.read_index().await {
+            Some(mut index) if index.fingerprint == before => {
+                change(&mut index);
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                listing.retain(|(listed, _, _)| *listed != name);
+                if let Ok(metadata) = fs::symlink_metadata(path).await {
+                    listing.push(listing_entry(name, &metadata));
+                }
+                index.fingerprint = index::fingerprint(listing);
+                self.write_index(&index).await
+            }
+            Some(_) => {
+                fs::remove_file(self.index_file()).await?;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    async fn load_enumerated(
+        &self,
+        index: usize,
+        id: TaskId,
+        header: bool,
+    ) -> Result<(usize, Task)> {
+        let task = if header {
+            self.load_task_header(&id).await?
+        } else {
+            self.load_task(&id).await?
+        };
+        Ok((index, task))
+    }
+
+    /// Reads task files concurrently, at most `LOAD_CONCURRENCY` at a time
+    async fn load_many(&self, ids: &[TaskId], header: bool) -> Result<Vec<Task>> {
+        let mut loaded: Vec<(usize, Task)> = stream::iter(ids.iter().cloned().enumerate())
+            .map(|(index, id)| self.load_enumerated(index, id, header))
+            .buffer_unordered(Self::LOAD_CONCURRENCY)
+            .try_collect()
+            .await?;
+        loaded.sort_unstable_by_key(|(index, _)| *index);
+        Ok(loaded.into_iter().map(|(_, task)| task).collect())
+    }
+
     fn task_file(&self, id: &TaskId) -> PathBuf {
         self.tasks_dir().join(format!("{}.json", id.as_str()))
     }
 
+    /// Holds the description of a task whose description is too large to
+    /// keep in its task file
+    ///
+    /// Only the description moves here. Tasks have no comment thread, and
+    /// the other long-form fields (acceptance criteria, agent notes, work
+    /// log) are read by filters, metrics and secret scans, so they stay in
+    /// the header.
+    fn body_file(&self, id: &TaskId) -> PathBuf {
+        self.tasks_dir().join(format!("{}.body.md", id.as_str()))
+    }
+
+    /// Reads a task file without its body, returning the raw contents too
+    async fn read_header(&self, id: &TaskId) -> Result<(Task, Vec<u8>)> {
+        let file_path = self.task_file(id);
+
+        if !file_path.exists() {
+            return Err(HlaviError::TaskNotFound(id.to_string()));
+        }
+
+        let contents = fs::read(&file_path).await?;
+        let mut task: Task = serde_json::from_slice(&contents)?;
+        // Older files may carry duplicate criterion IDs
+        task.repair_criterion_ids();
+        task.body_omitted = task.description.is_none() && self.body_file(id).exists();
+        Ok((task, contents))
+    }
+
     async fn ensure_directory_exists(&self, path: &Path) -> Result<()> {
         if !path.exists() {
             fs::create_dir_all(path).await?;
@@ -52,20 +335,35 @@ impl FileStorage {
 #[async_trait]
 impl Storage for FileStorage {
     async fn initialize(&self) -> Result<()> {
-        // Create .hlavi directory structure
+        // Create storage directory structure
         self.ensure_directory_exists(&self.root_path).await?;
         self.ensure_directory_exists(&self.tasks_dir()).await?;
 
+        // Write default config unless the user already provided one
+        let config = self.load_project_config().await?;
+        if !self.config_file().exists() {
+            self.save_project_config(&config).await?;
+        }
+
         // Create default board if it doesn't exist
         if !self.board_file().exists() {
-            let board = Board::default();
-            self.save_board(&board).await?;
+            let mut board_config = BoardConfig::preset(config.preset);
+            if let Some(mode) = &config.agent.mode {
+                for column in board_config
+                    .columns
+                    .iter_mut()
+                    .filter(|col| col.agent_enabled)
+                {
+                    column.agent_mode = Some(mode.clone());
+                }
+            }
+            self.save_board(&Board::new(board_config)).await?;
         }
 
         // Create .gitignore
         let gitignore_path = self.root_path.join(".gitignore");
         if !gitignore_path.exists() {
-            fs::write(gitignore_path, "# Local caches\n*.db\n*.db-*\n").await?;
+            fs::write(gitignore_path, Self::GITIGNORE).await?;
         }
 
         Ok(())
@@ -74,24 +372,96 @@ impl Storage for FileStorage {
     async fn save_task(&self, task: &Task) -> Result<()> {
         self.ensure_directory_exists(&self.tasks_dir()).await?;
 
-        let json = serde_json::to_string_pretty(task)?;
+        let body_path = self.body_file(&task.id);
+        let header;
+        let stored = match task.description.as_deref() {
+            Some(body) if body.len() > Self::BODY_THRESHOLD => {
+                fs::write(&body_path, body).await?;
+                header = Task {
+                    description: None,
+                    ..task.clone()
+                };
+                &header
+            }
+            _ => {
+                // A header keeps the body it was loaded without
+                if !task.body_omitted && body_path.exists() {
+                    fs::remove_file(&body_path).await?;
+                }
+                task
+            }
+        };
+        let json = serde_json::to_string_pretty(stored)?;
         let file_path = self.task_file(&task.id);
 
-        fs::write(file_path, json).await?;
-        Ok(())
+        let listing = self.tasks_listing().await?;
+        fs::write(&file_path, json).await?;
+        self.update_index(listing, &file_path, |index| index.upsert(task))
+            .await
     }
 
     async fn load_task(&self, id: &TaskId) -> Result<Task> {
+        Ok(self.load_task_tagged(id).await?.0)
+    }
+
+    async fn load_task_tagged(&self, id: &TaskId) -> Result<(Task, String)> {
+        let (mut task, mut contents) = self.read_header(id).await?;
+        if task.body_omitted {
+            let body = fs::read_to_string(self.body_file(id)).await?;
+            contents.extend_from_slice(body.as_bytes());
+            task.description = Some(body);
+            task.body_omitted = false;
+        }
+        let mut etag = storage::etag(&contents);
+        // Older files have no UUID; assign one and persist it so it stays stable
+        if task.uuid.is_nil() {
+            task.uuid = uuid::Uuid::new_v4();
+            etag = self.save_task_tagged(&task).await?;
+        }
+
+        Ok((task, etag))
+    }
+
+    async fn load_task_header(&self, id: &TaskId) -> Result<Task> {
+        let (mut task, _) = self.read_header(id).await?;
+        if task.uuid.is_nil() {
+            task.uuid = uuid::Uuid::new_v4();
+            self.save_task(&task).await?;
+        }
+        Ok(task)
+    }
+
+    async fn load_task_headers(&self, ids: &[TaskId]) -> Result<Vec<Task>> {
+        self.load_many(ids, true).await
+    }
+
+    async fn load_task_full(&self, mut task: Task) -> Result<Task> {
+        if task.body_omitted {
+            task.description = Some(fs::read_to_string(self.body_file(&task.id)).await?);
+            task.body_omitted = false;
+        }
+        Ok(task)
+    }
+
+    /// Covers the body file as well as the task file
+    async fn save_task_tagged(&self, task: &Task) -> Result<String> {
+        self.save_task(task).await?;
+        self.task_etag(&task.id).await
+    }
+
+    async fn task_etag(&self, id: &TaskId) -> Result<String> {
         let file_path = self.task_file(id);
 
         if !file_path.exists() {
             return Err(HlaviError::TaskNotFound(id.to_string()));
         }
 
-        let contents = fs::read_to_string(&file_path).await?;
-        let task: Task = serde_json::from_str(&contents)?;
-
-        Ok(task)
+        let mut contents = fs::read(file_path).await?;
+        let body_path = self.body_file(id);
+        if body_path.exists() {
+            contents.extend(fs::read(body_path).await?);
+        }
+        Ok(storage::etag(&contents))
     }
 
     async fn list_task_ids(&self) -> Result<Vec<TaskId>> {
@@ -119,36 +489,43 @@ impl Storage for FileStorage {
         Ok(ids)
     }
 
+    async fn load_tasks(&self, ids: &[TaskId]) -> Result<Vec<Task>> {
+        self.load_many(ids, false).await
+    }
+
     async fn search_tasks(&self, query: &str) -> Result<Vec<Task>> {
-        let task_ids = self.list_task_ids().await?;
-        let query_lower = query.to_lowercase();
-        let mut matching_tasks = Vec::new();
-
-        for id in task_ids {
-            let task = self.load_task(&id).await?;
-
-            // Check if query matches title
-            let title_matches = task.title.to_lowercase().contains(&query_lower);
-
-            // Check if query matches description
-            let description_matches = task
-                .description
-                .as_ref()
-                .map(|d| d.to_lowercase().contains(&query_lower))
-                .unwrap_or(false);
-
-            // Check if query matches any acceptance criteria
-            let ac_matches = task
-                .acceptance_criteria
-                .iter()
-                .any(|ac| ac.description.to_lowercase().contains(&query_lower));
-
-            if title_matches || description_matches || ac_matches {
-                matching_tasks.push(task);
-            }
-        }
+        let mut tasks = self.load_all_tasks().await?;
+        tasks.retain(|task| task.matches_query(query));
+        Ok(tasks)
+    }
 
-        Ok(matching_tasks)
+    /// Uses the summary index to skip task files the constraints rule out
+    async fn search_tasks_with(
+        &self,
+        query: &str,
+        constraints: &SearchConstraints,
+    ) -> Result<Vec<Task>> {
+        let ids = if constraints.is_empty() {
+            self.list_task_ids().await?
+        } else {
+            self.load_index()
+                .await?
+                .tasks
+                .into_values()
+                .filter(|summary| constraints.matches_summary(summary))
+                .map(|summary| summary.id)
+                .collect()
+        };
+        let mut tasks = self.load_tasks(&ids).await?;
+        tasks.retain(|task| constraints.matches(task) && task.matches_query(query));
+        Ok(tasks)
+    }
+
+    async fn list_summaries(&self) -> Result<Vec<TaskSummary>> {
+        let mut summaries: Vec<TaskSummary> =
+            self.load_index().await?.tasks.into_values().collect();
+        summaries.sort_by_key(|s| s.id.number());
+        Ok(summaries)
     }
 
     async fn delete_task(&self, id: &TaskId) -> Result<()> {
@@ -158,11 +535,20 @@ impl Storage for FileStorage {
             return Err(HlaviError::TaskNotFound(id.to_string()));
         }
 
-        fs::remove_file(file_path).await?;
-        Ok(())
+        let body_path = self.body_file(id);
+        if body_path.exists() {
+            fs::remove_file(body_path).await?;
+        }
+        let listing = self.tasks_listing().await?;
+        fs::remove_file(&file_path).await?;
+        self.update_index(listing, &file_path, |index| {
+            index.tasks.remove(id.as_str());
+        })
+        .await
     }
 
     async fn save_board(&self, board: &Board) -> Result<()> {
+        board.config.validate()?;
         self.ensure_directory_exists(&self.root_path).await?;
 
         let json = serde_json::to_string_pretty(board)?;
@@ -180,6 +566,7 @@ impl Storage for FileStorage {
 
         let contents = fs::read_to_string(&board_file).await?;
         let board: Board = serde_json::from_str(&contents)?;
+        board.config.validate()?;
 
         Ok(board)
     }
@@ -187,6 +574,130 @@ impl Storage for FileStorage {
     async fn is_initialized(&self) -> bool {
         self.root_path.exists() && self.board_file().exists()
     }
+
+    async fn load_project_config(&self) -> Result<ProjectConfig> {
+        let config_file = self.config_file();
+
+        if !config_file.exists() {
+            return Ok(ProjectConfig::default());
+        }
+
+        let contents = fs::read_to_string(&config_file).await?;
+        ProjectConfig::from_toml(&contents)
+    }
+
+    async fn save_snapshot(&self, snapshot: &BoardSnapshot) -> Result<()> {
+        BoardSnapshot::validate_label(&snapshot.label)?;
+        let file_path = self.snapshot_file(&snapshot.label);
+        if file_path.exists() {
+            return Err(HlaviError::StorageError(format!(
+                "Snapshot '{}' already exists",
+                snapshot.label
+            )));
+        }
+
+        self.ensure_directory_exists(&self.snapshots_dir()).await?;
+        let json = serde_json::to_string_pretty(snapshot)?;
+        fs::write(file_path, json).await?;
+        Ok(())
+    }
+
+    async fn load_snapshot(&self, label: &str) -> Result<BoardSnapshot> {
+        BoardSnapshot::validate_label(label)?;
+        let file_path = self.snapshot_file(label);
+        if !file_path.exists() {
+            return Err(HlaviError::StorageError(format!(
+                "Snapshot '{}' not found",
+                label
+            )));
+        }
+
+        let contents = fs::read_to_string(&file_path).await?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    async fn list_snapshots(&self) -> Result<Vec<String>> {
+        let snapshots_dir = self.snapshots_dir();
+        if !snapshots_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = fs::read_dir(&snapshots_dir).await?;
+        let mut labels = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                    labels.push(stem.to_string());
+                }
+            }
+        }
+        labels.sort();
+        Ok(labels)
+    }
+
+    async fn load_read_state(&self, actor: &str) -> Result<ReadState> {
+        let file_path = self.read_state_file(actor);
+        if !file_path.exists() {
+            return Ok(ReadState::new(actor));
+        }
+
+        let contents = fs::read_to_string(&file_path).await?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    async fn save_read_state(&self, state: &ReadState) -> Result<()> {
+        let file_path = self.read_state_file(&state.actor);
+        if let Some(dir) = file_path.parent() {
+            self.ensure_directory_exists(dir).await?;
+        }
+        self.ensure_ignored("local/").await?;
+        let json = serde_json::to_string_pretty(state)?;
+        fs::write(file_path, json).await?;
+        Ok(())
+    }
+
+    async fn load_view_preferences(&self, actor: &str) -> Result<ViewPreferences> {
+        let file_path = self.view_preferences_file(actor);
+        if !file_path.exists() {
+            return Ok(ViewPreferences::new(actor));
+        }
+
+        let contents = fs::read_to_string(&file_path).await?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    async fn save_view_preferences(&self, preferences: &ViewPreferences) -> Result<()> {
+        let file_path = self.view_preferences_file(&preferences.actor);
+        if let Some(dir) = file_path.parent() {
+            self.ensure_directory_exists(dir).await?;
+        }
+        self.ensure_ignored("local/").await?;
+        let json = serde_json::to_string_pretty(preferences)?;
+        fs::write(file_path, json).await?;
+        Ok(())
+    }
+
+    async fn load_notification_queue(&self) -> Result<NotificationQueue> {
+        let file_path = self.notification_queue_file();
+        if !file_path.exists() {
+            return Ok(NotificationQueue::new());
+        }
+
+        let contents = fs::read_to_string(&file_path).await?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    async fn save_notification_queue(&self, queue: &NotificationQueue) -> Result<()> {
+        let file_path = self.notification_queue_file();
+        if let Some(dir) = file_path.parent() {
+            self.ensure_directory_exists(dir).await?;
+        }
+        self.ensure_ignored("local/").await?;
+        let json = serde_json::to_string_pretty(queue)?;
+        fs::write(file_path, json).await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -208,6 +719,165 @@ mod tests {
         assert!(storage.board_file().exists());
     }
 
+    #[tokio::test]
+    async fn test_initialize_writes_and_applies_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+
+        assert!(storage.config_file().exists());
+        assert_eq!(
+            storage.load_project_config().await.unwrap(),
+            ProjectConfig::default()
+        );
+
+        // A config written before initialization is kept and applied
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        fs::create_dir_all(storage.root_path()).await.unwrap();
+        fs::write(
+            storage.config_file(),
+            "preset = \"agent_pipeline\"\n[agent]\nmode = \"unattended\"\n",
+        )
+        .await
+        .unwrap();
+        storage.initialize().await.unwrap();
+
+        let board = storage.load_board().await.unwrap();
+        assert_eq!(board.config.name, "Agent Pipeline");
+        assert_eq!(
+            board.get_agent_mode_for_status(&crate::domain::TaskStatus::Review),
+            Some(crate::domain::board::AgentMode::Unattended)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_config_is_reported() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        fs::create_dir_all(storage.root_path()).await.unwrap();
+        fs::write(storage.config_file(), "id_prefix = \"1\"\n")
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            storage.initialize().await,
+            Err(HlaviError::ConfigError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_board_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+
+        let mut board = storage.load_board().await.unwrap();
+        board.config.columns.pop();
+        assert!(matches!(
+            storage.save_board(&board).await,
+            Err(HlaviError::ConfigError(_))
+        ));
+
+        // Hand-edited files are checked on load too
+        fs::write(storage.board_file(), serde_json::to_string(&board).unwrap())
+            .await
+            .unwrap();
+        assert!(matches!(
+            storage.load_board().await,
+            Err(HlaviError::ConfigError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_lookup_by_uuid_or_id() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+
+        let task = Task::new(TaskId::new(1), "Tracked".to_string());
+        storage.save_task(&task).await.unwrap();
+        storage
+            .save_task(&Task::new(TaskId::new(2), "Other".to_string()))
+            .await
+            .unwrap();
+
+        let by_uuid = storage.find_task(&task.uuid.to_string()).await.unwrap();
+        assert_eq!(by_uuid.id, task.id);
+        let by_id = storage.find_task("hla1").await.unwrap();
+        assert_eq!(by_id.uuid, task.uuid);
+        assert!(matches!(
+            storage.load_task_by_uuid(&uuid::Uuid::new_v4()).await,
+            Err(HlaviError::TaskNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_legacy_task_gets_stable_uuid() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+
+        let mut task = Task::new(TaskId::new(1), "Legacy".to_string());
+        task.uuid = uuid::Uuid::nil();
+        storage.save_task(&task).await.unwrap();
+
+        let first = storage.load_task(&task.id).await.unwrap();
+        let second = storage.load_task(&task.id).await.unwrap();
+        assert!(!first.uuid.is_nil());
+        assert_eq!(first.uuid, second.uuid);
+    }
+
+    #[tokio::test]
+    async fn test_custom_storage_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::with_dir(temp_dir.path(), "hlavi");
+        storage.initialize().await.unwrap();
+
+        assert_eq!(storage.root_path(), temp_dir.path().join("hlavi"));
+        assert!(temp_dir.path().join("hlavi").join("board.json").exists());
+        assert!(!temp_dir.path().join(".hlavi").exists());
+
+        let elsewhere = TempDir::new().unwrap();
+        let storage = FileStorage::with_dir("/unused", elsewhere.path());
+        assert_eq!(storage.root_path(), elsewhere.path());
+    }
+
+    #[tokio::test]
+    async fn test_read_state_is_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        // A board created before read state existed
+        let gitignore = storage.root_path().join(".gitignore");
+        std::fs::write(&gitignore, "*.db").unwrap();
+
+        let mut state = storage.load_read_state("Ana Lee").await.unwrap();
+        assert!(state.viewed.is_empty());
+        state.mark_viewed(&TaskId::new(1), chrono::Utc::now());
+        storage.save_read_state(&state).await.unwrap();
+
+        assert!(storage.root_path().join("local/read/ana_lee.json").exists());
+        assert_eq!(storage.load_read_state("ana lee").await.unwrap(), state);
+        assert_eq!(
+            std::fs::read_to_string(&gitignore).unwrap(),
+            "*.db\nlocal/\n"
+        );
+    }
+
+    #[test]
+    fn test_dir_from_env() {
+        assert_eq!(FileStorage::dir_from_env(None), PathBuf::from(".hlavi"));
+        assert_eq!(
+            FileStorage::dir_from_env(Some(OsString::new())),
+            PathBuf::from(".hlavi")
+        );
+        assert_eq!(
+            FileStorage::dir_from_env(Some(OsString::from("boards"))),
+            PathBuf::from("boards")
+        );
+    }
+
     #[tokio::test]
     async fn test_task_save_and_load() {
         let temp_dir = TempDir::new().unwrap();
@@ -287,7 +957,8 @@ mod tests {
         storage.initialize().await.unwrap();
 
         let mut task = Task::new(TaskId::new(1), "Test Task".to_string());
-        task.set_description("This is a detailed description".to_string());
+        task.set_description("This is a detailed description".to_string())
+            .unwrap();
         storage.save_task(&task).await.unwrap();
 
         let results = storage.search_tasks("detailed").await.unwrap();
@@ -344,11 +1015,15 @@ mod tests {
         storage.initialize().await.unwrap();
 
         let mut task1 = Task::new(TaskId::new(1), "Authentication Feature".to_string());
-        task1.set_description("Implement user authentication".to_string());
+        task1
+            .set_description("Implement user authentication".to_string())
+            .unwrap();
         task1.add_acceptance_criterion("User can login with password".to_string());
 
         let mut task2 = Task::new(TaskId::new(2), "Another Feature".to_string());
-        task2.set_description("Some other feature".to_string());
+        task2
+            .set_description("Some other feature".to_string())
+            .unwrap();
 
         storage.save_task(&task1).await.unwrap();
         storage.save_task(&task2).await.unwrap();
@@ -363,4 +1038,177 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id.as_str(), "HLA1");
     }
+
+    #[tokio::test]
+    async fn test_search_with_constraints_skips_by_status() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+
+        let mut open = Task::new(TaskId::new(1), "Parser crash".to_string());
+        open.status = crate::domain::TaskStatus::Open;
+        open.add_tag("bug".to_string());
+        let mut done = Task::new(TaskId::new(2), "Parser rewrite".to_string());
+        done.status = crate::domain::TaskStatus::Done;
+        let mut board = storage.load_board().await.unwrap();
+        for task in [&open, &done] {
+            storage.save_task(task).await.unwrap();
+            board.add_task(task);
+        }
+        storage.save_board(&board).await.unwrap();
+        storage.list_summaries().await.unwrap();
+        // Never read: the index already rules it out. Length and mtime are
+        // kept so the index still looks fresh.
+        let path = storage.task_file(&done.id);
+        let metadata = std::fs::metadata(&path).unwrap();
+        std::fs::write(&path, "x".repeat(metadata.len() as usize)).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(metadata.modified().unwrap())
+            .unwrap();
+
+        let constraints = SearchConstraints::default()
+            .with_status(crate::domain::TaskStatus::Open)
+            .with_tag("bug");
+        let results = storage
+            .search_tasks_with("parser", &constraints)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, open.id);
+    }
+
+    #[tokio::test]
+    async fn test_index_follows_saves_and_external_edits() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        let mut task = Task::new(TaskId::new(1), "First".to_string());
+        storage.save_task(&task).await.unwrap();
+        storage
+            .save_task(&Task::new(TaskId::new(2), "Second".to_string()))
+            .await
+            .unwrap();
+
+        let summaries = storage.list_summaries().await.unwrap();
+        assert_eq!(summaries.len(), 2);
+        assert!(storage.index_file().exists());
+
+        // Saves and deletes keep the index current
+        task.title = "Renamed".to_string();
+        storage.save_task(&task).await.unwrap();
+        storage.delete_task(&TaskId::new(2)).await.unwrap();
+        let index = storage.read_index().await.unwrap();
+        assert_eq!(index.tasks.len(), 1);
+        assert_eq!(index.tasks["HLA1"].title, "Renamed");
+        // The fingerprint derived from the changed file matches a full listing
+        assert_eq!(
+            index.fingerprint,
+            storage.tasks_fingerprint().await.unwrap()
+        );
+        storage
+            .save_task(&Task::new(TaskId::new(4), "Added".to_string()))
+            .await
+            .unwrap();
+        let index = storage.read_index().await.unwrap();
+        assert_eq!(
+            index.fingerprint,
+            storage.tasks_fingerprint().await.unwrap()
+        );
+        storage.delete_task(&TaskId::new(4)).await.unwrap();
+
+        // A file written behind the storage's back triggers a rebuild
+        let mut pulled = Task::new(TaskId::new(3), "Pulled".to_string());
+        pulled.add_tag("Remote".to_string());
+        std::fs::write(
+            storage.task_file(&pulled.id),
+            serde_json::to_string(&pulled).unwrap(),
+        )
+        .unwrap();
+        let summaries = storage.list_summaries().await.unwrap();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[1].tags, vec!["remote".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_task_etag() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        let mut task = Task::new(TaskId::new(1), "Tagged".to_string());
+
+        let saved = storage.save_task_tagged(&task).await.unwrap();
+        let (_, loaded) = storage.load_task_tagged(&task.id).await.unwrap();
+        assert_eq!(saved, loaded);
+        assert_eq!(storage.task_etag(&task.id).await.unwrap(), saved);
+
+        task.add_tag("changed".to_string());
+        let updated = storage.save_task_tagged(&task).await.unwrap();
+        assert_ne!(updated, saved);
+
+        // Edits made outside the storage are detected too
+        std::fs::write(
+            storage.task_file(&task.id),
+            serde_json::to_string(&task).unwrap(),
+        )
+        .unwrap();
+        assert_ne!(storage.task_etag(&task.id).await.unwrap(), updated);
+        assert!(storage.task_etag(&TaskId::new(9)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_large_description_loaded_on_demand() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        let mut task = Task::new(TaskId::new(1), "Agent report".to_string());
+        let body = "log line\n".repeat(1000);
+        task.set_description(body.clone()).unwrap();
+        storage.save_task(&task).await.unwrap();
+        assert!(storage.body_file(&task.id).exists());
+
+        let mut header = storage.load_task_header(&task.id).await.unwrap();
+        assert!(header.body_omitted);
+        assert_eq!(header.description, None);
+
+        // Saving a header keeps the body it was loaded without
+        header.add_tag("triaged".to_string());
+        storage.save_task(&header).await.unwrap();
+        let full = storage.load_task_full(header).await.unwrap();
+        assert_eq!(full.description.as_deref(), Some(body.as_str()));
+        assert_eq!(storage.load_task(&task.id).await.unwrap(), full);
+
+        // A short description moves back into the task file
+        let mut full = full;
+        full.set_description("Short".to_string()).unwrap();
+        storage.save_task(&full).await.unwrap();
+        assert!(!storage.body_file(&task.id).exists());
+        let header = storage.load_task_header(&task.id).await.unwrap();
+        assert_eq!(header.description.as_deref(), Some("Short"));
+    }
+
+    #[tokio::test]
+    async fn test_load_tasks_keeps_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        storage.initialize().await.unwrap();
+        for n in 1..=100 {
+            storage
+                .save_task(&Task::new(TaskId::new(n), format!("Task {}", n)))
+                .await
+                .unwrap();
+        }
+
+        let ids: Vec<TaskId> = (1..=100).rev().map(TaskId::new).collect();
+        let tasks = storage.load_tasks(&ids).await.unwrap();
+        let loaded: Vec<TaskId> = tasks.into_iter().map(|t| t.id).collect();
+        assert_eq!(loaded, ids);
+
+        assert!(storage
+            .load_tasks(&[TaskId::new(1), TaskId::new(500)])
+            .await
+            .is_err());
+    }
 }
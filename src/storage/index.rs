@@ -0,0 +1,73 @@
+//! The summary index FileStorage keeps in `index.json`.
+//!
+//! The index is a local cache: it records a fingerprint of the task files it
+//! was built from, and is rebuilt whenever the files change behind its back
+//! (for example after a `git pull`).
+
+use crate::{
+    domain::{Task, TaskSummary},
+    storage::checksum::Fnv1a,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct TaskIndex {
+    /// Fingerprint of the tasks directory when the index was written
+    pub fingerprint: String,
+    /// Summaries keyed by task ID
+    pub tasks: BTreeMap<String, TaskSummary>,
+}
+
+impl TaskIndex {
+    pub fn build<'a>(tasks: impl IntoIterator<Item = &'a Task>, fingerprint: String) -> Self {
+        Self {
+            fingerprint,
+            tasks: tasks
+                .into_iter()
+                .map(|task| (task.id.as_str().to_string(), TaskSummary::from(task)))
+                .collect(),
+        }
+    }
+
+    pub fn upsert(&mut self, task: &Task) {
+        self.tasks
+            .insert(task.id.as_str().to_string(), TaskSummary::from(task));
+    }
+}
+
+/// A stable fingerprint of a directory listing
+///
+/// Each entry is a file name with its size and modification time; any
+/// added, removed or rewritten file changes the result.
+pub(crate) fn fingerprint(mut entries: Vec<(String, u64, u128)>) -> String {
+    entries.sort();
+    let mut hasher = Fnv1a::new();
+    for (name, len, modified) in &entries {
+        hasher.write(name.as_bytes());
+        hasher.write(&len.to_le_bytes());
+        hasher.write(&modified.to_le_bytes());
+    }
+    format!("{}:{:016x}", entries.len(), hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_changes_with_files() {
+        let entries = vec![
+            ("HLA1.json".to_string(), 10, 5),
+            ("HLA2.json".to_string(), 20, 6),
+        ];
+        let mut reordered = entries.clone();
+        reordered.reverse();
+        assert_eq!(fingerprint(entries.clone()), fingerprint(reordered));
+
+        let mut touched = entries.clone();
+        touched[1].2 = 7;
+        assert_ne!(fingerprint(entries.clone()), fingerprint(touched));
+        assert_ne!(fingerprint(entries), fingerprint(Vec::new()));
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{
+    domain::{
+        Board, BoardConfig, NotificationQueue, ProjectConfig, ReadState, Task, TaskId,
+        ViewPreferences,
+    },
+    error::{HlaviError, Result},
+    storage::Storage,
+};
+use async_trait::async_trait;
+use std::{collections::BTreeMap, sync::RwLock};
+
+/// Storage backend that keeps everything in memory
+///
+/// Nothing is written to disk, which makes it suitable for simulations,
+/// previews and tests. Contents are lost when the storage is dropped.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    config: ProjectConfig,
+    board: RwLock<Option<Board>>,
+    tasks: RwLock<BTreeMap<String, Task>>,
+    read_states: RwLock<BTreeMap<String, ReadState>>,
+    view_preferences: RwLock<BTreeMap<String, ViewPreferences>>,
+    notifications: RwLock<NotificationQueue>,
+}
+
+impl MemoryStorage {
+    /// Creates an empty, uninitialized storage
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a storage already holding `board` and `tasks`
+    pub fn with_contents(config: ProjectConfig, board: Board, tasks: Vec<Task>) -> Self {
+        Self {
+            config,
+            board: RwLock::new(Some(board)),
+            tasks: RwLock::new(
+                tasks
+                    .into_iter()
+                    .map(|task| (task.id.as_str().to_string(), task))
+                    .collect(),
+            ),
+            read_states: RwLock::new(BTreeMap::new()),
+            view_preferences: RwLock::new(BTreeMap::new()),
+            notifications: RwLock::new(NotificationQueue::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl Storage for MemoryStorage {
+    async fn initialize(&self) -> Result<()> {
+        let mut board = self.board.write().expect("memory storage lock poisoned");
+        if board.is_none() {
+            *board = Some(Board::new(BoardConfig::preset(self.config.preset)));
+        }
+        Ok(())
+    }
+
+    async fn save_task(&self, task: &Task) -> Result<()> {
+        self.tasks
+            .write()
+            .expect("memory storage lock poisoned")
+            .insert(task.id.as_str().to_string(), task.clone());
+        Ok(())
+    }
+
+    async fn load_task(&self, id: &TaskId) -> Result<Task> {
+        self.tasks
+            .read()
+            .expect("memory storage lock poisoned")
+            .get(id.as_str())
+            .cloned()
+            .ok_or_else(|| HlaviError::TaskNotFound(id.to_string()))
+    }
+
+    async fn list_task_ids(&self) -> Result<Vec<TaskId>> {
+        let tasks = self.tasks.read().expect("memory storage lock poisoned");
+        Ok(tasks.values().map(|task| task.id.clone()).collect())
+    }
+
+    async fn search_tasks(&self, query: &str) -> Result<Vec<Task>> {
+        let tasks = self.tasks.read().expect("memory storage lock poisoned");
+        Ok(tasks
+            .values()
+            .filter(|task| task.matches_query(query))
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_task(&self, id: &TaskId) -> Result<()> {
+        self.tasks
+            .write()
+            .expect("memory storage lock poisoned")
+            .remove(id.as_str())
+            .map(|_| ())
+            .ok_or_else(|| HlaviError::TaskNotFound(id.to_string()))
+    }
+
+    async fn save_board(&self, board: &Board) -> Result<()> {
+        board.config.validate()?;
+        *self.board.write().expect("memory storage lock poisoned") = Some(board.clone());
+        Ok(())
+    }
+
+    async fn load_board(&self) -> Result<Board> {
+        self.board
+            .read()
+            .expect("memory storage lock poisoned")
+            .clone()
+            .ok_or(HlaviError::BoardNotInitialized)
+    }
+
+    async fn is_initialized(&self) -> bool {
+        self.board
+            .read()
+            .expect("memory storage lock poisoned")
+            .is_some()
+    }
+
+    async fn load_project_config(&self) -> Result<ProjectConfig> {
+        Ok(self.config.clone())
+    }
+
+    async fn load_read_state(&self, actor: &str) -> Result<ReadState> {
+        let states = self
+            .read_states
+            .read()
+            .expect("memory storage lock poisoned");
+        Ok(states
+            .get(&actor.to_lowercase())
+            .cloned()
+            .unwrap_or_else(|| ReadState::new(actor)))
+    }
+
+    async fn save_read_state(&self, state: &ReadState) -> Result<()> {
+        self.read_states
+            .write()
+            .expect("memory storage lock poisoned")
+            .insert(state.actor.to_lowercase(), state.clone());
+        Ok(())
+    }
+
+    async fn load_view_preferences(&self, actor: &str) -> Result<ViewPreferences> {
+        let preferences = self
+            .view_preferences
+            .read()
+            .expect("memory storage lock poisoned");
+        Ok(preferences
+            .get(&actor.to_lowercase())
+            .cloned()
+            .unwrap_or_else(|| ViewPreferences::new(actor)))
+    }
+
+    async fn save_view_preferences(&self, preferences: &ViewPreferences) -> Result<()> {
+        self.view_preferences
+            .write()
+            .expect("memory storage lock poisoned")
+            .insert(preferences.actor.to_lowercase(), preferences.clone());
+        Ok(())
+    }
+
+    async fn load_notification_queue(&self) -> Result<NotificationQueue> {
+        Ok(self
+            .notifications
+            .read()
+            .expect("memory storage lock poisoned")
+            .clone())
+    }
+
+    async fn save_notification_queue(&self, queue: &NotificationQueue) -> Result<()> {
+        *self
+            .notifications
+            .write()
+            .expect("memory storage lock poisoned") = queue.clone();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_storage_round_trip() {
+        let storage = MemoryStorage::new();
+        assert!(!storage.is_initialized().await);
+        storage.initialize().await.unwrap();
+        assert!(storage.load_board().await.is_ok());
+
+        let task = Task::new(TaskId::new(1), "Kept in memory".to_string());
+        storage.save_task(&task).await.unwrap();
+        assert_eq!(storage.load_task(&task.id).await.unwrap(), task);
+        assert_eq!(storage.search_tasks("memory").await.unwrap().len(), 1);
+
+        storage.delete_task(&task.id).await.unwrap();
+        assert!(matches!(
+            storage.load_task(&task.id).await,
+            Err(HlaviError::TaskNotFound(_))
+        ));
+    }
+}
@@ -1,10 +1,21 @@
 use crate::{
-    domain::{Board, Task, TaskId},
-    error::Result,
+    domain::{
+        Board, BoardSnapshot, NotificationQueue, ProjectConfig, ReadState, SearchConstraints, Task,
+        TaskId, TaskSummary, ViewPreferences,
+    },
+    error::{HlaviError, Result},
 };
 use async_trait::async_trait;
+use std::str::FromStr;
+use uuid::Uuid;
 
+mod checksum;
 pub mod file_storage;
+mod index;
+pub mod memory_storage;
+
+pub use checksum::{etag, task_etag};
+pub use memory_storage::MemoryStorage;
 
 #[cfg(feature = "sqlite-storage")]
 pub mod sqlite_storage;
@@ -21,13 +32,116 @@ pub trait Storage: Send + Sync {
     /// Loads a task by ID
     async fn load_task(&self, id: &TaskId) -> Result<Task>;
 
+    /// Loads a task together with its ETag
+    ///
+    /// The ETag is a hash of the stored content; it changes whenever the
+    /// task does.
+    async fn load_task_tagged(&self, id: &TaskId) -> Result<(Task, String)> {
+        let task = self.load_task(id).await?;
+        let etag = task_etag(&task)?;
+        Ok((task, etag))
+    }
+
+    /// Loads a task without its description if the backend stores large
+    /// descriptions separately
+    ///
+    /// An omitted description is `None` with `body_omitted` set; fill it in
+    /// with `load_task_full`. Backends that store tasks whole return the full
+    /// task.
+    async fn load_task_header(&self, id: &TaskId) -> Result<Task> {
+        self.load_task(id).await
+    }
+
+    /// Loads several task headers, in the order of `ids`
+    async fn load_task_headers(&self, ids: &[TaskId]) -> Result<Vec<Task>> {
+        self.load_tasks(ids).await
+    }
+
+    /// Fills in the description of a task loaded as a header
+    async fn load_task_full(&self, task: Task) -> Result<Task> {
+        if task.body_omitted {
+            self.load_task(&task.id).await
+        } else {
+            Ok(task)
+        }
+    }
+
+    /// Saves a task and returns its new ETag
+    async fn save_task_tagged(&self, task: &Task) -> Result<String> {
+        self.save_task(task).await?;
+        task_etag(task)
+    }
+
+    /// The current ETag of a task, for checking whether a cached copy is
+    /// still current
+    ///
+    /// The default implementation loads the task; backends should override
+    /// it to hash the stored content without deserializing it.
+    async fn task_etag(&self, id: &TaskId) -> Result<String> {
+        Ok(self.load_task_tagged(id).await?.1)
+    }
+
     /// Lists all task IDs
     async fn list_task_ids(&self) -> Result<Vec<TaskId>>;
 
+    /// Loads several tasks, in the order of `ids`
+    ///
+    /// The default implementation loads them one at a time; backends that
+    /// can read concurrently should override it.
+    async fn load_tasks(&self, ids: &[TaskId]) -> Result<Vec<Task>> {
+        let mut tasks = Vec::with_capacity(ids.len());
+        for id in ids {
+            tasks.push(self.load_task(id).await?);
+        }
+        Ok(tasks)
+    }
+
+    /// Loads every task
+    async fn load_all_tasks(&self) -> Result<Vec<Task>> {
+        let ids = self.list_task_ids().await?;
+        self.load_tasks(&ids).await
+    }
+
+    /// Lists a summary of every task, ordered by ID number
+    ///
+    /// The default implementation loads every task; backends that keep an
+    /// index should override it.
+    async fn list_summaries(&self) -> Result<Vec<TaskSummary>> {
+        let mut summaries: Vec<TaskSummary> = self
+            .load_all_tasks()
+            .await?
+            .iter()
+            .map(TaskSummary::from)
+            .collect();
+        summaries.sort_by_key(|s| s.id.number());
+        Ok(summaries)
+    }
+
     /// Searches for tasks matching the query in title, description, or acceptance criteria
     /// Returns a vector of tasks that match the query (case-insensitive)
     async fn search_tasks(&self, query: &str) -> Result<Vec<Task>>;
 
+    /// Searches like `search_tasks`, keeping only tasks that satisfy
+    /// `constraints`
+    ///
+    /// Constraints are checked before the text query. Backends that can tell
+    /// a task's status or dates without loading it should override this to
+    /// skip those tasks entirely.
+    async fn search_tasks_with(
+        &self,
+        query: &str,
+        constraints: &SearchConstraints,
+    ) -> Result<Vec<Task>> {
+        let mut matching = Vec::new();
+        for id in self.list_task_ids().await? {
+            let task = self.load_task(&id).await?;
+            if constraints.matches(&task) && task.matches_query(query) {
+                matching.push(task);
+            }
+        }
+        Ok(matching)
+    }
+
     /// Deletes a task
     async fn delete_task(&self, id: &TaskId) -> Result<()>;
 
@@ -39,4 +153,90 @@ pub trait Storage: Send + Sync {
 
     /// Checks if the project is initialized
     async fn is_initialized(&self) -> bool;
+
+    /// Loads a task by its stable UUID
+    ///
+    /// The default implementation scans every task; backends with an index
+    /// should override it.
+    async fn load_task_by_uuid(&self, uuid: &Uuid) -> Result<Task> {
+        for id in self.list_task_ids().await? {
+            let task = self.load_task(&id).await?;
+            if &task.uuid == uuid {
+                return Ok(task);
+            }
+        }
+        Err(HlaviError::TaskNotFound(uuid.to_string()))
+    }
+
+    /// Loads a task by either its display ID (`HLA12`) or its UUID
+    async fn find_task(&self, identifier: &str) -> Result<Task> {
+        match Uuid::from_str(identifier) {
+            Ok(uuid) => self.load_task_by_uuid(&uuid).await,
+            Err(_) => self.load_task(&TaskId::from_str(identifier)?).await,
+        }
+    }
+
+    /// Loads the project settings; backends without a config file use defaults
+    async fn load_project_config(&self) -> Result<ProjectConfig> {
+        Ok(ProjectConfig::default())
+    }
+
+    /// Persists a snapshot; an existing snapshot with the same label is never
+    /// overwritten
+    async fn save_snapshot(&self, _snapshot: &BoardSnapshot) -> Result<()> {
+        Err(HlaviError::StorageError(
+            "Snapshots are not supported by this storage backend".to_string(),
+        ))
+    }
+
+    /// Loads the snapshot saved under `label`
+    async fn load_snapshot(&self, label: &str) -> Result<BoardSnapshot> {
+        Err(HlaviError::StorageError(format!(
+            "Snapshot '{}' not found",
+            label
+        )))
+    }
+
+    /// Lists the labels of all saved snapshots
+    async fn list_snapshots(&self) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Loads an actor's local read state; an actor with none gets an empty one
+    async fn load_read_state(&self, actor: &str) -> Result<ReadState> {
+        Ok(ReadState::new(actor))
+    }
+
+    /// Persists an actor's read state outside the shared task data
+    async fn save_read_state(&self, _state: &ReadState) -> Result<()> {
+        Err(HlaviError::StorageError(
+            "Read state is not supported by this storage backend".to_string(),
+        ))
+    }
+
+    /// Loads an actor's local view preferences; an actor with none gets
+    /// empty ones
+    async fn load_view_preferences(&self, actor: &str) -> Result<ViewPreferences> {
+        Ok(ViewPreferences::new(actor))
+    }
+
+    /// Persists an actor's view preferences outside the shared board config
+    async fn save_view_preferences(&self, _preferences: &ViewPreferences) -> Result<()> {
+        Err(HlaviError::StorageError(
+            "View preferences are not supported by this storage backend".to_string(),
+        ))
+    }
+
+    /// Loads the notifications waiting for delivery
+    async fn load_notification_queue(&self) -> Result<NotificationQueue> {
+        Ok(NotificationQueue::new())
+    }
+
+    /// Persists the notifications waiting for delivery, so they survive a
+    /// restart
+    async fn save_notification_queue(&self, _queue: &NotificationQueue) -> Result<()> {
+        Err(HlaviError::StorageError(
+            "Notification queues are not supported by this storage backend".to_string(),
+        ))
+    }
 }
@@ -0,0 +1,552 @@
+//! Reconciliation of two storage backends (e.g., a local `FileStorage` and a
+//! remote copy).
+//!
+//! Each sync records the version of every task both sides agreed on in a
+//! [`SyncState`]. The next sync uses those versions as the common ancestor for
+//! three-way merges, so edits made on both sides since then are combined
+//! field by field instead of one side overwriting the other.
+//!
+//! Tasks are matched by UUID rather than display ID, since two stores can
+//! each create an `HLA1` of their own before they first sync.
+
+use crate::{
+    domain::{merge::merge, MergeConflict, Task, TaskId},
+    error::{HlaviError, Result},
+    storage::Storage,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// How to resolve tasks both sides changed incompatibly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPolicy {
+    /// Merge, taking side A's value for conflicting fields
+    PreferA,
+    /// Merge, taking side B's value for conflicting fields
+    PreferB,
+    /// Merge, taking the more recently updated side's value for conflicting fields
+    NewestWins,
+    /// Leave conflicting tasks untouched on both sides and report them
+    ReportConflicts,
+}
+
+/// Versions both sides agreed on at the last sync, used as merge bases
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncState {
+    /// Keyed by task UUID, or by display ID for tasks without one
+    pub bases: HashMap<String, Task>,
+}
+
+/// Which side of a sync
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    A,
+    B,
+}
+
+/// What a sync did
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncReport {
+    /// Tasks written to side A (new or updated on B)
+    pub copied_to_a: Vec<TaskId>,
+    /// Tasks written to side B (new or updated on A)
+    pub copied_to_b: Vec<TaskId>,
+    /// Tasks changed on both sides and merged
+    pub merged: Vec<TaskId>,
+    /// Tasks deleted from a side because the other side deleted them
+    pub deleted: Vec<(TaskId, Side)>,
+    /// Tasks with conflicting edits, with how they were left
+    pub conflicts: Vec<(TaskId, Vec<MergeConflict>)>,
+    /// Display IDs used by a different task on each side; both are left
+    /// untouched until one of them is renumbered
+    pub collisions: Vec<TaskId>,
+}
+
+impl SyncReport {
+    /// Returns true if nothing needed to change
+    pub fn is_noop(&self) -> bool {
+        self.copied_to_a.is_empty()
+            && self.copied_to_b.is_empty()
+            && self.merged.is_empty()
+            && self.deleted.is_empty()
+            && self.conflicts.is_empty()
+            && self.collisions.is_empty()
+    }
+
+    /// Converts unresolved conflicts and ID collisions into an error, for
+    /// callers that treat them as failures
+    pub fn into_result(self) -> Result<Self> {
+        if !self.collisions.is_empty() {
+            let ids: Vec<&str> = self.collisions.iter().map(TaskId::as_str).collect();
+            return Err(HlaviError::StorageError(format!(
+                "Sync found different tasks sharing the ID(s) {}",
+                ids.join(", ")
+            )));
+        }
+        if self.conflicts.is_empty() {
+            Ok(self)
+        } else {
+            Err(HlaviError::StorageError(format!(
+                "Sync left {} task(s) in conflict",
+                self.conflicts.len()
+            )))
+        }
+    }
+}
+
+/// The key a task is matched by across stores: its UUID, or its display ID
+/// for tasks written before UUIDs existed
+fn identity(task: &Task) -> String {
+    if task.uuid.is_nil() {
+        task.id.as_str().to_string()
+    } else {
+        task.uuid.to_string()
+    }
+}
+
+async fn load_all(storage: &dyn Storage) -> Result<HashMap<String, Task>> {
+    let mut tasks = HashMap::new();
+    for id in storage.list_task_ids().await? {
+        let task = storage.load_task(&id).await?;
+        tasks.insert(identity(&task), task);
+    }
+    Ok(tasks)
+}
+
+/// Finds display IDs held by different tasks on each side, returning the
+/// keys of every task involved
+fn collisions(
+    tasks_a: &HashMap<String, Task>,
+    tasks_b: &HashMap<String, Task>,
+    report: &mut SyncReport,
+) -> HashSet<String> {
+    let by_id: HashMap<&str, &String> = tasks_b
+        .iter()
+        .map(|(key, task)| (task.id.as_str(), key))
+        .collect();
+    let mut keys = HashSet::new();
+    for (key_a, task_a) in tasks_a {
+        if let Some(key_b) = by_id.get(task_a.id.as_str()).filter(|key| **key != key_a) {
+            keys.insert(key_a.clone());
+            keys.insert((*key_b).clone());
+            report.collisions.push(task_a.id.clone());
+        }
+    }
+    report.collisions.sort_by(|x, y| x.as_str().cmp(y.as_str()));
+    keys
+}
+
+fn changed(base: &Task, task: &Task) -> bool {
+    !base.diff(task).is_empty()
+}
+
+/// Reconciles the tasks and board counters of two storages
+///
+/// `state` is read for merge bases and updated with the agreed versions; the
+/// caller should persist it between syncs. Tasks deleted on one side are
+/// deleted on the other unless the other side edited them since the last sync.
+/// Different tasks that share a display ID are reported as collisions and
+/// neither is copied, merged or deleted.
+pub async fn sync(
+    a: &dyn Storage,
+    b: &dyn Storage,
+    state: &mut SyncState,
+    policy: SyncPolicy,
+) -> Result<SyncReport> {
+    let tasks_a = load_all(a).await?;
+    let tasks_b = load_all(b).await?;
+    let mut report = SyncReport::default();
+    let collided = collisions(&tasks_a, &tasks_b, &mut report);
+
+    let keys: BTreeSet<&String> = tasks_a
+        .keys()
+        .chain(tasks_b.keys())
+        .chain(state.bases.keys())
+        .collect();
+    let mut bases = HashMap::new();
+    let mut conflicted = HashSet::new();
+
+    for key in keys {
+        let base = state.bases.get(key);
+        if collided.contains(key) {
+            if let Some(base) = base {
+                bases.insert(key.clone(), base.clone());
+            }
+            conflicted.insert(key.clone());
+            continue;
+        }
+        let agreed = match (tasks_a.get(key), tasks_b.get(key)) {
+            (Some(task_a), None) => {
+                if base.is_some_and(|base| !changed(base, task_a)) {
+                    a.delete_task(&task_a.id).await?;
+                    report.deleted.push((task_a.id.clone(), Side::A));
+                    None
+                } else {
+                    b.save_task(task_a).await?;
+                    report.copied_to_b.push(task_a.id.clone());
+                    Some(task_a.clone())
+                }
+            }
+            (None, Some(task_b)) => {
+                if base.is_some_and(|base| !changed(base, task_b)) {
+                    b.delete_task(&task_b.id).await?;
+                    report.deleted.push((task_b.id.clone(), Side::B));
+                    None
+                } else {
+                    a.save_task(task_b).await?;
+                    report.copied_to_a.push(task_b.id.clone());
+                    Some(task_b.clone())
+                }
+            }
+            (Some(task_a), Some(task_b)) => {
+                reconcile(a, b, base, task_a, task_b, policy, &mut report).await?
+            }
+            (None, None) => None,
+        };
+
+        match agreed {
+            Some(task) => {
+                bases.insert(key.clone(), task);
+            }
+            // Left in conflict: the last agreed version stays the base, so the
+            // next sync still sees both sides' edits
+            None if tasks_a.contains_key(key) && tasks_b.contains_key(key) => {
+                if let Some(base) = base {
+                    bases.insert(key.clone(), base.clone());
+                }
+                conflicted.insert(key.clone());
+            }
+            None => {}
+        }
+    }
+    state.bases = bases;
+
+    let agreed: Vec<&Task> = state
+        .bases
+        .iter()
+        .filter(|(key, _)| !conflicted.contains(*key))
+        .map(|(_, task)| task)
+        .collect();
+    sync_boards(a, b, &agreed).await?;
+    Ok(report)
+}
+
+/// Reconciles a task present on both sides, returning the agreed version
+/// (`None` if the sides were left in conflict)
+async fn reconcile(
+    a: &dyn Storage,
+    b: &dyn Storage,
+    base: Option<&Task>,
+    task_a: &Task,
+    task_b: &Task,
+    policy: SyncPolicy,
+    report: &mut SyncReport,
+) -> Result<Option<Task>> {
+    if !changed(task_a, task_b) {
+        return Ok(Some(task_a.clone()));
+    }
+
+    // Both sides hold the same task (matched by UUID) but never synced it;
+    // treat the older side as the base
+    let fallback;
+    let base = match base {
+        Some(base) => base,
+        None => {
+            fallback = if task_a.updated_at <= task_b.updated_at {
+                task_a.clone()
+            } else {
+                task_b.clone()
+            };
+            &fallback
+        }
+    };
+
+    let a_changed = changed(base, task_a);
+    let b_changed = changed(base, task_b);
+    if a_changed && !b_changed {
+        b.save_task(task_a).await?;
+        report.copied_to_b.push(task_a.id.clone());
+        return Ok(Some(task_a.clone()));
+    }
+    if b_changed && !a_changed {
+        a.save_task(task_b).await?;
+        report.copied_to_a.push(task_b.id.clone());
+        return Ok(Some(task_b.clone()));
+    }
+
+    let prefer_a = match policy {
+        SyncPolicy::PreferA | SyncPolicy::ReportConflicts => true,
+        SyncPolicy::PreferB => false,
+        SyncPolicy::NewestWins => task_a.updated_at >= task_b.updated_at,
+    };
+    let outcome = if prefer_a {
+        merge(base, task_a, task_b)
+    } else {
+        merge(base, task_b, task_a)
+    };
+
+    if !outcome.is_clean() {
+        report
+            .conflicts
+            .push((task_a.id.clone(), outcome.conflicts.clone()));
+        if policy == SyncPolicy::ReportConflicts {
+            return Ok(None);
+        }
+    }
+
+    a.save_task(&outcome.merged).await?;
+    b.save_task(&outcome.merged).await?;
+    report.merged.push(task_a.id.clone());
+    Ok(Some(outcome.merged))
+}
+
+/// Brings both boards' task maps and ID counters in line with the synced tasks
+///
+/// Archived, trashed and draft tasks are taken off the boards.
+async fn sync_boards(a: &dyn Storage, b: &dyn Storage, tasks: &[&Task]) -> Result<()> {
+    let mut board_a = a.load_board().await?;
+    let mut board_b = b.load_board().await?;
+    let next = board_a.next_task_number.max(board_b.next_task_number);
+
+    for board in [&mut board_a, &mut board_b] {
+        board.next_task_number = next;
+        for task in tasks {
+            if task.is_on_board() {
+                board.add_task(task);
+            } else {
+                board.remove_task(&task.id);
+            }
+        }
+    }
+
+    a.save_board(&board_a).await?;
+    b.save_board(&board_b).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{domain::Priority, storage::file_storage::FileStorage};
+    use tempfile::TempDir;
+
+    async fn storages() -> (TempDir, FileStorage, FileStorage) {
+        let temp_dir = TempDir::new().unwrap();
+        let a = FileStorage::new(temp_dir.path().join("a"));
+        let b = FileStorage::new(temp_dir.path().join("b"));
+        a.initialize().await.unwrap();
+        b.initialize().await.unwrap();
+        (temp_dir, a, b)
+    }
+
+    #[tokio::test]
+    async fn test_new_tasks_are_copied_both_ways() {
+        let (_dir, a, b) = storages().await;
+        a.save_task(&Task::new(TaskId::new(1), "On A".to_string()))
+            .await
+            .unwrap();
+        b.save_task(&Task::new(TaskId::new(2), "On B".to_string()))
+            .await
+            .unwrap();
+
+        let mut state = SyncState::default();
+        let report = sync(&a, &b, &mut state, SyncPolicy::ReportConflicts)
+            .await
+            .unwrap();
+
+        assert_eq!(report.copied_to_b, vec![TaskId::new(1)]);
+        assert_eq!(report.copied_to_a, vec![TaskId::new(2)]);
+        assert_eq!(b.load_task(&TaskId::new(1)).await.unwrap().title, "On A");
+        assert_eq!(state.bases.len(), 2);
+
+        let again = sync(&a, &b, &mut state, SyncPolicy::ReportConflicts)
+            .await
+            .unwrap();
+        assert!(again.is_noop());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_edits_are_merged() {
+        let (_dir, a, b) = storages().await;
+        let task = Task::new(TaskId::new(1), "Shared".to_string());
+        a.save_task(&task).await.unwrap();
+        let mut state = SyncState::default();
+        sync(&a, &b, &mut state, SyncPolicy::ReportConflicts)
+            .await
+            .unwrap();
+
+        let mut on_a = task.clone();
+        on_a.set_title("Renamed on A".to_string()).unwrap();
+        a.save_task(&on_a).await.unwrap();
+        let mut on_b = task.clone();
+        on_b.set_priority(Priority::High);
+        b.save_task(&on_b).await.unwrap();
+
+        let report = sync(&a, &b, &mut state, SyncPolicy::ReportConflicts)
+            .await
+            .unwrap();
+
+        assert_eq!(report.merged, vec![TaskId::new(1)]);
+        for side in [&a, &b] {
+            let merged = side.load_task(&TaskId::new(1)).await.unwrap();
+            assert_eq!(merged.title, "Renamed on A");
+            assert_eq!(merged.priority, Some(Priority::High));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conflicts_follow_policy() {
+        let (_dir, a, b) = storages().await;
+        let task = Task::new(TaskId::new(1), "Shared".to_string());
+        a.save_task(&task).await.unwrap();
+        let mut state = SyncState::default();
+        sync(&a, &b, &mut state, SyncPolicy::ReportConflicts)
+            .await
+            .unwrap();
+
+        let mut on_a = task.clone();
+        on_a.set_title("A".to_string()).unwrap();
+        a.save_task(&on_a).await.unwrap();
+        let mut on_b = task.clone();
+        on_b.set_title("B".to_string()).unwrap();
+        b.save_task(&on_b).await.unwrap();
+
+        let mut reported = state.clone();
+        let report = sync(&a, &b, &mut reported, SyncPolicy::ReportConflicts)
+            .await
+            .unwrap();
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(a.load_task(&TaskId::new(1)).await.unwrap().title, "A");
+        assert_eq!(b.load_task(&TaskId::new(1)).await.unwrap().title, "B");
+        assert!(report.into_result().is_err());
+
+        let report = sync(&a, &b, &mut state, SyncPolicy::PreferB).await.unwrap();
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(a.load_task(&TaskId::new(1)).await.unwrap().title, "B");
+    }
+
+    #[tokio::test]
+    async fn test_deletions_propagate() {
+        let (_dir, a, b) = storages().await;
+        a.save_task(&Task::new(TaskId::new(1), "Doomed".to_string()))
+            .await
+            .unwrap();
+        let mut state = SyncState::default();
+        sync(&a, &b, &mut state, SyncPolicy::ReportConflicts)
+            .await
+            .unwrap();
+
+        b.delete_task(&TaskId::new(1)).await.unwrap();
+        let report = sync(&a, &b, &mut state, SyncPolicy::ReportConflicts)
+            .await
+            .unwrap();
+
+        assert_eq!(report.deleted, vec![(TaskId::new(1), Side::A)]);
+        assert!(a.load_task(&TaskId::new(1)).await.is_err());
+        assert!(state.bases.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reported_conflicts_keep_their_base() {
+        let (_dir, a, b) = storages().await;
+        let task = Task::new(TaskId::new(1), "Shared".to_string());
+        a.save_task(&task).await.unwrap();
+        let mut state = SyncState::default();
+        sync(&a, &b, &mut state, SyncPolicy::ReportConflicts)
+            .await
+            .unwrap();
+
+        let mut on_a = task.clone();
+        on_a.set_title("A".to_string()).unwrap();
+        a.save_task(&on_a).await.unwrap();
+        let mut on_b = task.clone();
+        on_b.set_title("B".to_string()).unwrap();
+        b.save_task(&on_b).await.unwrap();
+
+        for _ in 0..2 {
+            let report = sync(&a, &b, &mut state, SyncPolicy::ReportConflicts)
+                .await
+                .unwrap();
+            assert_eq!(report.conflicts.len(), 1);
+            assert!(report.copied_to_a.is_empty() && report.copied_to_b.is_empty());
+            assert_eq!(state.bases[&task.uuid.to_string()].title, "Shared");
+        }
+        assert_eq!(a.load_task(&TaskId::new(1)).await.unwrap().title, "A");
+        assert_eq!(b.load_task(&TaskId::new(1)).await.unwrap().title, "B");
+    }
+
+    #[tokio::test]
+    async fn test_archiving_propagates() {
+        let (_dir, a, b) = storages().await;
+        let task = Task::new(TaskId::new(1), "Shared".to_string());
+        a.save_task(&task).await.unwrap();
+        let mut state = SyncState::default();
+        sync(&a, &b, &mut state, SyncPolicy::ReportConflicts)
+            .await
+            .unwrap();
+
+        let mut on_a = task.clone();
+        on_a.archive(chrono::Utc::now());
+        a.save_task(&on_a).await.unwrap();
+        let report = sync(&a, &b, &mut state, SyncPolicy::ReportConflicts)
+            .await
+            .unwrap();
+
+        assert_eq!(report.copied_to_b, vec![TaskId::new(1)]);
+        let on_b = b.load_task(&TaskId::new(1)).await.unwrap();
+        assert_eq!(on_b.archived_at, on_a.archived_at);
+    }
+
+    #[tokio::test]
+    async fn test_archived_tasks_leave_both_boards() {
+        let (_dir, a, b) = storages().await;
+        let task = Task::new(TaskId::new(1), "Shared".to_string());
+        a.save_task(&task).await.unwrap();
+        let mut state = SyncState::default();
+        sync(&a, &b, &mut state, SyncPolicy::ReportConflicts)
+            .await
+            .unwrap();
+        assert!(b.load_board().await.unwrap().tasks.contains_key("HLA1"));
+
+        let mut on_a = task.clone();
+        on_a.archive(chrono::Utc::now());
+        a.save_task(&on_a).await.unwrap();
+        sync(&a, &b, &mut state, SyncPolicy::ReportConflicts)
+            .await
+            .unwrap();
+
+        for side in [&a, &b] {
+            assert!(!side.load_board().await.unwrap().tasks.contains_key("HLA1"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_separately_created_tasks_with_one_id_collide() {
+        let (_dir, a, b) = storages().await;
+        a.save_task(&Task::new(TaskId::new(1), "Laptop task".to_string()))
+            .await
+            .unwrap();
+        b.save_task(&Task::new(TaskId::new(1), "Desktop task".to_string()))
+            .await
+            .unwrap();
+
+        let mut state = SyncState::default();
+        let report = sync(&a, &b, &mut state, SyncPolicy::PreferB).await.unwrap();
+
+        assert_eq!(report.collisions, vec![TaskId::new(1)]);
+        assert!(report.copied_to_a.is_empty() && report.copied_to_b.is_empty());
+        assert!(report.merged.is_empty());
+        assert_eq!(
+            a.load_task(&TaskId::new(1)).await.unwrap().title,
+            "Laptop task"
+        );
+        assert_eq!(
+            b.load_task(&TaskId::new(1)).await.unwrap().title,
+            "Desktop task"
+        );
+        assert!(state.bases.is_empty());
+        assert!(report.into_result().is_err());
+    }
+}
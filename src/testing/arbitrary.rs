@@ -0,0 +1,273 @@
+//! proptest `Arbitrary` impls, enabled with the `proptest` feature.
+//!
+//! Generated tasks are built with [`TaskBuilder`], so they carry fixed,
+//! whole-second timestamps around [`base_time`] and survive any serde
+//! round-trip. Boards hold tasks with distinct IDs.
+
+use crate::{
+    domain::{automation::RuleCondition, Priority, Severity, Task, TaskStatus, ViewFilter},
+    testing::{base_time, BoardBuilder, GeneratedBoard, TaskBuilder},
+};
+use chrono::{DateTime, Duration, Utc};
+use proptest::{collection, option, prelude::*, sample};
+
+const MAX_AGE_SECONDS: i64 = 90 * 24 * 60 * 60;
+
+impl Arbitrary for TaskStatus {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        sample::select(TaskStatus::ALL.to_vec()).boxed()
+    }
+}
+
+impl Arbitrary for Priority {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        sample::select(Priority::ALL.to_vec()).boxed()
+    }
+}
+
+impl Arbitrary for Severity {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        sample::select(Severity::ALL.to_vec()).boxed()
+    }
+}
+
+fn timestamp() -> impl Strategy<Value = DateTime<Utc>> {
+    (-MAX_AGE_SECONDS..MAX_AGE_SECONDS).prop_map(|s| base_time() + Duration::seconds(s))
+}
+
+/// Tasks numbered from `numbers`
+fn task(numbers: impl Strategy<Value = u32>) -> impl Strategy<Value = Task> {
+    (
+        numbers,
+        "[A-Z][a-z]{0,11}( [a-z]{1,8}){0,4}",
+        option::of("[a-z ,.]{1,60}"),
+        any::<TaskStatus>(),
+        option::of(any::<Priority>()),
+        option::of(any::<Severity>()),
+        collection::btree_set("[a-z]{1,8}", 0..4),
+        option::of("[a-z]{2,8}"),
+        option::of(sample::select(vec!["bug", "feature", "chore"])),
+        -1_000i64..1_000,
+        0..MAX_AGE_SECONDS,
+        collection::vec(("[a-z]{1,8}( [a-z]{1,8}){0,3}", any::<bool>()), 0..4),
+    )
+        .prop_map(
+            |(
+                number,
+                title,
+                description,
+                status,
+                priority,
+                severity,
+                tags,
+                assignee,
+                task_type,
+                rank,
+                age,
+                criteria,
+            )| {
+                let mut builder = TaskBuilder::new(number, &title)
+                    .created_at(base_time() - Duration::seconds(age))
+                    .rank(rank);
+                if let Some(description) = &description {
+                    builder = builder.description(description);
+                }
+                if let Some(priority) = priority {
+                    builder = builder.priority(priority);
+                }
+                if let Some(assignee) = &assignee {
+                    builder = builder.assignee(assignee);
+                }
+                if let Some(task_type) = task_type {
+                    builder = builder.task_type(task_type);
+                }
+                for tag in &tags {
+                    builder = builder.tag(tag);
+                }
+                for (text, done) in &criteria {
+                    builder = builder.criterion(text, *done);
+                }
+                if status != TaskStatus::New {
+                    builder = builder.status(status);
+                }
+                builder.with(|task| task.severity = severity).build()
+            },
+        )
+}
+
+impl Arbitrary for Task {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        task(1u32..10_000).boxed()
+    }
+}
+
+impl Arbitrary for GeneratedBoard {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        collection::btree_set(1u32..1_000, 0..16)
+            .prop_flat_map(|numbers| {
+                numbers
+                    .into_iter()
+                    .map(|number| task(Just(number)))
+                    .collect::<Vec<_>>()
+            })
+            .prop_map(|tasks| {
+                tasks
+                    .into_iter()
+                    .fold(BoardBuilder::new(), BoardBuilder::task)
+                    .build()
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for RuleCondition {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let seconds = 1i64..MAX_AGE_SECONDS;
+        let leaf = prop_oneof![
+            "[a-z]{1,8}".prop_map(|tag| RuleCondition::HasTag { tag }),
+            any::<TaskStatus>().prop_map(|status| RuleCondition::StatusIs { status }),
+            any::<Priority>().prop_map(|priority| RuleCondition::PriorityIs { priority }),
+            "[a-z]{1,8}".prop_map(|task_type| RuleCondition::TypeIs { task_type }),
+            any::<Severity>().prop_map(|severity| RuleCondition::SeverityAtLeast { severity }),
+            "[a-z]{1,8}".prop_map(|component| RuleCondition::HasComponent { component }),
+            "[a-z]{1,8}".prop_map(|environment| RuleCondition::EnvironmentIs { environment }),
+            Just(RuleCondition::AllAcceptanceCriteriaCompleted),
+            Just(RuleCondition::IsBlocked),
+            seconds
+                .clone()
+                .prop_map(|seconds| RuleCondition::UpdatedWithin { seconds }),
+            timestamp().prop_map(|at| RuleCondition::UpdatedAfter { at }),
+            seconds.prop_map(|seconds| RuleCondition::CreatedWithin { seconds }),
+            timestamp().prop_map(|at| RuleCondition::CreatedAfter { at }),
+            "[a-z]{1,8}".prop_map(|query| RuleCondition::TextMatches { query }),
+        ];
+        leaf.prop_recursive(3, 16, 4, |inner| {
+            prop_oneof![
+                collection::vec(inner.clone(), 0..4)
+                    .prop_map(|conditions| RuleCondition::All { conditions }),
+                collection::vec(inner.clone(), 0..4)
+                    .prop_map(|conditions| RuleCondition::Any { conditions }),
+                inner.prop_map(|condition| RuleCondition::Not {
+                    condition: Box::new(condition)
+                }),
+            ]
+        })
+        .boxed()
+    }
+}
+
+impl Arbitrary for ViewFilter {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            collection::vec(any::<TaskStatus>(), 0..3),
+            collection::vec(any::<RuleCondition>(), 0..3),
+            option::of("[a-z]{1,8}"),
+        )
+            .prop_map(|(statuses, conditions, query)| ViewFilter {
+                statuses,
+                conditions,
+                query,
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TAGS: &[&str] = &["api", "docs", "ui", "infra"];
+
+    /// Tag edits on one replica: true adds the tag, false removes it
+    fn tag_edits() -> impl Strategy<Value = Vec<(bool, &'static str)>> {
+        collection::vec((any::<bool>(), sample::select(TAGS)), 0..8)
+    }
+
+    fn edit_tags(task: &mut Task, edits: &[(bool, &str)]) {
+        for (add, tag) in edits {
+            if *add {
+                task.add_tag(tag.to_string());
+            } else {
+                task.remove_tag(tag);
+            }
+            task.refresh_crdt();
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_transitions_follow_the_workflow(mut task in any::<Task>(), target in any::<TaskStatus>()) {
+            let legal = task.status.can_transition_to(&target);
+            // Staying in the same status is allowed but records nothing
+            let moved = legal && task.status != target;
+            let history = task.status_history.len();
+            prop_assert_eq!(task.transition_to(target.clone(), None).is_ok(), legal);
+            if legal {
+                prop_assert_eq!(&task.status, &target);
+            }
+            prop_assert_eq!(task.status_history.len(), history + usize::from(moved));
+        }
+
+        #[test]
+        fn test_task_serde_round_trip(task in any::<Task>()) {
+            let json = serde_json::to_string(&task).unwrap();
+            prop_assert_eq!(serde_json::from_str::<Task>(&json).unwrap(), task);
+        }
+
+        #[test]
+        fn test_board_serde_round_trip(generated in any::<GeneratedBoard>()) {
+            let json = serde_json::to_value(&generated.board).unwrap();
+            let board: crate::domain::Board = serde_json::from_value(json.clone()).unwrap();
+            prop_assert_eq!(serde_json::to_value(&board).unwrap(), json);
+            prop_assert_eq!(board.tasks.len(), generated.tasks.len());
+        }
+
+        #[test]
+        fn test_filter_serde_round_trip(filter in any::<ViewFilter>()) {
+            let json = serde_json::to_string(&filter).unwrap();
+            prop_assert_eq!(serde_json::from_str::<ViewFilter>(&json).unwrap(), filter);
+        }
+
+        #[test]
+        fn test_crdt_merge_commutes(
+            base in any::<Task>(),
+            ours in tag_edits(),
+            theirs in tag_edits(),
+        ) {
+            let mut laptop = base;
+            laptop.tags.retain(|tag| !TAGS.contains(&tag.as_str()));
+            laptop.enable_crdt("laptop".to_string());
+            let mut desktop = laptop.clone();
+            desktop.crdt.as_mut().unwrap().replica = "desktop".to_string();
+            edit_tags(&mut laptop, &ours);
+            edit_tags(&mut desktop, &theirs);
+
+            let mut left = laptop.clone();
+            left.merge_crdt(&desktop);
+            let mut right = desktop.clone();
+            right.merge_crdt(&laptop);
+            prop_assert_eq!(&left.tags, &right.tags);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+/// A time limit for an operation
+///
+/// ```
+/// use hlavi_core::testing::{generate_board, Budget};
+/// use std::time::Duration;
+///
+/// let generated = generate_board(1_000, 42);
+/// let sorted = Budget::new("sort 1k tasks", Duration::from_secs(1)).measure(|| {
+///     let mut tasks = generated.tasks.clone();
+///     hlavi_core::sort_tasks(&mut tasks, hlavi_core::SortField::Rank, hlavi_core::SortOrder::Descending);
+///     tasks
+/// });
+/// sorted.assert_within();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Budget {
+    pub name: String,
+    pub limit: Duration,
+}
+
+/// The result of an operation and how long it took
+#[derive(Debug, Clone)]
+pub struct Measured<T> {
+    pub value: T,
+    pub elapsed: Duration,
+    pub budget: Budget,
+}
+
+impl Budget {
+    pub fn new(name: &str, limit: Duration) -> Self {
+        Self {
+            name: name.to_string(),
+            limit,
+        }
+    }
+
+    /// Runs `operation` once, timing it
+    pub fn measure<T>(&self, operation: impl FnOnce() -> T) -> Measured<T> {
+        let start = Instant::now();
+        let value = operation();
+        self.measured(value, start.elapsed())
+    }
+
+    /// Awaits `operation` once, timing it
+    pub async fn measure_async<T>(&self, operation: impl Future<Output = T>) -> Measured<T> {
+        let start = Instant::now();
+        let value = operation.await;
+        self.measured(value, start.elapsed())
+    }
+
+    fn measured<T>(&self, value: T, elapsed: Duration) -> Measured<T> {
+        Measured {
+            value,
+            elapsed,
+            budget: self.clone(),
+        }
+    }
+}
+
+impl<T> Measured<T> {
+    pub fn within_budget(&self) -> bool {
+        self.elapsed <= self.budget.limit
+    }
+
+    /// How far past the limit the operation ran, if it did
+    pub fn overrun(&self) -> Option<Duration> {
+        self.elapsed
+            .checked_sub(self.budget.limit)
+            .filter(|d| !d.is_zero())
+    }
+
+    /// Returns the value, panicking with the timings if the budget was
+    /// exceeded
+    pub fn assert_within(self) -> T {
+        assert!(
+            self.within_budget(),
+            "'{}' took {:?}, over its budget of {:?}",
+            self.budget.name,
+            self.elapsed,
+            self.budget.limit
+        );
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget() {
+        let fast = Budget::new("fast", Duration::from_secs(60)).measure(|| 2 + 2);
+        assert!(fast.within_budget());
+        assert_eq!(fast.overrun(), None);
+        assert_eq!(fast.assert_within(), 4);
+
+        let slow = Budget::new("slow", Duration::ZERO)
+            .measure(|| std::thread::sleep(Duration::from_millis(2)));
+        assert!(!slow.within_budget());
+        assert!(slow.overrun().is_some());
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{
+    domain::{history::StatusTransition, Board, BoardConfig, Priority, Task, TaskId, TaskStatus},
+    testing::{base_time, GeneratedBoard},
+};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// Builds a task with fixed timestamps, for assertions that do not depend
+/// on the clock
+///
+/// Every timestamp is [`base_time`] unless set; the UUID comes from the
+/// number, so the same builder always gives the same task.
+#[derive(Debug, Clone)]
+pub struct TaskBuilder {
+    task: Task,
+}
+
+impl TaskBuilder {
+    pub fn new(number: u32, title: &str) -> Self {
+        let mut task = Task::new(TaskId::new(number), title.to_string());
+        task.uuid = Uuid::from_u64_pair(0, u64::from(number));
+        task.created_at = base_time();
+        task.updated_at = base_time();
+        Self { task }
+    }
+
+    /// Dates the task's creation and last update at `at`
+    pub fn created_at(mut self, at: DateTime<Utc>) -> Self {
+        self.task.created_at = at;
+        self.task.updated_at = at;
+        self
+    }
+
+    /// Dates the task's creation `days` before [`base_time`]
+    pub fn created_days_ago(self, days: i64) -> Self {
+        self.created_at(base_time() - Duration::days(days))
+    }
+
+    /// Moves the task to `status` an hour after its last update, recording
+    /// the transition
+    pub fn status(mut self, status: TaskStatus) -> Self {
+        let at = self.task.updated_at + Duration::hours(1);
+        self.task.status_history.push(StatusTransition {
+            from: self.task.status.clone(),
+            to: status.clone(),
+            at,
+        });
+        if matches!(status, TaskStatus::Done | TaskStatus::Closed) {
+            self.task.completed_at = Some(at);
+        }
+        self.task.status = status;
+        self.task.status_changed_at = Some(at);
+        self.task.updated_at = at;
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.task.description = Some(description.to_string());
+        self
+    }
+
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.task.priority = Some(priority);
+        self
+    }
+
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.task.tags.push(tag.to_string());
+        self
+    }
+
+    pub fn task_type(mut self, task_type: &str) -> Self {
+        self.task.task_type = Some(task_type.to_string());
+        self
+    }
+
+    pub fn assignee(mut self, assignee: &str) -> Self {
+        self.task.assignee = Some(assignee.to_string());
+        self
+    }
+
+    pub fn rank(mut self, rank: i64) -> Self {
+        self.task.rank = rank;
+        self
+    }
+
+    pub fn parent(mut self, parent: u32) -> Self {
+        self.task.parent = Some(TaskId::new(parent));
+        self
+    }
+
+    /// Adds an acceptance criterion, dated like the task
+    pub fn criterion(mut self, description: &str, completed: bool) -> Self {
+        let updated_at = self.task.updated_at;
+        let id = self.task.add_acceptance_criterion(description.to_string());
+        self.task.updated_at = updated_at;
+        if let Some(ac) = self
+            .task
+            .acceptance_criteria
+            .iter_mut()
+            .find(|ac| ac.id == id)
+        {
+            ac.created_at = self.task.created_at;
+            if completed {
+                ac.completed = true;
+                ac.completed_at = Some(updated_at);
+            }
+        }
+        self
+    }
+
+    /// Changes anything the builder has no method for
+    pub fn with(mut self, change: impl FnOnce(&mut Task)) -> Self {
+        change(&mut self.task);
+        self
+    }
+
+    pub fn build(self) -> Task {
+        self.task
+    }
+}
+
+impl From<TaskBuilder> for Task {
+    fn from(builder: TaskBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Builds a board holding fixture tasks
+#[derive(Debug, Clone, Default)]
+pub struct BoardBuilder {
+    config: BoardConfig,
+    tasks: Vec<Task>,
+}
+
+impl BoardBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn config(mut self, config: BoardConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn task(mut self, task: impl Into<Task>) -> Self {
+        self.tasks.push(task.into());
+        self
+    }
+
+    /// The board, tracking every task, with the next ID after the highest
+    /// task number
+    pub fn build(self) -> GeneratedBoard {
+        let mut board = Board::new(self.config);
+        for task in &self.tasks {
+            board.add_task(task);
+            if let Some(number) = task.id.number() {
+                board.next_task_number = board.next_task_number.max(number + 1);
+            }
+        }
+        GeneratedBoard {
+            board,
+            tasks: self.tasks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixtures_are_fixed() {
+        let build = || {
+            BoardBuilder::new()
+                .task(
+                    TaskBuilder::new(1, "Login")
+                        .created_days_ago(3)
+                        .priority(Priority::High)
+                        .criterion("Works", true)
+                        .status(TaskStatus::Open),
+                )
+                .task(TaskBuilder::new(4, "Logout").tag("ux"))
+                .build()
+        };
+        let fixture = build();
+        assert_eq!(fixture.tasks, build().tasks);
+
+        let login = &fixture.tasks[0];
+        assert_eq!(login.created_at, base_time() - Duration::days(3));
+        assert_eq!(login.updated_at, login.created_at + Duration::hours(1));
+        assert_eq!(login.status_history.len(), 1);
+        assert!(login.acceptance_criteria[0].completed);
+        assert_eq!(fixture.board.next_task_number, 5);
+        assert_eq!(fixture.board.count_in_status(&TaskStatus::Open), 1);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::domain::{
+    history::StatusTransition, Board, BoardConfig, Priority, Task, TaskId, TaskStatus,
+};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use uuid::Uuid;
+
+/// Time every generated task is dated relative to, so output does not depend
+/// on the clock
+pub fn base_time() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0)
+        .single()
+        .unwrap_or_default()
+}
+
+const VERBS: &[&str] = &[
+    "Fix", "Add", "Remove", "Refactor", "Document", "Speed up", "Test", "Migrate",
+];
+const NOUNS: &[&str] = &[
+    "login form",
+    "search index",
+    "export job",
+    "billing page",
+    "sync client",
+    "audit log",
+    "settings screen",
+    "webhook retry",
+    "report builder",
+    "upload flow",
+];
+const TAGS: &[&str] = &["backend", "frontend", "infra", "ux", "security", "docs"];
+const TYPES: &[&str] = &["bug", "feature", "chore"];
+const PEOPLE: &[&str] = &["ana", "ben", "chidi", "dara", "emeka"];
+
+/// Small, fast pseudo-random generator (SplitMix64)
+///
+/// Not for anything security related; it only makes generated data
+/// reproducible from a seed without pulling in a dependency.
+#[derive(Debug, Clone)]
+pub struct SeededRng(u64);
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`; `bound` must be non-zero
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// True with the given chance, from 0.0 to 1.0
+    pub fn chance(&mut self, probability: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= probability
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
+
+/// A synthetic board and its tasks
+#[derive(Debug, Clone)]
+pub struct GeneratedBoard {
+    pub board: Board,
+    pub tasks: Vec<Task>,
+}
+
+impl GeneratedBoard {
+    /// A memory storage holding the board and tasks, ready for a
+    /// `BoardService`
+    #[cfg(feature = "storage")]
+    pub fn into_storage(self) -> crate::storage::MemoryStorage {
+        crate::storage::MemoryStorage::with_contents(
+            crate::domain::ProjectConfig::default(),
+            self.board,
+            self.tasks,
+        )
+    }
+}
+
+/// Generates a board with `n_tasks` tasks, the same for the same seed
+///
+/// Tasks are spread across every status with realistic fields: titles and
+/// descriptions, priorities, tags, types, assignees, acceptance criteria,
+/// ranks and status history. Timestamps count back from [`base_time`].
+pub fn generate_board(n_tasks: usize, seed: u64) -> GeneratedBoard {
+    let mut rng = SeededRng::new(seed);
+    let mut board = Board::new(BoardConfig::default());
+    let mut tasks = Vec::with_capacity(n_tasks);
+
+    for _ in 0..n_tasks {
+        let id: TaskId = board.next_task_id();
+        let title = format!("{} {}", rng.pick(VERBS), rng.pick(NOUNS));
+        let mut task = Task::new(id, title);
+
+        task.uuid = Uuid::from_u64_pair(rng.next_u64(), rng.next_u64());
+        task.rank = rng.below(10_000) as i64;
+        task.description = Some(format!(
+            "The {} needs attention.\n\nSteps:\n1. Open the {}\n2. Check the {}",
+            rng.pick(NOUNS),
+            rng.pick(NOUNS),
+            rng.pick(NOUNS)
+        ));
+        if rng.chance(0.8) {
+            task.priority = Some(*rng.pick(&Priority::ALL));
+        }
+        for _ in 0..rng.below(3) {
+            task.add_tag(rng.pick(TAGS).to_string());
+        }
+        if rng.chance(0.7) {
+            task.task_type = Some(rng.pick(TYPES).to_string());
+        }
+        if rng.chance(0.6) {
+            task.assignee = Some(rng.pick(PEOPLE).to_string());
+        }
+        for n in 0..rng.below(4) {
+            task.add_acceptance_criterion(format!("Criterion {}", n + 1));
+        }
+        // Setters stamp the current time; replace it with generated times
+        let created = base_time() - Duration::minutes(rng.below(90 * 24 * 60) as i64);
+        task.created_at = created;
+        task.updated_at = created;
+        for ac in &mut task.acceptance_criteria {
+            ac.created_at = created;
+        }
+
+        // Walk the workflow forward, keeping the history consistent
+        let steps = rng.below(TaskStatus::ALL.len() as u64) as usize;
+        let mut at = created;
+        for to in TaskStatus::ALL.iter().take(steps + 1).skip(1) {
+            at += Duration::minutes(1 + rng.below(3 * 24 * 60) as i64);
+            task.status_history.push(StatusTransition {
+                from: task.status.clone(),
+                to: to.clone(),
+                at,
+            });
+            task.status = to.clone();
+            task.status_changed_at = Some(at);
+            task.updated_at = at;
+        }
+        if matches!(task.status, TaskStatus::Done | TaskStatus::Closed) {
+            task.completed_at = task.status_changed_at;
+            for ac in &mut task.acceptance_criteria {
+                ac.completed = true;
+                ac.completed_at = task.completed_at;
+            }
+        }
+
+        board.add_task(&task);
+        tasks.push(task);
+    }
+
+    GeneratedBoard { board, tasks }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generation_is_deterministic() {
+        let first = generate_board(200, 7);
+        let second = generate_board(200, 7);
+        assert_eq!(first.tasks, second.tasks);
+        assert_ne!(first.tasks, generate_board(200, 8).tasks);
+
+        assert_eq!(first.board.tasks.len(), 200);
+        assert!(first
+            .tasks
+            .iter()
+            .all(|t| t.updated_at <= base_time() + Duration::days(30)));
+        for status in TaskStatus::ALL {
+            assert!(first.board.count_in_status(&status) > 0);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Helpers for testing and benchmarking against Hlavi, enabled with the
+//! `testing` feature.
+//!
+//! [`generate_board`] builds large synthetic boards from a seed, so
+//! performance work is measured on the same data every run and downstream
+//! users can stress-test their own setups. [`Budget`] times an operation
+//! against a limit, for tests that guard against slowdowns.
+//!
+//! [`TaskBuilder`] and [`BoardBuilder`] build small fixtures with fixed
+//! timestamps. With the `storage` feature, [`FlakyStorage`] and
+//! [`SlowStorage`] wrap any storage to inject failures and delays, so
+//! error and loading paths can be tested against the real `Storage` trait.
+//!
+//! With the `proptest` feature, tasks, boards, statuses, priorities,
+//! severities, rule conditions and view filters implement proptest's
+//! `Arbitrary`, so they can be generated with `any::<Task>()`.
+
+#[cfg(feature = "proptest")]
+mod arbitrary;
+mod budget;
+mod fixtures;
+mod generate;
+#[cfg(feature = "storage")]
+mod storage;
+
+pub use budget::{Budget, Measured};
+pub use fixtures::{BoardBuilder, TaskBuilder};
+pub use generate::{base_time, generate_board, GeneratedBoard, SeededRng};
+#[cfg(feature = "storage")]
+pub use storage::{FlakyStorage, SlowStorage, StorageOp};
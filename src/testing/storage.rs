@@ -0,0 +1,443 @@
+use crate::{
+    domain::{
+        Board, BoardSnapshot, NotificationQueue, ProjectConfig, ReadState, SearchConstraints, Task,
+        TaskId, TaskSummary, ViewPreferences,
+    },
+    error::{HlaviError, Result},
+    storage::Storage,
+    testing::SeededRng,
+};
+use async_trait::async_trait;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::Mutex,
+    time::Duration,
+};
+use uuid::Uuid;
+
+/// Kinds of storage call a wrapper can fail or slow down
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StorageOp {
+    Initialize,
+    SaveTask,
+    LoadTask,
+    ListTasks,
+    Search,
+    DeleteTask,
+    SaveBoard,
+    LoadBoard,
+    /// Loading project settings, and the initialized check
+    LoadConfig,
+    /// Saving, loading and listing snapshots
+    Snapshot,
+    /// Actor-local state: read state, view preferences and the notification
+    /// queue
+    LocalState,
+}
+
+impl StorageOp {
+    pub const ALL: [StorageOp; 11] = [
+        StorageOp::Initialize,
+        StorageOp::SaveTask,
+        StorageOp::LoadTask,
+        StorageOp::ListTasks,
+        StorageOp::Search,
+        StorageOp::DeleteTask,
+        StorageOp::SaveBoard,
+        StorageOp::LoadBoard,
+        StorageOp::LoadConfig,
+        StorageOp::Snapshot,
+        StorageOp::LocalState,
+    ];
+
+    /// Whether the operation changes stored data
+    pub fn is_write(&self) -> bool {
+        matches!(
+            self,
+            StorageOp::Initialize
+                | StorageOp::SaveTask
+                | StorageOp::DeleteTask
+                | StorageOp::SaveBoard
+        )
+    }
+}
+
+impl fmt::Display for StorageOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            StorageOp::Initialize => "initialize",
+            StorageOp::SaveTask => "save_task",
+            StorageOp::LoadTask => "load_task",
+            StorageOp::ListTasks => "list_tasks",
+            StorageOp::Search => "search",
+            StorageOp::DeleteTask => "delete_task",
+            StorageOp::SaveBoard => "save_board",
+            StorageOp::LoadBoard => "load_board",
+            StorageOp::LoadConfig => "load_config",
+            StorageOp::Snapshot => "snapshot",
+            StorageOp::LocalState => "local_state",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Implements `Storage` for a wrapper with an `inner` storage and an async
+/// `before(op)` hook, forwarding every method, including the defaults the
+/// inner storage may override
+macro_rules! wrap_storage {
+    ($wrapper:ident) => {
+        #[async_trait]
+        impl<S: Storage> Storage for $wrapper<S> {
+            async fn initialize(&self) -> Result<()> {
+                self.before(StorageOp::Initialize).await?;
+                self.inner.initialize().await
+            }
+
+            async fn save_task(&self, task: &Task) -> Result<()> {
+                self.before(StorageOp::SaveTask).await?;
+                self.inner.save_task(task).await
+            }
+
+            async fn load_task(&self, id: &TaskId) -> Result<Task> {
+                self.before(StorageOp::LoadTask).await?;
+                self.inner.load_task(id).await
+            }
+
+            async fn load_task_tagged(&self, id: &TaskId) -> Result<(Task, String)> {
+                self.before(StorageOp::LoadTask).await?;
+                self.inner.load_task_tagged(id).await
+            }
+
+            async fn load_task_header(&self, id: &TaskId) -> Result<Task> {
+                self.before(StorageOp::LoadTask).await?;
+                self.inner.load_task_header(id).await
+            }
+
+            async fn load_task_headers(&self, ids: &[TaskId]) -> Result<Vec<Task>> {
+                self.before(StorageOp::LoadTask).await?;
+                self.inner.load_task_headers(ids).await
+            }
+
+            async fn load_task_full(&self, task: Task) -> Result<Task> {
+                self.before(StorageOp::LoadTask).await?;
+                self.inner.load_task_full(task).await
+            }
+
+            async fn save_task_tagged(&self, task: &Task) -> Result<String> {
+                self.before(StorageOp::SaveTask).await?;
+                self.inner.save_task_tagged(task).await
+            }
+
+            async fn task_etag(&self, id: &TaskId) -> Result<String> {
+                self.before(StorageOp::LoadTask).await?;
+                self.inner.task_etag(id).await
+            }
+
+            async fn list_task_ids(&self) -> Result<Vec<TaskId>> {
+                self.before(StorageOp::ListTasks).await?;
+                self.inner.list_task_ids().await
+            }
+
+            async fn load_tasks(&self, ids: &[TaskId]) -> Result<Vec<Task>> {
+                self.before(StorageOp::LoadTask).await?;
+                self.inner.load_tasks(ids).await
+            }
+
+            async fn load_all_tasks(&self) -> Result<Vec<Task>> {
+                self.before(StorageOp::ListTasks).await?;
+                self.inner.load_all_tasks().await
+            }
+
+            async fn list_summaries(&self) -> Result<Vec<TaskSummary>> {
+                self.before(StorageOp::ListTasks).await?;
+                self.inner.list_summaries().await
+            }
+
+            async fn search_tasks(&self, query: &str) -> Result<Vec<Task>> {
+                self.before(StorageOp::Search).await?;
+                self.inner.search_tasks(query).await
+            }
+
+            async fn search_tasks_with(
+                &self,
+                query: &str,
+                constraints: &SearchConstraints,
+            ) -> Result<Vec<Task>> {
+                self.before(StorageOp::Search).await?;
+                self.inner.search_tasks_with(query, constraints).await
+            }
+
+            async fn delete_task(&self, id: &TaskId) -> Result<()> {
+                self.before(StorageOp::DeleteTask).await?;
+                self.inner.delete_task(id).await
+            }
+
+            async fn save_board(&self, board: &Board) -> Result<()> {
+                self.before(StorageOp::SaveBoard).await?;
+                self.inner.save_board(board).await
+            }
+
+            async fn load_board(&self) -> Result<Board> {
+                self.before(StorageOp::LoadBoard).await?;
+                self.inner.load_board().await
+            }
+
+            /// A failure here reports the project as not initialized
+            async fn is_initialized(&self) -> bool {
+                self.before(StorageOp::LoadConfig).await.is_ok()
+                    && self.inner.is_initialized().await
+            }
+
+            async fn load_task_by_uuid(&self, uuid: &Uuid) -> Result<Task> {
+                self.before(StorageOp::LoadTask).await?;
+                self.inner.load_task_by_uuid(uuid).await
+            }
+
+            async fn find_task(&self, identifier: &str) -> Result<Task> {
+                self.before(StorageOp::LoadTask).await?;
+                self.inner.find_task(identifier).await
+            }
+
+            async fn load_project_config(&self) -> Result<ProjectConfig> {
+                self.before(StorageOp::LoadConfig).await?;
+                self.inner.load_project_config().await
+            }
+
+            async fn save_snapshot(&self, snapshot: &BoardSnapshot) -> Result<()> {
+                self.before(StorageOp::Snapshot).await?;
+                self.inner.save_snapshot(snapshot).await
+            }
+
+            async fn load_snapshot(&self, label: &str) -> Result<BoardSnapshot> {
+                self.before(StorageOp::Snapshot).await?;
+                self.inner.load_snapshot(label).await
+            }
+
+            async fn list_snapshots(&self) -> Result<Vec<String>> {
+                self.before(StorageOp::Snapshot).await?;
+                self.inner.list_snapshots().await
+            }
+
+            async fn load_read_state(&self, actor: &str) -> Result<ReadState> {
+                self.before(StorageOp::LocalState).await?;
+                self.inner.load_read_state(actor).await
+            }
+
+            async fn save_read_state(&self, state: &ReadState) -> Result<()> {
+                self.before(StorageOp::LocalState).await?;
+                self.inner.save_read_state(state).await
+            }
+
+            async fn load_view_preferences(&self, actor: &str) -> Result<ViewPreferences> {
+                self.before(StorageOp::LocalState).await?;
+                self.inner.load_view_preferences(actor).await
+            }
+
+            async fn save_view_preferences(&self, preferences: &ViewPreferences) -> Result<()> {
+                self.before(StorageOp::LocalState).await?;
+                self.inner.save_view_preferences(preferences).await
+            }
+
+            async fn load_notification_queue(&self) -> Result<NotificationQueue> {
+                self.before(StorageOp::LocalState).await?;
+                self.inner.load_notification_queue().await
+            }
+
+            async fn save_notification_queue(&self, queue: &NotificationQueue) -> Result<()> {
+                self.before(StorageOp::LocalState).await?;
+                self.inner.save_notification_queue(queue).await
+            }
+        }
+    };
+}
+
+#[derive(Debug, Default)]
+struct Failures {
+    next: HashMap<StorageOp, u32>,
+    always: HashSet<StorageOp>,
+    random: Option<(f64, SeededRng)>,
+    injected: u64,
+}
+
+/// A storage that fails on demand, for testing error paths against the real
+/// trait
+///
+/// Failures are `HlaviError::StorageError`s naming the operation. Calls that
+/// are not failed go to the wrapped storage unchanged.
+#[derive(Debug)]
+pub struct FlakyStorage<S> {
+    inner: S,
+    failures: Mutex<Failures>,
+}
+
+impl<S: Storage> FlakyStorage<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            failures: Mutex::new(Failures::default()),
+        }
+    }
+
+    /// Fails the next `times` calls of `op`
+    pub fn fail_next(&self, op: StorageOp, times: u32) {
+        *self.lock().next.entry(op).or_default() += times;
+    }
+
+    /// Fails every call of `op` until healed
+    pub fn fail_always(&self, op: StorageOp) {
+        self.lock().always.insert(op);
+    }
+
+    /// Fails any call with the given chance, from 0.0 to 1.0, the same
+    /// calls for the same seed
+    pub fn with_failure_rate(self, rate: f64, seed: u64) -> Self {
+        self.lock().random = Some((rate, SeededRng::new(seed)));
+        self
+    }
+
+    /// Stops injecting failures
+    pub fn heal(&self) {
+        let mut failures = self.lock();
+        failures.next.clear();
+        failures.always.clear();
+        failures.random = None;
+    }
+
+    /// How many failures have been injected
+    pub fn failures(&self) -> u64 {
+        self.lock().injected
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    async fn before(&self, op: StorageOp) -> Result<()> {
+        let mut failures = self.lock();
+        let planned = match failures.next.get_mut(&op) {
+            Some(remaining) if *remaining > 0 => {
+                *remaining -= 1;
+                true
+            }
+            _ => false,
+        };
+        let fail = planned
+            || failures.always.contains(&op)
+            || failures
+                .random
+                .as_mut()
+                .is_some_and(|(rate, rng)| rng.chance(*rate));
+        if !fail {
+            return Ok(());
+        }
+        failures.injected += 1;
+        Err(HlaviError::StorageError(format!(
+            "Injected failure in {}",
+            op
+        )))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Failures> {
+        self.failures.lock().expect("flaky storage lock poisoned")
+    }
+}
+
+wrap_storage!(FlakyStorage);
+
+/// A storage that waits before every call, for testing loading states and
+/// timeouts
+#[derive(Debug)]
+pub struct SlowStorage<S> {
+    inner: S,
+    delay: Duration,
+    delays: HashMap<StorageOp, Duration>,
+}
+
+impl<S: Storage> SlowStorage<S> {
+    /// Delays every call by `delay`
+    pub fn new(inner: S, delay: Duration) -> Self {
+        Self {
+            inner,
+            delay,
+            delays: HashMap::new(),
+        }
+    }
+
+    /// Delays calls of `op` by `delay` instead
+    pub fn with_delay(mut self, op: StorageOp, delay: Duration) -> Self {
+        self.delays.insert(op, delay);
+        self
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    async fn before(&self, op: StorageOp) -> Result<()> {
+        let delay = self.delays.get(&op).copied().unwrap_or(self.delay);
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        Ok(())
+    }
+}
+
+wrap_storage!(SlowStorage);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        service::BoardService,
+        testing::{BoardBuilder, TaskBuilder},
+    };
+
+    fn fixture() -> crate::storage::MemoryStorage {
+        BoardBuilder::new()
+            .task(TaskBuilder::new(1, "Login"))
+            .build()
+            .into_storage()
+    }
+
+    #[tokio::test]
+    async fn test_flaky_storage() {
+        let storage = FlakyStorage::new(fixture());
+        storage.fail_next(StorageOp::LoadTask, 1);
+        let service = BoardService::new(storage);
+        let id = TaskId::new(1);
+
+        let err = service.load_task(&id).await.unwrap_err();
+        assert!(err.to_string().contains("Injected failure in load_task"));
+        assert_eq!(service.load_task(&id).await.unwrap().title, "Login");
+
+        service.storage().fail_always(StorageOp::SaveBoard);
+        assert!(service.create_task("Logout".to_string()).await.is_err());
+        service.storage().heal();
+        service.create_task("Logout".to_string()).await.unwrap();
+        assert_eq!(service.storage().failures(), 2);
+
+        let random = FlakyStorage::new(fixture()).with_failure_rate(1.0, 7);
+        assert!(random.load_board().await.is_err());
+        assert!(!random.is_initialized().await);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_storage() {
+        let storage = SlowStorage::new(fixture(), Duration::from_millis(10))
+            .with_delay(StorageOp::LoadBoard, Duration::from_secs(5));
+        let start = tokio::time::Instant::now();
+        storage.load_task(&TaskId::new(1)).await.unwrap();
+        assert_eq!(start.elapsed(), Duration::from_millis(10));
+        storage.load_board().await.unwrap();
+        assert_eq!(start.elapsed(), Duration::from_millis(5010));
+    }
+}
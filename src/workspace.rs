@@ -0,0 +1,388 @@
+//! Discovery of multiple Hlavi projects under one directory tree.
+//!
+//! Monorepos often hold several projects, each with its own storage
+//! directory. [`discover`] finds them and [`Workspace`] opens them together so
+//! frontends can list work across all of them with a single call. Tasks
+//! returned from workspace queries carry a [`QualifiedTaskId`] such as
+//! `backend/HLA12`, since plain IDs are only unique within one project.
+
+use crate::{
+    domain::{Task, TaskId, TaskStatus},
+    error::{HlaviError, Result},
+    service::BoardService,
+    storage::{file_storage::FileStorage, Storage},
+};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tokio::fs;
+
+/// Directories never searched for projects
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target"];
+
+/// Finds every initialized project under `root`, including `root` itself
+///
+/// A project is a directory whose storage directory (see
+/// `FileStorage::default_dir`) contains a board. Hidden directories,
+/// `node_modules` and `target` are not searched. Results are sorted by path.
+pub async fn discover(root: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+    let storage_dir = FileStorage::default_dir();
+    let mut projects = Vec::new();
+    let mut pending = vec![root.as_ref().to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        if FileStorage::with_dir(&dir, &storage_dir)
+            .is_initialized()
+            .await
+        {
+            projects.push(dir.clone());
+        }
+
+        let mut entries = fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_type().await?.is_dir() {
+                continue;
+            }
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_ref()) {
+                continue;
+            }
+            pending.push(entry.path());
+        }
+    }
+
+    projects.sort();
+    Ok(projects)
+}
+
+/// A task ID qualified by its project name, written `project/ID`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QualifiedTaskId {
+    pub project: String,
+    pub id: TaskId,
+}
+
+impl QualifiedTaskId {
+    pub fn new(project: impl Into<String>, id: TaskId) -> Self {
+        Self {
+            project: project.into(),
+            id,
+        }
+    }
+}
+
+impl fmt::Display for QualifiedTaskId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.project, self.id)
+    }
+}
+
+impl FromStr for QualifiedTaskId {
+    type Err = HlaviError;
+
+    /// Splits at the last `/`, so nested project names like `services/api/HLA3` work
+    fn from_str(s: &str) -> Result<Self> {
+        let (project, id) = s
+            .rsplit_once('/')
+            .filter(|(project, _)| !project.is_empty())
+            .ok_or_else(|| HlaviError::InvalidTaskId(s.to_string()))?;
+        Ok(Self::new(project, TaskId::from_str(id)?))
+    }
+}
+
+/// A task returned from a workspace query
+#[derive(Debug, Clone)]
+pub struct WorkspaceTask {
+    pub id: QualifiedTaskId,
+    pub task: Task,
+}
+
+/// A project opened as part of a workspace
+pub struct Project {
+    /// Path relative to the workspace root, `/`-separated (e.g. `services/api`)
+    pub name: String,
+    pub root: PathBuf,
+    pub service: BoardService<FileStorage>,
+}
+
+/// Several projects opened together
+pub struct Workspace {
+    root: PathBuf,
+    projects: Vec<Project>,
+}
+
+impl Workspace {
+    /// Discovers and opens every project under `root`
+    pub async fn open(root: impl AsRef<Path>) -> Result<Self> {
+        let root = root.as_ref().to_path_buf();
+        let projects = discover(&root)
+            .await?
+            .into_iter()
+            .map(|path| Project {
+                name: project_name(&root, &path),
+                service: BoardService::new(FileStorage::new(&path)),
+                root: path,
+            })
+            .collect();
+        Ok(Self { root, projects })
+    }
+
+    /// The directory the workspace was opened at
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn projects(&self) -> &[Project] {
+        &self.projects
+    }
+
+    /// Looks up a project by name
+    pub fn project(&self, name: &str) -> Option<&Project> {
+        self.projects.iter().find(|p| p.name == name)
+    }
+
+    /// Lists the tasks of every project, grouped by project in name order
+    pub async fn list_tasks(&self) -> Result<Vec<WorkspaceTask>> {
+        self.filter(|_| true).await
+    }
+
+    /// Lists the tasks of every project that satisfy `predicate`
+    pub async fn filter(&self, predicate: impl Fn(&Task) -> bool) -> Result<Vec<WorkspaceTask>> {
+        let mut all = Vec::new();
+        for project in &self.projects {
+            for task in project.service.list_tasks().await? {
+                if predicate(&task) {
+                    all.push(qualify(project, task));
+                }
+            }
+        }
+        Ok(all)
+    }
+
+    /// Searches every project (see `Storage::search_tasks`)
+    pub async fn search(&self, query: &str) -> Result<Vec<WorkspaceTask>> {
+        let mut all = Vec::new();
+        for project in &self.projects {
+            for task in project.service.storage().search_tasks(query).await? {
+                all.push(qualify(project, task));
+            }
+        }
+        Ok(all)
+    }
+
+    /// Tasks in the given status across all projects, e.g. all in-progress work
+    pub async fn tasks_in_status(&self, status: &TaskStatus) -> Result<Vec<WorkspaceTask>> {
+        self.filter(|task| &task.status == status).await
+    }
+
+    /// Moves a task to another project's board
+    ///
+    /// The task is re-created under an ID minted by the destination (see
+    /// `Task::relocate_as`), and the source task is closed as a tombstone
+    /// with a `MovedTo` link so old references can be redirected. Returns the
+    /// new qualified ID.
+    pub async fn move_task(
+        &self,
+        from_project: &str,
+        id: &TaskId,
+        to_project: &str,
+    ) -> Result<QualifiedTaskId> {
+        if from_project == to_project {
+            return Err(HlaviError::Other(format!(
+                "{} is already in project '{}'",
+                id, from_project
+            )));
+        }
+        let source = self.require_project(from_project)?;
+        let destination = self.require_project(to_project)?;
+
+        let mut task = source.service.load_task(id).await?;
+        let moved = destination
+            .service
+            .create_task_with(|new_id| Ok(task.relocate_as(new_id, from_project)))
+            .await?;
+
+        task.mark_moved(to_project.to_string(), moved.id.clone());
+        source.service.update_task(task).await?;
+        Ok(QualifiedTaskId::new(to_project, moved.id))
+    }
+
+    fn require_project(&self, name: &str) -> Result<&Project> {
+        self.project(name)
+            .ok_or_else(|| HlaviError::Other(format!("Unknown project '{}'", name)))
+    }
+
+    /// Loads a task by its qualified ID
+    pub async fn load_task(&self, id: &QualifiedTaskId) -> Result<Task> {
+        let project = self
+            .project(&id.project)
+            .ok_or_else(|| HlaviError::Other(format!("Unknown project '{}'", id.project)))?;
+        project.service.load_task(&id.id).await
+    }
+}
+
+fn qualify(project: &Project, task: Task) -> WorkspaceTask {
+    WorkspaceTask {
+        id: QualifiedTaskId::new(project.name.clone(), task.id.clone()),
+        task,
+    }
+}
+
+/// Relative path from the workspace root, or the directory's own name for the root project
+fn project_name(root: &Path, project: &Path) -> String {
+    let relative = project.strip_prefix(root).unwrap_or(project);
+    if relative.as_os_str().is_empty() {
+        return root
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| ".".to_string());
+    }
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn init_project(path: &Path, titles: &[&str]) {
+        fs::create_dir_all(path).await.unwrap();
+        let service = BoardService::new(FileStorage::new(path));
+        service.storage().initialize().await.unwrap();
+        for title in titles {
+            service.create_task(title.to_string()).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_discover_finds_nested_projects() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        init_project(root, &[]).await;
+        init_project(&root.join("services/api"), &[]).await;
+        init_project(&root.join("web"), &[]).await;
+        init_project(&root.join("node_modules/dep"), &[]).await;
+        fs::create_dir_all(root.join("docs")).await.unwrap();
+
+        let found = discover(root).await.unwrap();
+
+        assert_eq!(
+            found,
+            vec![
+                root.to_path_buf(),
+                root.join("services/api"),
+                root.join("web")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_workspace_lists_across_projects() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        init_project(&root.join("backend"), &["API", "DB"]).await;
+        init_project(&root.join("frontend"), &["UI"]).await;
+
+        let workspace = Workspace::open(root).await.unwrap();
+        let names: Vec<_> = workspace.projects().iter().map(|p| &p.name).collect();
+        assert_eq!(names, vec!["backend", "frontend"]);
+        assert!(workspace.project("frontend").is_some());
+
+        let tasks = workspace.list_tasks().await.unwrap();
+        let listed: Vec<_> = tasks.iter().map(|t| t.id.to_string()).collect();
+        assert_eq!(
+            listed,
+            vec!["backend/HLA1", "backend/HLA2", "frontend/HLA1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_aggregated_queries() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        init_project(&root.join("backend"), &["Fix login", "Add cache"]).await;
+        init_project(&root.join("frontend"), &["Login page"]).await;
+        let workspace = Workspace::open(root).await.unwrap();
+
+        let mut task = workspace
+            .load_task(&"frontend/HLA1".parse().unwrap())
+            .await
+            .unwrap();
+        task.transition_to(TaskStatus::Open, None).unwrap();
+        task.transition_to(TaskStatus::InProgress, None).unwrap();
+        let frontend = workspace.project("frontend").unwrap();
+        frontend.service.update_task(task).await.unwrap();
+
+        let in_progress = workspace
+            .tasks_in_status(&TaskStatus::InProgress)
+            .await
+            .unwrap();
+        assert_eq!(in_progress.len(), 1);
+        assert_eq!(in_progress[0].id.to_string(), "frontend/HLA1");
+
+        let found: Vec<_> = workspace
+            .search("login")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.id.to_string())
+            .collect();
+        assert_eq!(found, vec!["backend/HLA1", "frontend/HLA1"]);
+
+        assert!(workspace
+            .load_task(&"mobile/HLA1".parse().unwrap())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_move_task_between_projects() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        init_project(&root.join("backend"), &["Misfiled"]).await;
+        init_project(&root.join("frontend"), &["Existing"]).await;
+        let workspace = Workspace::open(root).await.unwrap();
+
+        let new_id = workspace
+            .move_task("backend", &TaskId::new(1), "frontend")
+            .await
+            .unwrap();
+        assert_eq!(new_id.to_string(), "frontend/HLA2");
+
+        let moved = workspace.load_task(&new_id).await.unwrap();
+        assert_eq!(moved.title, "Misfiled");
+
+        let tombstone = workspace
+            .load_task(&"backend/HLA1".parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(tombstone.status, TaskStatus::Closed);
+        let redirect = tombstone.links.last().unwrap();
+        assert_eq!(redirect.project.as_deref(), Some("frontend"));
+        assert_eq!(redirect.target, moved.id);
+
+        assert!(workspace
+            .move_task("backend", &TaskId::new(1), "backend")
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_qualified_id_parsing() {
+        let id: QualifiedTaskId = "services/api/hla12".parse().unwrap();
+        assert_eq!(id.project, "services/api");
+        assert_eq!(id.id.as_str(), "HLA12");
+        assert_eq!(id.to_string(), "services/api/HLA12");
+
+        assert!("HLA12".parse::<QualifiedTaskId>().is_err());
+        assert!("/HLA12".parse::<QualifiedTaskId>().is_err());
+        assert!("backend/oops".parse::<QualifiedTaskId>().is_err());
+    }
+}